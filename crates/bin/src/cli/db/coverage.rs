@@ -0,0 +1,415 @@
+use std::{path::Path, sync::Arc};
+
+use alloy_primitives::{Address, FixedBytes, B256, U256};
+use brontes_classifier::Classifier;
+use brontes_core::decoding::Parser as DParser;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::ParserMetricsListener;
+use brontes_types::{
+    init_thread_pools,
+    normalized_actions::Action,
+    structured_trace::{TraceActions, TransactionTraceWithLogs},
+    FastHashMap, FastHashSet, ToFloatNearest, ToScaledRational, TreeSearchBuilder,
+    UnboundedYapperReceiver,
+};
+use clap::Parser;
+use comfy_table::Table;
+use futures::StreamExt;
+use itertools::Itertools;
+use reth_rpc_types::trace::parity::TraceOutput;
+use serde::Serialize;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::{
+    cli::{determine_max_tasks, get_env_vars, get_tracing_provider, load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Coverage {
+    /// Start Block
+    #[arg(long = "start", short)]
+    pub start_block: u64,
+    /// End Block
+    #[arg(long = "end", short)]
+    pub end_block:   u64,
+    /// Amount of entries to output
+    #[arg(long, default_value_t = 50)]
+    pub top:         usize,
+    /// Output the report as JSON instead of a table
+    #[arg(long, default_value_t = false)]
+    pub json:        bool,
+    /// Max number of blocks to classify concurrently
+    #[arg(long)]
+    pub max_tasks:   Option<u64>,
+}
+
+impl Coverage {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        if self.start_block > self.end_block {
+            eyre::bail!("start block is after the end block");
+        }
+
+        let db_path = get_env_vars()?;
+
+        let max_tasks = determine_max_tasks(self.max_tasks);
+        init_thread_pools(max_tasks as usize);
+        let (metrics_tx, metrics_rx) = unbounded_channel();
+        let metrics_listener = ParserMetricsListener::new(UnboundedYapperReceiver::new(
+            metrics_rx,
+            10_000,
+            "metrics".to_string(),
+        ));
+        ctx.task_executor
+            .spawn_critical("metrics", metrics_listener);
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let tracer =
+            get_tracing_provider(Path::new(&db_path), max_tasks, ctx.task_executor.clone());
+        let parser = DParser::new(metrics_tx, libmdbx, tracer.clone()).await;
+
+        // only the classification is needed, the pricing updates are dropped
+        let (tx, mut rx) = unbounded_channel();
+        ctx.task_executor
+            .spawn(async move { while rx.recv().await.is_some() {} });
+        let classifier = Classifier::new(libmdbx, tx, Arc::new(tracer.clone()));
+
+        let mut report = CoverageReport::default();
+        let mut blocks = futures::stream::iter(self.start_block..=self.end_block)
+            .map(|block| {
+                let parser = &parser;
+                let classifier = &classifier;
+                async move {
+                    let Some((_, traces, header)) = parser.execute(block, 0, None).await else {
+                        tracing::warn!(%block, "failed to trace block, skipping it");
+                        return None
+                    };
+
+                    Some(Arc::new(classifier.build_block_tree(traces, header, false).await))
+                }
+            })
+            .buffer_unordered(max_tasks as usize);
+
+        while let Some(tree) = blocks.next().await {
+            let Some(tree) = tree else { continue };
+            report.add_block(
+                tree.collect_all(TreeSearchBuilder::default().with_action(Action::is_unclassified)),
+            );
+        }
+
+        let rows = report.into_rows(libmdbx, self.top)?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+        } else {
+            println!("{}", coverage_table(&rows));
+        }
+
+        Ok(())
+    }
+}
+
+/// Unclassified call frames with the same target, selector and first emitted
+/// event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CoverageKey {
+    address:  Address,
+    selector: Option<FixedBytes<4>>,
+    topic:    Option<B256>,
+}
+
+#[derive(Debug, Default)]
+struct CoverageStats {
+    calls:    u64,
+    gas_used: u64,
+    value:    U256,
+    txs:      FastHashSet<B256>,
+}
+
+#[derive(Debug, Default)]
+struct CoverageReport {
+    entries: FastHashMap<CoverageKey, CoverageStats>,
+}
+
+impl CoverageReport {
+    /// Adds the unclassified frames of the transactions of a block
+    fn add_block(&mut self, txs: impl IntoIterator<Item = (B256, Vec<Action>)>) {
+        for (tx_hash, actions) in txs {
+            for action in actions {
+                if let Action::Unclassified(trace) = action {
+                    self.add(tx_hash, &trace);
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, tx_hash: B256, trace: &TransactionTraceWithLogs) {
+        // static calls don't change state, the frame of a delegate call is
+        // already counted at the proxy and creations have no target
+        if trace.is_static_call() || trace.is_delegate_call() || trace.is_create() {
+            return
+        }
+
+        let calldata = trace.get_calldata();
+        let key = CoverageKey {
+            address:  trace.get_to_address(),
+            selector: (calldata.len() >= 4).then(|| FixedBytes::from_slice(&calldata[..4])),
+            topic:    trace
+                .logs
+                .first()
+                .and_then(|log| log.topics().first().copied()),
+        };
+
+        // the gas used includes the gas of the nested calls
+        let gas_used = match &trace.trace.result {
+            Some(TraceOutput::Call(output)) => output.gas_used.to::<u64>(),
+            Some(TraceOutput::Create(output)) => output.gas_used.to::<u64>(),
+            None => 0,
+        };
+
+        let stats = self.entries.entry(key).or_default();
+        stats.calls += 1;
+        stats.gas_used += gas_used;
+        stats.value += trace.get_msg_value();
+        stats.txs.insert(tx_hash);
+    }
+
+    /// The `top` entries that used the most gas. Entries that used the same
+    /// amount of gas are ranked by the value they were sent.
+    fn ranked(self, top: usize) -> Vec<(CoverageKey, CoverageStats)> {
+        self.entries
+            .into_iter()
+            .sorted_unstable_by(|(_, a), (_, b)| {
+                b.gas_used
+                    .cmp(&a.gas_used)
+                    .then_with(|| b.value.cmp(&a.value))
+            })
+            .take(top)
+            .collect_vec()
+    }
+
+    /// The ranked entries, named with the address metadata and the protocol
+    /// the address is registered as
+    fn into_rows<DB: LibmdbxReader>(self, db: &DB, top: usize) -> eyre::Result<Vec<CoverageRow>> {
+        let entries = self.ranked(top);
+
+        let metadata = db.try_fetch_address_metadatas(
+            entries
+                .iter()
+                .map(|(key, _)| key.address)
+                .unique()
+                .collect(),
+        )?;
+
+        Ok(entries
+            .into_iter()
+            .map(|(key, stats)| CoverageRow {
+                address:   key.address,
+                name:      metadata
+                    .get(&key.address)
+                    .and_then(|metadata| metadata.describe()),
+                protocol:  db
+                    .get_protocol(key.address)
+                    .ok()
+                    .map(|protocol| protocol.to_string()),
+                selector:  key.selector,
+                topic:     key.topic,
+                calls:     stats.calls,
+                txs:       stats.txs.len(),
+                gas_used:  stats.gas_used,
+                value_eth: stats.value.to_scaled_rational(18).to_float(),
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CoverageRow {
+    address:   Address,
+    name:      Option<String>,
+    protocol:  Option<String>,
+    selector:  Option<FixedBytes<4>>,
+    topic:     Option<B256>,
+    calls:     u64,
+    txs:       usize,
+    gas_used:  u64,
+    value_eth: f64,
+}
+
+fn coverage_table(rows: &[CoverageRow]) -> Table {
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Address",
+        "Name",
+        "Protocol",
+        "Selector",
+        "Event",
+        "Calls",
+        "Txs",
+        "Gas Used",
+        "ETH Value",
+    ]);
+
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    for row in rows {
+        table.add_row([
+            format!("{:?}", row.address),
+            or_dash(row.name.clone()),
+            or_dash(row.protocol.clone()),
+            or_dash(row.selector.map(|selector| format!("{selector:?}"))),
+            or_dash(row.topic.map(|topic| format!("{topic:?}"))),
+            row.calls.to_string(),
+            row.txs.to_string(),
+            row.gas_used.to_string(),
+            format!("{:.4}", row.value_eth),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Bytes, Log, LogData, U64};
+    use brontes_types::normalized_actions::NormalizedTransfer;
+    use reth_rpc_types::trace::parity::{
+        Action as TraceAction, CallAction, CallOutput, CallType, TransactionTrace,
+    };
+
+    use super::*;
+
+    const SELECTOR: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+
+    fn frame(
+        to: Address,
+        call_type: CallType,
+        input: &[u8],
+        gas_used: u64,
+        value: u64,
+        topic: Option<B256>,
+    ) -> Action {
+        Action::Unclassified(TransactionTraceWithLogs {
+            trace:        TransactionTrace {
+                action:        TraceAction::Call(CallAction {
+                    from: Address::with_last_byte(0xff),
+                    call_type,
+                    gas: U64::from(1_000_000),
+                    input: Bytes::copy_from_slice(input),
+                    to,
+                    value: U256::from(value),
+                }),
+                error:         None,
+                result:        Some(TraceOutput::Call(CallOutput {
+                    gas_used: U64::from(gas_used),
+                    output:   Bytes::new(),
+                })),
+                subtraces:     0,
+                trace_address: vec![],
+            },
+            logs:         topic
+                .into_iter()
+                .map(|topic| Log {
+                    address: to,
+                    data:    LogData::new_unchecked(vec![topic], Bytes::new()),
+                })
+                .collect(),
+            msg_sender:   Address::with_last_byte(0xff),
+            trace_idx:    0,
+            decoded_data: None,
+        })
+    }
+
+    fn call(to: u8, gas_used: u64, value: u64) -> Action {
+        frame(Address::with_last_byte(to), CallType::Call, &SELECTOR, gas_used, value, None)
+    }
+
+    fn key(to: u8, selector: Option<[u8; 4]>, topic: Option<B256>) -> CoverageKey {
+        CoverageKey {
+            address: Address::with_last_byte(to),
+            selector: selector.map(FixedBytes::from),
+            topic,
+        }
+    }
+
+    #[test]
+    fn test_aggregation() {
+        let (tx_a, tx_b) = (B256::with_last_byte(0xa), B256::with_last_byte(0xb));
+        let topic = B256::with_last_byte(0xe);
+        let to = Address::with_last_byte(1);
+
+        let mut report = CoverageReport::default();
+        report.add_block([
+            (
+                tx_a,
+                vec![
+                    call(1, 100, 1),
+                    call(1, 50, 0),
+                    // same target and selector with another event
+                    frame(to, CallType::Call, &SELECTOR, 10, 0, Some(topic)),
+                    // calls without a selector
+                    frame(to, CallType::Call, &[], 20, 5, None),
+                    // classified actions aren't counted
+                    Action::Transfer(NormalizedTransfer::default()),
+                ],
+            ),
+            (
+                tx_b,
+                vec![
+                    call(1, 200, 2),
+                    // these don't change state or are counted at the proxy
+                    frame(to, CallType::StaticCall, &SELECTOR, 1_000, 0, None),
+                    frame(to, CallType::DelegateCall, &SELECTOR, 1_000, 0, None),
+                ],
+            ),
+        ]);
+        report.add_block([(B256::with_last_byte(0xc), vec![])]);
+
+        assert_eq!(report.entries.len(), 3);
+
+        let stats = &report.entries[&key(1, Some(SELECTOR), None)];
+        assert_eq!(stats.calls, 3);
+        assert_eq!(stats.gas_used, 350);
+        assert_eq!(stats.value, U256::from(3));
+        assert_eq!(stats.txs.len(), 2);
+
+        let stats = &report.entries[&key(1, Some(SELECTOR), Some(topic))];
+        assert_eq!((stats.calls, stats.gas_used, stats.txs.len()), (1, 10, 1));
+
+        let stats = &report.entries[&key(1, None, None)];
+        assert_eq!((stats.calls, stats.gas_used, stats.value), (1, 20, U256::from(5)));
+    }
+
+    #[test]
+    fn test_ranking() {
+        let tx = B256::with_last_byte(0xa);
+        let mut report = CoverageReport::default();
+        report.add_block([(
+            tx,
+            vec![
+                call(1, 100, 0),
+                call(2, 300, 0),
+                call(3, 100, 7),
+                call(4, 50, 100),
+                call(2, 0, 1),
+            ],
+        )]);
+
+        let ranked = |report: CoverageReport, top| {
+            report
+                .ranked(top)
+                .into_iter()
+                .map(|(key, stats)| (key.address, stats.gas_used))
+                .collect_vec()
+        };
+        let address = Address::with_last_byte;
+
+        // by gas first, the value only breaks ties
+        assert_eq!(
+            ranked(std::mem::take(&mut report), 10),
+            vec![(address(2), 300), (address(3), 100), (address(1), 100), (address(4), 50)]
+        );
+
+        report.add_block([(tx, vec![call(1, 100, 0), call(2, 300, 0), call(3, 100, 7)])]);
+        assert_eq!(ranked(report, 2), vec![(address(2), 300), (address(3), 100)]);
+    }
+}
@@ -13,7 +13,8 @@ pub struct Clear {
         value_delimiter = ',',
         default_value = "CexPrice,DexPrice,CexTrades,BlockInfo,InitializedState,MevBlocks,\
                          TokenDecimals,AddressToProtocolInfo,PoolCreationBlocks,Builder,\
-                         AddressMeta,SearcherEOAs,SearcherContracts,SubGraphs,TxTraces"
+                         AddressMeta,SearcherEOAs,SearcherContracts,SubGraphs,PoolState,\
+                         UniswapV4PoolKeys,TxTraces"
     )]
     pub tables:                  Vec<Tables>,
     /// Mark metadata as uninitialized in the initialized state table
@@ -66,7 +67,11 @@ impl Clear {
                 AddressMeta,
                 SearcherEOAs,
                 SearcherContracts,
-                TxTraces
+                TxTraces,
+                BlockAnalysisRollups,
+                SubGraphs,
+                PoolState,
+                UniswapV4PoolKeys
             )
         });
 
@@ -53,6 +53,10 @@ impl Insert {
             SearcherEOAs,
             SearcherContracts,
             InitializedState,
+            BlockAnalysisRollups,
+            SubGraphs,
+            PoolState,
+            UniswapV4PoolKeys,
             PoolCreationBlocks = &self.key,
             &self.value
         );
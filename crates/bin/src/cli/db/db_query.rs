@@ -1,23 +1,51 @@
+use std::fmt::Debug;
+
 use brontes_database::{
     libmdbx::{cursor::CompressedCursor, Libmdbx},
     CompressedTable, IntoTableKey, Tables,
 };
 use brontes_libmdbx::RO;
 use brontes_types::init_thread_pools;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use comfy_table::Table as ComfyTable;
 use itertools::Itertools;
 use reth_interfaces::db::DatabaseErrorInfo;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::query_filter::{parse_filter, resolve_path, Filter};
 
 #[derive(Debug, Parser)]
 pub struct DatabaseQuery {
     /// Table to query
     #[arg(long, short)]
-    pub table: Tables,
+    pub table:  Tables,
     /// Key for table query. Use Rust range syntax for ranges:
     /// --key 80 (single key)
     /// --key 80..100 (range)
     #[arg(long, short)]
-    pub key:   String,
+    pub key:    String,
+    /// Only output entries matching the predicate, e.g
+    /// --filter 'mev_type == Sandwich && profit_usd > 1000'
+    /// Fields are resolved by their path in the json output of the entry.
+    /// MevBlocks are filtered per bundle
+    #[arg(long, value_parser = parse_filter)]
+    pub filter: Option<Filter>,
+    /// Only output these fields of each entry, e.g
+    /// --fields header.tx_hash,header.profit_usd
+    #[arg(long, value_delimiter = ',')]
+    pub fields: Option<Vec<String>>,
+    /// Output format. If omitted and no filter or fields are given, the
+    /// entries are debug printed
+    #[arg(long)]
+    pub format: Option<QueryFormat>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum QueryFormat {
+    Json,
+    Csv,
+    Table,
 }
 
 impl DatabaseQuery {
@@ -31,14 +59,11 @@ impl DatabaseQuery {
             match $table {
                 $(
                     Tables::$tables => {
-                        println!(
-                            "{:#?}",
-                            $fn(
-                                tx.$query::<brontes_database::libmdbx::tables::$tables>(
-                                    brontes_database::libmdbx::tables::$tables::into_key($args)
-                                    ).unwrap(),
-                            ).unwrap()
-                        )
+                        self.output($fn(
+                            tx.$query::<brontes_database::libmdbx::tables::$tables>(
+                                brontes_database::libmdbx::tables::$tables::into_key($args)
+                                ).unwrap(),
+                        ).unwrap())?
                     }
                 )+
             }
@@ -47,12 +72,9 @@ impl DatabaseQuery {
             match $table {
                 $(
                     Tables::$tables => {
-                        println!(
-                            "{:#?}",
-                            $fn(
-                                tx.$query::<brontes_database::libmdbx::tables::$tables>()?, self
-                            )?
-                        )
+                        self.output($fn(
+                            tx.$query::<brontes_database::libmdbx::tables::$tables>()?, &self.key
+                        )?)?
                     }
                 )+
             }
@@ -77,7 +99,11 @@ impl DatabaseQuery {
                     AddressMeta,
                     SearcherEOAs,
                     SearcherContracts,
-                    TxTraces
+                    TxTraces,
+                    BlockAnalysisRollups,
+                    SubGraphs,
+                    PoolState,
+                    UniswapV4PoolKeys
                 );
             } else {
                 match_table!(
@@ -97,6 +123,10 @@ impl DatabaseQuery {
                     SearcherEOAs,
                     SearcherContracts,
                     TxTraces,
+                    BlockAnalysisRollups,
+                    SubGraphs,
+                    PoolState,
+                    UniswapV4PoolKeys,
                     PoolCreationBlocks = &self.key
                 );
             }
@@ -105,18 +135,111 @@ impl DatabaseQuery {
         })?;
         Ok(())
     }
+
+    fn output<V: Debug + Serialize>(&self, value: V) -> eyre::Result<()> {
+        if self.format.is_none() && self.filter.is_none() && self.fields.is_none() {
+            println!("{:#?}", value);
+            return Ok(())
+        }
+
+        let rows = match serde_json::to_value(value)? {
+            Value::Array(rows) => rows,
+            row => vec![row],
+        };
+
+        let rows = rows
+            .into_iter()
+            // a mev block is queried by its bundles
+            .flat_map(|row| match (self.table, row) {
+                (Tables::MevBlocks, Value::Object(mut block)) => match block.remove("mev") {
+                    Some(Value::Array(bundles)) => bundles,
+                    _ => vec![],
+                },
+                (_, row) => vec![row],
+            })
+            .filter(|row| {
+                self.filter
+                    .as_ref()
+                    .map_or(true, |filter| filter.matches(row))
+            })
+            .map(|row| self.project(row))
+            .collect_vec();
+
+        match self.format.unwrap_or(QueryFormat::Json) {
+            QueryFormat::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+            QueryFormat::Csv => {
+                let columns = columns(&rows);
+                println!("{}", columns.iter().map(|c| csv_escape(c)).join(","));
+                for row in &rows {
+                    println!("{}", columns.iter().map(|c| csv_escape(&cell(row, c))).join(","));
+                }
+            }
+            QueryFormat::Table => {
+                let columns = columns(&rows);
+                let mut table = ComfyTable::new();
+                table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+                table.set_header(&columns);
+                for row in &rows {
+                    table.add_row(columns.iter().map(|c| cell(row, c)).collect_vec());
+                }
+                println!("{table}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Selects the requested fields of a row, keyed by the requested path.
+    fn project(&self, row: Value) -> Value {
+        let Some(fields) = &self.fields else { return row };
+
+        Value::Object(
+            fields
+                .iter()
+                .map(|field| {
+                    (field.clone(), resolve_path(&row, field).cloned().unwrap_or(Value::Null))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The columns of the rows, in the order they first appear.
+fn columns(rows: &[Value]) -> Vec<String> {
+    rows.iter()
+        .filter_map(|row| row.as_object())
+        .flat_map(|row| row.keys().cloned())
+        .unique()
+        .collect()
+}
+
+/// Renders a value for a csv or table cell, nested values are rendered as json.
+fn cell(row: &Value, column: &str) -> String {
+    match row.get(column) {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(string)) => string.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
 }
 
 fn process_range_query<T, E>(
     mut cursor: CompressedCursor<T, RO>,
-    config: DatabaseQuery,
+    key: &str,
 ) -> eyre::Result<Vec<T::DecompressedValue>>
 where
     T: CompressedTable,
     T: for<'a> IntoTableKey<&'a str, T::Key, E>,
     T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
 {
-    let range = config.key.split("..").collect_vec();
+    let range = key.split("..").collect_vec();
     let start = range[0];
     let end = range[1];
 
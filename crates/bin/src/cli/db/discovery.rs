@@ -1,5 +1,6 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use brontes_classifier::forks::{init_fork_registry, ForkRegistry};
 use brontes_core::decoding::Parser as DParser;
 use brontes_metrics::ParserMetricsListener;
 use brontes_types::{init_thread_pools, UnboundedYapperReceiver};
@@ -19,15 +20,22 @@ use crate::{
 pub struct DiscoveryFill {
     /// Start Block
     #[arg(long, short)]
-    pub start_block: Option<u64>,
+    pub start_block:   Option<u64>,
     /// Max number of tasks to run concurrently
     #[arg(long, short)]
-    pub max_tasks:   Option<usize>,
+    pub max_tasks:     Option<usize>,
+    /// TOML file with the protocol forks whose pools are discovered with the
+    /// discovery of their base protocol
+    #[arg(long)]
+    pub fork_registry: Option<PathBuf>,
 }
 
 impl DiscoveryFill {
     pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
         let db_path = get_env_vars()?;
+        if let Some(fork_registry) = &self.fork_registry {
+            init_fork_registry(ForkRegistry::from_file(fork_registry)?)?;
+        }
 
         let max_tasks = self.max_tasks.unwrap_or(num_cpus::get_physical());
         init_thread_pools(max_tasks);
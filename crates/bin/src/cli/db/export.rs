@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use brontes_database::{parquet::ParquetExporter, Tables};
+use brontes_database::{
+    parquet::{ParquetExporter, DEFAULT_PARTITION_SIZE},
+    Tables,
+};
 use clap::Parser;
 use futures::future::join_all;
 use tokio::task::spawn;
@@ -12,26 +15,36 @@ use crate::{
 };
 #[derive(Debug, Parser)]
 pub struct Export {
-    /// Optional tables to exports, if omitted will export all supported tables
+    /// Optional tables to exports, if omitted will export the mev and metadata
+    /// tables. TxTraces, DexPrice, CexPrice, CexTrades and PoolCreationBlocks
+    /// are keyed by block and require a start block
     #[arg(long, short, default_values = &["MevBlocks", "AddressMeta", "SearcherContracts", "Builder"], value_delimiter = ',', ignore_case=true)]
-    pub tables:      Vec<Tables>,
+    pub tables:         Vec<Tables>,
     /// Optional Start Block, if omitted it will export the entire range to
     /// parquet
     #[arg(long, short)]
-    pub start_block: Option<u64>,
+    pub start_block:    Option<u64>,
     /// Optional End Block
     #[arg(long, short)]
-    pub end_block:   Option<u64>,
+    pub end_block:      Option<u64>,
     /// Optional path, will default to "data_exports/"
     #[arg(long, short)]
-    pub path:        Option<String>,
+    pub path:           Option<String>,
+    /// Amount of blocks per file when exporting tables keyed by block
+    #[arg(long, default_value_t = DEFAULT_PARTITION_SIZE)]
+    pub partition_size: u64,
 }
 
 impl Export {
     pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
         let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
-        let exporter =
-            Arc::new(ParquetExporter::new(self.start_block, self.end_block, self.path, libmdbx));
+        let exporter = Arc::new(ParquetExporter::new(
+            self.start_block,
+            self.end_block,
+            self.path,
+            self.partition_size,
+            libmdbx,
+        ));
 
         let futures = self.tables.into_iter().map(|t| {
             let exporter = exporter.clone();
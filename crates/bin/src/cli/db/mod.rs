@@ -5,6 +5,7 @@ use crate::runner::CliContext;
 mod cex_data;
 #[cfg(feature = "local-clickhouse")]
 mod clickhouse_download;
+mod coverage;
 mod db_clear;
 mod db_insert;
 mod db_query;
@@ -14,6 +15,9 @@ mod discovery;
 mod ensure_test_traces;
 mod export;
 mod init;
+mod query_filter;
+mod record_fixture;
+mod rollup;
 mod table_stats;
 #[cfg(feature = "local-clickhouse")]
 mod tip_tracer;
@@ -54,6 +58,18 @@ pub enum DatabaseCommands {
     /// Export libmbdx data to parquet
     #[command(name = "export")]
     Export(export::Export),
+    /// Records the blocks of the given blocks or txs as self-contained fixtures
+    /// that the tests can replay without a node or clickhouse
+    #[command(name = "record-fixture")]
+    RecordFixture(record_fixture::RecordFixture),
+    /// Ranks the contracts, selectors and events of the call frames the
+    /// classifier leaves unclassified in a range by the gas they use
+    #[command(name = "coverage")]
+    Coverage(coverage::Coverage),
+    /// Rolls up the block analysis of a range into hourly, daily or per epoch
+    /// summaries
+    #[command(name = "rollup")]
+    Rollup(rollup::Rollup),
     /// Downloads a database snapshot. Without specified blocks, it fetches
     /// the full range. With start/end blocks, it downloads that range and
     /// merges it into the current database.
@@ -92,6 +108,9 @@ impl Database {
             DatabaseCommands::DbClear(cmd) => cmd.execute(brontes_db_path).await,
             DatabaseCommands::UploadSnapshot(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::Export(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::RecordFixture(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::Rollup(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::Coverage(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::TableStats(cmd) => cmd.execute(brontes_db_path),
             DatabaseCommands::DownloadSnapshot(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::CexData(cmd) => cmd.execute(brontes_db_path, ctx).await,
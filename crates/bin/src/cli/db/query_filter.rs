@@ -0,0 +1,428 @@
+use std::{cmp::Ordering, iter::Peekable, str::Chars};
+
+use serde_json::Value;
+
+/// A predicate over the json representation of a table entry, e.g
+/// `mev_type == Sandwich && (profit_usd > 1000 || bribe_usd > 500)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Compare { path: String, op: CompareOp, value: Literal },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Neq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Null,
+}
+
+impl Filter {
+    pub fn matches(&self, row: &Value) -> bool {
+        match self {
+            Filter::And(lhs, rhs) => lhs.matches(row) && rhs.matches(row),
+            Filter::Or(lhs, rhs) => lhs.matches(row) || rhs.matches(row),
+            Filter::Not(inner) => !inner.matches(row),
+            Filter::Compare { path, op, value } => {
+                let field = resolve_path(row, path).unwrap_or(&Value::Null);
+                compare(field, value).is_some_and(|ord| op.holds(ord))
+            }
+        }
+    }
+}
+
+impl CompareOp {
+    fn holds(&self, ord: Ordering) -> bool {
+        match self {
+            CompareOp::Eq => ord.is_eq(),
+            CompareOp::Neq => ord.is_ne(),
+            CompareOp::Gt => ord.is_gt(),
+            CompareOp::Gte => ord.is_ge(),
+            CompareOp::Lt => ord.is_lt(),
+            CompareOp::Lte => ord.is_le(),
+        }
+    }
+}
+
+/// Orders a field against a literal. Returns `None` when the two can't be
+/// compared, in which case the comparison doesn't match.
+fn compare(field: &Value, literal: &Literal) -> Option<Ordering> {
+    match (field, literal) {
+        (Value::Null, Literal::Null) => Some(Ordering::Equal),
+        (_, Literal::Null) => Some(Ordering::Greater),
+        (Value::Bool(field), Literal::Bool(lit)) => Some(field.cmp(lit)),
+        (Value::Number(field), Literal::Number(lit)) => field.as_f64()?.partial_cmp(lit),
+        // large numbers are serialized as strings
+        (Value::String(field), Literal::Number(lit)) => field.parse::<f64>().ok()?.partial_cmp(lit),
+        // addresses and hashes are serialized with mixed casing
+        (Value::String(field), Literal::String(lit)) => {
+            Some(field.to_lowercase().cmp(&lit.to_lowercase()))
+        }
+        (Value::Number(field), Literal::String(lit)) => {
+            field.as_f64()?.partial_cmp(&lit.parse::<f64>().ok()?)
+        }
+        _ => None,
+    }
+}
+
+/// Resolves a dot separated path in a row. If the path doesn't exist from the
+/// root of the row, the first nested object that contains the path is used.
+/// This allows for `mev_type` instead of `header.mev_type`.
+pub fn resolve_path<'a>(row: &'a Value, path: &str) -> Option<&'a Value> {
+    resolve_from_root(row, path).or_else(|| match row {
+        Value::Object(map) => map.values().find_map(|value| resolve_path(value, path)),
+        _ => None,
+    })
+}
+
+fn resolve_from_root<'a>(row: &'a Value, path: &str) -> Option<&'a Value> {
+    match row {
+        Value::Object(map) => {
+            // some keys contain dots themselves, e.g `balance_deltas.address`
+            if let Some(value) = map.get(path) {
+                return Some(value)
+            }
+            let (head, rest) = path.split_once('.')?;
+            resolve_from_root(map.get(head)?, rest)
+        }
+        Value::Array(values) => {
+            let (head, rest) = path.split_once('.').unwrap_or((path, ""));
+            let value = values.get(head.parse::<usize>().ok()?)?;
+            if rest.is_empty() {
+                Some(value)
+            } else {
+                resolve_from_root(value, rest)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Parses a filter expression. `&&` binds tighter than `||`, and expressions
+/// can be grouped with parentheses or negated with `!`.
+pub fn parse_filter(input: &str) -> Result<Filter, String> {
+    let tokens = tokenize(input)?;
+    let mut parser = FilterParser { tokens, pos: 0 };
+    let filter = parser.parse_or()?;
+
+    match parser.tokens.get(parser.pos) {
+        None => Ok(filter),
+        Some(token) => Err(format!("unexpected token {token:?}")),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    String(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut chars = input.chars().peekable();
+    let mut tokens = Vec::new();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return Err(format!("expected {c}{c}"))
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            '=' | '!' | '>' | '<' => {
+                chars.next();
+                let eq = chars.next_if_eq(&'=').is_some();
+                tokens.push(match (c, eq) {
+                    ('=', true) => Token::Op(CompareOp::Eq),
+                    ('!', true) => Token::Op(CompareOp::Neq),
+                    ('!', false) => Token::Not,
+                    ('>', true) => Token::Op(CompareOp::Gte),
+                    ('>', false) => Token::Op(CompareOp::Gt),
+                    ('<', true) => Token::Op(CompareOp::Lte),
+                    ('<', false) => Token::Op(CompareOp::Lt),
+                    _ => return Err("expected ==".to_string()),
+                });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => string.push(next),
+                        None => return Err(format!("unterminated string {c}{string}")),
+                    }
+                }
+                tokens.push(Token::String(string));
+            }
+            _ => {
+                let word = take_word(&mut chars);
+                if word.is_empty() {
+                    return Err(format!("unexpected character {c}"))
+                }
+                // hex values such as addresses are kept as strings
+                let numeric = word.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+                    && !word.starts_with("0x");
+                match word.parse::<f64>() {
+                    Ok(number) if numeric => tokens.push(Token::Number(number)),
+                    _ => tokens.push(Token::Ident(word)),
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn take_word(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut word = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-')) {
+        word.push(c);
+    }
+
+    word
+}
+
+struct FilterParser {
+    tokens: Vec<Token>,
+    pos:    usize,
+}
+
+impl FilterParser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_is(&self, token: &Token) -> bool {
+        self.tokens.get(self.pos) == Some(token)
+    }
+
+    fn parse_or(&mut self) -> Result<Filter, String> {
+        let mut lhs = self.parse_and()?;
+        while self.peek_is(&Token::Or) {
+            self.pos += 1;
+            lhs = Filter::Or(Box::new(lhs), Box::new(self.parse_and()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Filter, String> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek_is(&Token::And) {
+            self.pos += 1;
+            lhs = Filter::And(Box::new(lhs), Box::new(self.parse_unary()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Filter, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Filter::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("expected )".to_string()),
+                }
+            }
+            Some(Token::Ident(path)) => {
+                let Some(Token::Op(op)) = self.next() else {
+                    return Err(format!("expected a comparison after {path}"))
+                };
+                let value = match self.next() {
+                    Some(Token::Number(number)) => Literal::Number(number),
+                    Some(Token::String(string)) => Literal::String(string),
+                    Some(Token::Ident(ident)) => match ident.as_str() {
+                        "true" => Literal::Bool(true),
+                        "false" => Literal::Bool(false),
+                        "null" => Literal::Null,
+                        _ => Literal::String(ident),
+                    },
+                    token => {
+                        return Err(format!("expected a value to compare {path} to, got {token:?}"))
+                    }
+                };
+
+                Ok(Filter::Compare { path, op, value })
+            }
+            token => Err(format!("expected a field, got {token:?}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn cmp(path: &str, op: CompareOp, value: Literal) -> Filter {
+        Filter::Compare { path: path.to_string(), op, value }
+    }
+
+    fn matches(filter: &str, row: &Value) -> bool {
+        parse_filter(filter).unwrap().matches(row)
+    }
+
+    #[test]
+    fn test_precedence() {
+        let a = cmp("a", CompareOp::Eq, Literal::Number(1.0));
+        let b = cmp("b", CompareOp::Eq, Literal::Number(2.0));
+        let c = cmp("c", CompareOp::Eq, Literal::Number(3.0));
+
+        assert_eq!(
+            parse_filter("a == 1 || b == 2 && c == 3").unwrap(),
+            Filter::Or(
+                Box::new(a.clone()),
+                Box::new(Filter::And(Box::new(b.clone()), Box::new(c.clone())))
+            )
+        );
+        assert_eq!(
+            parse_filter("a == 1 && b == 2 || c == 3").unwrap(),
+            Filter::Or(
+                Box::new(Filter::And(Box::new(a.clone()), Box::new(b.clone()))),
+                Box::new(c.clone())
+            )
+        );
+
+        let row = json!({ "a": 1, "b": 0, "c": 0 });
+        assert!(matches("a == 1 || b == 2 && c == 3", &row));
+        assert!(!matches("(a == 1 || b == 2) && c == 3", &row));
+    }
+
+    #[test]
+    fn test_parens() {
+        let a = cmp("a", CompareOp::Gt, Literal::Number(1.0));
+        let b = cmp("b", CompareOp::Lte, Literal::Number(2.0));
+        let c = cmp("c", CompareOp::Neq, Literal::String("x".to_string()));
+
+        assert_eq!(
+            parse_filter("(a > 1 || b <= 2) && c != 'x'").unwrap(),
+            Filter::And(Box::new(Filter::Or(Box::new(a), Box::new(b))), Box::new(c))
+        );
+        assert_eq!(parse_filter("((a > 1))").unwrap(), parse_filter("a > 1").unwrap());
+
+        assert!(parse_filter("(a > 1").is_err());
+        assert!(parse_filter("a > 1)").is_err());
+        assert!(parse_filter("()").is_err());
+    }
+
+    #[test]
+    fn test_not() {
+        let a = cmp("a", CompareOp::Eq, Literal::Number(1.0));
+        assert_eq!(parse_filter("!a == 1").unwrap(), Filter::Not(Box::new(a.clone())));
+        assert_eq!(
+            parse_filter("!!a == 1").unwrap(),
+            Filter::Not(Box::new(Filter::Not(Box::new(a))))
+        );
+
+        let row = json!({ "a": 1, "b": 2 });
+        assert!(!matches("!a == 1", &row));
+        assert!(matches("!(a == 1 && b == 1)", &row));
+        assert!(!matches("!(a == 1 || b == 1)", &row));
+        // `!` negates the next expression, not the whole conjunction
+        assert!(matches("!a == 2 && b == 2", &row));
+        assert!(matches("a != 2", &row));
+    }
+
+    #[test]
+    fn test_numeric_strings() {
+        // large numbers are serialized as strings
+        let row = json!({ "amount": "1000000000000000000000", "profit": 12.5, "symbol": "5" });
+        assert!(matches("amount > 1000", &row));
+        assert!(!matches("amount < 1000", &row));
+        assert!(matches("profit == '12.5'", &row));
+        assert!(matches("symbol == 5", &row));
+        assert!(matches("profit >= -1", &row));
+
+        // hex values are compared as strings, ignoring the casing
+        let row = json!({ "address": "0xAbCd", "name": "abc" });
+        assert!(matches("address == 0xabcd", &row));
+        assert!(matches("address == '0xABCD'", &row));
+        // a string that isn't a number doesn't compare against one
+        assert!(!matches("name > 1", &row));
+        assert!(!matches("name < 1", &row));
+    }
+
+    #[test]
+    fn test_null_comparisons() {
+        let row = json!({ "bribe": null, "profit": 1 });
+
+        assert!(matches("bribe == null", &row));
+        assert!(!matches("bribe != null", &row));
+        assert!(matches("profit != null", &row));
+        assert!(!matches("profit == null", &row));
+        // a missing field is null
+        assert!(matches("missing == null", &row));
+        assert!(!matches("missing > 0", &row));
+        assert!(!matches("missing == 0", &row));
+    }
+
+    #[test]
+    fn test_unterminated_string() {
+        assert!(parse_filter("mev_type == 'Sandwich").is_err());
+        assert!(parse_filter("mev_type == \"Sandwich").is_err());
+        assert!(parse_filter("mev_type == 'Sandwich\"").is_err());
+        assert_eq!(
+            parse_filter("mev_type == 'Jit Sandwich'").unwrap(),
+            cmp("mev_type", CompareOp::Eq, Literal::String("Jit Sandwich".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let row = json!({
+            "header": { "mev_type": "Sandwich", "block_number": 1 },
+            "data": { "victims": [{ "tx_hash": "0x01" }], "balance_deltas.address": "0x02" },
+            "block_number": 2,
+        });
+
+        assert_eq!(resolve_path(&row, "header.mev_type"), Some(&json!("Sandwich")));
+        // falls back to the nested object that has the path
+        assert_eq!(resolve_path(&row, "mev_type"), Some(&json!("Sandwich")));
+        assert_eq!(resolve_path(&row, "victims.0.tx_hash"), Some(&json!("0x01")));
+        // the root takes priority over nested objects
+        assert_eq!(resolve_path(&row, "block_number"), Some(&json!(2)));
+        // keys can contain dots
+        assert_eq!(resolve_path(&row, "balance_deltas.address"), Some(&json!("0x02")));
+
+        assert_eq!(resolve_path(&row, "victims.1.tx_hash"), None);
+        assert_eq!(resolve_path(&row, "header.missing"), None);
+    }
+}
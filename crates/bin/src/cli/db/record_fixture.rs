@@ -0,0 +1,210 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use alloy_primitives::{Address, Bytes, B256};
+use alloy_rpc_types::AnyReceiptEnvelope;
+use brontes_classifier::Classifier;
+use brontes_core::decoding::Parser as DParser;
+use brontes_database::libmdbx::fixture::{eth_call_block, RecordedEthCall};
+use brontes_metrics::ParserMetricsListener;
+use brontes_types::{
+    init_thread_pools, structured_trace::TxTrace, traits::TracingProvider, UnboundedYapperReceiver,
+};
+use clap::Parser;
+use itertools::Itertools;
+use reth_primitives::{
+    BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Header, StorageValue, TxHash,
+};
+use reth_rpc_types::{
+    state::StateOverride, BlockOverrides, Log, TransactionReceipt, TransactionRequest,
+};
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::{
+    cli::{determine_max_tasks, get_env_vars, get_tracing_provider, load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct RecordFixture {
+    /// Blocks to record
+    #[arg(long, value_delimiter = ',')]
+    pub blocks:           Vec<u64>,
+    /// Transactions to record, the whole block of each tx is recorded
+    #[arg(long, value_delimiter = ',')]
+    pub tx_hashes:        Vec<B256>,
+    /// Directory the fixtures are written to
+    #[arg(long, default_value = "fixtures")]
+    pub dir:              String,
+    /// Amount of blocks around the recorded block to keep the cex trades of
+    #[arg(long, default_value = "2")]
+    pub cex_trade_window: u64,
+}
+
+impl RecordFixture {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        if self.blocks.is_empty() && self.tx_hashes.is_empty() {
+            eyre::bail!("no blocks or tx hashes to record a fixture for");
+        }
+
+        let db_path = get_env_vars()?;
+
+        let max_tasks = determine_max_tasks(None);
+        init_thread_pools(max_tasks as usize);
+        let (metrics_tx, metrics_rx) = unbounded_channel();
+
+        let metrics_listener = ParserMetricsListener::new(UnboundedYapperReceiver::new(
+            metrics_rx,
+            10_000,
+            "metrics".to_string(),
+        ));
+        ctx.task_executor
+            .spawn_critical("metrics", metrics_listener);
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let tracer = RecordingTracer::new(get_tracing_provider(
+            Path::new(&db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+        ));
+        let parser = DParser::new(metrics_tx, libmdbx, tracer.clone()).await;
+
+        // the block is classified so that the eth_calls and the pools and tokens
+        // the classifier discovers end up in the fixture
+        let (pricing_tx, mut pricing_rx) = unbounded_channel();
+        ctx.task_executor
+            .spawn(async move { while pricing_rx.recv().await.is_some() {} });
+        let classifier = Classifier::new(libmdbx, pricing_tx, Arc::new(tracer.clone()));
+
+        let mut blocks = self.blocks;
+        for tx_hash in self.tx_hashes {
+            blocks.push(tracer.block_and_tx_index(tx_hash).await?.0);
+        }
+
+        for block in blocks.into_iter().sorted_unstable().dedup() {
+            tracer.take_calls();
+            let Some((block_hash, traces, header)) = parser.execute(block, 0, None).await else {
+                eyre::bail!("failed to trace block {block}");
+            };
+            classifier
+                .build_block_tree(traces.clone(), header.clone(), false)
+                .await;
+
+            let cex_trade_blocks =
+                block.saturating_sub(self.cex_trade_window)..=block + self.cex_trade_window;
+            let path = libmdbx
+                .record_fixture(block_hash, header, traces, cex_trade_blocks, tracer.take_calls())?
+                .write(&self.dir)?;
+
+            tracing::info!(%block, path = %path.display(), "recorded fixture");
+        }
+
+        Ok(())
+    }
+}
+
+/// Records the result of every `eth_call` so it can be replayed from the
+/// fixture.
+#[derive(Clone)]
+struct RecordingTracer<T> {
+    inner: T,
+    calls: Arc<Mutex<Vec<RecordedEthCall>>>,
+}
+
+impl<T: TracingProvider> RecordingTracer<T> {
+    fn new(inner: T) -> Self {
+        Self { inner, calls: Arc::default() }
+    }
+
+    fn take_calls(&self) -> Vec<RecordedEthCall> {
+        std::mem::take(&mut *self.calls.lock().unwrap())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: TracingProvider> TracingProvider for RecordingTracer<T> {
+    async fn eth_call(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> eyre::Result<Bytes> {
+        // calls with overrides aren't replayed
+        let record = state_overrides.is_none() && block_overrides.is_none();
+        let (to, input) = (request.to, request.input.input().cloned().unwrap_or_default());
+
+        let output = self
+            .inner
+            .eth_call(request, block_number, state_overrides, block_overrides)
+            .await?;
+
+        if record {
+            self.calls.lock().unwrap().push(RecordedEthCall {
+                block: eth_call_block(block_number),
+                to,
+                input,
+                output: output.clone(),
+            });
+        }
+
+        Ok(output)
+    }
+
+    async fn block_hash_for_id(&self, block_num: u64) -> eyre::Result<Option<B256>> {
+        self.inner.block_hash_for_id(block_num).await
+    }
+
+    #[cfg(feature = "local-reth")]
+    fn best_block_number(&self) -> eyre::Result<u64> {
+        self.inner.best_block_number()
+    }
+
+    #[cfg(not(feature = "local-reth"))]
+    async fn best_block_number(&self) -> eyre::Result<u64> {
+        self.inner.best_block_number().await
+    }
+
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> eyre::Result<Option<Vec<TxTrace>>> {
+        self.inner.replay_block_transactions(block_id).await
+    }
+
+    async fn block_receipts(
+        &self,
+        number: BlockNumberOrTag,
+    ) -> eyre::Result<Option<Vec<TransactionReceipt<AnyReceiptEnvelope<Log>>>>> {
+        self.inner.block_receipts(number).await
+    }
+
+    async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
+        self.inner.header_by_number(number).await
+    }
+
+    async fn block_and_tx_index(&self, hash: TxHash) -> eyre::Result<(u64, usize)> {
+        self.inner.block_and_tx_index(hash).await
+    }
+
+    async fn get_storage(
+        &self,
+        block_number: Option<u64>,
+        address: Address,
+        storage_key: B256,
+    ) -> eyre::Result<Option<StorageValue>> {
+        self.inner
+            .get_storage(block_number, address, storage_key)
+            .await
+    }
+
+    async fn get_bytecode(
+        &self,
+        block_number: Option<u64>,
+        address: Address,
+    ) -> eyre::Result<Option<Bytecode>> {
+        self.inner.get_bytecode(block_number, address).await
+    }
+}
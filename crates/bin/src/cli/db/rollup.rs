@@ -0,0 +1,113 @@
+use brontes_types::db::{
+    block_analysis::BlockAnalysis,
+    block_analysis_rollup::{BlockAnalysisRollup, RollupBucket},
+    traits::{DBWriter, LibmdbxReader},
+};
+use clap::Parser;
+use tracing::{info, warn};
+
+use crate::{
+    cli::{load_database, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Rollup {
+    /// Start Block
+    #[arg(long = "start", short)]
+    pub start_block: u64,
+    /// End Block
+    #[arg(long = "end", short)]
+    pub end_block:   u64,
+    /// Span of the summaries, one of hour, day or epoch
+    #[arg(long, short, default_value = "day")]
+    pub bucket:      RollupBucket,
+}
+
+impl Rollup {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let db =
+            static_object(load_database(&ctx.task_executor, brontes_db_path, None, None).await?);
+
+        let (start_block, end_block) =
+            bucket_bounds(self.bucket, self.start_block, self.end_block, |block| {
+                db.get_block_timestamp(block).ok()
+            })?;
+        if (start_block, end_block) != (self.start_block, self.end_block) {
+            info!(start_block, end_block, "widened the range to whole buckets");
+        }
+
+        let blocks = db.try_fetch_mev_blocks(Some(start_block), end_block)?;
+        info!(blocks = blocks.len(), "loaded mev blocks");
+
+        let analyses = blocks.into_iter().filter_map(|block| {
+            let block_number = block.block.block_number;
+            let Ok(timestamp) = db.get_block_timestamp(block_number) else {
+                warn!(block_number, "missing block metadata, skipping block");
+                return None
+            };
+
+            Some((timestamp, BlockAnalysis::new(&block.block, &block.mev)))
+        });
+
+        let rollups = BlockAnalysisRollup::from_analyses(self.bucket, analyses);
+        info!(rollups = rollups.len(), bucket = %self.bucket, "writing block analysis rollups");
+
+        db.write_block_analysis_rollups(rollups).await
+    }
+}
+
+/// Widens the range to the first block of the bucket `start` falls in and the
+/// last block of the bucket `end` falls in. Rollups overwrite the stored ones,
+/// so they always have to be built from all the blocks of their bucket. Blocks
+/// without a timestamp end the bucket, as nothing is known about them.
+fn bucket_bounds(
+    bucket: RollupBucket,
+    start: u64,
+    end: u64,
+    timestamp: impl Fn(u64) -> Option<u64>,
+) -> eyre::Result<(u64, u64)> {
+    let bucket_of = |block| timestamp(block).map(|timestamp| bucket.bucket_of(timestamp));
+    let missing = |block| eyre::eyre!("missing block metadata for block {block}");
+
+    let first_bucket = bucket_of(start).ok_or_else(|| missing(start))?;
+    let mut start = start;
+    while start > 0 && bucket_of(start - 1) == Some(first_bucket) {
+        start -= 1;
+    }
+
+    let last_bucket = bucket_of(end).ok_or_else(|| missing(end))?;
+    let mut end = end;
+    while bucket_of(end + 1) == Some(last_bucket) {
+        end += 1;
+    }
+
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 12 second blocks, block 0 starts a day and every hour is 300 blocks
+    fn timestamp(block: u64) -> Option<u64> {
+        (block < 10_000).then_some(86_400 + block * 12)
+    }
+
+    #[test]
+    fn test_mid_bucket_range_is_widened() {
+        assert_eq!(bucket_bounds(RollupBucket::Hour, 450, 700, timestamp).unwrap(), (300, 899));
+        assert_eq!(bucket_bounds(RollupBucket::Hour, 300, 599, timestamp).unwrap(), (300, 599));
+        assert_eq!(bucket_bounds(RollupBucket::Day, 450, 700, timestamp).unwrap(), (0, 7_199));
+    }
+
+    #[test]
+    fn test_unknown_blocks_end_the_bucket() {
+        // the last day isn't over yet, it is rolled up as far as it is known
+        assert_eq!(
+            bucket_bounds(RollupBucket::Day, 7_300, 9_000, timestamp).unwrap(),
+            (7_200, 9_999)
+        );
+        assert!(bucket_bounds(RollupBucket::Day, 450, 10_000, timestamp).is_err());
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use alloy_primitives::B256;
+use brontes_classifier::Classifier;
+use brontes_core::decoding::Parser as DParser;
+use brontes_database::libmdbx::{LibmdbxReadWriter, LibmdbxReader};
+use brontes_inspect::{
+    composer::{
+        explain_block_inspection, init_mev_rules, BundleRemoval, ExplainedResults, MevRules,
+    },
+    sandwich::DEFAULT_SANDWICH_BLOCK_WINDOW,
+    Inspectors,
+};
+use brontes_metrics::ParserMetricsListener;
+use brontes_types::{
+    constants::USDT_ADDRESS_STRING,
+    db::cex::CexExchange,
+    display::tree::{display_token_deltas, display_tx_tree},
+    init_thread_pools,
+    mev::{Bundle, Mev},
+    normalized_actions::{accounting::ActionAccounting, Action},
+    traits::TracingProvider,
+    tree::{BlockTree, TxInfo},
+    BlockData, MultiBlockData, UnboundedYapperReceiver,
+};
+use clap::Parser;
+use colored::Colorize;
+use tokio::sync::mpsc::unbounded_channel;
+
+use super::run::TimeWindowArgs;
+use crate::{
+    cli::{
+        determine_max_tasks, get_block_simulator, get_env_vars, get_tracing_provider,
+        init_inspectors, load_libmdbx, static_object,
+    },
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Explain {
+    /// Hash of the transaction to explain
+    pub tx_hash:          B256,
+    /// Quote asset the bundles are priced in
+    #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
+    pub quote_asset:      String,
+    /// Inspectors to run. If omitted it defaults to running all inspectors
+    #[arg(long, short, value_delimiter = ',')]
+    pub inspectors:       Option<Vec<Inspectors>>,
+    /// CEX exchanges to consider for cex-dex analysis
+    #[arg(
+        long,
+        short,
+        default_value = "Binance,Coinbase,Okex,BybitSpot,Kucoin",
+        value_delimiter = ','
+    )]
+    pub cex_exchanges:    Vec<CexExchange>,
+    /// TOML file with the precedence and composition rules to explain the
+    /// bundles with. If omitted the built-in rules are used
+    #[arg(long)]
+    pub mev_rules:        Option<PathBuf>,
+    /// Time window arguments for cex data
+    #[clap(flatten)]
+    pub time_window_args: TimeWindowArgs,
+}
+
+impl Explain {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let db_path = get_env_vars()?;
+        let quote_asset = self.quote_asset.parse()?;
+        if let Some(mev_rules) = &self.mev_rules {
+            init_mev_rules(MevRules::from_file(mev_rules)?)?;
+        }
+
+        let max_tasks = determine_max_tasks(None);
+        init_thread_pools(max_tasks as usize);
+        let (metrics_tx, metrics_rx) = unbounded_channel();
+        let metrics_listener = ParserMetricsListener::new(UnboundedYapperReceiver::new(
+            metrics_rx,
+            10_000,
+            "metrics".to_string(),
+        ));
+        ctx.task_executor
+            .spawn_critical("metrics", metrics_listener);
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let tracer =
+            get_tracing_provider(Path::new(&db_path), max_tasks, ctx.task_executor.clone());
+        let parser = DParser::new(metrics_tx, libmdbx, tracer.clone()).await;
+
+        let (block, tx_index) = tracer.block_and_tx_index(self.tx_hash).await?;
+        let (_, traces, header) = parser
+            .execute(block, 0, None)
+            .await
+            .ok_or_else(|| eyre::eyre!("failed to trace block {block}"))?;
+
+        // the pricing updates of the classifier aren't needed, the prices are read
+        // from the db
+        let (tx, mut rx) = unbounded_channel();
+        ctx.task_executor
+            .spawn(async move { while rx.recv().await.is_some() {} });
+        let classifier = Classifier::new(libmdbx, tx, Arc::new(tracer.clone()));
+        let tree = Arc::new(classifier.build_block_tree(traces, header, false).await);
+
+        let metadata = libmdbx.get_metadata(block, quote_asset).or_else(|e| {
+            tracing::warn!(%block, err=%e, "no dex prices for block, bundles won't be priced");
+            libmdbx.get_metadata_no_dex_price(block, quote_asset)
+        })?;
+
+        let inspectors = init_inspectors(
+            quote_asset,
+            libmdbx,
+            self.inspectors,
+            self.cex_exchanges,
+            self.time_window_args.trade_config(),
+            DEFAULT_SANDWICH_BLOCK_WINDOW,
+            get_block_simulator(&tracer),
+            false,
+        );
+
+        // a single block is inspected, so sandwiches spanning multiple blocks
+        // aren't found
+        let data = MultiBlockData {
+            per_block_data: vec![BlockData {
+                metadata: Arc::new(metadata),
+                tree:     tree.clone(),
+            }],
+            blocks:         1,
+        };
+        let results = explain_block_inspection(inspectors, data, libmdbx);
+
+        println!(
+            "{}",
+            ExplainedTx { tx_hash: self.tx_hash, block, tx_index, tree, results, db: libmdbx }
+        );
+
+        Ok(())
+    }
+}
+
+struct ExplainedTx {
+    tx_hash:  B256,
+    block:    u64,
+    tx_index: usize,
+    tree:     Arc<BlockTree<Action>>,
+    results:  ExplainedResults,
+    db:       &'static LibmdbxReadWriter,
+}
+
+impl ExplainedTx {
+    fn involves_tx(&self, bundle: &Bundle) -> bool {
+        bundle.data.mev_transaction_hashes().contains(&self.tx_hash)
+    }
+
+    fn display_tx_info(&self, info: &TxInfo, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "  EOA:          {:?}", info.eoa)?;
+        match info.mev_contract {
+            Some(contract) => writeln!(f, "  MEV Contract: {contract:?}")?,
+            None => writeln!(f, "  MEV Contract: None")?,
+        }
+        if let Some(contract_type) = &info.contract_type {
+            writeln!(f, "  Contract:     {contract_type:?}")?;
+        }
+        writeln!(f, "  Private:      {}", info.is_private)?;
+        writeln!(f, "  Verified:     {}", info.is_verified_contract)?;
+        if let Some(searcher) = &info.searcher_eoa_info {
+            writeln!(f, "  Searcher EOA: {searcher:?}")?;
+        }
+        if let Some(searcher) = &info.searcher_contract_info {
+            writeln!(f, "  Searcher Contract: {searcher:?}")?;
+        }
+        info.gas_details.pretty_print_with_spaces(f, 2)
+    }
+}
+
+impl fmt::Display for ExplainedTx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {:?} (block {}, index {})\n",
+            "Transaction".bold().underline(),
+            self.tx_hash,
+            self.block,
+            self.tx_index
+        )?;
+
+        let Some(root) = self.tree.get_root(self.tx_hash) else {
+            return writeln!(f, "{}", "tx isn't part of the block tree, the root call failed".red())
+        };
+
+        if let Some(info) = self.tree.get_tx_info(self.tx_hash, self.db) {
+            self.display_tx_info(&info, f)?;
+        }
+
+        writeln!(f, "\n{}", "Call Tree".bold().underline())?;
+        display_tx_tree(root, f)?;
+
+        let deltas = root
+            .data_store
+            .0
+            .iter()
+            .flatten()
+            .flatten()
+            .cloned()
+            .account_for_actions();
+        writeln!(f, "\n{}", "Token Deltas".bold().underline())?;
+        display_token_deltas(
+            &deltas,
+            |token| {
+                self.db
+                    .try_fetch_token_info(token)
+                    .ok()
+                    .map(|info| info.symbol.clone())
+            },
+            f,
+        )?;
+
+        writeln!(f, "\n{}", "Inspector Results".bold().underline())?;
+        let found = self
+            .results
+            .inspector_results
+            .iter()
+            .filter(|bundle| self.involves_tx(bundle))
+            .collect::<Vec<_>>();
+        if found.is_empty() {
+            writeln!(f, "  no inspector produced a bundle with this tx")?;
+        }
+        for bundle in found {
+            writeln!(f, "  {} bundle at {:?}", bundle.header.mev_type, bundle.header.tx_hash)?;
+        }
+
+        writeln!(f, "\n{}", "Removed Bundles".bold().underline())?;
+        let removed = self
+            .results
+            .removed
+            .iter()
+            .filter(|(bundle, _)| self.involves_tx(bundle))
+            .collect::<Vec<_>>();
+        if removed.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for (bundle, removal) in removed {
+            let reason = match removal {
+                BundleRemoval::Composed { into } => format!("composed into a {into} bundle"),
+                BundleRemoval::Deduplicated { by, dominant_tx_hash } => {
+                    format!("deduplicated by the {by} bundle at {dominant_tx_hash:?}")
+                }
+                BundleRemoval::Duplicate => {
+                    "overlaps with a bundle of the same type that was kept".to_string()
+                }
+                BundleRemoval::Unprofitable => "not profitable".to_string(),
+            };
+            writeln!(
+                f,
+                "  {} bundle at {:?}: {}",
+                bundle.header.mev_type,
+                bundle.header.tx_hash,
+                reason.yellow()
+            )?;
+        }
+
+        writeln!(f, "\n{}", "Reported Bundles".bold().underline())?;
+        let reported = self
+            .results
+            .results
+            .mev_details
+            .iter()
+            .filter(|bundle| self.involves_tx(bundle))
+            .collect::<Vec<_>>();
+        if reported.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for bundle in reported {
+            writeln!(f, "{bundle}")?;
+        }
+
+        Ok(())
+    }
+}
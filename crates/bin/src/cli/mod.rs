@@ -1,8 +1,10 @@
 use clap::{Parser, Subcommand};
 
 mod db;
+mod explain;
 mod misc;
 mod run;
+mod serve;
 mod utils;
 mod version_data;
 pub use utils::*;
@@ -38,4 +40,11 @@ pub enum Commands {
     /// Brontes database commands
     #[command(name = "db")]
     Database(db::Database),
+    /// Serve the brontes db over a json-rpc api
+    #[command(name = "serve")]
+    Serve(serve::Serve),
+    /// Explain how a transaction was classified and which bundles it ended up
+    /// in
+    #[command(name = "explain")]
+    Explain(explain::Explain),
 }
@@ -1,14 +1,26 @@
-use std::{path::Path, time::Duration};
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use brontes_classifier::forks::{init_fork_registry, ForkRegistry};
 use brontes_core::decoding::Parser as DParser;
 use brontes_database::clickhouse::cex_config::CexDownloadConfig;
-use brontes_inspect::Inspectors;
+use brontes_inspect::{
+    composer::{init_mev_rules, MevRules},
+    sandwich::DEFAULT_SANDWICH_BLOCK_WINDOW,
+    Inspectors,
+};
 use brontes_metrics::ParserMetricsListener;
 use brontes_types::{
     constants::USDT_ADDRESS_STRING,
-    db::cex::{trades::CexDexTradeConfig, CexExchange},
+    db::cex::{
+        trades::{CexDexTradeConfig, DEFAULT_INVENTORY_MARKOUT_HORIZONS},
+        CexExchange,
+    },
     db_write_trigger::{backup_server_heartbeat, start_hr_monitor, HeartRateMonitor},
-    init_thread_pools, UnboundedYapperReceiver,
+    init_thread_pools, BrontesTaskExecutor, UnboundedYapperReceiver,
 };
 use clap::Parser;
 use tokio::sync::mpsc::unbounded_channel;
@@ -16,9 +28,10 @@ use tokio::sync::mpsc::unbounded_channel;
 use super::{determine_max_tasks, get_env_vars, load_clickhouse, load_database, static_object};
 use crate::{
     banner::rain,
-    cli::{get_tracing_provider, init_inspectors, load_tip_database},
+    cli::{get_block_simulator, get_tracing_provider, init_inspectors, load_tip_database},
     runner::CliContext,
-    BrontesRunConfig, MevProcessor, RangeType,
+    BrontesRunConfig, MevProcessor, NdJsonFileSink, OutputSink, RangeType, UnixSocketSink,
+    WebsocketSink,
 };
 
 const SECONDS_TO_US_FLOAT: f64 = 1_000_000.0;
@@ -64,6 +77,23 @@ pub struct RunArgs {
         value_delimiter = ','
     )]
     pub cex_exchanges:        Vec<CexExchange>,
+    /// Amount of blocks a sandwich can span. A window of 1 only detects
+    /// sandwiches within a single block
+    #[arg(long, default_value_t = DEFAULT_SANDWICH_BLOCK_WINDOW)]
+    pub sandwich_window:      usize,
+    /// TOML file with the precedence and composition rules used to
+    /// deduplicate and compose the bundles of a block. If omitted the built-in
+    /// rules are used
+    #[arg(long)]
+    pub mev_rules:            Option<PathBuf>,
+    /// TOML file with the protocol forks that are classified and priced as
+    /// their base protocol
+    #[arg(long)]
+    pub fork_registry:        Option<PathBuf>,
+    /// Re-executes the blocks of sandwiches and jits without the mev txs to
+    /// calculate the losses of their victims. Requires a local reth db
+    #[arg(long, default_value = "false")]
+    pub victim_losses:        bool,
     /// Force DEX price calculation for every block, ignoring existing database
     /// values.
     #[arg(long, short, default_value = "false")]
@@ -97,6 +127,9 @@ pub struct RunArgs {
     /// stored in the Clickhouse database.
     #[arg(long, short)]
     pub run_id:               Option<u64>,
+    /// Sinks to stream the mev produced at tip to
+    #[clap(flatten)]
+    pub output_args:          OutputSinkArgs,
 
     /// shows a cool display at startup
     #[arg(long, short, default_value_t = false)]
@@ -119,6 +152,14 @@ impl RunArgs {
         tracing::info!(target: "brontes", "got env vars");
         let quote_asset = self.quote_asset.parse()?;
         tracing::info!(target: "brontes", "parsed quote asset");
+        if let Some(mev_rules) = &self.mev_rules {
+            init_mev_rules(MevRules::from_file(mev_rules)?)?;
+            tracing::info!(target: "brontes", "loaded mev rules");
+        }
+        if let Some(fork_registry) = &self.fork_registry {
+            init_fork_registry(ForkRegistry::from_file(fork_registry)?)?;
+            tracing::info!(target: "brontes", "loaded fork registry");
+        }
         let task_executor = ctx.task_executor;
 
         let max_tasks = determine_max_tasks(self.max_tasks);
@@ -159,7 +200,9 @@ impl RunArgs {
             .as_ref()
             .map(|f| {
                 f.len() == 1
-                    && (f.contains(&Inspectors::CexDex) || f.contains(&Inspectors::CexDexMarkout))
+                    && (f.contains(&Inspectors::CexDex)
+                        || f.contains(&Inspectors::CexDexMarkout)
+                        || f.contains(&Inspectors::CexDexInventory))
             })
             .unwrap_or(false);
 
@@ -168,18 +211,30 @@ impl RunArgs {
         }
 
         let trade_config = self.time_window_args.trade_config();
+        let sinks = static_object(self.output_args.init_sinks(&task_executor).await?);
 
+        let tracer =
+            get_tracing_provider(Path::new(&reth_db_path), max_tasks, task_executor.clone());
+
+        let simulator = if self.victim_losses {
+            Some(
+                get_block_simulator(&tracer)
+                    .ok_or_else(|| eyre::eyre!("victim losses require a local reth db"))?,
+            )
+        } else {
+            None
+        };
         let inspectors = init_inspectors(
             quote_asset,
             libmdbx,
             self.inspectors,
             self.cex_exchanges,
             trade_config,
+            self.sandwich_window,
+            simulator,
             self.with_metrics,
         );
 
-        let tracer =
-            get_tracing_provider(Path::new(&reth_db_path), max_tasks, task_executor.clone());
         let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer.clone()).await);
 
         let executor = task_executor.clone();
@@ -202,6 +257,7 @@ impl RunArgs {
                     self.with_metrics,
                     snapshot_mode,
                     load_window,
+                    sinks,
                 )
                 .build(task_executor, shutdown)
                 .await
@@ -254,11 +310,26 @@ impl RunArgs {
 
     /// the time window in seconds for downloading
     fn load_time_window(&self) -> usize {
-        self.time_window_args
+        let window = self
+            .time_window_args
             .max_vwap_pre
             .max(self.time_window_args.max_vwap_post)
             .max(self.time_window_args.max_optimistic_pre)
-            .max(self.time_window_args.max_optimistic_post) as usize
+            .max(self.time_window_args.max_optimistic_post) as usize;
+
+        // the inventory markout needs trades up until its last horizon
+        if self.time_window_args.inventory_window != 0 {
+            let max_horizon = self
+                .time_window_args
+                .inventory_horizons
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or_default();
+            window.max((max_horizon as f64 + self.time_window_args.max_vwap_post) as usize)
+        } else {
+            window
+        }
     }
 
     fn check_proper_range(&self) -> eyre::Result<()> {
@@ -295,6 +366,43 @@ fn parse_ranges(ranges: &[String]) -> Result<Vec<(u64, u64)>, String> {
         .collect()
 }
 
+#[derive(Debug, Parser)]
+pub struct OutputSinkArgs {
+    /// Appends every mev block produced at tip to this file as newline
+    /// delimited json
+    #[arg(long = "output-ndjson")]
+    pub ndjson:      Option<PathBuf>,
+    /// Streams every mev block produced at tip as newline delimited json to
+    /// the clients connected to a unix socket at this path
+    #[arg(long = "output-unix-socket")]
+    pub unix_socket: Option<PathBuf>,
+    /// Serves a websocket json-rpc api on this address, where the mev blocks
+    /// produced at tip can be subscribed to with `mev_subscribe`
+    #[arg(long = "output-ws")]
+    pub ws:          Option<SocketAddr>,
+}
+
+impl OutputSinkArgs {
+    async fn init_sinks(
+        &self,
+        executor: &BrontesTaskExecutor,
+    ) -> eyre::Result<Vec<Box<dyn OutputSink>>> {
+        let mut sinks: Vec<Box<dyn OutputSink>> = Vec::new();
+
+        if let Some(path) = &self.ndjson {
+            sinks.push(Box::new(NdJsonFileSink::new(path, executor).await?));
+        }
+        if let Some(path) = &self.unix_socket {
+            sinks.push(Box::new(UnixSocketSink::new(path, executor)?));
+        }
+        if let Some(addr) = self.ws {
+            sinks.push(Box::new(WebsocketSink::new(addr).await?));
+        }
+
+        Ok(sinks)
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct TimeWindowArgs {
     /// The initial sliding time window (BEFORE) for cex prices or trades
@@ -386,10 +494,26 @@ pub struct TimeWindowArgs {
     /// Cex Dex Quotes price time offset from block timestamp
     #[arg(long = "quote-offset", default_value = "0.0")]
     pub quote_offset: f64,
+
+    /// Amount of blocks to accumulate the inventory of labelled cex-dex
+    /// searchers over before marking it out against later cex prices. Used by
+    /// the CexDexInventory inspector. Enabling this extends the cex trades
+    /// loaded to cover the markout horizons. Disabled when set to 0
+    #[arg(long = "inventory-window", default_value = "0")]
+    pub inventory_window: usize,
+
+    /// Seconds after the last block of the inventory window to mark the
+    /// inventory out at
+    #[arg(
+        long = "inventory-horizons",
+        value_delimiter = ',',
+        default_values_t = DEFAULT_INVENTORY_MARKOUT_HORIZONS
+    )]
+    pub inventory_horizons: Vec<u64>,
 }
 
 impl TimeWindowArgs {
-    fn trade_config(&self) -> CexDexTradeConfig {
+    pub(crate) fn trade_config(&self) -> CexDexTradeConfig {
         CexDexTradeConfig {
             initial_vwap_pre_block_us:  (self.initial_vwap_pre * SECONDS_TO_US_FLOAT) as u64,
             initial_vwap_post_block_us: (self.initial_vwap_post * SECONDS_TO_US_FLOAT) as u64,
@@ -417,6 +541,10 @@ impl TimeWindowArgs {
             pre_decay_weight_op:               self.pre_decay_weight_optimistic,
             post_decay_weight_op:              self.post_decay_weight_optimistic,
             quote_offset_from_block_us:        (self.quote_offset * SECONDS_TO_US_FLOAT) as u64,
+            inventory_window_blocks:           self.inventory_window,
+            inventory_markout_horizons:        Box::leak(
+                self.inventory_horizons.clone().into_boxed_slice(),
+            ),
         }
     }
 }
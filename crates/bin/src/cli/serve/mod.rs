@@ -0,0 +1,68 @@
+use std::{net::SocketAddr, path::Path, sync::Arc};
+
+use brontes_classifier::Classifier;
+use brontes_database::libmdbx::LibmdbxReadWriter;
+use brontes_types::init_thread_pools;
+use clap::Parser;
+use eyre::WrapErr;
+use jsonrpsee::server::Server;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::{
+    cli::{determine_max_tasks, get_env_vars, get_tracing_provider, static_object},
+    runner::CliContext,
+};
+
+mod rpc;
+pub use rpc::*;
+
+#[derive(Debug, Parser)]
+pub struct Serve {
+    /// Address to serve the json-rpc api on
+    #[arg(long, default_value = "127.0.0.1:8645")]
+    pub addr: SocketAddr,
+    /// Also serve `mev_getBundlesByTx` and `tree_getBlockTree`. These need
+    /// the reth db at `DB_PATH` to resolve the block of a tx and for the
+    /// block headers the trees are classified with
+    #[arg(long, default_value = "false")]
+    pub node: bool,
+}
+
+impl Serve {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let max_tasks = determine_max_tasks(None);
+        init_thread_pools(max_tasks as usize);
+
+        let libmdbx =
+            static_object(LibmdbxReadWriter::init_db_read_only(brontes_db_path, None, false)?);
+
+        let mut module = BrontesRpc::new(libmdbx).into_rpc()?.remove_context();
+        if self.node {
+            let db_path = get_env_vars()?;
+            let tracer = Arc::new(get_tracing_provider(
+                Path::new(&db_path),
+                max_tasks,
+                ctx.task_executor.clone(),
+            ));
+
+            // the block trees are built without pricing, we just need to drain the
+            // updates the classifier sends out
+            let (tx, mut rx) = unbounded_channel();
+            ctx.task_executor
+                .spawn(async move { while rx.recv().await.is_some() {} });
+
+            let classifier = Classifier::new(libmdbx, tx, tracer.clone());
+            module.merge(NodeRpc::new(libmdbx, tracer, classifier).into_rpc()?)?;
+        }
+
+        let server = Server::builder()
+            .build(self.addr)
+            .await
+            .wrap_err("Could not bind to address")?;
+
+        tracing::info!(target: "brontes", addr = %self.addr, "serving json-rpc api");
+        server.start(module).stopped().await;
+
+        Ok(())
+    }
+}
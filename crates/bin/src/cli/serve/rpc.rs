@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, B256};
+use brontes_classifier::Classifier;
+use brontes_database::libmdbx::{LibmdbxReadWriter, LibmdbxReader};
+use brontes_types::{
+    db::{dex::DexPrices, mev_block::MevBlockWithClassified, normalized_actions::TransactionRoot},
+    mev::{Bundle, Mev},
+    pair::Pair,
+    traits::TracingProvider,
+};
+use jsonrpsee::{
+    types::{error::INTERNAL_ERROR_CODE, ErrorObjectOwned},
+    RpcModule,
+};
+use serde_json::{json, Value};
+
+/// Serves the data brontes has written to libmdbx. Only needs the brontes db.
+pub struct BrontesRpc<DB: LibmdbxReader> {
+    libmdbx: &'static DB,
+}
+
+impl<DB: LibmdbxReader> BrontesRpc<DB> {
+    pub fn new(libmdbx: &'static DB) -> Self {
+        Self { libmdbx }
+    }
+
+    pub fn into_rpc(self) -> eyre::Result<RpcModule<Self>> {
+        let mut module = RpcModule::new(self);
+
+        module.register_blocking_method("mev_getBlock", |params, rpc| {
+            rpc.get_block(params.one()?).map_err(internal_error)
+        })?;
+        module.register_blocking_method("mev_getSearcher", |params, rpc| {
+            rpc.get_searcher(params.one()?).map_err(internal_error)
+        })?;
+        module.register_blocking_method("dex_getPrice", |params, rpc| {
+            let (block, tx_idx, pair) = params.parse()?;
+            rpc.get_dex_price(block, tx_idx, pair)
+                .map_err(internal_error)
+        })?;
+
+        Ok(module)
+    }
+
+    /// `mev_getBlock(number)`
+    fn get_block(&self, block: u64) -> eyre::Result<Option<MevBlockWithClassified>> {
+        Ok(self
+            .libmdbx
+            .try_fetch_mev_blocks(Some(block), block)?
+            .into_iter()
+            .find(|mev_block| mev_block.block.block_number == block))
+    }
+
+    /// `mev_getSearcher(address)`, the address can either be the searchers
+    /// eoa or contract.
+    fn get_searcher(&self, address: Address) -> eyre::Result<Value> {
+        let (eoa, contract) = self
+            .libmdbx
+            .try_fetch_searcher_info(address, Some(address))?;
+
+        Ok(json!({ "eoa": eoa, "contract": contract }))
+    }
+
+    /// `dex_getPrice(block, tx_idx, [token0, token1])`
+    fn get_dex_price(
+        &self,
+        block: u64,
+        tx_idx: usize,
+        pair: Pair,
+    ) -> eyre::Result<Option<DexPrices>> {
+        Ok(self.libmdbx.get_dex_quotes(block)?.price_at(pair, tx_idx))
+    }
+}
+
+/// Serves the methods that need the node. `mev_getBundlesByTx` resolves the
+/// block of the tx through it and `tree_getBlockTree` isn't stored, so it is
+/// classified from the stored traces, which needs the block header.
+pub struct NodeRpc<T: TracingProvider> {
+    libmdbx:    &'static LibmdbxReadWriter,
+    tracer:     Arc<T>,
+    classifier: Classifier<'static, T, LibmdbxReadWriter>,
+}
+
+impl<T: TracingProvider> NodeRpc<T> {
+    pub fn new(
+        libmdbx: &'static LibmdbxReadWriter,
+        tracer: Arc<T>,
+        classifier: Classifier<'static, T, LibmdbxReadWriter>,
+    ) -> Self {
+        Self { libmdbx, tracer, classifier }
+    }
+
+    pub fn into_rpc(self) -> eyre::Result<RpcModule<Self>> {
+        let mut module = RpcModule::new(self);
+
+        module.register_async_method("mev_getBundlesByTx", |params, rpc| async move {
+            let tx_hash = params.one()?;
+            rpc.get_bundles_by_tx(tx_hash).await.map_err(internal_error)
+        })?;
+        module.register_async_method("tree_getBlockTree", |params, rpc| async move {
+            let block = params.one()?;
+            rpc.get_block_tree(block).await.map_err(internal_error)
+        })?;
+
+        Ok(module)
+    }
+
+    /// `mev_getBundlesByTx(hash)`
+    async fn get_bundles_by_tx(&self, tx_hash: B256) -> eyre::Result<Vec<Bundle>> {
+        let (block, _) = self.tracer.block_and_tx_index(tx_hash).await?;
+        let libmdbx = self.libmdbx;
+
+        tokio::task::spawn_blocking(move || bundles_with_tx(libmdbx, block, tx_hash)).await?
+    }
+
+    /// `tree_getBlockTree(number)`
+    async fn get_block_tree(&self, block: u64) -> eyre::Result<Vec<TransactionRoot>> {
+        let mut traces = self.libmdbx.load_trace(block)?;
+        traces.sort_by(|a, b| a.tx_index.cmp(&b.tx_index));
+        traces.dedup_by(|a, b| a.tx_index.eq(&b.tx_index));
+
+        let header = self
+            .tracer
+            .header_by_number(block)
+            .await?
+            .ok_or_else(|| eyre::eyre!("no header found for block {block}"))?;
+
+        let tree = self
+            .classifier
+            .build_block_tree(traces, header, false)
+            .await;
+
+        Ok(tree
+            .tx_roots
+            .iter()
+            .map(|root| (root, block).into())
+            .collect())
+    }
+}
+
+/// The bundles of `block` that include `tx_hash`
+fn bundles_with_tx<DB: LibmdbxReader>(
+    libmdbx: &DB,
+    block: u64,
+    tx_hash: B256,
+) -> eyre::Result<Vec<Bundle>> {
+    Ok(libmdbx
+        .try_fetch_mev_blocks(Some(block), block)?
+        .into_iter()
+        .filter(|mev_block| mev_block.block.block_number == block)
+        .flat_map(|mev_block| mev_block.mev)
+        .filter(|bundle| bundle.data.mev_transaction_hashes().contains(&tx_hash))
+        .collect())
+}
+
+fn internal_error(e: eyre::Report) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_database::libmdbx::DBWriter;
+    use brontes_types::{
+        db::searcher::SearcherInfo,
+        mev::{AtomicArb, BundleData, BundleHeader, MevBlock},
+    };
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const BLOCK: u64 = 100;
+
+    fn tx_hash() -> B256 {
+        B256::with_last_byte(1)
+    }
+
+    /// The db lives in `dir`, which has to be kept around for as long as the db
+    /// is used
+    struct TestDb {
+        db:   &'static LibmdbxReadWriter,
+        _dir: TempDir,
+    }
+
+    async fn test_db() -> TestDb {
+        let dir = TempDir::new().unwrap();
+        let db: &'static LibmdbxReadWriter =
+            Box::leak(Box::new(LibmdbxReadWriter::init_db_tests(dir.path()).unwrap()));
+
+        let bundle = Bundle {
+            header: BundleHeader { block_number: BLOCK, tx_hash: tx_hash(), ..Default::default() },
+            data:   BundleData::AtomicArb(AtomicArb { tx_hash: tx_hash(), ..Default::default() }),
+        };
+        db.save_mev_blocks(
+            BLOCK,
+            MevBlock { block_number: BLOCK, ..Default::default() },
+            vec![bundle],
+        )
+        .await
+        .unwrap();
+        db.write_searcher_eoa_info(Address::with_last_byte(2), SearcherInfo::default())
+            .await
+            .unwrap();
+
+        TestDb { db, _dir: dir }
+    }
+
+    async fn request(module: &RpcModule<BrontesRpc<LibmdbxReadWriter>>, req: Value) -> Value {
+        let (res, _) = module.raw_json_request(&req.to_string(), 1).await.unwrap();
+        serde_json::from_str(&res).unwrap()
+    }
+
+    fn call(method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })
+    }
+
+    #[brontes_macros::test]
+    async fn test_dispatch() {
+        let test_db = test_db().await;
+        let module = BrontesRpc::new(test_db.db).into_rpc().unwrap();
+
+        let res = request(&module, call("mev_getBlock", json!([BLOCK]))).await;
+        assert_eq!(res["result"]["block"]["block_number"], BLOCK);
+        let res = request(&module, call("mev_getBlock", json!([BLOCK + 1]))).await;
+        assert!(res["result"].is_null());
+
+        let res =
+            request(&module, call("mev_getSearcher", json!([Address::with_last_byte(2)]))).await;
+        assert!(!res["result"]["eoa"].is_null());
+        assert!(res["result"]["contract"].is_null());
+
+        // these need the node, they are only served alongside a tracer
+        let res = request(&module, call("mev_getBundlesByTx", json!([tx_hash()]))).await;
+        assert_eq!(res["error"]["code"], -32601);
+        let res = request(&module, call("tree_getBlockTree", json!([BLOCK]))).await;
+        assert_eq!(res["error"]["code"], -32601);
+    }
+
+    #[brontes_macros::test]
+    async fn test_bundles_with_tx() {
+        let test_db = test_db().await;
+
+        assert_eq!(bundles_with_tx(test_db.db, BLOCK, tx_hash()).unwrap().len(), 1);
+        assert!(bundles_with_tx(test_db.db, BLOCK, B256::with_last_byte(9))
+            .unwrap()
+            .is_empty());
+        assert!(bundles_with_tx(test_db.db, BLOCK + 1, tx_hash())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[brontes_macros::test]
+    async fn test_param_parsing() {
+        let test_db = test_db().await;
+        let module = BrontesRpc::new(test_db.db).into_rpc().unwrap();
+        let invalid_params = |res: Value| assert_eq!(res["error"]["code"], -32602, "{res}");
+
+        invalid_params(request(&module, call("mev_getBlock", json!([]))).await);
+        invalid_params(request(&module, call("mev_getBlock", json!(["latest"]))).await);
+        invalid_params(request(&module, call("dex_getPrice", json!([BLOCK, 0]))).await);
+        invalid_params(request(&module, call("mev_getSearcher", json!(["0x01"]))).await);
+
+        let res = request(&module, call("mev_getBlocks", json!([BLOCK]))).await;
+        assert_eq!(res["error"]["code"], -32601);
+    }
+}
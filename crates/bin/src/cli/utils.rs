@@ -27,6 +27,7 @@ use brontes_types::{
     },
     db_write_trigger::HeartRateMonitor,
     mev::Bundle,
+    traits::BlockSimulator,
     BrontesTaskExecutor,
 };
 use itertools::Itertools;
@@ -136,6 +137,17 @@ pub fn get_tracing_provider(
     TracingClient::new(db_path, tracing_tasks, executor.clone())
 }
 
+/// Blocks can only be re-executed against a local reth db
+#[cfg(not(feature = "local-reth"))]
+pub fn get_block_simulator(_: &LocalProvider) -> Option<&'static dyn BlockSimulator> {
+    None
+}
+
+#[cfg(feature = "local-reth")]
+pub fn get_block_simulator(tracer: &TracingClient) -> Option<&'static dyn BlockSimulator> {
+    Some(static_object(tracer.clone()))
+}
+
 pub fn determine_max_tasks(max_tasks: Option<u64>) -> u64 {
     match max_tasks {
         Some(max_tasks) => max_tasks,
@@ -156,6 +168,8 @@ pub fn init_inspectors<DB: LibmdbxReader>(
     inspectors: Option<Vec<Inspectors>>,
     cex_exchanges: Vec<CexExchange>,
     trade_config: CexDexTradeConfig,
+    sandwich_block_window: usize,
+    simulator: Option<&'static dyn BlockSimulator>,
     metrics: bool,
 ) -> &'static [&'static dyn Inspector<Result = Vec<Bundle>>] {
     let mut res = Vec::new();
@@ -169,6 +183,8 @@ pub fn init_inspectors<DB: LibmdbxReader>(
             db,
             &cex_exchanges,
             trade_config,
+            sandwich_block_window,
+            simulator,
             metrics.clone(),
         ));
     }
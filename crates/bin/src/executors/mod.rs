@@ -28,11 +28,14 @@ use brontes_classifier::Classifier;
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::libmdbx::LibmdbxInit;
 use brontes_inspect::Inspector;
-use brontes_pricing::{BrontesBatchPricer, GraphManager, LoadState};
+use brontes_pricing::{
+    checkpoint::PRICING_CHECKPOINT_INTERVAL, uniswap_v4::register_pool_key, BrontesBatchPricer,
+    GraphManager, LoadState,
+};
 use brontes_types::{
     db::traits::LibmdbxReader, BrontesTaskExecutor, FastHashMap, UnboundedYapperReceiver,
 };
-use futures::{stream::FuturesUnordered, Future, StreamExt};
+use futures::{stream::FuturesUnordered, Future, StreamExt, TryStreamExt};
 use indicatif::MultiProgress;
 use itertools::Itertools;
 pub use range::RangeExecutorWithPricing;
@@ -65,6 +68,7 @@ pub struct BrontesRunConfig<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseH
     pub metrics: bool,
     pub is_snapshot: bool,
     pub cex_window: usize,
+    pub sinks: &'static [Box<dyn OutputSink>],
     _p: PhantomData<P>,
 }
 
@@ -88,6 +92,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         metrics: bool,
         is_snapshot: bool,
         cex_window: usize,
+        sinks: &'static [Box<dyn OutputSink>],
     ) -> Self {
         Self {
             clickhouse,
@@ -105,6 +110,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             tip_db,
             is_snapshot,
             cex_window,
+            sinks,
             _p: PhantomData,
         }
     }
@@ -141,8 +147,15 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     async fn build_internal(self, executor: BrontesTaskExecutor) -> eyre::Result<Brontes> {
         let futures = FuturesUnordered::new();
 
+        // v4 pools are loaded through their key, which the classifier only sees
+        // when the pool is touched
+        for key in self.libmdbx.load_uniswap_v4_pool_keys()? {
+            register_pool_key(key);
+        }
+
         let pricing_metrics = self.metrics.then(DexPricingMetrics::default);
         let (should_run_tip_inspector, end_block) = self.should_run_tip_inspector().await;
+        let range_start_block = self.range_type.get_start_block(self.libmdbx);
 
         if self.is_snapshot {
             let (start_block, db_end_block) = self.libmdbx.get_db_range()?;
@@ -161,38 +174,41 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
 
         if !should_run_tip_inspector {
             self.build_range_executors(executor.clone(), end_block, pricing_metrics.clone())
-                .for_each(|block_range| {
+                .try_for_each(|block_range| {
                     futures.push(executor.spawn_critical_with_graceful_shutdown_signal(
                         "Range Executor",
                         |shutdown| async move {
                             block_range.run_until_graceful_shutdown(shutdown).await;
                         },
                     ));
-                    std::future::ready(())
+                    std::future::ready(Ok(()))
                 })
-                .await;
+                .await?;
         } else {
             if self.range_type.get_start_block(self.libmdbx).is_some() {
                 self.build_range_executors(executor.clone(), end_block, pricing_metrics.clone())
-                    .for_each(|block_range| {
+                    .try_for_each(|block_range| {
                         futures.push(executor.spawn_critical_with_graceful_shutdown_signal(
                             "Range Executor",
                             |shutdown| async move {
                                 block_range.run_until_graceful_shutdown(shutdown).await;
                             },
                         ));
-                        std::future::ready(())
+                        std::future::ready(Ok(()))
                     })
-                    .await;
+                    .await?;
             }
             tracing::info!("starting tip inspector");
-            let tip_inspector = self.build_tip_inspector(
+            let back_from_tip = self.range_type.back_from_tip();
+            // the tip inspector needs the config to rebuild its state on a reorg
+            let this = static_object(self);
+            let tip_inspector = this.build_tip_inspector(
                 usize::MAX,
                 executor.clone(),
                 end_block,
-                self.range_type.back_from_tip(),
+                back_from_tip,
                 pricing_metrics,
-            );
+            )?;
 
             futures.push(executor.spawn_critical_with_graceful_shutdown_signal(
                 "Tip Inspector",
@@ -202,13 +218,9 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
 
         let metrics = FinishedRange::default();
         metrics.running_ranges.increment(futures.len() as f64);
-        metrics.total_set_range.increment(
-            end_block
-                - self
-                    .range_type
-                    .get_start_block(self.libmdbx)
-                    .unwrap_or(end_block),
-        );
+        metrics
+            .total_set_range
+            .increment(end_block - range_start_block.unwrap_or(end_block));
 
         Ok(Brontes { futures, metrics })
     }
@@ -234,7 +246,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     /// # Returns
     ///
     /// Returns a `Stream` that yields `RangeExecutorWithPricing<T, DB, CH, P>`
-    /// instances.
+    /// instances, or the error that occurred while initializing one.
     ///
     /// # Notes
     ///
@@ -246,7 +258,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         executor: BrontesTaskExecutor,
         end_block: u64,
         pricing_metrics: Option<DexPricingMetrics>,
-    ) -> impl Stream<Item = RangeExecutorWithPricing<T, DB, CH, P>> + '_ {
+    ) -> impl Stream<Item = eyre::Result<RangeExecutorWithPricing<T, DB, CH, P>>> + '_ {
         let chunks = match &self.range_type {
             RangeType::SingleRange { start_block, from_db_tip, .. } => {
                 let start_block = if *from_db_tip {
@@ -315,24 +327,26 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                             .unwrap();
                     }
 
+                    let state_collector = self.init_state_collector(
+                        batch_id,
+                        executor.clone(),
+                        start_block,
+                        end_block,
+                        false,
+                        pricing_metrics,
+                    )?;
+
                     #[allow(clippy::async_yields_async)]
-                    RangeExecutorWithPricing::new(
+                    Ok(RangeExecutorWithPricing::new(
                         batch_id,
                         start_block,
                         end_block,
-                        self.init_state_collector(
-                            batch_id,
-                            executor.clone(),
-                            start_block,
-                            end_block,
-                            false,
-                            pricing_metrics,
-                        ),
+                        state_collector,
                         self.libmdbx,
                         self.inspectors,
                         prgrs_bar,
                         metrics,
-                    )
+                    ))
                 }
             },
         ))
@@ -340,29 +354,32 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     }
 
     fn build_tip_inspector(
-        &self,
+        &'static self,
         range_id: usize,
         executor: BrontesTaskExecutor,
         start_block: u64,
         back_from_tip: u64,
         pricing_metrics: Option<DexPricingMetrics>,
-    ) -> TipInspector<T, DB, CH, P> {
+    ) -> eyre::Result<TipInspector<T, DB, CH, P>> {
         let state_collector = self.init_state_collector(
             range_id,
-            executor,
+            executor.clone(),
             start_block,
             start_block,
             true,
-            pricing_metrics,
-        );
-        TipInspector::new(
+            pricing_metrics.clone(),
+        )?;
+        Ok(TipInspector::new(
             start_block,
             back_from_tip,
             state_collector,
             self.parser,
             self.tip_db,
             self.inspectors,
-        )
+            self,
+            executor,
+            pricing_metrics,
+        ))
     }
 
     /// Initializes a StateCollector for a specific range of blocks.
@@ -383,7 +400,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     /// # Returns
     ///
     /// Returns a `StateCollector<T, DB, CH>` initialized with the specified
-    /// parameters.
+    /// parameters, or an error if the pools of the range can't be loaded.
     fn init_state_collector(
         &self,
         range_id: usize,
@@ -392,17 +409,24 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         end_block: u64,
         tip: bool,
         pricing_metrics: Option<DexPricingMetrics>,
-    ) -> StateCollector<T, DB, CH> {
+    ) -> eyre::Result<StateCollector<T, DB, CH>> {
         let shutdown = Arc::new(AtomicBool::new(false));
         let (tx, rx) = unbounded_channel();
         let classifier = static_object(Classifier::new(self.libmdbx, tx, self.parser.get_tracer()));
 
-        let pairs = self.libmdbx.protocols_created_before(start_block).unwrap();
+        let checkpoint = (!tip)
+            .then(|| resume_checkpoint(self.libmdbx, start_block))
+            .flatten();
+        let resume_block = checkpoint
+            .as_ref()
+            .map(|checkpoint| checkpoint.block)
+            .unwrap_or(start_block);
+
+        let pairs = self.libmdbx.protocols_created_before(resume_block)?;
 
         let rest_pairs = self
             .libmdbx
-            .protocols_created_range(start_block + 1, end_block)
-            .unwrap()
+            .protocols_created_range(resume_block + 1, end_block)?
             .into_iter()
             .flat_map(|(_, pools)| {
                 pools
@@ -413,7 +437,22 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             })
             .collect::<FastHashMap<_, _>>();
 
-        let pair_graph = GraphManager::init_from_db_state(pairs, pricing_metrics.clone());
+        let mut pair_graph = GraphManager::init_from_db_state(pairs, pricing_metrics.clone());
+        if let Some(checkpoint) = checkpoint {
+            tracing::info!(%range_id, %resume_block, "resuming dex pricing from checkpoint");
+            pair_graph.restore_checkpoint(checkpoint);
+        }
+
+        let (checkpoint_tx, mut checkpoint_rx) = unbounded_channel();
+        let libmdbx = self.libmdbx;
+        executor.spawn(async move {
+            while let Some(checkpoint) = checkpoint_rx.recv().await {
+                let block = checkpoint.block;
+                if let Err(e) = libmdbx.save_pricing_checkpoint(checkpoint) {
+                    tracing::error!(%block, error = %e, "failed to save pricing checkpoint");
+                }
+            }
+        });
 
         let data_req = Arc::new(AtomicBool::new(true));
 
@@ -424,12 +463,13 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             pair_graph,
             UnboundedYapperReceiver::new(rx, 100_000, "batch pricer".into()),
             self.parser.get_tracer(),
-            start_block,
+            resume_block,
             rest_pairs,
             data_req.clone(),
             pricing_metrics.clone(),
             executor.clone(),
-        );
+        )
+        .with_checkpoints(checkpoint_tx);
 
         let pricing = WaitingForPricerFuture::new(pricer, executor);
         let fetcher = MetadataLoader::new(
@@ -450,7 +490,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
 
         let window = MultiBlockWindow::new(block_window_size);
 
-        StateCollector::new(
+        Ok(StateCollector::new(
             shutdown,
             fetcher,
             classifier,
@@ -459,6 +499,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             window,
             self.quote_asset,
         )
+        .with_catch_up(resume_block..start_block))
     }
 
     async fn init_block_range_tables(
@@ -630,6 +671,18 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     }
 }
 
+/// The last pricing checkpoint before `start_block`, as long as catching up
+/// from it is cheaper than building the subgraphs again.
+fn resume_checkpoint<DB: LibmdbxInit>(db: &DB, start_block: u64) -> Option<PricingCheckpoint> {
+    db.load_pricing_checkpoint(start_block)
+        .inspect_err(
+            |e| tracing::warn!(%start_block, error = %e, "failed to load pricing checkpoint"),
+        )
+        .ok()
+        .flatten()
+        .filter(|checkpoint| checkpoint.block + PRICING_CHECKPOINT_INTERVAL > start_block)
+}
+
 #[cfg(feature = "sorella-server")]
 fn calculate_buffer_size(state_to_init: &StateToInitialize, max_tasks: usize) -> usize {
     if state_to_init.ranges_to_init.is_empty() {
@@ -703,3 +756,78 @@ impl RangeType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use brontes_database::libmdbx::{DBWriter, LibmdbxReadWriter};
+    use brontes_pricing::{
+        checkpoint::{PoolStatesCheckpoint, SubGraphsCheckpoint},
+        protocols::uniswap_v2::UniswapV2Pool,
+        types::{PoolState, PoolVariants},
+    };
+
+    use super::*;
+
+    fn checkpoint(block: u64) -> PricingCheckpoint {
+        let pool = UniswapV2Pool {
+            address: Address::with_last_byte(1),
+            token_a: Address::with_last_byte(2),
+            token_b: Address::with_last_byte(3),
+            reserve_0: block as u128,
+            reserve_1: 1,
+            ..Default::default()
+        };
+
+        PricingCheckpoint {
+            block,
+            sub_graphs: SubGraphsCheckpoint::default(),
+            pool_states: PoolStatesCheckpoint {
+                states: vec![PoolState::new(PoolVariants::UniswapV2(pool), block - 1)],
+            },
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_resume_from_pricing_checkpoint() {
+        let path = std::env::temp_dir().join(format!("brontes-checkpoint-{}", std::process::id()));
+        let db = LibmdbxReadWriter::init_db_tests(&path).unwrap();
+
+        let interval = PRICING_CHECKPOINT_INTERVAL;
+        for block in [interval, 2 * interval] {
+            db.save_pricing_checkpoint(checkpoint(block)).unwrap();
+        }
+
+        // the checkpoint round trips through libmdbx
+        assert_eq!(db.load_pricing_checkpoint(interval).unwrap(), Some(checkpoint(interval)));
+
+        // a range resumes from the latest checkpoint at or before its start
+        let resumed = resume_checkpoint(&db, 2 * interval + 10).unwrap();
+        assert_eq!(resumed, checkpoint(2 * interval));
+        let resumed = resume_checkpoint(&db, 2 * interval - 1).unwrap();
+        assert_eq!(resumed, checkpoint(interval));
+
+        // too far from the last checkpoint or before the first one
+        assert!(resume_checkpoint(&db, 3 * interval).is_none());
+        assert!(resume_checkpoint(&db, interval - 1).is_none());
+
+        let _ = std::fs::remove_dir_all(path);
+    }
+
+    #[brontes_macros::test]
+    async fn test_rollback_drops_pricing_checkpoints() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path()).unwrap();
+
+        let interval = PRICING_CHECKPOINT_INTERVAL;
+        for block in [interval, 2 * interval, 3 * interval] {
+            db.save_pricing_checkpoint(checkpoint(block)).unwrap();
+        }
+
+        // the checkpoints at and after the reorged block are orphaned
+        db.delete_block_data(2 * interval).await.unwrap();
+
+        assert_eq!(db.load_pricing_checkpoint(3 * interval).unwrap(), Some(checkpoint(interval)));
+        assert!(resume_checkpoint(&db, 2 * interval + 10).is_none());
+        assert_eq!(resume_checkpoint(&db, 2 * interval - 1), Some(checkpoint(interval)));
+    }
+}
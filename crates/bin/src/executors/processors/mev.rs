@@ -15,14 +15,14 @@ use brontes_types::normalized_actions::Action;
 #[cfg(feature = "local-clickhouse")]
 use brontes_types::tree::BlockTree;
 use brontes_types::{
-    db::block_analysis::BlockAnalysis,
+    db::{block_analysis::BlockAnalysis, mev_block::MevBlockWithClassified},
     execute_on,
     mev::{Bundle, MevBlock, MevType},
     BlockData, MultiBlockData,
 };
 use tracing::debug;
 
-use crate::Processor;
+use crate::{OutputSink, Processor};
 
 #[derive(Debug, Clone, Copy)]
 pub struct MevProcessor;
@@ -34,6 +34,7 @@ impl Processor for MevProcessor {
     async fn process_results<DB: DBWriter + LibmdbxReader>(
         db: &'static DB,
         inspectors: &'static [&dyn Inspector<Result = Self::InspectType>],
+        sinks: &'static [Box<dyn OutputSink>],
         data: MultiBlockData,
     ) {
         let last = data.get_most_recent_block().clone();
@@ -54,7 +55,7 @@ impl Processor for MevProcessor {
         let ComposerResults { block_details, mev_details, block_analysis, .. } =
             execute_on!(async_inspect, { run_block_inspection(inspectors, data, db) }).await;
 
-        insert_mev_results(db, block_details, mev_details, block_analysis).await;
+        insert_mev_results(db, sinks, block_details, mev_details, block_analysis).await;
     }
 }
 
@@ -76,6 +77,7 @@ async fn insert_tree<DB: DBWriter + LibmdbxReader>(
 
 async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
     database: &'static DB,
+    sinks: &'static [Box<dyn OutputSink>],
     block_details: MevBlock,
     mev_details: Vec<Bundle>,
     analysis: BlockAnalysis,
@@ -87,6 +89,11 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
     );
 
     let block_number = block_details.block_number;
+    if !sinks.is_empty() {
+        let output =
+            MevBlockWithClassified { block: block_details.clone(), mev: mev_details.clone() };
+        sinks.iter().for_each(|sink| sink.publish(&output));
+    }
     output_mev_and_update_searcher_info(database, &mev_details).await;
 
     // Attempt to save the MEV block details
@@ -119,7 +126,11 @@ async fn output_mev_and_update_searcher_info<DB: DBWriter + LibmdbxReader>(
             mev.to_string()
         );
 
-        if mev.header.mev_type == MevType::Unknown || mev.header.mev_type == MevType::SearcherTx {
+        // the inventory bundles re-count the txs of cex-dex bundles
+        if matches!(
+            mev.header.mev_type,
+            MevType::Unknown | MevType::SearcherTx | MevType::CexDexInventory
+        ) {
             continue
         }
 
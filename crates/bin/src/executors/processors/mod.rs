@@ -1,10 +1,12 @@
 pub mod mev;
+pub mod sink;
 
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
 use brontes_inspect::Inspector;
 use brontes_types::MultiBlockData;
 use futures::Future;
 pub use mev::*;
+pub use sink::*;
 
 pub trait Processor: Send + Sync + 'static + Unpin + Copy + Clone {
     type InspectType: Send + Sync + Unpin;
@@ -12,6 +14,7 @@ pub trait Processor: Send + Sync + 'static + Unpin + Copy + Clone {
     fn process_results<DB: DBWriter + LibmdbxReader>(
         db: &'static DB,
         inspectors: &'static [&dyn Inspector<Result = Self::InspectType>],
+        sinks: &'static [Box<dyn OutputSink>],
         data: MultiBlockData,
     ) -> impl Future<Output = ()> + Send;
 }
@@ -0,0 +1,263 @@
+use std::{net::SocketAddr, path::Path, sync::Arc};
+
+use brontes_types::{db::mev_block::MevBlockWithClassified, BrontesTaskExecutor};
+use jsonrpsee::{
+    core::SubscriptionResult,
+    server::{Server, ServerHandle},
+    PendingSubscriptionSink, RpcModule, SubscriptionMessage,
+};
+use tokio::{
+    fs::OpenOptions,
+    io::AsyncWriteExt,
+    net::UnixListener,
+    sync::{
+        broadcast::{self, error::RecvError},
+        mpsc::{unbounded_channel, UnboundedSender},
+    },
+};
+
+/// Amount of blocks a subscriber can fall behind before it starts missing
+/// blocks.
+const SUBSCRIBER_BUFFER: usize = 64;
+
+/// Receives every mev block as it is produced at tip, alongside it being
+/// written to the db. Publishing happens while processing the block, so
+/// implementations must not block.
+pub trait OutputSink: Send + Sync + 'static {
+    fn publish(&self, block: &MevBlockWithClassified);
+}
+
+/// Appends every mev block to a file as a line of json.
+pub struct NdJsonFileSink {
+    tx: UnboundedSender<String>,
+}
+
+impl NdJsonFileSink {
+    pub async fn new(path: impl AsRef<Path>, executor: &BrontesTaskExecutor) -> eyre::Result<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+
+        let (tx, mut rx) = unbounded_channel::<String>();
+        executor.spawn(async move {
+            while let Some(line) = rx.recv().await {
+                let res = match file.write_all(line.as_bytes()).await {
+                    Ok(()) => file.flush().await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = res {
+                    tracing::error!(err=%e, "failed to write mev block to ndjson file");
+                }
+            }
+        });
+
+        Ok(Self { tx })
+    }
+}
+
+impl OutputSink for NdJsonFileSink {
+    fn publish(&self, block: &MevBlockWithClassified) {
+        if let Some(line) = to_json_line(block) {
+            let _ = self.tx.send(line);
+        }
+    }
+}
+
+/// Streams every mev block as a line of json to all clients connected to a
+/// unix socket.
+pub struct UnixSocketSink {
+    tx: broadcast::Sender<Arc<String>>,
+}
+
+impl UnixSocketSink {
+    pub fn new(path: impl AsRef<Path>, executor: &BrontesTaskExecutor) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        // the socket file of a previous run is left behind
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        let listener = UnixListener::bind(path)?;
+
+        let (tx, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+        let subscribers = tx.clone();
+        let conn_executor = executor.clone();
+        executor.spawn(async move {
+            loop {
+                let mut stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        tracing::error!(err=%e, "failed to accept unix socket connection");
+                        continue
+                    }
+                };
+
+                let mut rx = subscribers.subscribe();
+                conn_executor.spawn(async move {
+                    loop {
+                        match rx.recv().await {
+                            Ok(line) => {
+                                if stream.write_all(line.as_bytes()).await.is_err() {
+                                    break
+                                }
+                            }
+                            Err(RecvError::Lagged(skipped)) => {
+                                tracing::warn!(%skipped, "unix socket client is lagging behind");
+                            }
+                            Err(RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+        });
+
+        Ok(Self { tx })
+    }
+}
+
+impl OutputSink for UnixSocketSink {
+    fn publish(&self, block: &MevBlockWithClassified) {
+        if self.tx.receiver_count() == 0 {
+            return
+        }
+
+        if let Some(line) = to_json_line(block) {
+            let _ = self.tx.send(Arc::new(line));
+        }
+    }
+}
+
+/// Serves a websocket json-rpc subscription, `mev_subscribe`, that pushes every
+/// mev block to its subscribers.
+pub struct WebsocketSink {
+    tx:      broadcast::Sender<Arc<MevBlockWithClassified>>,
+    // the server is stopped once the handle is dropped
+    _server: ServerHandle,
+}
+
+impl WebsocketSink {
+    pub async fn new(addr: SocketAddr) -> eyre::Result<Self> {
+        let (tx, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+
+        let mut module = RpcModule::new(tx.clone());
+        module.register_subscription(
+            "mev_subscribe",
+            "mev_subscription",
+            "mev_unsubscribe",
+            |_, pending, tx| subscribe_mev(pending, tx),
+        )?;
+
+        let server = Server::builder().build(addr).await?;
+        tracing::info!(target: "brontes", %addr, "serving mev subscriptions");
+
+        Ok(Self { tx, _server: server.start(module) })
+    }
+}
+
+impl OutputSink for WebsocketSink {
+    fn publish(&self, block: &MevBlockWithClassified) {
+        if self.tx.receiver_count() == 0 {
+            return
+        }
+
+        let _ = self.tx.send(Arc::new(block.clone()));
+    }
+}
+
+async fn subscribe_mev(
+    pending: PendingSubscriptionSink,
+    tx: Arc<broadcast::Sender<Arc<MevBlockWithClassified>>>,
+) -> SubscriptionResult {
+    let mut rx = tx.subscribe();
+    let sink = pending.accept().await?;
+
+    loop {
+        match rx.recv().await {
+            Ok(block) => {
+                let msg = SubscriptionMessage::from_json(&*block)?;
+                if sink.send(msg).await.is_err() {
+                    break
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!(%skipped, "websocket subscriber is lagging behind");
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+
+    Ok(())
+}
+
+fn to_json_line(block: &MevBlockWithClassified) -> Option<String> {
+    match serde_json::to_string(block) {
+        Ok(mut line) => {
+            line.push('\n');
+            Some(line)
+        }
+        Err(e) => {
+            tracing::error!(err=%e, block=block.block.block_number, "failed to serialize mev block");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::{
+        mev::{Bundle, BundleData, BundleHeader, MevBlock, MevType},
+        BrontesTaskManager,
+    };
+
+    use super::*;
+
+    fn mev_block(block_number: u64) -> MevBlockWithClassified {
+        MevBlockWithClassified {
+            block: MevBlock { block_number, ..Default::default() },
+            mev:   vec![Bundle {
+                header: BundleHeader {
+                    block_number,
+                    mev_type: MevType::AtomicArb,
+                    ..Default::default()
+                },
+                data:   BundleData::default(),
+            }],
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_ndjson_sink_writes_a_line_per_block() {
+        let path = std::env::temp_dir().join(format!("brontes-sink-{}.ndjson", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let executor = BrontesTaskManager::current().executor();
+        let sink = NdJsonFileSink::new(&path, &executor).await.unwrap();
+        sink.publish(&mev_block(1));
+        sink.publish(&mev_block(2));
+        drop(sink);
+
+        // the writer task exits once the sink is dropped and the lines are written
+        let mut contents = String::new();
+        for _ in 0..50 {
+            contents = tokio::fs::read_to_string(&path).await.unwrap();
+            if contents.lines().count() == 2 {
+                break
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        assert!(contents.ends_with('\n'));
+        let blocks = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(blocks.len(), 2);
+        for (block, expected) in blocks.iter().zip([1, 2]) {
+            assert_eq!(block["block"]["block_number"], expected);
+            assert_eq!(block["mev"].as_array().unwrap().len(), 1);
+        }
+
+        let _ = std::fs::remove_file(path);
+    }
+}
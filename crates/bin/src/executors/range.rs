@@ -52,11 +52,14 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
         progress_bar: Option<ProgressBar>,
         global_metrics: Option<GlobalRangeMetrics>,
     ) -> Self {
+        // a range that resumes from a pricing checkpoint first catches the
+        // pricer up to the start of the range
+        let current_block = state_collector.first_block().unwrap_or(start_block);
         Self {
             id,
             collector: state_collector,
             insert_futures: FuturesUnordered::default(),
-            current_block: start_block,
+            current_block,
             end_block,
             libmdbx,
             inspectors,
@@ -98,13 +101,16 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
         let metrics = self.global_metrics.clone();
         let inspectors = self.inspectors;
         let libmdbx = self.libmdbx;
+        // only the mev produced at tip is streamed to the output sinks
         self.insert_futures.push(Box::pin(async move {
             if let Some(metrics) = metrics {
                 metrics
-                    .meter_processing(|| Box::pin(P::process_results(libmdbx, inspectors, data)))
+                    .meter_processing(|| {
+                        Box::pin(P::process_results(libmdbx, inspectors, &[], data))
+                    })
                     .await
             } else {
-                P::process_results(libmdbx, inspectors, data).await
+                P::process_results(libmdbx, inspectors, &[], data).await
             }
         }));
     }
@@ -129,7 +135,11 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
             self.collector.fetch_state_for(block, id, metrics);
 
             self.current_block += 1;
-            if let Some(pb) = self.progress_bar.as_ref() {
+            if let Some(pb) = self
+                .progress_bar
+                .as_ref()
+                .filter(|_| !self.collector.is_catching_up(block))
+            {
                 pb.inc(1)
             };
         }
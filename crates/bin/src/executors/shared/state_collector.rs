@@ -1,4 +1,5 @@
 use std::{
+    ops::Range,
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering::SeqCst},
@@ -41,6 +42,9 @@ pub struct StateCollector<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH:
     collection_future: Option<CollectionFut<'static>>,
     multi_block:       MultiBlockWindow,
     quote_asset:       Address,
+    /// Blocks between a pricing checkpoint and the start of the range. They
+    /// only advance the state of the pricer and aren't inspected
+    catch_up:          Option<Range<u64>>,
 }
 
 impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>
@@ -64,9 +68,27 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>
             collection_future: None,
             multi_block,
             quote_asset,
+            catch_up: None,
         }
     }
 
+    pub fn with_catch_up(mut self, blocks: Range<u64>) -> Self {
+        self.catch_up = (!blocks.is_empty()).then_some(blocks);
+        self
+    }
+
+    /// The block the range starts collecting state at, which is before the
+    /// start of the range when it resumes from a pricing checkpoint
+    pub fn first_block(&self) -> Option<u64> {
+        self.catch_up.as_ref().map(|blocks| blocks.start)
+    }
+
+    pub fn is_catching_up(&self, block: u64) -> bool {
+        self.catch_up
+            .as_ref()
+            .is_some_and(|blocks| blocks.contains(&block))
+    }
+
     pub fn get_shutdown(&self) -> Arc<AtomicBool> {
         self.mark_as_finished.clone()
     }
@@ -118,7 +140,13 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>
     pub fn fetch_state_for(&mut self, block: u64, id: usize, metrics: Option<GlobalRangeMetrics>) {
         let execute_fut = self.parser.execute(block, id, metrics.clone());
 
-        let generate_pricing = self.metadata_fetcher.generate_dex_pricing(block, self.db);
+        // catch up blocks only update the pool states of the pricer and never
+        // reach the range executor
+        let catching_up = self.is_catching_up(block);
+        let metrics = metrics.filter(|_| !catching_up);
+
+        let generate_pricing =
+            !catching_up && self.metadata_fetcher.generate_dex_pricing(block, self.db);
         self.collection_future = Some(Box::pin(
             Self::state_future(generate_pricing, block, execute_fut, self.classifier, id, metrics)
                 .instrument(span!(Level::ERROR, "mev processor", block_number=%block)),
@@ -143,6 +171,9 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle> Str
     ) -> std::task::Poll<Option<Self::Item>> {
         if let Some(mut collection_future) = self.collection_future.take() {
             match collection_future.poll_unpin(cx) {
+                Poll::Ready(Ok((_, tree))) if self.is_catching_up(tree.header.number) => {
+                    cx.waker().wake_by_ref();
+                }
                 Poll::Ready(Ok((block_hash, tree))) => {
                     let db = self.db;
                     let quote_asset = self.quote_asset;
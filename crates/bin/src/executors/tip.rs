@@ -1,45 +1,75 @@
 use std::{
+    collections::VecDeque,
     marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
     time::Duration,
 };
 
+use alloy_primitives::BlockHash;
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::{
     clickhouse::ClickhouseHandle,
-    libmdbx::{DBWriter, LibmdbxReader},
+    libmdbx::{DBWriter, LibmdbxInit},
 };
 use brontes_inspect::Inspector;
-use brontes_types::MultiBlockData;
-use futures::{pin_mut, stream::FuturesUnordered, Future, StreamExt};
+use brontes_metrics::pricing::DexPricingMetrics;
+use brontes_types::{BrontesTaskExecutor, MultiBlockData};
+use futures::{pin_mut, stream::FuturesUnordered, Future, FutureExt, StreamExt};
 use reth_tasks::shutdown::GracefulShutdown;
 use tokio::time::{interval, Interval};
 use tracing::debug;
 
-use super::shared::state_collector::StateCollector;
+use super::{shared::state_collector::StateCollector, BrontesRunConfig};
 use crate::Processor;
 
-pub struct TipInspector<
-    T: TracingProvider,
-    DB: LibmdbxReader + DBWriter,
-    CH: ClickhouseHandle,
-    P: Processor,
-> {
+/// the max amount of processed blocks we keep the hashes of. Any reorg deeper
+/// than this won't be detected
+const MAX_REORG_DEPTH: usize = 64;
+
+type BoxedFuture<O> = Pin<Box<dyn Future<Output = O> + Send + 'static>>;
+
+enum ReorgState {
+    Idle,
+    /// comparing the hashes of the processed blocks against the canonical
+    /// chain. resolves to the first block that was reorged out if any
+    Checking(BoxedFuture<Option<u64>>),
+    /// a reorg was detected from the given block onwards. waiting for all
+    /// in flight block processing to finish before removing the stale data
+    Draining(u64),
+    /// removing the stale data for all blocks from the given block onwards
+    RollingBack(u64, BoxedFuture<()>),
+}
+
+impl ReorgState {
+    fn is_rolling_back(&self) -> bool {
+        matches!(self, Self::Draining(_) | Self::RollingBack(..))
+    }
+}
+
+pub struct TipInspector<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor> {
     current_block:      u64,
     back_from_tip:      u64,
     parser:             &'static Parser<T, DB>,
     state_collector:    StateCollector<T, DB, CH>,
     database:           &'static DB,
     inspectors:         &'static [&'static dyn Inspector<Result = P::InspectType>],
-    processing_futures: FuturesUnordered<Pin<Box<dyn Future<Output = ()> + Send + 'static>>>,
+    processing_futures: FuturesUnordered<BoxedFuture<()>>,
     poll_interval:      Interval,
+    /// used to rebuild the state collector after a reorg
+    config:             &'static BrontesRunConfig<T, DB, CH, P>,
+    executor:           BrontesTaskExecutor,
+    pricing_metrics:    Option<DexPricingMetrics>,
+    /// hashes of the most recently processed blocks, oldest first
+    processed_blocks:   VecDeque<(u64, BlockHash)>,
+    reorg_state:        ReorgState,
     _p:                 PhantomData<P>,
 }
 
-impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P: Processor>
+impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     TipInspector<T, DB, CH, P>
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         current_block: u64,
         back_from_tip: u64,
@@ -47,6 +77,9 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         parser: &'static Parser<T, DB>,
         database: &'static DB,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
+        config: &'static BrontesRunConfig<T, DB, CH, P>,
+        executor: BrontesTaskExecutor,
+        pricing_metrics: Option<DexPricingMetrics>,
     ) -> Self {
         Self {
             back_from_tip,
@@ -57,6 +90,11 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
             processing_futures: FuturesUnordered::new(),
             database,
             poll_interval: interval(Duration::from_secs(3)),
+            config,
+            executor,
+            pricing_metrics,
+            processed_blocks: VecDeque::with_capacity(MAX_REORG_DEPTH),
+            reorg_state: ReorgState::Idle,
             _p: PhantomData,
         }
     }
@@ -116,15 +154,148 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
 
     fn on_price_finish(&mut self, data: MultiBlockData) {
         debug!(target:"brontes::tip_inspector","Completed DEX pricing");
+        let block = data.get_most_recent_block();
+        // metadata without a block hash can't be checked for reorgs
+        if !block.metadata.block_hash.is_zero() {
+            if self.processed_blocks.len() == MAX_REORG_DEPTH {
+                self.processed_blocks.pop_front();
+            }
+            self.processed_blocks
+                .push_back((block.metadata.block_num, block.metadata.block_hash.into()));
+        }
+
         self.processing_futures.push(Box::pin(P::process_results(
             self.database,
             self.inspectors,
+            self.config.sinks,
             data,
         )));
     }
+
+    /// starts comparing the processed blocks against the canonical chain
+    fn check_for_reorg(&mut self) {
+        if !matches!(self.reorg_state, ReorgState::Idle) || self.processed_blocks.is_empty() {
+            return
+        }
+
+        let parser = self.parser;
+        let processed = self.processed_blocks.clone();
+        self.reorg_state =
+            ReorgState::Checking(Box::pin(find_fork_block(processed, move |block| {
+                parser.get_block_hash_for_number(block)
+            })));
+    }
+
+    /// drives the reorg state machine. On a detected reorg, the state collector
+    /// is restarted from the fork block, and once all in flight blocks have
+    /// been processed, the data for the reorged blocks is removed and they
+    /// are queued to be processed again.
+    fn poll_reorg(&mut self, cx: &mut Context<'_>) {
+        loop {
+            match &mut self.reorg_state {
+                ReorgState::Idle => return,
+                ReorgState::Checking(fut) => {
+                    let Poll::Ready(fork_block) = fut.poll_unpin(cx) else { return };
+                    let Some(fork_block) = fork_block else {
+                        self.reorg_state = ReorgState::Idle;
+                        return
+                    };
+                    tracing::warn!(
+                        %fork_block,
+                        current_block=%self.current_block,
+                        "detected chain reorg, rolling back"
+                    );
+
+                    let state_collector = match self.config.init_state_collector(
+                        usize::MAX,
+                        self.executor.clone(),
+                        fork_block,
+                        fork_block,
+                        true,
+                        self.pricing_metrics.clone(),
+                    ) {
+                        Ok(state_collector) => state_collector,
+                        Err(e) => {
+                            tracing::error!(
+                                %fork_block,
+                                error = %e,
+                                "failed to rebuild the state collector, skipping the rollback"
+                            );
+                            self.reorg_state = ReorgState::Idle;
+                            return
+                        }
+                    };
+                    self.state_collector.range_finished(cx.waker());
+                    self.state_collector = state_collector;
+                    self.processed_blocks
+                        .retain(|(block, _)| *block < fork_block);
+                    self.reorg_state = ReorgState::Draining(fork_block);
+                }
+                ReorgState::Draining(fork_block) => {
+                    if !self.processing_futures.is_empty() {
+                        return
+                    }
+                    let fork_block = *fork_block;
+                    let database = self.database;
+                    let end_block = self.current_block;
+
+                    self.reorg_state = ReorgState::RollingBack(
+                        fork_block,
+                        Box::pin(roll_back_blocks(database, fork_block, end_block)),
+                    );
+                }
+                ReorgState::RollingBack(fork_block, fut) => {
+                    if fut.poll_unpin(cx).is_pending() {
+                        return
+                    }
+                    self.current_block = *fork_block;
+                    self.reorg_state = ReorgState::Idle;
+                    // make sure we start processing the reorged blocks again
+                    cx.waker().wake_by_ref();
+                }
+            }
+        }
+    }
+}
+
+/// walks the processed blocks from newest to oldest comparing their hashes
+/// against the canonical chain, returning the lowest block that no longer
+/// matches.
+async fn find_fork_block<F, Fut>(
+    processed: VecDeque<(u64, BlockHash)>,
+    canonical_hash: F,
+) -> Option<u64>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = eyre::Result<Option<BlockHash>>>,
+{
+    let mut fork_block = None;
+    for (block, hash) in processed.into_iter().rev() {
+        match canonical_hash(block).await {
+            Ok(Some(canonical)) if canonical == hash => break,
+            Ok(_) => fork_block = Some(block),
+            Err(e) => {
+                tracing::error!(err=%e, %block, "failed to fetch canonical block hash");
+                break
+            }
+        }
+    }
+    fork_block
+}
+
+/// removes the data of the reorged blocks from `fork_block` up to `end_block`.
+/// Sandwiches spanning blocks are stored under their backrun block, which is
+/// never before their frontrun block, so all sandwiches with a reorged
+/// frontrun are removed as well.
+async fn roll_back_blocks<DB: DBWriter>(database: &DB, fork_block: u64, end_block: u64) {
+    for block in fork_block..end_block {
+        if let Err(e) = database.delete_block_data(block).await {
+            tracing::error!(err=%e, %block, "failed to delete reorged block data");
+        }
+    }
 }
 
-impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P: Processor> Future
+impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor> Future
     for TipInspector<T, DB, CH, P>
 {
     type Output = ();
@@ -132,9 +303,19 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         // given we pull the next block sync, we use this to trigger looking
         // for the next block.
-        while self.poll_interval.poll_tick(cx).is_ready() {}
+        let mut ticked = false;
+        while self.poll_interval.poll_tick(cx).is_ready() {
+            ticked = true;
+        }
 
-        if self.start_block_inspector() && self.state_collector.should_process_next_block() {
+        if ticked {
+            self.check_for_reorg();
+        }
+
+        if !self.reorg_state.is_rolling_back()
+            && self.start_block_inspector()
+            && self.state_collector.should_process_next_block()
+        {
             let block = self.current_block;
             tracing::info!(%block,"starting new tip block");
             self.state_collector.fetch_state_for(block, 0, None);
@@ -150,6 +331,177 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         }
         while let Poll::Ready(Some(_)) = self.processing_futures.poll_next_unpin(cx) {}
 
+        self.poll_reorg(cx);
+
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256};
+    use brontes_database::libmdbx::{LibmdbxInit, LibmdbxReadWriter, LibmdbxReader};
+    use brontes_types::{
+        db::{
+            dex::{DexPrices, DexQuotes},
+            searcher::SearcherInfo,
+            uniswap_v4_pool_key::UniswapV4PoolKey,
+        },
+        mev::{Bundle, BundleData, BundleHeader, MevBlock, MevType},
+        pair::Pair,
+        FastHashMap, Protocol,
+    };
+    use malachite::Rational;
+
+    use super::*;
+
+    const FIRST_BLOCK: u64 = 100;
+    const LAST_BLOCK: u64 = 105;
+    const FORK_BLOCK: u64 = 103;
+
+    fn block_hash(block: u64, fork: u64) -> BlockHash {
+        B256::left_padding_from(&(block * 10 + fork).to_be_bytes())
+    }
+
+    /// the hashes of a chain that was reorged from [`FORK_BLOCK`] onwards
+    fn canonical_hash(block: u64) -> std::future::Ready<eyre::Result<Option<BlockHash>>> {
+        let fork = if block >= FORK_BLOCK { 1 } else { 0 };
+        std::future::ready(Ok(Some(block_hash(block, fork))))
+    }
+
+    fn processed_blocks() -> VecDeque<(u64, BlockHash)> {
+        (FIRST_BLOCK..=LAST_BLOCK)
+            .map(|block| (block, block_hash(block, 0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_fork_block() {
+        let fork_block =
+            futures::executor::block_on(find_fork_block(processed_blocks(), canonical_hash));
+        assert_eq!(fork_block, Some(FORK_BLOCK));
+
+        let fork_block =
+            futures::executor::block_on(find_fork_block(processed_blocks(), |block| {
+                std::future::ready(Ok(Some(block_hash(block, 0))))
+            }));
+        assert_eq!(fork_block, None);
+    }
+
+    #[brontes_macros::test]
+    async fn test_reorg_rolls_back_block_data() {
+        let path = std::env::temp_dir().join(format!("brontes-reorg-{}", std::process::id()));
+        let db = LibmdbxReadWriter::init_db_tests(&path).unwrap();
+
+        let eoa = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(2);
+        let pool = Address::with_last_byte(3);
+        let header = BundleHeader {
+            eoa,
+            mev_contract: Some(contract),
+            mev_type: MevType::AtomicArb,
+            profit_usd: 10.0,
+            bribe_usd: 1.0,
+            ..Default::default()
+        };
+        let price = DexPrices {
+            pre_state:             Rational::from(1),
+            post_state:            Rational::from(2),
+            pool_liquidity:        Rational::from(3),
+            goes_through:          Pair(pool, eoa),
+            is_transfer:           false,
+            first_hop_connections: 1,
+        };
+
+        // process the blocks the same way the mev processor does
+        let mut searcher = SearcherInfo::default();
+        for block in FIRST_BLOCK..=LAST_BLOCK {
+            let mut quotes = FastHashMap::default();
+            quotes.insert(Pair(pool, eoa), price.clone());
+            db.write_dex_quotes(block, Some(DexQuotes(vec![Some(quotes)])))
+                .await
+                .unwrap();
+            db.save_traces(block, vec![]).await.unwrap();
+
+            searcher.update_with_bundle(&header);
+            db.write_searcher_info(eoa, Some(contract), searcher.clone(), Some(searcher.clone()))
+                .await
+                .unwrap();
+            db.save_mev_blocks(
+                block,
+                MevBlock { block_number: block, ..Default::default() },
+                vec![Bundle {
+                    header: BundleHeader { block_number: block, ..header.clone() },
+                    data:   BundleData::default(),
+                }],
+            )
+            .await
+            .unwrap();
+        }
+        db.insert_pool(FORK_BLOCK + 1, pool, &[eoa, contract], None, Protocol::UniswapV2)
+            .await
+            .unwrap();
+
+        // a v4 pool initialized before the fork and one initialized after it
+        let v4_keys = [FIRST_BLOCK, FORK_BLOCK + 1].map(|block| {
+            let key = UniswapV4PoolKey {
+                currency0: eoa,
+                currency1: contract,
+                fee: block as u32,
+                ..Default::default()
+            };
+            (block, key)
+        });
+        for (block, key) in v4_keys {
+            db.insert_pool(block, key.pool_address(), &[eoa, contract], None, Protocol::UniswapV4)
+                .await
+                .unwrap();
+            db.insert_uniswap_v4_pool_key(key).await.unwrap();
+        }
+
+        let fork_block = find_fork_block(processed_blocks(), canonical_hash)
+            .await
+            .unwrap();
+        roll_back_blocks(&db, fork_block, LAST_BLOCK + 1).await;
+
+        for block in FIRST_BLOCK..=LAST_BLOCK {
+            let reorged = block >= FORK_BLOCK;
+            assert_eq!(db.load_trace(block).is_err(), reorged, "traces of {block}");
+            assert_eq!(
+                db.get_dex_quotes(block)
+                    .map(|quotes| quotes.0.iter().all(Option::is_none))
+                    .unwrap_or(true),
+                reorged,
+                "dex quotes of {block}"
+            );
+        }
+
+        let mev_blocks = db.try_fetch_mev_blocks(None, LAST_BLOCK).unwrap();
+        assert_eq!(
+            mev_blocks
+                .iter()
+                .map(|mev| mev.block.block_number)
+                .collect::<Vec<_>>(),
+            (FIRST_BLOCK..FORK_BLOCK).collect::<Vec<_>>()
+        );
+
+        assert!(db.get_protocol_details(pool).is_err());
+        assert!(db
+            .protocols_created_range(FORK_BLOCK, LAST_BLOCK + 1)
+            .unwrap()
+            .is_empty());
+        assert_eq!(db.load_uniswap_v4_pool_keys().unwrap(), vec![v4_keys[0].1]);
+
+        // only the bundles of the blocks before the fork remain in the stats
+        let (eoa_info, contract_info) = db.try_fetch_searcher_info(eoa, Some(contract)).unwrap();
+        for info in [eoa_info.unwrap(), contract_info.unwrap()] {
+            let kept = FORK_BLOCK - FIRST_BLOCK;
+            assert_eq!(info.mev_count.bundle_count, kept);
+            assert_eq!(info.mev_count.atomic_backrun_count, Some(kept));
+            assert!((info.pnl.total - 10.0 * kept as f64).abs() < 1e-9);
+            assert!((info.gas_bids.total - kept as f64).abs() < 1e-9);
+        }
+
+        let _ = std::fs::remove_dir_all(path);
+    }
+}
@@ -1,6 +1,9 @@
 use brontes_macros::action_impl;
 use brontes_types::{
-    normalized_actions::{NormalizedFlashLoan, NormalizedLiquidation},
+    normalized_actions::{
+        NormalizedFlashLoan, NormalizedLiquidation, NormalizedLoan, NormalizedRepayment,
+        NormalizedSupply, NormalizedWithdraw,
+    },
     structured_trace::CallInfo,
     utils::ToScaledRational,
     Protocol,
@@ -113,16 +116,177 @@ action_impl!(
     }
 );
 
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::supplyCall,
+    Supply,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: supplyCall,
+    db_tx: &DB| {
+        let token = db_tx.try_fetch_token_info(call_data.asset)?;
+        let amount = call_data.amount.to_scaled_rational(token.decimals);
+
+        Ok(NormalizedSupply {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            from: info.msg_sender,
+            on_behalf_of: call_data.onBehalfOf,
+            token,
+            amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::depositCall,
+    Supply,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: depositCall,
+    db_tx: &DB| {
+        let token = db_tx.try_fetch_token_info(call_data.asset)?;
+        let amount = call_data.amount.to_scaled_rational(token.decimals);
+
+        Ok(NormalizedSupply {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            from: info.msg_sender,
+            on_behalf_of: call_data.onBehalfOf,
+            token,
+            amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::withdrawCall,
+    Withdraw,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: withdrawCall,
+    return_data: withdrawReturn,
+    db_tx: &DB| {
+        let token = db_tx.try_fetch_token_info(call_data.asset)?;
+        // the amount can be set to max to withdraw the full balance
+        let amount = return_data._0.to_scaled_rational(token.decimals);
+
+        Ok(NormalizedWithdraw {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            from: info.msg_sender,
+            recipient: call_data.to,
+            token,
+            amount,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::borrowCall,
+    Loan,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: borrowCall,
+    db_tx: &DB| {
+        let loaned_token = db_tx.try_fetch_token_info(call_data.asset)?;
+        let loan_amount = call_data.amount.to_scaled_rational(loaned_token.decimals);
+
+        Ok(NormalizedLoan {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            lender: info.from_address,
+            borrower: call_data.onBehalfOf,
+            recipient: info.msg_sender,
+            loaned_token,
+            loan_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::repayCall,
+    Repayment,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: repayCall,
+    return_data: repayReturn,
+    db_tx: &DB| {
+        let repayed_token = db_tx.try_fetch_token_info(call_data.asset)?;
+        // the amount can be set to max to repay the full debt
+        let repayment_amount = return_data._0.to_scaled_rational(repayed_token.decimals);
+
+        Ok(NormalizedRepayment {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            lender: info.from_address,
+            borrower: call_data.onBehalfOf,
+            payer: info.msg_sender,
+            repayed_token,
+            repayment_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
 #[cfg(test)]
 mod tests {
     use alloy_primitives::{hex, Address, B256, U256};
     use brontes_types::{
-        normalized_actions::{Action, NormalizedLiquidation},
+        constants::USDC_ADDRESS,
+        db::token_info::TokenInfoWithAddress,
+        normalized_actions::{
+            Action, NormalizedLiquidation, NormalizedLoan, NormalizedRepayment, NormalizedSupply,
+            NormalizedWithdraw,
+        },
         Protocol, TreeSearchBuilder,
     };
     use malachite::Rational;
 
-    use crate::test_utils::ClassifierTestUtils;
+    use crate::{
+        test_utils::{ClassifierTestUtils, TestCall},
+        AaveV3::{borrowCall, depositCall, repayCall, supplyCall, withdrawCall},
+    };
+
+    const LENDING_POOL: Address = Address::new(hex!("87870bca3f3fd6335c3f4ce8392d69350b4fa4e2"));
+    /// the implementation the lending pool delegates to, registered as the
+    /// protocol by the tests
+    const POOL_IMPL: Address = Address::new(hex!("00000000000000000000000000000000000a3a3a"));
+    const USER: Address = Address::new(hex!("00000000000000000000000000000000000000a1"));
+    const BEHALF_OF: Address = Address::new(hex!("00000000000000000000000000000000000000b2"));
+
+    async fn setup() -> ClassifierTestUtils {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol_with_tokens(
+            Protocol::AaveV3,
+            POOL_IMPL,
+            &[TokenInfoWithAddress::usdc()],
+        );
+
+        classifier_utils
+    }
 
     #[brontes_macros::test]
     async fn test_aave_v3_liquidation() {
@@ -155,4 +319,152 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[brontes_macros::test]
+    async fn test_aave_v3_supply() {
+        let classifier_utils = setup().await;
+        let call = TestCall::new(
+            POOL_IMPL,
+            USER,
+            supplyCall {
+                asset:        USDC_ADDRESS,
+                amount:       U256::from(1_500_000_000u64),
+                onBehalfOf:   BEHALF_OF,
+                referralCode: 0,
+            },
+        )
+        .delegated_from(LENDING_POOL);
+
+        let eq_action = Action::Supply(NormalizedSupply {
+            protocol:     Protocol::AaveV3,
+            trace_index:  1,
+            pool:         LENDING_POOL,
+            from:         USER,
+            on_behalf_of: BEHALF_OF,
+            token:        TokenInfoWithAddress::usdc(),
+            amount:       Rational::from(1_500),
+            msg_value:    U256::ZERO,
+        });
+
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action));
+    }
+
+    #[brontes_macros::test]
+    async fn test_aave_v3_deposit() {
+        let classifier_utils = setup().await;
+        let call = TestCall::new(
+            POOL_IMPL,
+            USER,
+            depositCall {
+                asset:        USDC_ADDRESS,
+                amount:       U256::from(1_500_000_000u64),
+                onBehalfOf:   BEHALF_OF,
+                referralCode: 0,
+            },
+        )
+        .delegated_from(LENDING_POOL);
+
+        let eq_action = Action::Supply(NormalizedSupply {
+            protocol:     Protocol::AaveV3,
+            trace_index:  1,
+            pool:         LENDING_POOL,
+            from:         USER,
+            on_behalf_of: BEHALF_OF,
+            token:        TokenInfoWithAddress::usdc(),
+            amount:       Rational::from(1_500),
+            msg_value:    U256::ZERO,
+        });
+
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action));
+    }
+
+    #[brontes_macros::test]
+    async fn test_aave_v3_withdraw() {
+        let classifier_utils = setup().await;
+        // withdrawing the full balance, the amount is taken from the return data
+        let call = TestCall::new(
+            POOL_IMPL,
+            USER,
+            withdrawCall { asset: USDC_ADDRESS, amount: U256::MAX, to: BEHALF_OF },
+        )
+        .delegated_from(LENDING_POOL);
+
+        let eq_action = Action::Withdraw(NormalizedWithdraw {
+            protocol:    Protocol::AaveV3,
+            trace_index: 1,
+            pool:        LENDING_POOL,
+            from:        USER,
+            recipient:   BEHALF_OF,
+            token:       TokenInfoWithAddress::usdc(),
+            amount:      Rational::from_signeds(2_500_001, 1_000_000),
+        });
+
+        assert_eq!(
+            classifier_utils.classify_call(&call.returns(U256::from(2_500_001))),
+            Some(eq_action)
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_aave_v3_borrow() {
+        let classifier_utils = setup().await;
+        let call = TestCall::new(
+            POOL_IMPL,
+            USER,
+            borrowCall {
+                asset:            USDC_ADDRESS,
+                amount:           U256::from(250_000_000u64),
+                interestRateMode: U256::from(2),
+                referralCode:     0,
+                onBehalfOf:       BEHALF_OF,
+            },
+        )
+        .delegated_from(LENDING_POOL);
+
+        let eq_action = Action::Loan(NormalizedLoan {
+            protocol:     Protocol::AaveV3,
+            trace_index:  1,
+            lender:       LENDING_POOL,
+            borrower:     BEHALF_OF,
+            recipient:    USER,
+            loaned_token: TokenInfoWithAddress::usdc(),
+            loan_amount:  Rational::from(250),
+            msg_value:    U256::ZERO,
+        });
+
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action));
+    }
+
+    #[brontes_macros::test]
+    async fn test_aave_v3_repay() {
+        let classifier_utils = setup().await;
+        // repaying the full debt, the amount is taken from the return data
+        let call = TestCall::new(
+            POOL_IMPL,
+            USER,
+            repayCall {
+                asset:            USDC_ADDRESS,
+                amount:           U256::MAX,
+                interestRateMode: U256::from(2),
+                onBehalfOf:       BEHALF_OF,
+            },
+        )
+        .delegated_from(LENDING_POOL);
+
+        let eq_action = Action::Repayment(NormalizedRepayment {
+            protocol:         Protocol::AaveV3,
+            trace_index:      1,
+            lender:           LENDING_POOL,
+            borrower:         BEHALF_OF,
+            payer:            USER,
+            repayed_token:    TokenInfoWithAddress::usdc(),
+            repayment_amount: Rational::from_signeds(250_010_000, 1_000_000),
+            msg_value:        U256::ZERO,
+        });
+
+        assert_eq!(
+            classifier_utils.classify_call(&call.returns(U256::from(250_010_000))),
+            Some(eq_action)
+        );
+    }
 }
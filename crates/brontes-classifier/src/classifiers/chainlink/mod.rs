@@ -0,0 +1,3 @@
+mod offchain_aggregator;
+
+pub use offchain_aggregator::*;
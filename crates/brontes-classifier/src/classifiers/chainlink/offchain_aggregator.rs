@@ -0,0 +1,39 @@
+use alloy_primitives::U256;
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedOracleUpdate, structured_trace::CallInfo, Protocol,
+    ToScaledRational,
+};
+
+/// Usd feeds report their answer with 8 decimals. Feeds are registered with
+/// the priced asset as their first token, so only usd feeds should be added
+/// to the classifier config.
+const USD_FEED_DECIMALS: u8 = 8;
+
+action_impl!(
+    Protocol::ChainlinkOffchainAggregator,
+    crate::ChainlinkOffchainAggregator::transmitCall,
+    OracleUpdate,
+    [NewTransmission],
+    logs: true,
+    |info: CallInfo, log_data: ChainlinkOffchainAggregatorTransmitCallLogs, db_tx: &DB| {
+        let logs = log_data.new_transmission_field?;
+        if logs.answer.is_negative() {
+            eyre::bail!("negative answer transmitted to {:?}", info.target_address);
+        }
+
+        let asset = db_tx
+            .get_protocol_details(info.target_address)
+            .ok()
+            .and_then(|details| db_tx.try_fetch_token_info(details.token0).ok());
+
+        Ok(NormalizedOracleUpdate {
+            protocol: Protocol::ChainlinkOffchainAggregator,
+            trace_index: info.trace_idx,
+            oracle: info.target_address,
+            from: logs.transmitter,
+            asset,
+            price: U256::from(logs.answer.unsigned_abs()).to_scaled_rational(USD_FEED_DECIMALS),
+        })
+    }
+);
@@ -1,9 +1,20 @@
+use alloy_primitives::{hex, Address};
+use brontes_database::libmdbx::LibmdbxReader;
 use brontes_macros::action_impl;
 use brontes_pricing::Protocol;
 use brontes_types::{
-    normalized_actions::NormalizedLiquidation, structured_trace::CallInfo, utils::ToScaledRational,
+    constants::WETH_ADDRESS,
+    db::token_info::TokenInfoWithAddress,
+    normalized_actions::{
+        NormalizedLiquidation, NormalizedLoan, NormalizedRepayment, NormalizedSupply,
+        NormalizedWithdraw,
+    },
+    structured_trace::CallInfo,
+    utils::ToScaledRational,
 };
 
+const CETH: Address = Address::new(hex!("4Ddc2D193948926D02f9B1fE9e1daa0718270ED5"));
+
 action_impl!(
     Protocol::CompoundV2,
     crate::CompoundV2CToken::liquidateBorrowCall,
@@ -37,17 +48,367 @@ action_impl!(
     }
 );
 
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::mintCall,
+    Supply,
+    [..Mint],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2MintCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.mint_field?;
+        let token = underlying(db_tx, info.target_address)?;
+        let amount = logs.mintAmount.to_scaled_rational(token.decimals);
+
+        Ok(NormalizedSupply {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            from: logs.minter,
+            on_behalf_of: logs.minter,
+            token,
+            amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::redeemCall,
+    Withdraw,
+    [..Redeem],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RedeemCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.redeem_field?;
+        let token = underlying(db_tx, info.target_address)?;
+        let amount = logs.redeemAmount.to_scaled_rational(token.decimals);
+
+        Ok(NormalizedWithdraw {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            from: logs.redeemer,
+            recipient: logs.redeemer,
+            token,
+            amount,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::redeemUnderlyingCall,
+    Withdraw,
+    [..Redeem],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RedeemUnderlyingCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.redeem_field?;
+        let token = underlying(db_tx, info.target_address)?;
+        let amount = logs.redeemAmount.to_scaled_rational(token.decimals);
+
+        Ok(NormalizedWithdraw {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            from: logs.redeemer,
+            recipient: logs.redeemer,
+            token,
+            amount,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::borrowCall,
+    Loan,
+    [..Borrow],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2BorrowCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.borrow_field?;
+        let loaned_token = underlying(db_tx, info.target_address)?;
+        let loan_amount = logs.borrowAmount.to_scaled_rational(loaned_token.decimals);
+
+        Ok(NormalizedLoan {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            lender: info.target_address,
+            borrower: logs.borrower,
+            recipient: logs.borrower,
+            loaned_token,
+            loan_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::repayBorrowCall,
+    Repayment,
+    [..RepayBorrow],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RepayBorrowCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.repay_borrow_field?;
+        let repayed_token = underlying(db_tx, info.target_address)?;
+        let repayment_amount = logs.repayAmount.to_scaled_rational(repayed_token.decimals);
+
+        Ok(NormalizedRepayment {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            lender: info.target_address,
+            borrower: logs.borrower,
+            payer: logs.payer,
+            repayed_token,
+            repayment_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::repayBorrowBehalfCall,
+    Repayment,
+    [..RepayBorrow],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RepayBorrowBehalfCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.repay_borrow_field?;
+        let repayed_token = underlying(db_tx, info.target_address)?;
+        let repayment_amount = logs.repayAmount.to_scaled_rational(repayed_token.decimals);
+
+        Ok(NormalizedRepayment {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            lender: info.target_address,
+            borrower: logs.borrower,
+            payer: logs.payer,
+            repayed_token,
+            repayment_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+/// The underlying token of a cToken is stored as the second token of the
+/// market when it is discovered, cETH holds eth. As the amounts of the actions
+/// are denominated in the underlying, markets that were discovered without it
+/// aren't classified.
+fn underlying<DB: LibmdbxReader>(
+    db_tx: &DB,
+    c_token: Address,
+) -> eyre::Result<TokenInfoWithAddress> {
+    if c_token == CETH {
+        return db_tx.try_fetch_token_info(WETH_ADDRESS)
+    }
+
+    let details = db_tx.get_protocol_details(c_token)?;
+    if details.token1 == Address::ZERO {
+        eyre::bail!("underlying of compound market {c_token:?} is unknown")
+    }
+
+    db_tx.try_fetch_token_info(details.token1)
+}
+
 #[cfg(test)]
 mod tests {
     use alloy_primitives::{hex, Address, B256, U256};
+    use alloy_sol_types::SolCall;
     use brontes_types::{
-        db::token_info::TokenInfoWithAddress,
-        normalized_actions::{Action, NormalizedLiquidation},
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{
+            Action, NormalizedLiquidation, NormalizedLoan, NormalizedRepayment, NormalizedSupply,
+            NormalizedWithdraw,
+        },
         Protocol, TreeSearchBuilder,
     };
     use malachite::Rational;
 
-    use crate::test_utils::ClassifierTestUtils;
+    use crate::{
+        test_utils::{ClassifierTestUtils, TestCall},
+        CompoundV2CToken::{
+            borrowCall, mintCall, redeemCall, redeemUnderlyingCall, repayBorrowBehalfCall,
+            repayBorrowCall, Borrow, Mint, Redeem, RepayBorrow,
+        },
+    };
+
+    const C_DAI: Address = Address::new(hex!("5d3a536e4d6dbd6114cc1ead35777bab948e3643"));
+    const DAI: Address = Address::new(hex!("6b175474e89094c44da98b954eedeac495271d0f"));
+    /// a market that was discovered without its underlying
+    const C_UNKNOWN: Address = Address::new(hex!("00000000000000000000000000000000000c0c0c"));
+    const USER: Address = Address::new(hex!("00000000000000000000000000000000000000a1"));
+    const BORROWER: Address = Address::new(hex!("00000000000000000000000000000000000000b2"));
+    /// 100 DAI
+    const AMOUNT: u128 = 100_000_000_000_000_000_000;
+
+    async fn setup() -> ClassifierTestUtils {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol_with_tokens(
+            Protocol::CompoundV2,
+            C_DAI,
+            &[token(C_DAI, "cDAI", 8), dai()],
+        );
+        classifier_utils.ensure_protocol_with_tokens(
+            Protocol::CompoundV2,
+            C_UNKNOWN,
+            &[token(C_UNKNOWN, "cUNKNOWN", 8)],
+        );
+
+        classifier_utils
+    }
+
+    fn token(address: Address, symbol: &str, decimals: u8) -> TokenInfoWithAddress {
+        TokenInfoWithAddress { address, inner: TokenInfo::new(decimals, symbol.to_string()) }
+    }
+
+    fn dai() -> TokenInfoWithAddress {
+        token(DAI, "DAI", 18)
+    }
+
+    /// a call from the user into the market, which returns 0 on success
+    fn market_call(market: Address, call: impl SolCall) -> TestCall {
+        TestCall::new(market, USER, call).returns(U256::ZERO)
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_mint() {
+        let classifier_utils = setup().await;
+        let call = market_call(C_DAI, mintCall { mintAmount: U256::from(AMOUNT) }).with_log(
+            C_DAI,
+            Mint { minter: USER, mintAmount: U256::from(AMOUNT), mintTokens: U256::from(1) },
+        );
+
+        let eq_action = Action::Supply(NormalizedSupply {
+            protocol:     Protocol::CompoundV2,
+            trace_index:  1,
+            pool:         C_DAI,
+            from:         USER,
+            on_behalf_of: USER,
+            token:        dai(),
+            amount:       Rational::from(100),
+            msg_value:    U256::ZERO,
+        });
+
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action));
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_mint_unknown_underlying() {
+        let classifier_utils = setup().await;
+        let call = market_call(C_UNKNOWN, mintCall { mintAmount: U256::from(AMOUNT) }).with_log(
+            C_UNKNOWN,
+            Mint { minter: USER, mintAmount: U256::from(AMOUNT), mintTokens: U256::from(1) },
+        );
+
+        assert_eq!(classifier_utils.classify_call(&call), None);
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_redeem() {
+        let classifier_utils = setup().await;
+        let redeem = Redeem {
+            redeemer:     USER,
+            redeemAmount: U256::from(AMOUNT),
+            redeemTokens: U256::from(1),
+        };
+
+        let eq_action = Action::Withdraw(NormalizedWithdraw {
+            protocol:    Protocol::CompoundV2,
+            trace_index: 1,
+            pool:        C_DAI,
+            from:        USER,
+            recipient:   USER,
+            token:       dai(),
+            amount:      Rational::from(100),
+        });
+
+        let call = market_call(C_DAI, redeemCall { redeemTokens: U256::from(1) })
+            .with_log(C_DAI, redeem.clone());
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action.clone()));
+
+        let call = market_call(C_DAI, redeemUnderlyingCall { redeemAmount: U256::from(AMOUNT) })
+            .with_log(C_DAI, redeem);
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action));
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_borrow() {
+        let classifier_utils = setup().await;
+        let call = market_call(C_DAI, borrowCall { borrowAmount: U256::from(AMOUNT) }).with_log(
+            C_DAI,
+            Borrow {
+                borrower:       BORROWER,
+                borrowAmount:   U256::from(AMOUNT),
+                accountBorrows: U256::from(AMOUNT),
+                totalBorrows:   U256::from(AMOUNT),
+            },
+        );
+
+        let eq_action = Action::Loan(NormalizedLoan {
+            protocol:     Protocol::CompoundV2,
+            trace_index:  1,
+            lender:       C_DAI,
+            borrower:     BORROWER,
+            recipient:    BORROWER,
+            loaned_token: dai(),
+            loan_amount:  Rational::from(100),
+            msg_value:    U256::ZERO,
+        });
+
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action));
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_repay_borrow() {
+        let classifier_utils = setup().await;
+        let repay = RepayBorrow {
+            payer:          USER,
+            borrower:       BORROWER,
+            repayAmount:    U256::from(AMOUNT),
+            accountBorrows: U256::ZERO,
+            totalBorrows:   U256::ZERO,
+        };
+
+        let eq_action = Action::Repayment(NormalizedRepayment {
+            protocol:         Protocol::CompoundV2,
+            trace_index:      1,
+            lender:           C_DAI,
+            borrower:         BORROWER,
+            payer:            USER,
+            repayed_token:    dai(),
+            repayment_amount: Rational::from(100),
+            msg_value:        U256::ZERO,
+        });
+
+        let call = market_call(C_DAI, repayBorrowCall { repayAmount: U256::from(AMOUNT) })
+            .with_log(C_DAI, repay.clone());
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action.clone()));
+
+        let call = market_call(
+            C_DAI,
+            repayBorrowBehalfCall { borrower: BORROWER, repayAmount: U256::from(AMOUNT) },
+        )
+        .with_log(C_DAI, repay);
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action));
+    }
 
     #[brontes_macros::test]
     async fn test_compound_v2_liquidation() {
@@ -0,0 +1,97 @@
+use alloy_primitives::Address;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    db::token_info::TokenInfoWithAddress,
+    normalized_actions::{NormalizedLiquidation, NormalizedSwap},
+    structured_trace::CallInfo,
+    utils::ToScaledRational,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+
+// Comet liquidations take two steps. `absorb` moves the collateral of the
+// underwater account into the reserves of the market and `buyCollateral`
+// sells it to the liquidator at a discount. The buy is linked to the absorb
+// by the multi frame classification, until then the liquidation has no
+// amounts. Only the first account of an absorb is classified.
+action_impl!(
+    Protocol::CompoundV3,
+    crate::CompoundV3Comet::absorbCall,
+    Liquidation,
+    [..AbsorbCollateral*, ..AbsorbDebt*],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV3AbsorbCallLogs,
+    db_tx: &DB | {
+        let debts = log_data.absorb_debt_field?;
+        let collaterals = log_data.absorb_collateral_field?;
+
+        let debt = debts
+            .first()
+            .ok_or_else(|| eyre::eyre!("absorb didn't absorb any debt"))?;
+        let collateral = collaterals
+            .iter()
+            .filter(|collateral| collateral.borrower == debt.borrower)
+            .max_by_key(|collateral| collateral.usdValue)
+            .ok_or_else(|| eyre::eyre!("absorb didn't absorb any collateral"))?;
+
+        let debt_asset = base_token(db_tx, info.target_address)?;
+        let collateral_asset = db_tx.try_fetch_token_info(collateral.asset)?;
+
+        Ok(NormalizedLiquidation {
+            protocol: Protocol::CompoundV3,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: debt.absorber,
+            debtor: debt.borrower,
+            collateral_asset,
+            debt_asset,
+            covered_debt: Rational::ZERO,
+            liquidated_collateral: Rational::ZERO,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV3,
+    crate::CompoundV3Comet::buyCollateralCall,
+    Swap,
+    [..BuyCollateral],
+    call_data: true,
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    call_data: buyCollateralCall,
+    log_data: CompoundV3BuyCollateralCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.buy_collateral_field?;
+        let token_in = base_token(db_tx, info.target_address)?;
+        let token_out = db_tx.try_fetch_token_info(logs.asset)?;
+        let amount_in = logs.baseAmount.to_scaled_rational(token_in.decimals);
+        let amount_out = logs.collateralAmount.to_scaled_rational(token_out.decimals);
+
+        Ok(NormalizedSwap {
+            protocol: Protocol::CompoundV3,
+            trace_index: info.trace_idx,
+            from: logs.buyer,
+            recipient: call_data.recipient,
+            pool: info.target_address,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+/// The base token of a market is stored as its first token
+fn base_token<DB: LibmdbxReader>(db_tx: &DB, comet: Address) -> eyre::Result<TokenInfoWithAddress> {
+    let details = db_tx.get_protocol_details(comet)?;
+    db_tx.try_fetch_token_info(details.token0)
+}
@@ -1,19 +1,24 @@
-use brontes_macros::action_impl;
-use brontes_pricing::Protocol;
+use alloy_primitives::Address;
+use brontes_macros::{action_impl, discovery_impl};
+use brontes_pricing::{make_call_request, Protocol};
 use brontes_types::{normalized_actions::NormalizedNewPool, structured_trace::CallInfo};
 
+use crate::CompoundV3Comet::{baseTokenCall, getAssetInfoCall, numAssetsCall};
+
 action_impl!(
     Protocol::CompoundV2,
     crate::CErc20Delegate::initialize_0Call,
     NewPool,
     [],
     call_data: true,
-    |info: CallInfo, _call_data: initialize_0Call, _| {
+    |info: CallInfo, call_data: initialize_0Call, _| {
+        // we store the underlying so that lending actions on the market can be
+        // denominated in it
         Ok(NormalizedNewPool {
             trace_index: info.trace_idx,
             protocol: Protocol::CompoundV2,
             pool_address: info.from_address,
-            tokens: vec![info.from_address]
+            tokens: vec![info.from_address, call_data.underlying_]
         })
     }
 );
@@ -35,6 +40,44 @@ action_impl!(
     }
 );
 
+// the configurator deploys a new implementation each time the configuration
+// of a market changes. The market is the proxy in front of it, the
+// implementation stores the configuration as immutables which we query for
+// the tokens of the market, the base token first.
+discovery_impl!(
+    CompoundV3Discovery,
+    crate::CompoundV3Configurator::deployCall,
+    0x316f9708bB98af7dA9c68C1C3b5e79039cD336E3,
+    |deployed_address: Address, trace_index: u64, call_data: deployCall, tracer| async move {
+        let Ok(base_token) =
+            make_call_request(baseTokenCall {}, &tracer, deployed_address, None).await
+        else {
+            return vec![]
+        };
+        let num_assets = make_call_request(numAssetsCall {}, &tracer, deployed_address, None)
+            .await
+            .map(|res| res._0)
+            .unwrap_or_default();
+
+        let mut tokens = vec![base_token._0];
+        for i in 0..num_assets {
+            let Ok(info) =
+                make_call_request(getAssetInfoCall { i }, &tracer, deployed_address, None).await
+            else {
+                break
+            };
+            tokens.push(info._0.asset);
+        }
+
+        vec![NormalizedNewPool {
+            trace_index,
+            protocol: Protocol::CompoundV3,
+            pool_address: call_data.cometProxy,
+            tokens,
+        }]
+    }
+);
+
 #[cfg(test)]
 mod tests {
     use alloy_primitives::{hex, B256};
@@ -55,7 +98,10 @@ mod tests {
             trace_index:  1,
             protocol:     Protocol::CompoundV2,
             pool_address: hex!("5d3a536e4d6dbd6114cc1ead35777bab948e3643").into(),
-            tokens:       vec![hex!("5d3a536e4d6dbd6114cc1ead35777bab948e3643").into()],
+            tokens:       vec![
+                hex!("5d3a536e4d6dbd6114cc1ead35777bab948e3643").into(),
+                hex!("6b175474e89094c44da98b954eedeac495271d0f").into(),
+            ],
         });
         let search = TreeSearchBuilder::default().with_action(Action::is_new_pool);
 
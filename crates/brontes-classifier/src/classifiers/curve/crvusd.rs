@@ -0,0 +1,360 @@
+use alloy_primitives::{Address, U256};
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    normalized_actions::{NormalizedLiquidation, NormalizedSwap},
+    structured_trace::CallInfo,
+    ToScaledRational,
+};
+
+use crate::CurvecrvUSDLlamma::TokenExchange;
+
+// crvUSD markets are stored with crvUSD as the first token and the collateral
+// as the second. This is also the order of the coins of a LLAMMA.
+
+action_impl!(
+    Protocol::CurvecrvUSDController,
+    crate::CurvecrvUSDController::liquidate_0Call,
+    Liquidation,
+    [..Liquidate],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: liquidate_0Call,
+    log_data: CurvecrvUSDControllerLiquidate_0CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.liquidate_field?;
+        parse_liquidation(info, call_data.user, logs.collateral_received, logs.debt, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::CurvecrvUSDController,
+    crate::CurvecrvUSDController::liquidate_1Call,
+    Liquidation,
+    [..Liquidate],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: liquidate_1Call,
+    log_data: CurvecrvUSDControllerLiquidate_1CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.liquidate_field?;
+        parse_liquidation(info, call_data.user, logs.collateral_received, logs.debt, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::CurvecrvUSDController,
+    crate::CurvecrvUSDController::liquidate_extendedCall,
+    Liquidation,
+    [..Liquidate],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: liquidate_extendedCall,
+    log_data: CurvecrvUSDControllerLiquidate_extendedCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.liquidate_field?;
+        parse_liquidation(info, call_data.user, logs.collateral_received, logs.debt, db_tx)
+    }
+);
+
+// Exchanges with a LLAMMA trade against the bands of the positions in soft
+// liquidation, arbitraging them is how soft liquidations are executed.
+
+action_impl!(
+    Protocol::CurvecrvUSDLlamma,
+    crate::CurvecrvUSDLlamma::exchange_0Call,
+    Swap,
+    [..TokenExchange],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: CurvecrvUSDLlammaExchange_0CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.token_exchange_field?;
+        parse_exchange(info, logs.buyer, logs, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::CurvecrvUSDLlamma,
+    crate::CurvecrvUSDLlamma::exchange_1Call,
+    Swap,
+    [..TokenExchange],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: exchange_1Call,
+    log_data: CurvecrvUSDLlammaExchange_1CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.token_exchange_field?;
+        parse_exchange(info, call_data._for, logs, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::CurvecrvUSDLlamma,
+    crate::CurvecrvUSDLlamma::exchange_dy_0Call,
+    Swap,
+    [..TokenExchange],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: CurvecrvUSDLlammaExchange_dy_0CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.token_exchange_field?;
+        parse_exchange(info, logs.buyer, logs, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::CurvecrvUSDLlamma,
+    crate::CurvecrvUSDLlamma::exchange_dy_1Call,
+    Swap,
+    [..TokenExchange],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: exchange_dy_1Call,
+    log_data: CurvecrvUSDLlammaExchange_dy_1CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.token_exchange_field?;
+        parse_exchange(info, call_data._for, logs, db_tx)
+    }
+);
+
+/// The liquidator repays the debt of the position, part of which can be
+/// covered by the crvUSD the position holds in the LLAMMA, and receives its
+/// collateral
+fn parse_liquidation<DB: LibmdbxReader>(
+    info: CallInfo,
+    debtor: Address,
+    collateral_received: U256,
+    debt: U256,
+    db_tx: &DB,
+) -> eyre::Result<NormalizedLiquidation> {
+    let details = db_tx.get_protocol_details(info.target_address)?;
+    let debt_asset = db_tx.try_fetch_token_info(details.token0)?;
+    let collateral_asset = db_tx.try_fetch_token_info(details.token1)?;
+    let covered_debt = debt.to_scaled_rational(debt_asset.decimals);
+    let liquidated_collateral = collateral_received.to_scaled_rational(collateral_asset.decimals);
+
+    Ok(NormalizedLiquidation {
+        protocol: Protocol::CurvecrvUSDController,
+        trace_index: info.trace_idx,
+        pool: info.target_address,
+        liquidator: info.from_address,
+        debtor,
+        collateral_asset,
+        debt_asset,
+        covered_debt,
+        liquidated_collateral,
+        msg_value: info.msg_value,
+    })
+}
+
+fn parse_exchange<DB: LibmdbxReader>(
+    info: CallInfo,
+    recipient: Address,
+    logs: TokenExchange,
+    db_tx: &DB,
+) -> eyre::Result<NormalizedSwap> {
+    let details = db_tx.get_protocol_details(info.target_address)?;
+    let coin = |id: U256| match u8::try_from(id) {
+        Ok(0) => Ok(details.token0),
+        Ok(1) => Ok(details.token1),
+        _ => Err(eyre::eyre!("LLAMMA only has two coins, got coin {id}")),
+    };
+
+    let token_in = db_tx.try_fetch_token_info(coin(logs.sold_id)?)?;
+    let token_out = db_tx.try_fetch_token_info(coin(logs.bought_id)?)?;
+    let amount_in = logs.tokens_sold.to_scaled_rational(token_in.decimals);
+    let amount_out = logs.tokens_bought.to_scaled_rational(token_out.decimals);
+
+    Ok(NormalizedSwap {
+        protocol: Protocol::CurvecrvUSDLlamma,
+        trace_index: info.trace_idx,
+        from: logs.buyer,
+        recipient,
+        pool: info.target_address,
+        token_in,
+        token_out,
+        amount_in,
+        amount_out,
+        msg_value: info.msg_value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, U256};
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{Action, NormalizedLiquidation, NormalizedSwap},
+        Protocol,
+    };
+    use malachite::Rational;
+
+    use crate::{
+        test_utils::{ClassifierTestUtils, TestCall},
+        CurvecrvUSDController::{liquidate_0Call, liquidate_1Call, Liquidate},
+        CurvecrvUSDLlamma::{exchange_0Call, exchange_1Call, TokenExchange},
+    };
+
+    const CRVUSD: Address = Address::new(hex!("f939e0a03fb07f59a73314e73794be0e57ac1b4e"));
+    const CONTROLLER: Address = Address::new(hex!("00000000000000000000000000000000000c4d00"));
+    const LLAMMA: Address = Address::new(hex!("00000000000000000000000000000000000a4d00"));
+    const LIQUIDATOR: Address = Address::new(hex!("00000000000000000000000000000000000000a1"));
+    const USER: Address = Address::new(hex!("00000000000000000000000000000000000000b2"));
+    const ONE: u128 = 1_000_000_000_000_000_000;
+
+    async fn setup() -> ClassifierTestUtils {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        for (protocol, address) in
+            [(Protocol::CurvecrvUSDController, CONTROLLER), (Protocol::CurvecrvUSDLlamma, LLAMMA)]
+        {
+            classifier_utils.ensure_protocol_with_tokens(
+                protocol,
+                address,
+                &[crvusd(), TokenInfoWithAddress::weth()],
+            );
+        }
+
+        classifier_utils
+    }
+
+    fn crvusd() -> TokenInfoWithAddress {
+        TokenInfoWithAddress {
+            address: CRVUSD,
+            inner:   TokenInfo { decimals: 18, symbol: "crvUSD".to_string() },
+        }
+    }
+
+    fn token_exchange(sold_id: u64, bought_id: u64, sold: u128, bought: u128) -> TokenExchange {
+        TokenExchange {
+            buyer:         LIQUIDATOR,
+            sold_id:       U256::from(sold_id),
+            tokens_sold:   U256::from(sold),
+            bought_id:     U256::from(bought_id),
+            tokens_bought: U256::from(bought),
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_crvusd_liquidation() {
+        let classifier_utils = setup().await;
+        let liquidate = Liquidate {
+            liquidator:          LIQUIDATOR,
+            user:                USER,
+            collateral_received: U256::from(2 * ONE),
+            stablecoin_received: U256::ZERO,
+            debt:                U256::from(3_000 * ONE),
+        };
+
+        let eq_action = Action::Liquidation(NormalizedLiquidation {
+            protocol:              Protocol::CurvecrvUSDController,
+            trace_index:           1,
+            pool:                  CONTROLLER,
+            liquidator:            LIQUIDATOR,
+            debtor:                USER,
+            collateral_asset:      TokenInfoWithAddress::weth(),
+            debt_asset:            crvusd(),
+            covered_debt:          Rational::from(3_000),
+            liquidated_collateral: Rational::from(2),
+            msg_value:             U256::ZERO,
+        });
+
+        let call = TestCall::new(
+            CONTROLLER,
+            LIQUIDATOR,
+            liquidate_0Call { user: USER, min_x: U256::ZERO },
+        )
+        .with_log(CONTROLLER, liquidate.clone());
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action.clone()));
+
+        let call = TestCall::new(
+            CONTROLLER,
+            LIQUIDATOR,
+            liquidate_1Call { user: USER, min_x: U256::ZERO, use_eth: true },
+        )
+        .with_log(CONTROLLER, liquidate);
+        assert_eq!(classifier_utils.classify_call(&call), Some(eq_action));
+    }
+
+    #[brontes_macros::test]
+    async fn test_llamma_exchange() {
+        let classifier_utils = setup().await;
+        // sells 1 WETH for 2000 crvUSD out of the bands in soft liquidation
+        let exchange = token_exchange(1, 0, ONE, 2_000 * ONE);
+
+        let swap = NormalizedSwap {
+            protocol:    Protocol::CurvecrvUSDLlamma,
+            trace_index: 1,
+            from:        LIQUIDATOR,
+            recipient:   LIQUIDATOR,
+            pool:        LLAMMA,
+            token_in:    TokenInfoWithAddress::weth(),
+            token_out:   crvusd(),
+            amount_in:   Rational::from(1),
+            amount_out:  Rational::from(2_000),
+            msg_value:   U256::ZERO,
+        };
+
+        let call = TestCall::new(
+            LLAMMA,
+            LIQUIDATOR,
+            exchange_0Call {
+                i:          U256::from(1),
+                j:          U256::ZERO,
+                in_amount:  U256::from(ONE),
+                min_amount: U256::ZERO,
+            },
+        )
+        .with_log(LLAMMA, exchange.clone());
+        assert_eq!(classifier_utils.classify_call(&call), Some(Action::Swap(swap.clone())));
+
+        // the bought coins are sent to `_for`
+        let call = TestCall::new(
+            LLAMMA,
+            LIQUIDATOR,
+            exchange_1Call {
+                i:          U256::from(1),
+                j:          U256::ZERO,
+                in_amount:  U256::from(ONE),
+                min_amount: U256::ZERO,
+                _for:       USER,
+            },
+        )
+        .with_log(LLAMMA, exchange);
+        assert_eq!(
+            classifier_utils.classify_call(&call),
+            Some(Action::Swap(NormalizedSwap { recipient: USER, ..swap }))
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_llamma_exchange_unknown_coin() {
+        let classifier_utils = setup().await;
+        let call = TestCall::new(
+            LLAMMA,
+            LIQUIDATOR,
+            exchange_0Call {
+                i:          U256::from(2),
+                j:          U256::ZERO,
+                in_amount:  U256::from(ONE),
+                min_amount: U256::ZERO,
+            },
+        )
+        .with_log(LLAMMA, token_exchange(2, 0, ONE, ONE));
+
+        assert_eq!(classifier_utils.classify_call(&call), None);
+    }
+}
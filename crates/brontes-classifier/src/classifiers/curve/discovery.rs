@@ -4,9 +4,12 @@ use alloy_primitives::{Address, U256};
 use brontes_macros::{curve_discovery_impl, discovery_impl};
 use brontes_pricing::make_call_request;
 use brontes_types::{
-    normalized_actions::pool::NormalizedNewPool, traits::TracingProvider, Protocol,
+    constants::CRV_USD_ADDRESS, normalized_actions::pool::NormalizedNewPool,
+    traits::TracingProvider, Protocol,
 };
 
+use crate::CurvecrvUSDController::ammCall;
+
 curve_discovery_impl!(
     CurveV1,
     crate::CurveV1MetapoolFactory,
@@ -62,6 +65,31 @@ discovery_impl!(
     }
 );
 
+// each market deploys a LLAMMA and the controller that lends against it, only
+// the controller has an amm
+discovery_impl!(
+    CurvecrvUSDMarketDiscovery,
+    crate::CurvecrvUSDControllerFactory::add_marketCall,
+    0xC9332fdCB1C491Dcc683bAe86Fe3cb70360738BC,
+    |deployed_address: Address, trace_index: u64, call_data: add_marketCall, tracer| async move {
+        let is_controller = make_call_request(ammCall {}, &tracer, deployed_address, None)
+            .await
+            .is_ok();
+        let protocol = if is_controller {
+            Protocol::CurvecrvUSDController
+        } else {
+            Protocol::CurvecrvUSDLlamma
+        };
+
+        vec![NormalizedNewPool {
+            trace_index,
+            protocol,
+            pool_address: deployed_address,
+            tokens: vec![CRV_USD_ADDRESS, call_data.token],
+        }]
+    }
+);
+
 alloy_sol_types::sol!(
     function coins(int128 arg0) external view returns (address);
     function coins(uint256 arg0) external view returns (address);
@@ -11,3 +11,6 @@ pub use mints::*;
 
 pub(crate) mod burns;
 pub use burns::*;
+
+mod crvusd;
+pub use crvusd::*;
@@ -0,0 +1,3 @@
+mod meta_aggregation_router_v2;
+
+pub use meta_aggregation_router_v2::*;
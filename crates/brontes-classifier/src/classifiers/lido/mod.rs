@@ -0,0 +1,5 @@
+mod steth;
+mod wsteth;
+
+pub use steth::*;
+pub use wsteth::*;
@@ -0,0 +1,36 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    constants::ETH_ADDRESS, normalized_actions::NormalizedWrap, structured_trace::CallInfo,
+    utils::ToScaledRational,
+};
+
+// stETH is minted 1:1 for the staked ETH, its balance is rebased afterwards
+action_impl!(
+    Protocol::LidoStETH,
+    crate::LidoStETH::submitCall,
+    Wrap,
+    [..Submitted],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: LidoStETHSubmitCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.submitted_field?;
+        let amount = logs.amount.to_scaled_rational(18);
+
+        Ok(NormalizedWrap {
+            protocol: Protocol::LidoStETH,
+            trace_index: info.trace_idx,
+            from: logs.sender,
+            recipient: logs.sender,
+            contract: info.target_address,
+            token_in: db_tx.try_fetch_token_info(ETH_ADDRESS)?,
+            token_out: db_tx.try_fetch_token_info(info.target_address)?,
+            amount_in: amount.clone(),
+            amount_out: amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
@@ -0,0 +1,70 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    constants::STETH_ADDRESS, normalized_actions::NormalizedWrap, structured_trace::CallInfo,
+    utils::ToScaledRational,
+};
+
+action_impl!(
+    Protocol::LidoWstETH,
+    crate::LidoWstETH::wrapCall,
+    Wrap,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: wrapCall,
+    return_data: wrapReturn,
+    db_tx: &DB | {
+        let token_in = db_tx.try_fetch_token_info(STETH_ADDRESS)?;
+        let token_out = db_tx.try_fetch_token_info(info.target_address)?;
+        let amount_in = call_data._stETHAmount.to_scaled_rational(token_in.decimals);
+        let amount_out = return_data._0.to_scaled_rational(token_out.decimals);
+
+        Ok(NormalizedWrap {
+            protocol: Protocol::LidoWstETH,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: info.from_address,
+            contract: info.target_address,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::LidoWstETH,
+    crate::LidoWstETH::unwrapCall,
+    Wrap,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: unwrapCall,
+    return_data: unwrapReturn,
+    db_tx: &DB | {
+        let token_in = db_tx.try_fetch_token_info(info.target_address)?;
+        let token_out = db_tx.try_fetch_token_info(STETH_ADDRESS)?;
+        let amount_in = call_data._wstETHAmount.to_scaled_rational(token_in.decimals);
+        let amount_out = return_data._0.to_scaled_rational(token_out.decimals);
+
+        Ok(NormalizedWrap {
+            protocol: Protocol::LidoWstETH,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: info.from_address,
+            contract: info.target_address,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            msg_value: info.msg_value,
+        })
+    }
+);
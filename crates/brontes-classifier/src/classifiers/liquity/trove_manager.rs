@@ -0,0 +1,220 @@
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    constants::{LUSD_ADDRESS, WETH_ADDRESS},
+    normalized_actions::NormalizedLiquidation,
+    structured_trace::CallInfo,
+    utils::ToScaledRational,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+
+use crate::LiquityTroveManager::{Liquidation, TroveLiquidated};
+
+action_impl!(
+    Protocol::Liquity,
+    crate::LiquityTroveManager::liquidateCall,
+    Liquidation,
+    [..TroveLiquidated*, ..Liquidation],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: LiquityLiquidateCallLogs,
+    db_tx: &DB | {
+        let troves = log_data.trove_liquidated_field?;
+        let liquidation = log_data.liquidation_field?;
+        parse_liquidation(info, troves, liquidation, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::Liquity,
+    crate::LiquityTroveManager::liquidateTrovesCall,
+    Liquidation,
+    [..TroveLiquidated*, ..Liquidation],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: LiquityLiquidateTrovesCallLogs,
+    db_tx: &DB | {
+        let troves = log_data.trove_liquidated_field?;
+        let liquidation = log_data.liquidation_field?;
+        parse_liquidation(info, troves, liquidation, db_tx)
+    }
+);
+
+action_impl!(
+    Protocol::Liquity,
+    crate::LiquityTroveManager::batchLiquidateTrovesCall,
+    Liquidation,
+    [..TroveLiquidated*, ..Liquidation],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: LiquityBatchLiquidateTrovesCallLogs,
+    db_tx: &DB | {
+        let troves = log_data.trove_liquidated_field?;
+        let liquidation = log_data.liquidation_field?;
+        parse_liquidation(info, troves, liquidation, db_tx)
+    }
+);
+
+/// The debt of liquidated troves is absorbed by the stability pool, which
+/// takes the rest of their collateral in return, or redistributed to the other
+/// troves if it is empty. The caller covers none of the debt and is only paid
+/// the gas compensation: its share of the collateral here and the LUSD part as
+/// a transfer out of the gas pool. All troves of a call are liquidated
+/// together, so the first one is used as the debtor.
+fn parse_liquidation<DB: LibmdbxReader>(
+    info: CallInfo,
+    troves: Vec<TroveLiquidated>,
+    liquidation: Liquidation,
+    db_tx: &DB,
+) -> eyre::Result<NormalizedLiquidation> {
+    let trove = troves
+        .first()
+        .ok_or_else(|| eyre::eyre!("liquidation without a liquidated trove"))?;
+
+    let debt_asset = db_tx.try_fetch_token_info(LUSD_ADDRESS)?;
+    let collateral_asset = db_tx.try_fetch_token_info(WETH_ADDRESS)?;
+    let liquidated_collateral = liquidation
+        ._collGasCompensation
+        .to_scaled_rational(collateral_asset.decimals);
+
+    Ok(NormalizedLiquidation {
+        protocol: Protocol::Liquity,
+        trace_index: info.trace_idx,
+        pool: info.target_address,
+        liquidator: info.from_address,
+        debtor: trove._borrower,
+        collateral_asset,
+        debt_asset,
+        covered_debt: Rational::ZERO,
+        liquidated_collateral,
+        msg_value: info.msg_value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, U256};
+    use brontes_types::{
+        constants::LUSD_ADDRESS,
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{Action, NormalizedLiquidation},
+        Protocol,
+    };
+    use malachite::{num::basic::traits::Zero, Rational};
+
+    use crate::{
+        test_utils::{ClassifierTestUtils, TestCall},
+        LiquityTroveManager::{
+            batchLiquidateTrovesCall, liquidateCall, Liquidation, TroveLiquidated,
+        },
+    };
+
+    const TROVE_MANAGER: Address = Address::new(hex!("a39739ef8b0231dbfa0dcda07d7e29faabcf4bb2"));
+    const LIQUIDATOR: Address = Address::new(hex!("00000000000000000000000000000000000000a1"));
+    const BORROWER: Address = Address::new(hex!("00000000000000000000000000000000000000b2"));
+    const OTHER_BORROWER: Address = Address::new(hex!("00000000000000000000000000000000000000b3"));
+    const ONE: u128 = 1_000_000_000_000_000_000;
+
+    async fn setup() -> ClassifierTestUtils {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol_with_tokens(
+            Protocol::Liquity,
+            TROVE_MANAGER,
+            &[lusd(), TokenInfoWithAddress::weth()],
+        );
+
+        classifier_utils
+    }
+
+    fn lusd() -> TokenInfoWithAddress {
+        TokenInfoWithAddress {
+            address: LUSD_ADDRESS,
+            inner:   TokenInfo { decimals: 18, symbol: "LUSD".to_string() },
+        }
+    }
+
+    fn trove_liquidated(borrower: Address, debt: u128, coll: u128) -> TroveLiquidated {
+        TroveLiquidated {
+            _borrower:  borrower,
+            _debt:      U256::from(debt * ONE),
+            _coll:      U256::from(coll * ONE),
+            _operation: 1,
+        }
+    }
+
+    /// 0.5% of the collateral is paid to the liquidator
+    fn liquidation(debt: u128, coll: u128) -> Liquidation {
+        Liquidation {
+            _liquidatedDebt:      U256::from(debt * ONE),
+            _liquidatedColl:      U256::from(coll * ONE),
+            _collGasCompensation: U256::from(coll * ONE / 200),
+            _LUSDGasCompensation: U256::from(200 * ONE),
+        }
+    }
+
+    fn eq_action(coll_gas_compensation: Rational) -> Action {
+        Action::Liquidation(NormalizedLiquidation {
+            protocol:              Protocol::Liquity,
+            trace_index:           1,
+            pool:                  TROVE_MANAGER,
+            liquidator:            LIQUIDATOR,
+            debtor:                BORROWER,
+            collateral_asset:      TokenInfoWithAddress::weth(),
+            debt_asset:            lusd(),
+            covered_debt:          Rational::ZERO,
+            liquidated_collateral: coll_gas_compensation,
+            msg_value:             U256::ZERO,
+        })
+    }
+
+    #[brontes_macros::test]
+    async fn test_liquity_liquidation() {
+        let classifier_utils = setup().await;
+        let call = TestCall::new(TROVE_MANAGER, LIQUIDATOR, liquidateCall { _borrower: BORROWER })
+            .with_log(TROVE_MANAGER, trove_liquidated(BORROWER, 2_000, 2))
+            .with_log(TROVE_MANAGER, liquidation(2_000, 2));
+
+        // the stability pool takes the rest of the collateral for the debt
+        assert_eq!(
+            classifier_utils.classify_call(&call),
+            Some(eq_action(Rational::from_unsigneds(1u32, 100u32)))
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_liquity_batch_liquidation() {
+        let classifier_utils = setup().await;
+        // the compensation for all troves is taken from the liquidation event,
+        // the first trove is the debtor
+        let call = TestCall::new(
+            TROVE_MANAGER,
+            LIQUIDATOR,
+            batchLiquidateTrovesCall { _troveArray: vec![BORROWER, OTHER_BORROWER] },
+        )
+        .with_log(TROVE_MANAGER, trove_liquidated(BORROWER, 2_000, 2))
+        .with_log(TROVE_MANAGER, trove_liquidated(OTHER_BORROWER, 4_000, 4))
+        .with_log(TROVE_MANAGER, liquidation(6_000, 6));
+
+        assert_eq!(
+            classifier_utils.classify_call(&call),
+            Some(eq_action(Rational::from_unsigneds(3u32, 100u32)))
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_liquity_liquidation_without_troves() {
+        let classifier_utils = setup().await;
+        let call = TestCall::new(
+            TROVE_MANAGER,
+            LIQUIDATOR,
+            batchLiquidateTrovesCall { _troveArray: vec![BORROWER] },
+        )
+        .with_log(TROVE_MANAGER, liquidation(0, 0));
+
+        assert_eq!(classifier_utils.classify_call(&call), None);
+    }
+}
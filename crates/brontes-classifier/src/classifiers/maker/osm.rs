@@ -0,0 +1,32 @@
+use alloy_primitives::U256;
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedOracleUpdate, structured_trace::CallInfo, Protocol,
+    ToScaledRational,
+};
+
+action_impl!(
+    Protocol::MakerOSM,
+    crate::MakerOSM::pokeCall,
+    OracleUpdate,
+    [LogValue],
+    logs: true,
+    |info: CallInfo, log_data: MakerOSMPokeCallLogs, db_tx: &DB| {
+        let logs = log_data.log_value_field?;
+
+        let asset = db_tx
+            .get_protocol_details(info.target_address)
+            .ok()
+            .and_then(|details| db_tx.try_fetch_token_info(details.token0).ok());
+
+        // the logged value is the price that just became current, as a wad
+        Ok(NormalizedOracleUpdate {
+            protocol: Protocol::MakerOSM,
+            trace_index: info.trace_idx,
+            oracle: info.target_address,
+            from: info.from_address,
+            asset,
+            price: U256::from_be_bytes(logs.val.0).to_scaled_rational(18),
+        })
+    }
+);
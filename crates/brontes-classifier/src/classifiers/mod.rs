@@ -38,9 +38,36 @@ pub use oneinch::*;
 pub mod clipper;
 pub use clipper::*;
 
+pub mod paraswap;
+pub use paraswap::*;
+
+pub mod kyberswap;
+pub use kyberswap::*;
+
+pub mod odos;
+pub use odos::*;
+
 pub mod dodo;
 pub use dodo::*;
 
+pub mod chainlink;
+pub use chainlink::*;
+
+pub mod morpho;
+pub use morpho::*;
+
+pub mod liquity;
+pub use liquity::*;
+
+pub mod weth;
+pub use weth::*;
+
+pub mod lido;
+pub use lido::*;
+
+pub mod rocketpool;
+pub use rocketpool::*;
+
 discovery_dispatch!(
     DiscoveryClassifier,
     SushiSwapV2Discovery,
@@ -63,7 +90,9 @@ discovery_dispatch!(
     CurveCryptoSwapDiscovery,
     CurveTriCryptoDiscovery,
     BalancerV1CoreDiscovery,
-    BalancerV1SmartPoolDiscovery
+    BalancerV1SmartPoolDiscovery,
+    CompoundV3Discovery,
+    CurvecrvUSDMarketDiscovery
 );
 
 action_dispatch!(
@@ -81,6 +110,10 @@ action_dispatch!(
     UniswapV3MintCall,
     UniswapV3BurnCall,
     UniswapV3CollectCall,
+    UniswapV4SwapCall,
+    UniswapV4ModifyLiquidityCall,
+    UniswapV4DonateCall,
+    UniswapV4InitializeCall,
     SushiSwapV3SwapCall,
     SushiSwapV3MintCall,
     SushiSwapV3BurnCall,
@@ -145,11 +178,22 @@ action_dispatch!(
     MakerPSMBuyGemCall,
     MakerPSMSellGemCall,
     MakerDssFlashFlashLoanCall,
+    MakerOSMPokeCall,
+    ChainlinkOffchainAggregatorTransmitCall,
     AaveV2LiquidationCallCall,
     AaveV3LiquidationCallCall,
     AaveV2FlashLoanCall,
     AaveV3FlashLoanCall,
     AaveV3FlashLoanSimpleCall,
+    AaveV2DepositCall,
+    AaveV2WithdrawCall,
+    AaveV2BorrowCall,
+    AaveV2RepayCall,
+    AaveV3SupplyCall,
+    AaveV3DepositCall,
+    AaveV3WithdrawCall,
+    AaveV3BorrowCall,
+    AaveV3RepayCall,
     BalancerV1SwapExactAmountInCall,
     BalancerV1SwapExactAmountOutCall,
     BalancerV1BindCall,
@@ -160,8 +204,35 @@ action_dispatch!(
     BalancerV2ExitPoolCall,
     BalancerV2RegisterTokensCall,
     CompoundV2LiquidateBorrowCall,
+    CompoundV2MintCall,
+    CompoundV2RedeemCall,
+    CompoundV2RedeemUnderlyingCall,
+    CompoundV2BorrowCall,
+    CompoundV2RepayBorrowCall,
+    CompoundV2RepayBorrowBehalfCall,
     CompoundV2Initialize_0Call,
     CompoundV2Initialize_1Call,
+    CompoundV3AbsorbCall,
+    CompoundV3BuyCollateralCall,
+    MorphoBlueLiquidateCall,
+    MorphoBlueFlashLoanCall,
+    LiquityLiquidateCall,
+    LiquityLiquidateTrovesCall,
+    LiquityBatchLiquidateTrovesCall,
+    CurvecrvUSDControllerLiquidate_0Call,
+    CurvecrvUSDControllerLiquidate_1Call,
+    CurvecrvUSDControllerLiquidate_extendedCall,
+    CurvecrvUSDLlammaExchange_0Call,
+    CurvecrvUSDLlammaExchange_1Call,
+    CurvecrvUSDLlammaExchange_dy_0Call,
+    CurvecrvUSDLlammaExchange_dy_1Call,
+    WETHDepositCall,
+    WETHWithdrawCall,
+    LidoStETHSubmitCall,
+    LidoWstETHWrapCall,
+    LidoWstETHUnwrapCall,
+    RocketPoolRETHMintCall,
+    RocketPoolRETHBurnCall,
     OneInchV5SwapCall,
     OneInchV5ClipperSwapCall,
     OneInchV5ClipperSwapToCall,
@@ -171,6 +242,17 @@ action_dispatch!(
     OneInchV5UniswapV3SwapToCall,
     OneInchV5UniswapV3SwapToWithPermitCall,
     OneInchFusionSettleOrdersCall,
+    ParaswapV5SimpleSwapCall,
+    ParaswapV5SimpleBuyCall,
+    ParaswapV5MultiSwapCall,
+    ParaswapV5MegaSwapCall,
+    KyberSwapAggregatorV2SwapCall,
+    KyberSwapAggregatorV2SwapGenericCall,
+    KyberSwapAggregatorV2SwapSimpleModeCall,
+    OdosV2SwapCall,
+    OdosV2SwapMultiCall,
+    OdosV2SwapCompactCall,
+    OdosV2SwapMultiCompactCall,
     ClipperExchangeSwapCall,
     ClipperExchangeSellEthForTokenCall,
     ClipperExchangeSellTokenForEthCall,
@@ -0,0 +1,78 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    normalized_actions::{NormalizedFlashLoan, NormalizedLiquidation},
+    structured_trace::CallInfo,
+    utils::ToScaledRational,
+};
+
+action_impl!(
+    Protocol::MorphoBlue,
+    crate::MorphoBlue::liquidateCall,
+    Liquidation,
+    [..Liquidate],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: liquidateCall,
+    log_data: MorphoBlueLiquidateCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.liquidate_field?;
+        // markets aren't deployed as contracts, their tokens are passed with each call
+        let market = call_data.marketParams;
+        let debt_asset = db_tx.try_fetch_token_info(market.loanToken)?;
+        let collateral_asset = db_tx.try_fetch_token_info(market.collateralToken)?;
+        let covered_debt = logs.repaidAssets.to_scaled_rational(debt_asset.decimals);
+        let liquidated_collateral = logs.seizedAssets.to_scaled_rational(collateral_asset.decimals);
+
+        Ok(NormalizedLiquidation {
+            protocol: Protocol::MorphoBlue,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: logs.caller,
+            debtor: logs.borrower,
+            collateral_asset,
+            debt_asset,
+            covered_debt,
+            liquidated_collateral,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::MorphoBlue,
+    crate::MorphoBlue::flashLoanCall,
+    FlashLoan,
+    [..FlashLoan],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: flashLoanCall,
+    log_data: MorphoBlueFlashLoanCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.flash_loan_field?;
+        let token = db_tx.try_fetch_token_info(call_data.token)?;
+        let amount = logs.assets.to_scaled_rational(token.decimals);
+
+        Ok(NormalizedFlashLoan {
+            protocol: Protocol::MorphoBlue,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            pool: info.target_address,
+            // the callback is always made to the caller
+            receiver_contract: logs.caller,
+            assets: vec![token],
+            amounts: vec![amount],
+            msg_value: info.msg_value,
+
+            // Empty
+            aave_mode: None,
+            child_actions: vec![],
+            repayments: vec![],
+            fees_paid: vec![],
+        })
+    }
+);
@@ -0,0 +1,3 @@
+mod blue;
+
+pub use blue::*;
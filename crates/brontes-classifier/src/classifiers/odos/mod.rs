@@ -0,0 +1,3 @@
+mod router_v2;
+
+pub use router_v2::*;
@@ -0,0 +1,92 @@
+use alloy_primitives::Address;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+action_impl!(
+    Protocol::OdosV2,
+    crate::OdosRouterV2::swapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: swapCall,
+    _db_tx: &DB | {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::OdosV2,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: call_data.tokenInfo.outputReceiver,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::OdosV2,
+    crate::OdosRouterV2::swapMultiCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: swapMultiCall,
+    _db_tx: &DB | {
+        let recipient = call_data
+            .outputs
+            .first()
+            .map(|output| output.receiver)
+            .unwrap_or_default();
+
+        Ok(NormalizedAggregator {
+            protocol: Protocol::OdosV2,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+// The compact swaps pack their arguments into a custom encoding, the recipient
+// is resolved from the payout of the router by the multi frame classification
+action_impl!(
+    Protocol::OdosV2,
+    crate::OdosRouterV2::swapCompactCall,
+    Aggregator,
+    [],
+    |info: CallInfo, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol:      Protocol::OdosV2,
+            trace_index:   info.trace_idx,
+            from:          info.from_address,
+            to:            info.target_address,
+            recipient:     Address::ZERO,
+            child_actions: vec![],
+            msg_value:     info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::OdosV2,
+    crate::OdosRouterV2::swapMultiCompactCall,
+    Aggregator,
+    [],
+    |info: CallInfo, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol:      Protocol::OdosV2,
+            trace_index:   info.trace_idx,
+            from:          info.from_address,
+            to:            info.target_address,
+            recipient:     Address::ZERO,
+            child_actions: vec![],
+            msg_value:     info.msg_value,
+        })
+    }
+);
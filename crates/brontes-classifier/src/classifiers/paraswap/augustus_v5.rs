@@ -0,0 +1,101 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+// A zero beneficiary pays out to the caller, the recipient is resolved by the
+// multi frame classification of the aggregator
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusSwapperV5::simpleSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: simpleSwapCall,
+    _db_tx: &DB | {
+        let recipient = call_data.data.beneficiary;
+
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusSwapperV5::simpleBuyCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: simpleBuyCall,
+    _db_tx: &DB | {
+        let recipient = call_data.data.beneficiary;
+
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusSwapperV5::multiSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: multiSwapCall,
+    _db_tx: &DB | {
+        let recipient = call_data.data.beneficiary;
+
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusSwapperV5::megaSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: megaSwapCall,
+    _db_tx: &DB | {
+        let recipient = call_data.data.beneficiary;
+
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
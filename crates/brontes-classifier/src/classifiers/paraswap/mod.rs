@@ -0,0 +1,3 @@
+mod augustus_v5;
+
+pub use augustus_v5::*;
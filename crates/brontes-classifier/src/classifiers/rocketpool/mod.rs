@@ -0,0 +1,3 @@
+mod reth;
+
+pub use reth::*;
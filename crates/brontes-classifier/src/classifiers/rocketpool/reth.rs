@@ -0,0 +1,63 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    constants::ETH_ADDRESS, normalized_actions::NormalizedWrap, structured_trace::CallInfo,
+    utils::ToScaledRational,
+};
+
+// rETH is only minted by the deposit pool, which receives the ETH of the
+// depositor and keeps the deposit fee. The deposit pool is the one wrapping the
+// ETH so the fee stays with it in the token deltas.
+action_impl!(
+    Protocol::RocketPoolRETH,
+    crate::RocketPoolRETH::mintCall,
+    Wrap,
+    [..TokensMinted],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: RocketPoolRETHMintCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.tokens_minted_field?;
+
+        Ok(NormalizedWrap {
+            protocol: Protocol::RocketPoolRETH,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: logs.to,
+            contract: info.target_address,
+            token_in: db_tx.try_fetch_token_info(ETH_ADDRESS)?,
+            token_out: db_tx.try_fetch_token_info(info.target_address)?,
+            amount_in: logs.ethAmount.to_scaled_rational(18),
+            amount_out: logs.amount.to_scaled_rational(18),
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::RocketPoolRETH,
+    crate::RocketPoolRETH::burnCall,
+    Wrap,
+    [..TokensBurned],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: RocketPoolRETHBurnCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.tokens_burned_field?;
+
+        Ok(NormalizedWrap {
+            protocol: Protocol::RocketPoolRETH,
+            trace_index: info.trace_idx,
+            from: logs.from,
+            recipient: logs.from,
+            contract: info.target_address,
+            token_in: db_tx.try_fetch_token_info(info.target_address)?,
+            token_out: db_tx.try_fetch_token_info(ETH_ADDRESS)?,
+            amount_in: logs.amount.to_scaled_rational(18),
+            amount_out: logs.ethAmount.to_scaled_rational(18),
+            msg_value: info.msg_value,
+        })
+    }
+);
@@ -0,0 +1,213 @@
+use alloy_primitives::{I256, U256};
+use brontes_macros::action_impl;
+use brontes_pricing::{
+    uniswap_v4::{register_pool_key, UniswapV4PoolKey},
+    Protocol,
+};
+use brontes_types::{
+    normalized_actions::{
+        Action, NormalizedBurn, NormalizedMint, NormalizedPoolConfigUpdate, NormalizedSwap,
+    },
+    structured_trace::CallInfo,
+    ToScaledRational,
+};
+
+use crate::UniswapV4PoolManager::PoolKey;
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4PoolManager::swapCall,
+    Swap,
+    [Swap],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: swapCall,
+    return_data: swapReturn,
+    db_tx: &DB| {
+        let key = into_pool_key(&call_data.key);
+        let pool = register_pool_key(key);
+        let (token_0_delta, token_1_delta) = split_balance_delta(return_data.swapDelta);
+
+        let t0_info = db_tx.try_fetch_token_info(key.token0())?;
+        let t1_info = db_tx.try_fetch_token_info(key.token1())?;
+
+        // the delta is from the perspective of the caller, a negative amount is
+        // owed to the pool
+        let (amount_in, amount_out, token_in, token_out) = if token_0_delta.is_negative() {
+            (
+                token_0_delta.unsigned_abs().to_scaled_rational(t0_info.decimals),
+                token_1_delta.unsigned_abs().to_scaled_rational(t1_info.decimals),
+                t0_info,
+                t1_info,
+            )
+        } else {
+            (
+                token_1_delta.unsigned_abs().to_scaled_rational(t1_info.decimals),
+                token_0_delta.unsigned_abs().to_scaled_rational(t0_info.decimals),
+                t1_info,
+                t0_info,
+            )
+        };
+
+        Ok(NormalizedSwap {
+            protocol: Protocol::UniswapV4,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: info.from_address,
+            pool,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            msg_value: info.msg_value
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4PoolManager::modifyLiquidityCall,
+    Action,
+    [ModifyLiquidity],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: modifyLiquidityCall,
+    return_data: modifyLiquidityReturn,
+    db_tx: &DB| {
+        let key = into_pool_key(&call_data.key);
+        let pool = register_pool_key(key);
+
+        let t0_info = db_tx.try_fetch_token_info(key.token0())?;
+        let t1_info = db_tx.try_fetch_token_info(key.token1())?;
+
+        // the caller delta includes the fees accrued by the position, we only want
+        // the liquidity that is added or removed
+        let (fee_0, fee_1) = split_balance_delta(return_data.feesAccrued);
+        let (token_0_delta, token_1_delta) = split_balance_delta(return_data.callerDelta);
+        let am0 = token_0_delta
+            .saturating_sub(fee_0)
+            .unsigned_abs()
+            .to_scaled_rational(t0_info.decimals);
+        let am1 = token_1_delta
+            .saturating_sub(fee_1)
+            .unsigned_abs()
+            .to_scaled_rational(t1_info.decimals);
+
+        if call_data.params.liquidityDelta.is_negative() {
+            Ok(Action::Burn(NormalizedBurn {
+                protocol: Protocol::UniswapV4,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient: info.from_address,
+                pool,
+                token: vec![t0_info, t1_info],
+                amount: vec![am0, am1],
+            }))
+        } else {
+            Ok(Action::Mint(NormalizedMint {
+                protocol: Protocol::UniswapV4,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient: info.from_address,
+                pool,
+                token: vec![t0_info, t1_info],
+                amount: vec![am0, am1],
+            }))
+        }
+    }
+);
+
+// donations are paid to the in range liquidity providers. As they add to the
+// pools balances, we treat them as a mint that doesn't return a position.
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4PoolManager::donateCall,
+    Mint,
+    [Donate],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: donateCall,
+    db_tx: &DB| {
+        let key = into_pool_key(&call_data.key);
+        let pool = register_pool_key(key);
+
+        let t0_info = db_tx.try_fetch_token_info(key.token0())?;
+        let t1_info = db_tx.try_fetch_token_info(key.token1())?;
+
+        let am0 = call_data.amount0.to_scaled_rational(t0_info.decimals);
+        let am1 = call_data.amount1.to_scaled_rational(t1_info.decimals);
+
+        Ok(NormalizedMint {
+            protocol: Protocol::UniswapV4,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: pool,
+            pool,
+            token: vec![t0_info, t1_info],
+            amount: vec![am0, am1],
+        })
+    }
+);
+
+// v4 pools aren't contracts, so they can't be discovered through create traces.
+// Instead we pick them up when they are initialized in the pool manager.
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4PoolManager::initializeCall,
+    PoolConfigUpdate,
+    [Initialize],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: initializeCall,
+    _| {
+        let key = into_pool_key(&call_data.key);
+        let pool_address = register_pool_key(key);
+
+        Ok(NormalizedPoolConfigUpdate {
+            trace_index: info.trace_idx,
+            protocol: Protocol::UniswapV4,
+            pool_address,
+            tokens: vec![key.token0(), key.token1()],
+        })
+    }
+);
+
+fn into_pool_key(key: &PoolKey) -> UniswapV4PoolKey {
+    UniswapV4PoolKey {
+        currency0:    key.currency0,
+        currency1:    key.currency1,
+        fee:          key.fee,
+        tick_spacing: key.tickSpacing,
+        hooks:        key.hooks,
+    }
+}
+
+/// A `BalanceDelta` packs the amount of currency0 in the upper 128 bits and the
+/// amount of currency1 in the lower 128 bits.
+fn split_balance_delta(delta: I256) -> (i128, i128) {
+    let raw = delta.into_raw();
+    let amount_0 = (raw >> 128).to::<u128>() as i128;
+    let amount_1 = (raw & U256::from(u128::MAX)).to::<u128>() as i128;
+
+    (amount_0, amount_1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_balance_delta() {
+        // -100 currency0, +250 currency1
+        let raw = (U256::from((-100i128) as u128) << 128) | U256::from(250u128);
+        let delta = I256::from_raw(raw);
+
+        assert_eq!(split_balance_delta(delta), (-100, 250));
+    }
+}
@@ -0,0 +1,62 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    constants::ETH_ADDRESS, normalized_actions::NormalizedWrap, structured_trace::CallInfo,
+    utils::ToScaledRational,
+};
+
+action_impl!(
+    Protocol::WETH,
+    crate::WETH::depositCall,
+    Wrap,
+    [Deposit],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: WETHDepositCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.deposit_field?;
+        let amount = logs.wad.to_scaled_rational(18);
+
+        Ok(NormalizedWrap {
+            protocol: Protocol::WETH,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: logs.dst,
+            contract: info.target_address,
+            token_in: db_tx.try_fetch_token_info(ETH_ADDRESS)?,
+            token_out: db_tx.try_fetch_token_info(info.target_address)?,
+            amount_in: amount.clone(),
+            amount_out: amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::WETH,
+    crate::WETH::withdrawCall,
+    Wrap,
+    [Withdrawal],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: WETHWithdrawCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.withdrawal_field?;
+        let amount = logs.wad.to_scaled_rational(18);
+
+        Ok(NormalizedWrap {
+            protocol: Protocol::WETH,
+            trace_index: info.trace_idx,
+            from: logs.src,
+            recipient: logs.src,
+            contract: info.target_address,
+            token_in: db_tx.try_fetch_token_info(info.target_address)?,
+            token_out: db_tx.try_fetch_token_info(ETH_ADDRESS)?,
+            amount_in: amount.clone(),
+            amount_out: amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
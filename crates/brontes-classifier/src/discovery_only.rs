@@ -3,7 +3,7 @@ use std::sync::Arc;
 use alloy_primitives::Log;
 use brontes_core::missing_token_info::load_missing_token_info;
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
-use brontes_pricing::types::DexPriceMsg;
+use brontes_pricing::{types::DexPriceMsg, uniswap_v4::get_pool_key, Protocol};
 use brontes_types::{
     normalized_actions::{pool::NormalizedNewPool, Action, MultiFrameRequest},
     structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
@@ -228,6 +228,15 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> DiscoveryOnlyClassif
                 {
                     error!(pool=?p.pool_address,"failed to update pool config");
                 }
+
+                // the state of a v4 pool can only be loaded with its key
+                if let Some(key) =
+                    get_pool_key(p.pool_address).filter(|_| p.protocol == Protocol::UniswapV4)
+                {
+                    if self.libmdbx.insert_uniswap_v4_pool_key(key).await.is_err() {
+                        error!(pool=?p.pool_address,"failed to save uniswap v4 pool key");
+                    }
+                }
             }
         } else {
             self.classify_transfer(trace_index, &trace, block).await
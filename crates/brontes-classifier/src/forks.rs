@@ -0,0 +1,291 @@
+//! Registry of protocols that are deployed from the same code as a protocol
+//! brontes already classifies, e.g. Uniswap V2 or Aave V3 forks. A fork reuses
+//! the classifiers and pricing state of its base protocol, so supporting one
+//! only requires an entry in a TOML file:
+//!
+//! ```toml
+//! [[fork]]
+//! name = "ShibaSwap"
+//! base = "UniswapV2"
+//! factory = "0x115934131916C8b277DD010Ee02de363c09d037c"
+//! init_code_hash = "0x65d1a3b1e46c6e4f1be1ad5f99ef14dc488ae0549dc97db9b30afe2241ce1c7a"
+//!
+//! [[fork]]
+//! name = "Spark"
+//! base = "AaveV3"
+//! contracts = ["0xC13e21B648A5Ee794902342038FF3aDAB66BE987"]
+//! ```
+//!
+//! Pools created by the factory of a fork are found by the fallback of the
+//! [`DiscoveryClassifier`](crate::DiscoveryClassifier) and are stored under
+//! the base protocol. Calls to the contracts of a fork fall back to the
+//! classifiers of the base protocol in the
+//! [`ProtocolClassifier`](crate::ProtocolClassifier).
+
+use std::{path::Path, sync::OnceLock};
+
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy_sol_types::SolCall;
+use brontes_types::{
+    normalized_actions::pool::NormalizedNewPool, traits::TracingProvider, FastHashMap, FastHashSet,
+    Protocol,
+};
+use serde::Deserialize;
+
+use crate::{
+    FactoryDiscovery, UniswapV2Discovery, UniswapV2Factory::createPairCall, UniswapV3Discovery,
+    UniswapV3Factory::createPoolCall,
+};
+
+static FORK_REGISTRY: OnceLock<ForkRegistry> = OnceLock::new();
+
+/// A protocol that behaves exactly like its `base` protocol
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ProtocolFork {
+    pub name:           String,
+    pub base:           Protocol,
+    /// The factory that creates the pools of the fork
+    #[serde(default)]
+    pub factory:        Option<Address>,
+    /// Hash of the pool creation code, used to check that a contract
+    /// created by the factory is a pool
+    #[serde(default)]
+    pub init_code_hash: Option<B256>,
+    /// Contracts of the fork that aren't created by a factory, such as the
+    /// pool of a lending market
+    #[serde(default)]
+    pub contracts:      Vec<Address>,
+}
+
+impl ProtocolFork {
+    /// The address the factory deploys the pool of `tokens` to. `tokens` need
+    /// to be sorted
+    fn pool_address(&self, tokens: &[Address], parent_calldata: &Bytes) -> Option<Address> {
+        let salt = match self.base {
+            Protocol::UniswapV2 => {
+                let mut packed = [0u8; 40];
+                packed[..20].copy_from_slice(&**tokens.first()?);
+                packed[20..].copy_from_slice(&**tokens.get(1)?);
+                keccak256(packed)
+            }
+            Protocol::UniswapV3 => {
+                let fee = createPoolCall::abi_decode(parent_calldata, false).ok()?.fee;
+                let mut encoded = [0u8; 96];
+                encoded[12..32].copy_from_slice(&**tokens.first()?);
+                encoded[44..64].copy_from_slice(&**tokens.get(1)?);
+                encoded[64..].copy_from_slice(&U256::from(fee).to_be_bytes::<32>());
+                keccak256(encoded)
+            }
+            _ => return None,
+        };
+
+        Some(self.factory?.create2(salt, self.init_code_hash?))
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ForkRegistry {
+    forks:       Vec<ProtocolFork>,
+    by_factory:  FastHashMap<Address, usize>,
+    by_contract: FastHashMap<Address, usize>,
+}
+
+#[derive(Deserialize)]
+struct ForkRegistryToml {
+    #[serde(default)]
+    fork: Vec<ProtocolFork>,
+}
+
+impl ForkRegistry {
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("failed to read fork registry at {}: {e}", path.display()))?;
+
+        Self::from_toml(&config)
+    }
+
+    pub fn from_toml(config: &str) -> eyre::Result<Self> {
+        let registry: ForkRegistryToml = toml::from_str(config)?;
+        Self::new(registry.fork)
+    }
+
+    pub fn new(forks: Vec<ProtocolFork>) -> eyre::Result<Self> {
+        let mut names = FastHashSet::default();
+        let mut by_factory = FastHashMap::default();
+        let mut by_contract = FastHashMap::default();
+
+        for (i, fork) in forks.iter().enumerate() {
+            if !names.insert(fork.name.as_str()) {
+                eyre::bail!("fork {} is registered more than once", fork.name)
+            }
+            if fork.factory.is_none() && fork.contracts.is_empty() {
+                eyre::bail!("fork {} has neither a factory nor contracts", fork.name)
+            }
+
+            if let Some(factory) = fork.factory {
+                if !matches!(fork.base, Protocol::UniswapV2 | Protocol::UniswapV3) {
+                    eyre::bail!("pools of {:?} forks can't be discovered", fork.base)
+                }
+                if fork.init_code_hash.is_none() {
+                    eyre::bail!("fork {} has a factory but no init code hash", fork.name)
+                }
+                if by_factory.insert(factory, i).is_some() {
+                    eyre::bail!("factory {factory:?} belongs to more than one fork")
+                }
+            }
+
+            for contract in &fork.contracts {
+                if by_contract.insert(*contract, i).is_some() {
+                    eyre::bail!("contract {contract:?} belongs to more than one fork")
+                }
+            }
+        }
+
+        Ok(Self { forks, by_factory, by_contract })
+    }
+
+    pub fn forks(&self) -> &[ProtocolFork] {
+        &self.forks
+    }
+
+    pub fn fork_of_factory(&self, factory: Address) -> Option<&ProtocolFork> {
+        self.by_factory.get(&factory).map(|i| &self.forks[*i])
+    }
+
+    pub fn fork_of_contract(&self, contract: Address) -> Option<&ProtocolFork> {
+        self.by_contract.get(&contract).map(|i| &self.forks[*i])
+    }
+}
+
+/// Sets the forks the classifier falls back to. Needs to be called before any
+/// block is classified, otherwise no forks are registered.
+pub fn init_fork_registry(registry: ForkRegistry) -> eyre::Result<()> {
+    FORK_REGISTRY
+        .set(registry)
+        .map_err(|_| eyre::eyre!("fork registry was already initialized"))
+}
+
+pub fn fork_registry() -> &'static ForkRegistry {
+    FORK_REGISTRY.get_or_init(ForkRegistry::default)
+}
+
+/// Discovers the pools created by the factory of a fork with the discovery of
+/// its base protocol. Contracts that aren't deployed to the address derived
+/// from the init code hash aren't pools and are skipped.
+pub(crate) async fn discover_fork_pools<T: TracingProvider>(
+    fork: &ProtocolFork,
+    tracer: std::sync::Arc<T>,
+    deployed_address: Address,
+    trace_idx: u64,
+    parent_calldata: Bytes,
+) -> Vec<NormalizedNewPool> {
+    let selector = &parent_calldata[0..4];
+    let pools = match fork.base {
+        Protocol::UniswapV2 if selector == createPairCall::SELECTOR => {
+            UniswapV2Discovery
+                .decode_create_trace(tracer, deployed_address, trace_idx, parent_calldata.clone())
+                .await
+        }
+        Protocol::UniswapV3 if selector == createPoolCall::SELECTOR => {
+            UniswapV3Discovery
+                .decode_create_trace(tracer, deployed_address, trace_idx, parent_calldata.clone())
+                .await
+        }
+        _ => return Vec::new(),
+    };
+
+    pools
+        .into_iter()
+        .filter(|pool| {
+            let is_pool =
+                fork.pool_address(&pool.tokens, &parent_calldata) == Some(pool.pool_address);
+            if is_pool {
+                tracing::debug!(
+                    target: "brontes_classifier::discovery",
+                    fork = fork.name,
+                    pool = ?pool.pool_address,
+                    "discovered fork pool"
+                );
+            } else {
+                tracing::debug!(
+                    target: "brontes_classifier::discovery",
+                    fork = fork.name,
+                    ?deployed_address,
+                    "created contract isn't a pool of the fork"
+                );
+            }
+            is_pool
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_uniswap_v2_pool_address() {
+        // the Uniswap V2 factory and the USDC/WETH pair
+        let fork = ProtocolFork {
+            name:           "UniswapV2".to_string(),
+            base:           Protocol::UniswapV2,
+            factory:        Some(Address::new(hex!("5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"))),
+            init_code_hash: Some(B256::new(hex!(
+                "96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f"
+            ))),
+            contracts:      vec![],
+        };
+        let tokens = [
+            Address::new(hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")),
+            Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")),
+        ];
+
+        assert_eq!(
+            fork.pool_address(&tokens, &Bytes::new()),
+            Some(Address::new(hex!("B4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc")))
+        );
+    }
+
+    #[test]
+    fn test_registry_lookup() {
+        let registry = ForkRegistry::from_toml(
+            r#"
+            [[fork]]
+            name = "ShibaSwap"
+            base = "UniswapV2"
+            factory = "0x115934131916C8b277DD010Ee02de363c09d037c"
+            init_code_hash = "0x65d1a3b1e46c6e4f1be1ad5f99ef14dc488ae0549dc97db9b30afe2241ce1c7a"
+
+            [[fork]]
+            name = "Spark"
+            base = "AaveV3"
+            contracts = ["0xC13e21B648A5Ee794902342038FF3aDAB66BE987"]
+            "#,
+        )
+        .unwrap();
+
+        let factory = Address::new(hex!("115934131916C8b277DD010Ee02de363c09d037c"));
+        let spark = Address::new(hex!("C13e21B648A5Ee794902342038FF3aDAB66BE987"));
+        assert_eq!(registry.fork_of_factory(factory).unwrap().name, "ShibaSwap");
+        assert_eq!(registry.fork_of_contract(spark).unwrap().base, Protocol::AaveV3);
+        assert!(registry.fork_of_contract(factory).is_none());
+    }
+
+    #[test]
+    fn test_factory_of_undiscoverable_base() {
+        let registry = ForkRegistry::from_toml(
+            r#"
+            [[fork]]
+            name = "Spark"
+            base = "AaveV3"
+            factory = "0xC13e21B648A5Ee794902342038FF3aDAB66BE987"
+            init_code_hash = "0x65d1a3b1e46c6e4f1be1ad5f99ef14dc488ae0549dc97db9b30afe2241ce1c7a"
+            "#,
+        );
+
+        assert!(registry.is_err());
+    }
+}
@@ -19,6 +19,7 @@ use futures::Future;
 pub mod tree_builder;
 pub use tree_builder::Classifier;
 pub mod discovery_only;
+pub mod forks;
 pub mod multi_frame_classification;
 
 #[cfg(feature = "tests")]
@@ -33,6 +34,7 @@ pub use classifiers::*;
 sol!(UniswapV2, "./classifier-abis/UniswapV2.json");
 sol!(SushiSwapV2, "./classifier-abis/SushiSwapV2.json");
 sol!(UniswapV3, "./classifier-abis/UniswapV3.json");
+sol!(UniswapV4PoolManager, "./classifier-abis/UniswapV4PoolManager.json");
 sol!(SushiSwapV3, "./classifier-abis/SushiSwapV3.json");
 sol!(PancakeSwapV2, "./classifier-abis/PancakeSwapV2.json");
 sol!(PancakeSwapV3, "./classifier-abis/PancakeSwapV3.json");
@@ -45,6 +47,8 @@ sol!(CurveV2MetapoolImpl, "./classifier-abis/CurveV2MetapoolImpl.json");
 sol!(CurveV2PlainImpl, "./classifier-abis/CurveV2PlainImpl.json");
 sol!(CurvecrvUSDPlainImpl, "./classifier-abis/CurvecrvUSDPlainImpl.json");
 sol!(CurveCryptoSwap, "./classifier-abis/CurveCryptoSwap.json");
+sol!(CurvecrvUSDController, "./classifier-abis/CurvecrvUSDController.json");
+sol!(CurvecrvUSDLlamma, "./classifier-abis/CurvecrvUSDLlamma.json");
 sol!(BalancerV1, "./classifier-abis/balancer/BalancerV1Pool.json");
 sol!(BalancerV2Vault, "./classifier-abis/balancer/BalancerV2Vault.json");
 sol!(AaveV2, "./classifier-abis/AaveV2Pool.json");
@@ -52,10 +56,21 @@ sol!(AaveV3, "./classifier-abis/AaveV3Pool.json");
 sol!(UniswapX, "./classifier-abis/UniswapXExclusiveDutchOrderReactor.json");
 sol!(MakerPSM, "./classifier-abis/maker/MakerPSM.json");
 sol!(MakerDssFlash, "./classifier-abis/maker/MakerDssFlash.json");
+sol!(MakerOSM, "./classifier-abis/maker/MakerOSM.json");
 sol!(CompoundV2CToken, "./classifier-abis/CompoundV2CToken.json");
+sol!(CompoundV3Comet, "./classifier-abis/compound/CompoundV3Comet.json");
+sol!(MorphoBlue, "./classifier-abis/morpho/MorphoBlue.json");
+sol!(LiquityTroveManager, "./classifier-abis/liquity/LiquityTroveManager.json");
+sol!(WETH, "./classifier-abis/weth/WETH9.json");
+sol!(LidoStETH, "./classifier-abis/lido/LidoStETH.json");
+sol!(LidoWstETH, "./classifier-abis/lido/LidoWstETH.json");
+sol!(RocketPoolRETH, "./classifier-abis/rocketpool/RocketTokenRETH.json");
 sol!(OneInchAggregationRouterV5, "./classifier-abis/OneInchAggregationRouterV5.json");
 sol!(OneInchFusionSettlement, "./classifier-abis/OneInchFusionSettlement.json");
 sol!(ClipperExchange, "./classifier-abis/ClipperExchange.json");
+sol!(ParaswapAugustusSwapperV5, "./classifier-abis/paraswap/AugustusSwapperV5.json");
+sol!(KyberSwapMetaAggregationRouterV2, "./classifier-abis/kyberswap/MetaAggregationRouterV2.json");
+sol!(OdosRouterV2, "./classifier-abis/odos/OdosRouterV2.json");
 sol!(CowswapGPv2Settlement, "./classifier-abis/cowswap/GPv2Settlement.json");
 sol!(ZeroXUniswapFeaure, "./classifier-abis/zero-x/ZeroXUniswapFeature.json");
 sol!(ZeroXUniswapV3Feature, "./classifier-abis/zero-x/ZeroXUniswapV3Feature.json");
@@ -66,6 +81,7 @@ sol!(ZeroXLiquidityProviderFeature, "./classifier-abis/zero-x/ZeroXLiquidityProv
 sol!(ZeroXInterface, "./classifier-abis/zero-x/ZeroXInterface.json");
 sol!(DodoDPPPool, "./classifier-abis/dodo/DPPPool.json");
 sol!(DodoDSPPool, "./classifier-abis/dodo/DSPPool.json");
+sol!(ChainlinkOffchainAggregator, "./classifier-abis/chainlink/ChainlinkOffchainAggregator.json");
 
 // Discovery
 sol!(UniswapV2Factory, "./classifier-abis/UniswapV2Factory.json");
@@ -75,9 +91,11 @@ sol!(CurveV2MetapoolFactory, "./classifier-abis/CurveMetapoolFactoryV2.json");
 sol!(CurvecrvUSDFactory, "./classifier-abis/CurveCRVUSDFactory.json");
 sol!(CurveCryptoSwapFactory, "./classifier-abis/CurveCryptoSwapFactory.json");
 sol!(CurveTriCryptoFactory, "./classifier-abis/CurveTriCryptoFactory.json");
+sol!(CurvecrvUSDControllerFactory, "./classifier-abis/CurvecrvUSDControllerFactory.json");
 sol!(PancakeSwapV3PoolDeployer, "./classifier-abis/PancakeSwapV3PoolDeployer.json");
 sol!(CompoundV2Comptroller, "./classifier-abis/CompoundV2Comptroller.json");
 sol!(CErc20Delegate, "./classifier-abis/CErc20Delegate.json");
+sol!(CompoundV3Configurator, "./classifier-abis/compound/CompoundV3Configurator.json");
 sol!(BalancerV1CorePoolFactory, "./classifier-abis/balancer/BalancerV1Factory.json");
 sol!(BalancerV1SmartPoolFactory, "./classifier-abis/balancer/BalancerV1CrpFactory.json");
 sol!(DodoDVMFactory, "./classifier-abis/dodo/DVMFactory.json");
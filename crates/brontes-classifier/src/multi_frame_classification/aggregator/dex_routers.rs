@@ -0,0 +1,138 @@
+use alloy_primitives::Address;
+use brontes_types::{
+    normalized_actions::{
+        Action, MultiCallFrameClassification, MultiFrameAction, MultiFrameRequest, NodeDataIndex,
+    },
+    Protocol, TreeSearchBuilder,
+};
+
+use crate::multi_frame_classification::MultiCallFrameClassifier;
+
+pub struct ParaswapAgg;
+pub struct KyberSwapAgg;
+pub struct OdosAgg;
+
+impl MultiCallFrameClassifier for ParaswapAgg {
+    const KEY: [u8; 2] = [Protocol::ParaswapV5 as u8, MultiFrameAction::Aggregator as u8];
+
+    fn create_classifier(
+        request: MultiFrameRequest,
+    ) -> Option<MultiCallFrameClassification<Action>> {
+        Some(router_classifier(request))
+    }
+}
+
+impl MultiCallFrameClassifier for KyberSwapAgg {
+    const KEY: [u8; 2] =
+        [Protocol::KyberSwapAggregatorV2 as u8, MultiFrameAction::Aggregator as u8];
+
+    fn create_classifier(
+        request: MultiFrameRequest,
+    ) -> Option<MultiCallFrameClassification<Action>> {
+        Some(router_classifier(request))
+    }
+}
+
+impl MultiCallFrameClassifier for OdosAgg {
+    const KEY: [u8; 2] = [Protocol::OdosV2 as u8, MultiFrameAction::Aggregator as u8];
+
+    fn create_classifier(
+        request: MultiFrameRequest,
+    ) -> Option<MultiCallFrameClassification<Action>> {
+        Some(router_classifier(request))
+    }
+}
+
+fn router_classifier(request: MultiFrameRequest) -> MultiCallFrameClassification<Action> {
+    MultiCallFrameClassification {
+        trace_index:         request.trace_idx,
+        search_siblings:     false,
+        tree_search_builder: TreeSearchBuilder::new().with_actions([
+            Action::is_swap,
+            Action::is_transfer,
+            Action::is_eth_transfer,
+        ]),
+        parse_fn:            Box::new(parse_router),
+    }
+}
+
+/// Collapses the swaps and transfers of the route into the aggregator. When
+/// the call didn't name a recipient, the recipient is whoever the router pays
+/// out to last, or the caller if the router doesn't pay out itself.
+fn parse_router(
+    this_action: &mut Action,
+    child_nodes: Vec<(NodeDataIndex, Action)>,
+) -> Vec<NodeDataIndex> {
+    let this = this_action.try_aggregator_mut().unwrap();
+    let mut prune_nodes = Vec::new();
+    let mut payout = None;
+
+    for (trace_index, action) in child_nodes {
+        match &action {
+            Action::Transfer(t) if t.from == this.to => payout = Some(t.to),
+            Action::EthTransfer(e) if e.from == this.to => payout = Some(e.to),
+            _ => {}
+        }
+
+        match action {
+            Action::Swap(_)
+            | Action::SwapWithFee(_)
+            | Action::Transfer(_)
+            | Action::EthTransfer(_) => {
+                this.child_actions.push(action);
+                prune_nodes.push(trace_index);
+            }
+            _ => {}
+        }
+    }
+
+    if this.recipient == Address::ZERO {
+        this.recipient = payout.unwrap_or(this.from);
+    }
+
+    prune_nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, U256};
+    use brontes_types::normalized_actions::{NormalizedAggregator, NormalizedTransfer};
+
+    use super::*;
+
+    #[test]
+    fn test_recipient_from_router_payout() {
+        let router: Address = hex!("Cf5540fFFCdC3d510B18bFcA6d2b9987b0772559").into();
+        let user: Address = hex!("D911560979B78821D7b045C79E36E9CbfC2F6C6F").into();
+        let executor: Address = hex!("B28Ca7e465C452cE4252598e0Bc96Aeba553CF82").into();
+        let receiver: Address = hex!("De74395831F3Ba9EdC7cBEE1fcB441cf24c0AF4d").into();
+
+        let mut aggregator = Action::Aggregator(NormalizedAggregator {
+            protocol:      Protocol::OdosV2,
+            trace_index:   0,
+            from:          user,
+            to:            router,
+            recipient:     Address::ZERO,
+            child_actions: vec![],
+            msg_value:     U256::ZERO,
+        });
+        let transfer = |trace_index, from, to| {
+            Action::Transfer(NormalizedTransfer { trace_index, from, to, ..Default::default() })
+        };
+        let index = |trace_index| NodeDataIndex { trace_index, data_idx: 0, multi_data_idx: 0 };
+
+        let pruned = parse_router(
+            &mut aggregator,
+            vec![
+                (index(1), transfer(1, user, router)),
+                (index(2), transfer(2, router, executor)),
+                (index(3), transfer(3, router, receiver)),
+            ],
+        );
+        let aggregator = aggregator.try_aggregator_mut().unwrap();
+
+        assert_eq!(pruned.len(), 3);
+        assert_eq!(aggregator.child_actions.len(), 3);
+        assert_eq!(aggregator.recipient, receiver);
+    }
+}
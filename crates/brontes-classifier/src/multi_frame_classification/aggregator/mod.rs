@@ -1,3 +1,5 @@
+pub mod dex_routers;
+pub use dex_routers::*;
 pub mod one_inch;
 pub use one_inch::*;
 pub mod zero_x;
@@ -18,6 +18,7 @@ impl MultiCallFrameClassifier for Cowswap {
     ) -> Option<MultiCallFrameClassification<Action>> {
         Some(MultiCallFrameClassification {
             trace_index:         request.trace_idx,
+            search_siblings:     false,
             tree_search_builder: TreeSearchBuilder::new().with_actions([Action::is_swap]),
             parse_fn:            Box::new(|this_action, child_nodes| {
                 let this = this_action.try_batch_mut().unwrap();
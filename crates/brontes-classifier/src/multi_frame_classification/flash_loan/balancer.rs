@@ -18,6 +18,7 @@ impl MultiCallFrameClassifier for BalancerV2 {
     ) -> Option<MultiCallFrameClassification<Action>> {
         Some(MultiCallFrameClassification {
             trace_index:         request.trace_idx,
+            search_siblings:     false,
             tree_search_builder: TreeSearchBuilder::new().with_actions([
                 Action::is_swap,
                 Action::is_transfer,
@@ -4,3 +4,5 @@ pub mod maker_dss;
 pub use maker_dss::*;
 pub mod dodo;
 pub use dodo::*;
+pub mod morpho_blue;
+pub use morpho_blue::*;
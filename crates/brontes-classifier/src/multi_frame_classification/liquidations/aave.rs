@@ -18,6 +18,7 @@ impl MultiCallFrameClassifier for AaveV2 {
     ) -> Option<MultiCallFrameClassification<Action>> {
         Some(MultiCallFrameClassification {
             trace_index:         request.trace_idx,
+            search_siblings:     false,
             tree_search_builder: TreeSearchBuilder::new().with_action(Action::is_transfer),
             parse_fn:            Box::new(parse_v2_v3),
         })
@@ -32,6 +33,7 @@ impl MultiCallFrameClassifier for AaveV3 {
     ) -> Option<MultiCallFrameClassification<Action>> {
         Some(MultiCallFrameClassification {
             trace_index:         request.trace_idx,
+            search_siblings:     false,
             tree_search_builder: TreeSearchBuilder::new().with_action(Action::is_transfer),
             parse_fn:            Box::new(parse_v2_v3),
         })
@@ -0,0 +1,140 @@
+use brontes_types::{
+    normalized_actions::{
+        Action, MultiCallFrameClassification, MultiFrameAction, MultiFrameRequest, NodeDataIndex,
+    },
+    Protocol, TreeSearchBuilder,
+};
+
+use crate::multi_frame_classification::MultiCallFrameClassifier;
+
+pub struct CompoundV3;
+
+impl MultiCallFrameClassifier for CompoundV3 {
+    const KEY: [u8; 2] = [Protocol::CompoundV3 as u8, MultiFrameAction::Liquidation as u8];
+
+    fn create_classifier(
+        request: MultiFrameRequest,
+    ) -> Option<MultiCallFrameClassification<Action>> {
+        // the collateral is bought in a call after the absorb, not from within it.
+        // The absorb itself is collected so that it can be dropped when nothing
+        // is bought
+        Some(MultiCallFrameClassification {
+            trace_index:         request.trace_idx,
+            search_siblings:     true,
+            tree_search_builder: TreeSearchBuilder::new()
+                .with_actions([Action::is_swap, Action::is_liquidation]),
+            parse_fn:            Box::new(parse_absorb),
+        })
+    }
+}
+
+/// Merges the purchases of the absorbed collateral into the liquidation. An
+/// absorb without a purchase only moves the debt to the protocol, nobody
+/// liquidated anything so it is dropped.
+fn parse_absorb(this: &mut Action, actions: Vec<(NodeDataIndex, Action)>) -> Vec<NodeDataIndex> {
+    let this = this.try_liquidation_mut().unwrap();
+    let mut absorb = None;
+
+    let mut pruned = actions
+        .into_iter()
+        .filter_map(|(index, action)| {
+            if index.trace_index == this.trace_index && action.is_liquidation() {
+                absorb = Some(index);
+                return None
+            }
+            let Action::Swap(buy) = action else { return None };
+            if buy.protocol != Protocol::CompoundV3
+                || buy.pool != this.pool
+                || buy.token_out.address != this.collateral_asset.address
+            {
+                return None
+            }
+
+            // the absorber doesn't have to be the one buying the collateral
+            this.liquidator = buy.recipient;
+            this.covered_debt += buy.amount_in;
+            this.liquidated_collateral += buy.amount_out;
+
+            Some(index)
+        })
+        .collect::<Vec<_>>();
+
+    if pruned.is_empty() {
+        pruned.extend(absorb);
+    }
+
+    pruned
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address};
+    use brontes_types::{
+        db::token_info::TokenInfoWithAddress,
+        normalized_actions::{NormalizedLiquidation, NormalizedSwap},
+    };
+    use malachite::Rational;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_absorb() {
+        let comet: Address = hex!("c3d688B66703497DAA19211EEdff47f25384cdc3").into();
+        let absorber: Address = hex!("De74395831F3Ba9EdC7cBEE1fcB441cf24c0AF4d").into();
+        let buyer: Address = hex!("D911560979B78821D7b045C79E36E9CbfC2F6C6F").into();
+
+        let mut absorb = Action::Liquidation(NormalizedLiquidation {
+            protocol: Protocol::CompoundV3,
+            trace_index: 1,
+            pool: comet,
+            liquidator: absorber,
+            collateral_asset: TokenInfoWithAddress::weth(),
+            debt_asset: TokenInfoWithAddress::usdc(),
+            ..Default::default()
+        });
+        let buy = |trace_index, token_out, amount_out| {
+            Action::Swap(NormalizedSwap {
+                protocol: Protocol::CompoundV3,
+                trace_index,
+                from: buyer,
+                recipient: buyer,
+                pool: comet,
+                token_in: TokenInfoWithAddress::usdc(),
+                token_out,
+                amount_in: Rational::from(1000),
+                amount_out: Rational::from(amount_out),
+                ..Default::default()
+            })
+        };
+        let index = |trace_index| NodeDataIndex { trace_index, data_idx: 0, multi_data_idx: 0 };
+        // the search collects the absorb itself as well
+        let collected_absorb = absorb.clone();
+
+        let pruned = parse_absorb(
+            &mut absorb.clone(),
+            vec![
+                (index(1), collected_absorb.clone()),
+                (index(3), buy(3, TokenInfoWithAddress::usdt(), 2)),
+            ],
+        );
+        // nothing bought the absorbed collateral, so the absorb is dropped
+        assert_eq!(pruned.iter().map(|i| i.trace_index).collect::<Vec<_>>(), vec![1]);
+
+        let pruned = parse_absorb(
+            &mut absorb,
+            vec![
+                (index(1), collected_absorb.clone()),
+                (index(2), buy(2, TokenInfoWithAddress::weth(), 1)),
+                // buys other collateral of the market
+                (index(3), buy(3, TokenInfoWithAddress::usdt(), 2)),
+                (index(4), buy(4, TokenInfoWithAddress::weth(), 2)),
+            ],
+        );
+
+        assert_eq!(pruned.iter().map(|i| i.trace_index).collect::<Vec<_>>(), vec![2, 4]);
+        let liquidation = absorb.try_liquidation().unwrap();
+        assert_eq!(liquidation.liquidator, buyer);
+        assert_eq!(liquidation.covered_debt, Rational::from(2000));
+        assert_eq!(liquidation.liquidated_collateral, Rational::from(3));
+    }
+}
@@ -1,2 +1,4 @@
 pub mod aave;
 pub use aave::*;
+pub mod compound_v3;
+pub use compound_v3::*;
@@ -9,12 +9,12 @@ pub mod batch;
 pub mod flash_loan;
 pub mod liquidations;
 
-use aggregator::{OneInchAggregator, OneInchFusion, ZeroXAgg};
+use aggregator::{KyberSwapAgg, OdosAgg, OneInchAggregator, OneInchFusion, ParaswapAgg, ZeroXAgg};
 use batch::{Cowswap, UniswapX, ZeroXBatch};
 use brontes_types::normalized_actions::{Action, MultiCallFrameClassification, MultiFrameRequest};
-use flash_loan::{BalancerV2, MakerDss};
+use flash_loan::{BalancerV2, MakerDss, MorphoBlue};
 use itertools::Itertools;
-use liquidations::{AaveV2, AaveV3};
+use liquidations::{AaveV2, AaveV3, CompoundV3};
 use tracing::debug;
 
 use self::flash_loan::Dodo;
@@ -44,9 +44,14 @@ pub fn parse_multi_frame_requests(
             BalancerV2::KEY => BalancerV2::create_classifier(request),
             AaveV2::KEY => AaveV2::create_classifier(request),
             AaveV3::KEY => AaveV3::create_classifier(request),
+            CompoundV3::KEY => CompoundV3::create_classifier(request),
             ZeroXAgg::KEY => ZeroXAgg::create_classifier(request),
+            ParaswapAgg::KEY => ParaswapAgg::create_classifier(request),
+            KyberSwapAgg::KEY => KyberSwapAgg::create_classifier(request),
+            OdosAgg::KEY => OdosAgg::create_classifier(request),
             ZeroXBatch::KEY => ZeroXBatch::create_classifier(request),
             MakerDss::KEY => MakerDss::create_classifier(request),
+            MorphoBlue::KEY => MorphoBlue::create_classifier(request),
             Dodo::KEY => Dodo::create_classifier(request),
             _ => {
                 debug!(?request, "no multi frame classification impl for this request");
@@ -6,7 +6,8 @@ use std::{
     },
 };
 
-use alloy_primitives::{Address, TxHash};
+use alloy_primitives::{Address, Bytes, Log, TxHash, U256};
+use alloy_sol_types::{SolCall, SolEvent};
 use brontes_core::{
     decoding::TracingProvider, BlockTracesWithHeaderAnd, TraceLoader, TraceLoaderError,
     TxTracesWithHeaderAnd,
@@ -24,7 +25,7 @@ use brontes_types::{
         address_to_protocol_info::ProtocolInfo, dex::DexQuotes, token_info::TokenInfoWithAddress,
     },
     normalized_actions::{pool::NormalizedNewPool, NormalizedTransfer},
-    structured_trace::TraceActions,
+    structured_trace::{CallFrameInfo, TraceActions},
     tree::BlockTree,
     BrontesTaskManager, FastHashMap, TreeCollector, TreeSearchBuilder, UnboundedYapperReceiver,
 };
@@ -584,6 +585,25 @@ impl ClassifierTestUtils {
         Ok(())
     }
 
+    /// classifies the call with the protocol that is stored for its target
+    pub fn classify_call(&self, call: &TestCall) -> Option<Action> {
+        let call_info = CallFrameInfo {
+            trace_idx:      1,
+            call_data:      call.call_data.clone(),
+            return_data:    call.return_data.clone(),
+            target_address: call.target,
+            from_address:   call.from,
+            logs:           &call.logs,
+            delegate_logs:  vec![],
+            msg_sender:     call.msg_sender,
+            msg_value:      U256::ZERO,
+        };
+
+        ProtocolClassifier::default()
+            .dispatch(call_info, self.trace_loader.libmdbx, 0, 0)
+            .map(|(_, action)| action)
+    }
+
     pub async fn test_discovery_classification(
         &self,
         txes: TxHash,
@@ -639,6 +659,29 @@ impl ClassifierTestUtils {
         }
     }
 
+    /// stores the protocol for the address along with the tokens it holds
+    pub fn ensure_protocol_with_tokens(
+        &self,
+        protocol: Protocol,
+        address: Address,
+        tokens: &[TokenInfoWithAddress],
+    ) {
+        let token = |i: usize| tokens.get(i).map(|token| token.address);
+        self.ensure_protocol(
+            protocol,
+            address,
+            token(0).unwrap_or_default(),
+            token(1),
+            token(2),
+            token(3),
+            token(4),
+            None,
+        );
+        tokens
+            .iter()
+            .for_each(|token| self.ensure_token(token.clone()));
+    }
+
     pub fn ensure_token(&self, token: TokenInfoWithAddress) {
         if let Err(e) = self
             .libmdbx
@@ -688,6 +731,48 @@ fn partially_eq<T: serde::Serialize>(a: &T, b: &T, ignore_fields: &[&str]) -> bo
     a_filtered == b_filtered
 }
 
+/// A call that is classified on its own, without replaying the transaction it
+/// came from. Used for actions that need specific call data, return data or
+/// logs to hit each branch of a classifier.
+#[derive(Debug, Clone, Default)]
+pub struct TestCall {
+    pub target:      Address,
+    pub from:        Address,
+    pub msg_sender:  Address,
+    pub call_data:   Bytes,
+    pub return_data: Bytes,
+    pub logs:        Vec<Log>,
+}
+
+impl TestCall {
+    pub fn new(target: Address, caller: Address, call: impl SolCall) -> Self {
+        Self {
+            target,
+            from: caller,
+            msg_sender: caller,
+            call_data: call.abi_encode().into(),
+            ..Default::default()
+        }
+    }
+
+    /// the target is called through a delegate call from the proxy
+    pub fn delegated_from(mut self, proxy: Address) -> Self {
+        self.from = proxy;
+        self
+    }
+
+    pub fn returns(mut self, amount: U256) -> Self {
+        self.return_data = amount.to_be_bytes::<32>().to_vec().into();
+        self
+    }
+
+    pub fn with_log(mut self, address: Address, event: impl SolEvent) -> Self {
+        self.logs
+            .push(Log { address, data: event.encode_log_data() });
+        self
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ClassifierTestUtilsError {
     #[error(transparent)]
@@ -16,7 +16,7 @@ use malachite::{num::basic::traits::Zero, Rational};
 mod tree_pruning;
 pub(crate) mod utils;
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
-use brontes_pricing::types::DexPriceMsg;
+use brontes_pricing::{types::DexPriceMsg, uniswap_v4::get_pool_key, Protocol};
 use brontes_types::{
     normalized_actions::{Action, SelfdestructWithIndex},
     structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
@@ -402,6 +402,15 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                 {
                     error!(pool=?p.pool_address,"failed to update pool config");
                 }
+
+                // the state of a v4 pool can only be loaded with its key
+                if let Some(key) =
+                    get_pool_key(p.pool_address).filter(|_| p.protocol == Protocol::UniswapV4)
+                {
+                    if self.libmdbx.insert_uniswap_v4_pool_key(key).await.is_err() {
+                        error!(pool=?p.pool_address,"failed to save uniswap v4 pool key");
+                    }
+                }
             }
 
             (vec![results.0], vec![results.1])
@@ -0,0 +1,451 @@
+//! Conversion of the geth `callTracer` output into the traces brontes
+//! produces with its own inspector, so that blocks can be traced over any
+//! json-rpc endpoint that supports `debug_traceBlockByNumber`.
+
+use alloy_primitives::{Address, Log, LogData, U256, U64};
+use brontes_types::structured_trace::{TransactionTraceWithLogs, TxTrace};
+use reth_primitives::{Bytes, B256};
+use reth_rpc_types::trace::parity::{
+    Action, CallAction, CallOutput, CallType, CreateAction, CreateOutput, SelfdestructAction,
+    TraceOutput, TransactionTrace,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The highest precompile address, the point evaluation precompile
+const LAST_PRECOMPILE: u64 = 0x0a;
+
+/// `debug_traceBlockByNumber` options for the call tracer with logs
+pub fn call_tracer_options() -> Value {
+    json!({ "tracer": "callTracer", "tracerConfig": { "withLog": true } })
+}
+
+/// The trace of a single transaction in a `debug_traceBlockByNumber`
+/// response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTraceResult {
+    #[serde(default)]
+    pub tx_hash: Option<B256>,
+    #[serde(default)]
+    pub result:  Option<CallFrame>,
+    #[serde(default)]
+    pub error:   Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub typ:      String,
+    pub from:     Address,
+    #[serde(default)]
+    pub to:       Option<Address>,
+    #[serde(default)]
+    pub value:    Option<U256>,
+    #[serde(default)]
+    pub gas:      U64,
+    #[serde(default)]
+    pub gas_used: U64,
+    #[serde(default)]
+    pub input:    Bytes,
+    #[serde(default)]
+    pub output:   Option<Bytes>,
+    #[serde(default)]
+    pub error:    Option<String>,
+    #[serde(default)]
+    pub calls:    Vec<CallFrame>,
+    #[serde(default)]
+    pub logs:     Vec<CallLogFrame>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallLogFrame {
+    #[serde(default)]
+    pub address: Option<Address>,
+    #[serde(default)]
+    pub topics:  Vec<B256>,
+    #[serde(default)]
+    pub data:    Bytes,
+}
+
+impl CallFrame {
+    fn is_delegate_call(&self) -> bool {
+        self.typ == "DELEGATECALL"
+    }
+
+    fn is_create(&self) -> bool {
+        matches!(self.typ.as_str(), "CREATE" | "CREATE2")
+    }
+
+    fn is_selfdestruct(&self) -> bool {
+        self.typ == "SELFDESTRUCT"
+    }
+
+    /// our inspector doesn't record value-less calls into precompiles, as they
+    /// are never of interest.
+    fn is_precompile_call(&self) -> bool {
+        let Some(to) = self.to else { return false };
+
+        !self.is_create()
+            && !self.is_selfdestruct()
+            && self.value.unwrap_or_default().is_zero()
+            && !to.is_zero()
+            && U256::from_be_slice(to.as_slice()) <= U256::from(LAST_PRECOMPILE)
+    }
+
+    /// the call frames that show up in our traces
+    fn traced_calls(&self) -> impl Iterator<Item = &CallFrame> {
+        self.calls.iter().filter(|call| !call.is_precompile_call())
+    }
+}
+
+/// Converts the call frame of a transaction into its trace. `gas_used` and
+/// `effective_price` are taken from the receipt of the transaction.
+pub fn into_tx_trace(
+    root: CallFrame,
+    block_number: u64,
+    tx_hash: B256,
+    tx_index: u64,
+    gas_used: u128,
+    effective_price: u128,
+) -> TxTrace {
+    let is_success = root.error.is_none();
+    let mut traces = Vec::new();
+    let mut trace_idx = 0;
+    flatten_frame(&root, vec![], root.value.unwrap_or_default(), &mut trace_idx, &mut traces);
+
+    TxTrace::new(block_number, traces, tx_hash, tx_index, gas_used, effective_price, is_success)
+}
+
+/// Walks the call frames depth first, in the same order our inspector records
+/// them in.
+fn flatten_frame(
+    frame: &CallFrame,
+    trace_address: Vec<usize>,
+    parent_value: U256,
+    trace_idx: &mut u64,
+    traces: &mut Vec<TransactionTraceWithLogs>,
+) {
+    let idx = *trace_idx;
+    *trace_idx += 1;
+
+    // delegate calls execute with the value of the call they were made in
+    let value =
+        if frame.is_delegate_call() { parent_value } else { frame.value.unwrap_or_default() };
+    let subtraces = frame.traced_calls().count();
+
+    let trace = TransactionTrace {
+        action: frame_action(frame, value),
+        error: frame.error.clone(),
+        result: frame_output(frame),
+        trace_address: trace_address.clone(),
+        subtraces,
+    };
+    let msg_sender = msg_sender(&trace.action, traces);
+    let logs = frame
+        .logs
+        .iter()
+        .map(|log| Log {
+            address: log.address.or(frame.to).unwrap_or_default(),
+            data:    LogData::new_unchecked(log.topics.clone(), log.data.clone()),
+        })
+        .collect();
+
+    traces.push(TransactionTraceWithLogs {
+        trace,
+        logs,
+        msg_sender,
+        trace_idx: idx,
+        decoded_data: None,
+    });
+
+    // our inspector doesn't record selfdestructs as frames of their own, they
+    // are added right after the call that self destructed, sharing its index
+    if let Some(selfdestruct) = frame.calls.iter().find(|call| call.is_selfdestruct()) {
+        let mut address = trace_address.clone();
+        address.push(subtraces - 1);
+
+        traces.push(TransactionTraceWithLogs {
+            trace: TransactionTrace {
+                action:        Action::Selfdestruct(SelfdestructAction {
+                    address:        frame.to.unwrap_or_default(),
+                    refund_address: selfdestruct.to.unwrap_or_default(),
+                    balance:        value,
+                }),
+                error:         None,
+                result:        None,
+                trace_address: address,
+                subtraces:     0,
+            },
+            logs: vec![],
+            msg_sender,
+            trace_idx: idx,
+            decoded_data: None,
+        });
+    }
+
+    let mut position = 0;
+    for call in &frame.calls {
+        // precompiles still take up an index, as they do with our inspector
+        if call.is_precompile_call() {
+            *trace_idx += 1;
+            continue
+        }
+        if call.is_selfdestruct() {
+            continue
+        }
+
+        let mut address = trace_address.clone();
+        address.push(position);
+        position += 1;
+
+        flatten_frame(call, address, value, trace_idx, traces);
+    }
+}
+
+/// The msg.sender of a delegate call is the msg.sender of the closest
+/// preceding call that isn't a delegate call.
+fn msg_sender(action: &Action, traces: &[TransactionTraceWithLogs]) -> Address {
+    match action {
+        Action::Call(call) if call.call_type == CallType::DelegateCall => traces
+            .iter()
+            .rev()
+            .find(|trace| match &trace.trace.action {
+                Action::Call(c) => c.call_type != CallType::DelegateCall,
+                Action::Create(_) => true,
+                _ => false,
+            })
+            .map(|trace| trace.msg_sender)
+            .unwrap_or(call.from),
+        Action::Call(call) => call.from,
+        Action::Create(call) => call.from,
+        Action::Reward(call) => call.author,
+        Action::Selfdestruct(call) => call.address,
+    }
+}
+
+fn frame_action(frame: &CallFrame, value: U256) -> Action {
+    if frame.is_create() {
+        Action::Create(CreateAction {
+            from: frame.from,
+            value,
+            gas: frame.gas,
+            init: frame.input.clone(),
+        })
+    } else {
+        let call_type = match frame.typ.as_str() {
+            "DELEGATECALL" => CallType::DelegateCall,
+            "STATICCALL" => CallType::StaticCall,
+            "CALLCODE" => CallType::CallCode,
+            _ => CallType::Call,
+        };
+
+        Action::Call(CallAction {
+            from: frame.from,
+            to: frame.to.unwrap_or_default(),
+            value,
+            gas: frame.gas,
+            input: frame.input.clone(),
+            call_type,
+        })
+    }
+}
+
+/// Same as our inspector, frames that error without reverting have no
+/// output.
+fn frame_output(frame: &CallFrame) -> Option<TraceOutput> {
+    if frame
+        .error
+        .as_ref()
+        .is_some_and(|error| error != "execution reverted")
+    {
+        return None
+    }
+
+    let output = frame.output.clone().unwrap_or_default();
+    if frame.is_create() {
+        Some(TraceOutput::Create(CreateOutput {
+            gas_used: frame.gas_used,
+            code:     output,
+            address:  frame.to.unwrap_or_default(),
+        }))
+    } else {
+        Some(TraceOutput::Call(CallOutput { gas_used: frame.gas_used, output }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+    use alloy_rpc_types_trace::geth::CallConfig;
+    use reth_tracing_ext::reth_tracer::{
+        config::TracingInspectorConfig, inspector::BrontesTracingInspector,
+    };
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        inspector_handle_register,
+        primitives::{AccountInfo, Bytecode, ExecutionResult, SpecId, TransactTo},
+        Evm, Inspector,
+    };
+    use revm_inspectors::tracing::{
+        TracingInspector, TracingInspectorConfig as GethTracingInspectorConfig,
+    };
+
+    use super::*;
+
+    const CALLER: Address = Address::new(hex!("1000000000000000000000000000000000000001"));
+    const TARGET: Address = Address::new(hex!("2000000000000000000000000000000000000002"));
+    const CHILD: Address = Address::new(hex!("3000000000000000000000000000000000000003"));
+    const REFUND: Address = Address::new(hex!("4000000000000000000000000000000000000004"));
+
+    /// `TARGET` calls into `CHILD`, which emits a log, and then self destructs
+    /// to `REFUND`
+    fn selfdestruct_db() -> CacheDB<EmptyDB> {
+        let target = [
+            &[0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73][..],
+            CHILD.as_slice(),
+            // GAS CALL POP PUSH20
+            &[0x5a, 0xf1, 0x50, 0x73],
+            REFUND.as_slice(),
+            // SELFDESTRUCT
+            &[0xff],
+        ]
+        .concat();
+        // MSTORE8(0, 1) LOG0(0, 1) STOP
+        let child = vec![0x60, 0x01, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xa0, 0x00];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            CALLER,
+            AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() },
+        );
+        for (address, code) in [(TARGET, target), (CHILD, child)] {
+            let code = Bytecode::new_raw(code.into());
+            db.insert_account_info(
+                address,
+                AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+            );
+        }
+
+        db
+    }
+
+    fn transact<I: Inspector<CacheDB<EmptyDB>>>(inspector: I) -> (I, ExecutionResult) {
+        let mut evm = Evm::builder()
+            .with_db(selfdestruct_db())
+            .with_external_context(inspector)
+            .with_spec_id(SpecId::SHANGHAI)
+            .modify_tx_env(|tx| {
+                tx.caller = CALLER;
+                tx.transact_to = TransactTo::Call(TARGET);
+                tx.value = U256::from(5);
+                tx.gas_limit = 1_000_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let res = evm.transact().unwrap().result;
+
+        (evm.context.external, res)
+    }
+
+    #[test]
+    fn test_selfdestruct_matches_inspector() {
+        let (inspector, res) =
+            transact(BrontesTracingInspector::new(TracingInspectorConfig::brontes()));
+        assert!(res.is_success());
+        let expected = inspector.build_trace(B256::ZERO, 1).unwrap();
+
+        let call_config = CallConfig::default().with_log();
+        let (inspector, res) = transact(TracingInspector::new(
+            GethTracingInspectorConfig::from_geth_call_config(&call_config),
+        ));
+        let frame = inspector
+            .into_geth_builder()
+            .geth_call_traces(call_config, res.gas_used());
+        let root: CallFrame = serde_json::from_value(serde_json::to_value(frame).unwrap()).unwrap();
+        let trace = into_tx_trace(root, 1, B256::ZERO, 0, res.gas_used() as u128, 0);
+
+        // gas is accounted differently by the call tracer, the rest has to line up
+        let layout = |traces: &[TransactionTraceWithLogs]| {
+            traces
+                .iter()
+                .map(|trace| {
+                    (
+                        trace.trace_idx,
+                        trace.trace.trace_address.clone(),
+                        trace.trace.subtraces,
+                        trace.msg_sender,
+                        trace.get_msg_value(),
+                        trace.logs.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(layout(&trace.trace), layout(&expected));
+
+        let selfdestruct = &trace.trace[1];
+        assert_eq!(selfdestruct.trace.action, expected[1].trace.action);
+        assert_eq!(
+            selfdestruct.trace.action,
+            Action::Selfdestruct(SelfdestructAction {
+                address:        TARGET,
+                refund_address: REFUND,
+                balance:        U256::from(5),
+            })
+        );
+    }
+
+    #[test]
+    fn test_call_frame_conversion() {
+        let root: CallFrame = serde_json::from_value(json!({
+            "type": "CALL",
+            "from": "0x1000000000000000000000000000000000000001",
+            "to": "0x2000000000000000000000000000000000000002",
+            "value": "0x5",
+            "gas": "0x1000",
+            "gasUsed": "0x100",
+            "input": "0x",
+            "calls": [
+                {
+                    "type": "STATICCALL",
+                    "from": "0x2000000000000000000000000000000000000002",
+                    "to": "0x0000000000000000000000000000000000000001",
+                    "gas": "0x10",
+                    "gasUsed": "0x10",
+                    "input": "0x"
+                },
+                {
+                    "type": "DELEGATECALL",
+                    "from": "0x2000000000000000000000000000000000000002",
+                    "to": "0x3000000000000000000000000000000000000003",
+                    "gas": "0x100",
+                    "gasUsed": "0x10",
+                    "input": "0x",
+                    "logs": [{
+                        "address": "0x2000000000000000000000000000000000000002",
+                        "topics": [],
+                        "data": "0x01"
+                    }]
+                }
+            ]
+        }))
+        .unwrap();
+
+        let trace = into_tx_trace(root, 1, B256::ZERO, 0, 0x100, 10);
+        assert!(trace.is_success);
+        assert_eq!(trace.trace.len(), 2);
+
+        let delegate = &trace.trace[1];
+        // the precompile call takes up an index but not a trace address
+        assert_eq!(delegate.trace_idx, 2);
+        assert_eq!(delegate.trace.trace_address, vec![0]);
+        assert_eq!(trace.trace[0].trace.subtraces, 1);
+        assert_eq!(
+            delegate.msg_sender,
+            Address::new(hex!("1000000000000000000000000000000000000001"))
+        );
+        assert_eq!(delegate.get_msg_value(), U256::from(5));
+        assert_eq!(delegate.logs.len(), 1);
+    }
+}
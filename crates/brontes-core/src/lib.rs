@@ -1,4 +1,6 @@
 #![feature(trait_alias)]
+#[cfg(not(feature = "local-reth"))]
+pub mod call_tracer;
 pub mod decoding;
 pub mod errors;
 pub mod executor;
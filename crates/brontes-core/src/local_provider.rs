@@ -13,6 +13,8 @@ use reth_rpc_types::{
     state::StateOverride, BlockOverrides, Log, TransactionReceipt, TransactionRequest,
 };
 
+use crate::call_tracer::{call_tracer_options, into_tx_trace, BlockTraceResult};
+
 #[derive(Debug, Clone)]
 pub struct LocalProvider {
     provider: Arc<RootProvider<Http<reqwest::Client>>>,
@@ -69,11 +71,75 @@ impl TracingProvider for LocalProvider {
         self.provider.get_block_number().await.map_err(Into::into)
     }
 
-    async fn replay_block_transactions(&self, _: BlockId) -> eyre::Result<Option<Vec<TxTrace>>> {
-        unreachable!(
-            "Currently we use a custom tracing model which does not allow for 
-                     a local trace to occur"
-        );
+    /// traces the block with the geth call tracer, which is converted into the
+    /// same traces that our own inspector produces.
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> eyre::Result<Option<Vec<TxTrace>>> {
+        let block_number = match block_id {
+            BlockId::Number(BlockNumberOrTag::Number(number)) => number,
+            block_id => {
+                let Some(block) = self.provider.get_block(block_id, false).await? else {
+                    return Ok(None)
+                };
+                block
+                    .header
+                    .number
+                    .ok_or_else(|| eyre::eyre!("block {block_id:?} has no number"))?
+            }
+        };
+
+        let Some(receipts) = self
+            .block_receipts(BlockNumberOrTag::Number(block_number))
+            .await?
+        else {
+            return Ok(None)
+        };
+
+        let results: Vec<BlockTraceResult> = self
+            .provider
+            .client()
+            .request(
+                "debug_traceBlockByNumber",
+                (BlockNumberOrTag::Number(block_number), call_tracer_options()),
+            )
+            .await?;
+
+        if results.len() != receipts.len() {
+            eyre::bail!(
+                "got {} traces for {} transactions in block {block_number}",
+                results.len(),
+                receipts.len()
+            );
+        }
+
+        results
+            .into_iter()
+            .zip(receipts)
+            .enumerate()
+            .map(|(tx_index, (result, receipt))| {
+                let tx_hash = result.tx_hash.ok_or_else(|| {
+                    eyre::eyre!("endpoint didn't return the tx hashes of the block traces")
+                })?;
+                let frame = match (result.result, result.error) {
+                    (Some(frame), _) => frame,
+                    (None, error) => {
+                        eyre::bail!("failed to trace tx {tx_hash:?}: {}", error.unwrap_or_default())
+                    }
+                };
+
+                Ok(into_tx_trace(
+                    frame,
+                    block_number,
+                    tx_hash,
+                    tx_index as u64,
+                    receipt.gas_used,
+                    receipt.effective_gas_price,
+                ))
+            })
+            .collect::<eyre::Result<Vec<_>>>()
+            .map(Some)
     }
 
     async fn block_receipts(
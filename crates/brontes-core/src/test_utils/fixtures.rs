@@ -0,0 +1,240 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+
+use alloy_rpc_types::AnyReceiptEnvelope;
+use brontes_database::libmdbx::{
+    fixture::{eth_call_block, BlockFixture, FixtureIndex, RecordedEthCall},
+    LibmdbxReadWriter, LibmdbxReader,
+};
+use brontes_types::{structured_trace::TxTrace, traits::TracingProvider, FastHashMap};
+use reth_primitives::{
+    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
+    B256,
+};
+use reth_rpc_types::{
+    state::StateOverride, BlockOverrides, Log, TransactionReceipt, TransactionRequest,
+};
+
+/// Serves blocks from the fixtures recorded with `brontes db record-fixture`.
+/// The rows of a fixture are written into libmdbx the first time its block is
+/// requested. `eth_call`s are answered with the results recorded alongside
+/// the block, anything else that needs node state isn't available.
+#[derive(Clone)]
+pub struct FixtureProvider {
+    dir:       PathBuf,
+    libmdbx:   &'static LibmdbxReadWriter,
+    index:     Arc<FixtureIndex>,
+    loaded:    Arc<RwLock<FastHashMap<u64, (B256, Header)>>>,
+    eth_calls: Arc<RwLock<FastHashMap<EthCallKey, Bytes>>>,
+}
+
+type EthCallKey = (Option<u64>, Option<Address>, Bytes);
+
+impl FixtureProvider {
+    pub fn new(dir: impl Into<PathBuf>, libmdbx: &'static LibmdbxReadWriter) -> eyre::Result<Self> {
+        let dir = dir.into();
+        let index = Arc::new(FixtureIndex::read(&dir)?);
+
+        Ok(Self { dir, libmdbx, index, loaded: Arc::default(), eth_calls: Arc::default() })
+    }
+
+    /// Loads the fixture of the block into libmdbx if it hasn't been already
+    pub fn load_block(&self, block: u64) -> eyre::Result<(B256, Header)> {
+        if let Some(loaded) = self.loaded.read().unwrap().get(&block) {
+            return Ok(loaded.clone())
+        }
+
+        let fixture = BlockFixture::read(&self.dir, block)?;
+        self.libmdbx.load_fixture(&fixture)?;
+        self.eth_calls.write().unwrap().extend(
+            fixture
+                .eth_calls
+                .into_iter()
+                .map(|RecordedEthCall { block, to, input, output }| ((block, to, input), output)),
+        );
+
+        let loaded = (fixture.block_hash, fixture.header);
+        self.loaded.write().unwrap().insert(block, loaded.clone());
+
+        Ok(loaded)
+    }
+
+    fn unavailable<T>(call: &str) -> eyre::Result<T> {
+        Err(eyre::eyre!("{call} isn't available when replaying from fixtures"))
+    }
+}
+
+#[async_trait::async_trait]
+impl TracingProvider for FixtureProvider {
+    async fn eth_call(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> eyre::Result<Bytes> {
+        if state_overrides.is_some() || block_overrides.is_some() {
+            return Self::unavailable("eth_call with overrides")
+        }
+
+        let block = eth_call_block(block_number);
+        let input = request.input.input().cloned().unwrap_or_default();
+        self.eth_calls
+            .read()
+            .unwrap()
+            .get(&(block, request.to, input))
+            .cloned()
+            .ok_or_else(|| {
+                eyre::eyre!("no eth_call to {:?} at block {block:?} was recorded", request.to)
+            })
+    }
+
+    async fn block_hash_for_id(&self, block_num: u64) -> eyre::Result<Option<B256>> {
+        self.load_block(block_num).map(|(hash, _)| Some(hash))
+    }
+
+    #[cfg(feature = "local-reth")]
+    fn best_block_number(&self) -> eyre::Result<u64> {
+        self.index
+            .0
+            .values()
+            .max()
+            .copied()
+            .ok_or_else(|| eyre::eyre!("no fixtures recorded"))
+    }
+
+    #[cfg(not(feature = "local-reth"))]
+    async fn best_block_number(&self) -> eyre::Result<u64> {
+        self.index
+            .0
+            .values()
+            .max()
+            .copied()
+            .ok_or_else(|| eyre::eyre!("no fixtures recorded"))
+    }
+
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> eyre::Result<Option<Vec<TxTrace>>> {
+        let BlockId::Number(BlockNumberOrTag::Number(block)) = block_id else {
+            return Self::unavailable("replaying a block by hash or tag")
+        };
+        self.load_block(block)?;
+
+        self.libmdbx.load_trace(block).map(Some)
+    }
+
+    async fn block_receipts(
+        &self,
+        _number: BlockNumberOrTag,
+    ) -> eyre::Result<Option<Vec<TransactionReceipt<AnyReceiptEnvelope<Log>>>>> {
+        Ok(None)
+    }
+
+    async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
+        self.load_block(number).map(|(_, header)| Some(header))
+    }
+
+    async fn block_and_tx_index(&self, hash: TxHash) -> eyre::Result<(u64, usize)> {
+        let block = *self
+            .index
+            .0
+            .get(&hash)
+            .ok_or_else(|| eyre::eyre!("no fixture recorded for tx {hash:?}"))?;
+        self.load_block(block)?;
+
+        let tx_index = self
+            .libmdbx
+            .load_trace(block)?
+            .into_iter()
+            .find(|trace| trace.tx_hash == hash)
+            .map(|trace| trace.tx_index)
+            .ok_or_else(|| eyre::eyre!("fixture of block {block} is missing tx {hash:?}"))?;
+
+        Ok((block, tx_index as usize))
+    }
+
+    async fn get_storage(
+        &self,
+        _block_number: Option<u64>,
+        _address: Address,
+        _storage_key: B256,
+    ) -> eyre::Result<Option<StorageValue>> {
+        Self::unavailable("get_storage")
+    }
+
+    async fn get_bytecode(
+        &self,
+        _block_number: Option<u64>,
+        _address: Address,
+    ) -> eyre::Result<Option<Bytecode>> {
+        Self::unavailable("get_bytecode")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reth_rpc_types::request::TransactionInput;
+
+    use super::*;
+
+    fn request(to: Address, input: &'static [u8]) -> TransactionRequest {
+        TransactionRequest {
+            to: Some(to),
+            input: TransactionInput::new(Bytes::from_static(input)),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replays_recorded_eth_calls() {
+        let dir =
+            std::env::temp_dir().join(format!("brontes-fixture-calls-{}", std::process::id()));
+        let libmdbx: &'static LibmdbxReadWriter =
+            Box::leak(Box::new(LibmdbxReadWriter::init_db_tests(dir.join("db")).unwrap()));
+
+        let pool = Address::with_last_byte(1);
+        let header = Header { number: 100, ..Default::default() };
+        BlockFixture {
+            block_number: 100,
+            block_hash:   B256::with_last_byte(1),
+            header:       header.clone(),
+            tx_hashes:    vec![B256::with_last_byte(2)],
+            rows:         vec![],
+            eth_calls:    vec![RecordedEthCall {
+                block:  Some(100),
+                to:     Some(pool),
+                input:  Bytes::from_static(&[1, 2]),
+                output: Bytes::from_static(&[3]),
+            }],
+        }
+        .write(&dir)
+        .unwrap();
+
+        let provider = FixtureProvider::new(&dir, libmdbx).unwrap();
+        assert_eq!(provider.header_by_number(100).await.unwrap(), Some(header));
+
+        let res = provider
+            .eth_call(request(pool, &[1, 2]), Some(100.into()), None, None)
+            .await
+            .unwrap();
+        assert_eq!(res, Bytes::from_static(&[3]));
+
+        // different input, block or target weren't recorded
+        assert!(provider
+            .eth_call(request(pool, &[1]), Some(100.into()), None, None)
+            .await
+            .is_err());
+        assert!(provider
+            .eth_call(request(pool, &[1, 2]), Some(101.into()), None, None)
+            .await
+            .is_err());
+        assert!(provider
+            .eth_call(request(Address::with_last_byte(2), &[1, 2]), Some(100.into()), None, None)
+            .await
+            .is_err());
+    }
+}
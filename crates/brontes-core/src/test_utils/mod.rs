@@ -1,6 +1,8 @@
 #[cfg(feature = "local-reth")]
 use std::sync::OnceLock;
-use std::{collections::hash_map::Entry, env, fs::OpenOptions, io::Write, sync::Arc};
+use std::{
+    collections::hash_map::Entry, env, fs::OpenOptions, io::Write, path::PathBuf, sync::Arc,
+};
 
 use alloy_primitives::Address;
 #[cfg(feature = "local-clickhouse")]
@@ -49,25 +51,50 @@ use crate::decoding::parser::TraceParser;
 #[cfg(not(feature = "local-reth"))]
 use crate::local_provider::LocalProvider;
 
+mod fixtures;
+pub use fixtures::FixtureProvider;
+
 const WINDOW_TIME_SEC: usize = 20;
 /// Functionality to load all state needed for any testing requirements
 pub struct TraceLoader {
     pub libmdbx:          &'static LibmdbxReadWriter,
     pub tracing_provider: TraceParser<Box<dyn TracingProvider>, LibmdbxReadWriter>,
+    /// set when replaying from fixtures, in which case nothing is fetched
+    /// from the node or clickhouse
+    pub fixtures:         Option<FixtureProvider>,
     // store so when we trace we don't get a closed rx error
     _metrics:             UnboundedReceiver<ParserMetricEvents>,
 }
 
 impl TraceLoader {
+    /// Replays only from fixtures if `BRONTES_TEST_FIXTURES` is set to the dir
+    /// they were recorded to
     pub async fn new() -> Self {
         let handle = tokio::runtime::Handle::current();
         init_thread_pools(32);
         let libmdbx = get_db_handle(handle.clone()).await;
 
         let (a, b) = unbounded_channel();
+
+        if let Some(dir) = fixtures_dir() {
+            let fixtures = FixtureProvider::new(dir, libmdbx).expect("failed to load fixtures");
+            let tracer = Box::new(fixtures.clone()) as Box<dyn TracingProvider>;
+            let tracing_provider = TraceParser::new(libmdbx, Arc::new(tracer), Arc::new(a)).await;
+
+            return Self { libmdbx, tracing_provider, fixtures: Some(fixtures), _metrics: b }
+        }
+
         let tracing_provider = init_trace_parser(handle, a, libmdbx, 10).await;
 
-        Self { libmdbx, tracing_provider, _metrics: b }
+        Self { libmdbx, tracing_provider, fixtures: None, _metrics: b }
+    }
+
+    fn ensure_online(&self) -> eyre::Result<()> {
+        if self.fixtures.is_some() {
+            eyre::bail!("missing data can't be fetched when replaying from fixtures")
+        }
+
+        Ok(())
     }
 
     pub fn get_provider(&self) -> Arc<Box<dyn TracingProvider>> {
@@ -78,6 +105,16 @@ impl TraceLoader {
         &self,
         block: u64,
     ) -> Result<(BlockHash, Vec<TxTrace>, Header), TraceLoaderError> {
+        if let Some(fixtures) = &self.fixtures {
+            fixtures.load_block(block)?;
+            return self
+                .tracing_provider
+                .clone()
+                .execute_block(block)
+                .await
+                .ok_or_else(|| TraceLoaderError::BlockTraceError(block))
+        }
+
         if let Some(traces) = self.tracing_provider.clone().execute_block(block).await {
             Ok(traces)
         } else {
@@ -95,6 +132,10 @@ impl TraceLoader {
         block: u64,
         pricing: bool,
     ) -> Result<Metadata, TraceLoaderError> {
+        if let Some(fixtures) = &self.fixtures {
+            fixtures.load_block(block)?;
+        }
+
         if pricing {
             if let Ok(res) = self.test_metadata_with_pricing(block, USDT_ADDRESS) {
                 Ok(res)
@@ -117,6 +158,7 @@ impl TraceLoader {
     }
 
     pub async fn fetch_missing_traces(&self, block: u64) -> eyre::Result<()> {
+        self.ensure_online()?;
         tracing::info!(%block, "fetching missing trces");
 
         let clickhouse = Box::leak(Box::new(load_clickhouse().await));
@@ -143,6 +185,7 @@ impl TraceLoader {
     }
 
     pub async fn fetch_missing_metadata(&self, block: u64) -> eyre::Result<()> {
+        self.ensure_online()?;
         tracing::info!(%block, "fetching missing metadata");
 
         let clickhouse = Box::leak(Box::new(load_clickhouse().await));
@@ -189,6 +232,7 @@ impl TraceLoader {
     }
 
     pub async fn fetch_missing_trades(&self, block: u64) -> eyre::Result<()> {
+        self.ensure_online()?;
         tracing::info!(%block, "fetching missing metadata");
 
         let clickhouse = Box::leak(Box::new(load_clickhouse().await));
@@ -447,6 +491,18 @@ pub async fn get_db_handle(handle: Handle) -> &'static LibmdbxReadWriter {
         .get_or_init(|| async {
             let _ = dotenv::dotenv();
             init_tracing();
+
+            // fixtures are replayed into a scratch db, so that only what they
+            // recorded is available
+            if fixtures_dir().is_some() {
+                let path = env::temp_dir().join(format!("brontes-fixtures-{}", std::process::id()));
+                return &*Box::leak(Box::new(
+                    LibmdbxReadWriter::init_db_tests(&path).unwrap_or_else(|e| {
+                        panic!("failed to open fixture db at {}, err={}", path.display(), e)
+                    }),
+                ))
+            }
+
             let brontes_db_path =
                 env::var("BRONTES_TEST_DB_PATH").expect("No BRONTES_TEST_DB_PATH in .env");
 
@@ -473,6 +529,12 @@ pub async fn get_db_handle(handle: Handle) -> &'static LibmdbxReadWriter {
         .await
 }
 
+/// The dir to replay fixtures from, set with `BRONTES_TEST_FIXTURES`
+pub fn fixtures_dir() -> Option<PathBuf> {
+    let _ = dotenv::dotenv();
+    env::var("BRONTES_TEST_FIXTURES").ok().map(PathBuf::from)
+}
+
 /// will trigger a update if a test with a new highest block is written
 /// or if any of the 3 critical tables are empty
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +11,7 @@ use brontes_types::{
     db::{
         address_to_protocol_info::ProtocolInfoClickhouse,
         block_analysis::BlockAnalysis,
+        block_analysis_rollup::BlockAnalysisRollup,
         builder::BuilderInfo,
         cex::{
             quotes::{CexQuotesConverter, RawCexQuotes},
@@ -62,6 +63,41 @@ use crate::{
 const SECONDS_TO_US: f64 = 1_000_000.0;
 const MAX_MARKOUT_TIME: f64 = 300.0;
 
+/// tables that store data keyed by block number that brontes writes to while
+/// processing a block, with the column the block is stored in. `ethereum.pools`
+/// is shared reference data and is never rolled back here, the pools
+/// discovered in a reorged block are only removed from libmdbx
+const BLOCK_SCOPED_TABLES: [(&str, &str); 14] = [
+    ("mev.mev_blocks", "block_number"),
+    ("mev.bundle_header", "block_number"),
+    ("mev.searcher_tx", "block_number"),
+    ("mev.cex_dex", "block_number"),
+    ("mev.cex_dex_quotes", "block_number"),
+    ("mev.cex_dex_inventory", "block_number"),
+    ("mev.liquidations", "block_number"),
+    ("mev.jit_sandwich", "block_number"),
+    ("mev.jit", "block_number"),
+    // sandwiches spanning blocks are stored under their backrun block
+    ("mev.sandwiches", "block_number"),
+    ("mev.atomic_arbs", "block_number"),
+    ("brontes.dex_price_mapping", "block_number"),
+    ("brontes.block_analysis", "block_number"),
+    ("brontes.tree", "block_number"),
+];
+
+/// the queries that remove all rows written for the given block
+fn delete_block_data_queries(block_number: u64) -> Vec<String> {
+    BLOCK_SCOPED_TABLES
+        .iter()
+        .map(|(table, column)| {
+            format!(
+                "ALTER TABLE {table} ON CLUSTER eth_cluster0 DELETE WHERE {column} = \
+                 {block_number}"
+            )
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct Clickhouse {
     pub tip:                 bool,
@@ -194,6 +230,9 @@ impl Clickhouse {
                         tx.send(vec![(s, self.tip, self.run_id).into()])?
                     }
                     BundleData::Unknown(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
+                    BundleData::CexDexInventory(s) => {
+                        tx.send(vec![(s, self.tip, self.run_id).into()])?
+                    }
                 };
 
                 Ok(()) as eyre::Result<()>
@@ -286,10 +325,41 @@ impl Clickhouse {
         Ok(())
     }
 
+    /// rollups are written once per range so they are inserted right away
+    pub async fn block_analysis_rollups(
+        &self,
+        rollups: Vec<BlockAnalysisRollup>,
+    ) -> eyre::Result<()> {
+        if rollups.is_empty() {
+            return Ok(())
+        }
+
+        if let Some(tx) = self.buffered_insert_tx.as_ref() {
+            tx.send(
+                rollups
+                    .into_iter()
+                    .map(|rollup| (rollup, true).into())
+                    .collect(),
+            )?
+        };
+
+        Ok(())
+    }
+
     pub async fn save_traces(&self, _block: u64, _traces: Vec<TxTrace>) -> eyre::Result<()> {
         Ok(())
     }
 
+    /// removes all rows written for the given block. used when the block has
+    /// been reorged out and needs to be re-processed
+    pub async fn delete_block_data(&self, block_number: u64) -> eyre::Result<()> {
+        for query in delete_block_data_queries(block_number) {
+            self.client.execute_remote(&query, &()).await?;
+        }
+
+        Ok(())
+    }
+
     async fn query_many_with_retry<Q, P>(
         &self,
         query: impl AsRef<str> + Send,
@@ -1379,6 +1449,14 @@ mod tests {
             .unwrap();
     }
 
+    async fn block_analysis_rollups(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
+        let case0 = BlockAnalysisRollup::default();
+
+        db.insert_one::<BrontesBlock_Analysis_Rollups>(&case0)
+            .await
+            .unwrap();
+    }
+
     async fn tree(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
         let tree = load_tree().await;
 
@@ -1411,6 +1489,7 @@ mod tests {
         token_info(database).await;
         tree(database).await;
         block_analysis(database).await;
+        block_analysis_rollups(database).await;
     }
 
     #[brontes_macros::test]
@@ -1423,4 +1502,22 @@ mod tests {
             .run_test_with_test_db(tables, |db| Box::pin(run_all(db)))
             .await;
     }
+
+    #[test]
+    fn test_delete_block_data_queries() {
+        let queries = delete_block_data_queries(18_000_000);
+
+        assert_eq!(queries.len(), BLOCK_SCOPED_TABLES.len());
+        assert!(queries.contains(
+            &"ALTER TABLE mev.sandwiches ON CLUSTER eth_cluster0 DELETE WHERE block_number = \
+              18000000"
+                .to_string()
+        ));
+        assert!(queries.contains(
+            &"ALTER TABLE mev.cex_dex_inventory ON CLUSTER eth_cluster0 DELETE WHERE block_number \
+              = 18000000"
+                .to_string()
+        ));
+        assert!(!queries.iter().any(|query| query.contains("ethereum.pools")));
+    }
 }
@@ -1,8 +1,9 @@
 use brontes_types::{
     db::{
         address_to_protocol_info::ProtocolInfoClickhouse, block_analysis::BlockAnalysis,
-        dex::DexQuotesWithBlockNumber, normalized_actions::TransactionRoot,
-        token_info::TokenInfoWithAddress, DbDataWithRunId, RunId,
+        block_analysis_rollup::BlockAnalysisRollup, dex::DexQuotesWithBlockNumber,
+        normalized_actions::TransactionRoot, token_info::TokenInfoWithAddress, DbDataWithRunId,
+        RunId,
     },
     mev::*,
 };
@@ -14,11 +15,13 @@ clickhouse_dbms!(
     [
         BrontesDex_Price_Mapping,
         BrontesBlock_Analysis,
+        BrontesBlock_Analysis_Rollups,
         MevMev_Blocks,
         MevBundle_Header,
         MevSearcher_Tx,
         MevCex_Dex_Quotes,
         MevCex_Dex,
+        MevCex_Dex_Inventory,
         MevLiquidations,
         MevJit_Sandwich,
         MevJit,
@@ -55,6 +58,13 @@ remote_clickhouse_table!(
     "crates/brontes-database/brontes-db/src/clickhouse/tables/"
 );
 
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Brontes, Block_Analysis_Rollups],
+    BlockAnalysisRollup,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
 remote_clickhouse_table!(
     BrontesClickhouseTables,
     [Mev, Mev_Blocks],
@@ -90,6 +100,13 @@ remote_clickhouse_table!(
     "crates/brontes-database/brontes-db/src/clickhouse/tables/"
 );
 
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Mev, Cex_Dex_Inventory],
+    DbDataWithRunId<CexDexInventory>,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
 remote_clickhouse_table!(
     BrontesClickhouseTables,
     [Mev, Liquidations],
@@ -235,6 +252,7 @@ db_types!(
     (SearcherTx, MevSearcher_Tx, true),
     (CexDex, MevCex_Dex, true),
     (CexDexQuote, MevCex_Dex_Quotes, true),
+    (CexDexInventory, MevCex_Dex_Inventory, true),
     (Liquidation, MevLiquidations, true),
     (JitLiquiditySandwich, MevJit_Sandwich, true),
     (JitLiquidity, MevJit, true),
@@ -244,5 +262,6 @@ db_types!(
     (ProtocolInfoClickhouse, EthereumPools, false),
     (TransactionRoot, BrontesTree, true),
     (BlockAnalysis, BrontesBlock_Analysis, true),
+    (BlockAnalysisRollup, BrontesBlock_Analysis_Rollups, false),
     (RunId, BrontesRun_Id, false)
 );
@@ -1,18 +1,22 @@
 use std::sync::Arc;
 
 use alloy_primitives::Address;
+use brontes_pricing::checkpoint::PricingCheckpoint;
 use brontes_types::{
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
         block_analysis::BlockAnalysis,
+        block_analysis_rollup::BlockAnalysisRollup,
         builder::BuilderInfo,
+        cex::quotes::CexPriceMap,
         dex::DexQuotes,
         metadata::Metadata,
         mev_block::MevBlockWithClassified,
         searcher::SearcherInfo,
         token_info::TokenInfoWithAddress,
         traits::{DBWriter, LibmdbxReader, ProtocolCreatedRange},
+        uniswap_v4_pool_key::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -54,6 +58,15 @@ impl<I: DBWriter + Send + Sync> DBWriter for ClickhouseMiddleware<I> {
         self.client.block_analysis(block_analysis).await
     }
 
+    async fn write_block_analysis_rollups(
+        &self,
+        rollups: Vec<BlockAnalysisRollup>,
+    ) -> eyre::Result<()> {
+        self.client.block_analysis_rollups(rollups.clone()).await?;
+
+        self.inner().write_block_analysis_rollups(rollups).await
+    }
+
     async fn write_dex_quotes(
         &self,
         block_number: u64,
@@ -166,6 +179,12 @@ impl<I: DBWriter + Send + Sync> DBWriter for ClickhouseMiddleware<I> {
 
         self.inner().save_traces(block, traces).await
     }
+
+    async fn delete_block_data(&self, block_number: u64) -> eyre::Result<()> {
+        self.client.delete_block_data(block_number).await?;
+
+        self.inner().delete_block_data(block_number).await
+    }
 }
 
 impl<I: LibmdbxInit> LibmdbxInit for ClickhouseMiddleware<I> {
@@ -196,6 +215,18 @@ impl<I: LibmdbxInit> LibmdbxInit for ClickhouseMiddleware<I> {
         self.inner.get_db_range()
     }
 
+    fn save_pricing_checkpoint(&self, checkpoint: PricingCheckpoint) -> eyre::Result<()> {
+        self.inner.save_pricing_checkpoint(checkpoint)
+    }
+
+    fn load_pricing_checkpoint(&self, block: u64) -> eyre::Result<Option<PricingCheckpoint>> {
+        self.inner.load_pricing_checkpoint(block)
+    }
+
+    fn load_uniswap_v4_pool_keys(&self) -> eyre::Result<Vec<UniswapV4PoolKey>> {
+        self.inner.load_uniswap_v4_pool_keys()
+    }
+
     async fn initialize_table_arbitrary<
         T: brontes_types::traits::TracingProvider,
         CH: ClickhouseHandle,
@@ -264,6 +295,10 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         self.inner.get_metadata_no_dex_price(block_num, quote_asset)
     }
 
+    fn get_block_timestamp(&self, block_num: u64) -> eyre::Result<u64> {
+        self.inner.get_block_timestamp(block_num)
+    }
+
     fn try_fetch_searcher_eoa_info(
         &self,
         searcher_eoa: Address,
@@ -355,6 +390,18 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         self.inner.fetch_all_address_metadata()
     }
 
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>> {
+        self.inner.fetch_all_token_info()
+    }
+
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>> {
+        self.inner.fetch_all_protocol_info()
+    }
+
+    fn get_cex_quotes(&self, block: u64) -> eyre::Result<CexPriceMap> {
+        self.inner.get_cex_quotes(block)
+    }
+
     fn get_dex_quotes(&self, block: u64) -> eyre::Result<DexQuotes> {
         self.inner.get_dex_quotes(block)
     }
@@ -410,6 +457,13 @@ impl<I: DBWriter + Send + Sync> DBWriter for ReadOnlyMiddleware<I> {
         self.client.block_analysis(block_analysis).await
     }
 
+    async fn write_block_analysis_rollups(
+        &self,
+        rollups: Vec<BlockAnalysisRollup>,
+    ) -> eyre::Result<()> {
+        self.client.block_analysis_rollups(rollups).await
+    }
+
     async fn write_dex_quotes(
         &self,
         block_number: u64,
@@ -494,6 +548,10 @@ impl<I: DBWriter + Send + Sync> DBWriter for ReadOnlyMiddleware<I> {
     async fn save_traces(&self, block: u64, traces: Vec<TxTrace>) -> eyre::Result<()> {
         self.client.save_traces(block, traces.clone()).await
     }
+
+    async fn delete_block_data(&self, block_number: u64) -> eyre::Result<()> {
+        self.client.delete_block_data(block_number).await
+    }
 }
 
 impl<I: LibmdbxInit> LibmdbxInit for ReadOnlyMiddleware<I> {
@@ -524,6 +582,18 @@ impl<I: LibmdbxInit> LibmdbxInit for ReadOnlyMiddleware<I> {
         self.inner.get_db_range()
     }
 
+    fn save_pricing_checkpoint(&self, checkpoint: PricingCheckpoint) -> eyre::Result<()> {
+        self.inner.save_pricing_checkpoint(checkpoint)
+    }
+
+    fn load_pricing_checkpoint(&self, block: u64) -> eyre::Result<Option<PricingCheckpoint>> {
+        self.inner.load_pricing_checkpoint(block)
+    }
+
+    fn load_uniswap_v4_pool_keys(&self) -> eyre::Result<Vec<UniswapV4PoolKey>> {
+        self.inner.load_uniswap_v4_pool_keys()
+    }
+
     async fn initialize_table_arbitrary<
         T: brontes_types::traits::TracingProvider,
         CH: ClickhouseHandle,
@@ -592,6 +662,10 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         self.inner.get_metadata_no_dex_price(block_num, quote_asset)
     }
 
+    fn get_block_timestamp(&self, block_num: u64) -> eyre::Result<u64> {
+        self.inner.get_block_timestamp(block_num)
+    }
+
     fn fetch_all_searcher_eoa_info(&self) -> eyre::Result<Vec<(Address, SearcherInfo)>> {
         self.inner.fetch_all_searcher_eoa_info()
     }
@@ -677,6 +751,18 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         self.inner.fetch_all_address_metadata()
     }
 
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>> {
+        self.inner.fetch_all_token_info()
+    }
+
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>> {
+        self.inner.fetch_all_protocol_info()
+    }
+
+    fn get_cex_quotes(&self, block: u64) -> eyre::Result<CexPriceMap> {
+        self.inner.get_cex_quotes(block)
+    }
+
     fn get_dex_quotes(&self, block: u64) -> eyre::Result<DexQuotes> {
         self.inner.get_dex_quotes(block)
     }
@@ -121,6 +121,7 @@ impl ClickhouseBuffered {
             (MevMev_Blocks, MevBlock),
             (MevCex_Dex_Quotes, CexDexQuote),
             (MevCex_Dex, CexDex),
+            (MevCex_Dex_Inventory, CexDexInventory),
             (MevSearcher_Tx, SearcherTx),
             (MevJit, JitLiquidity),
             (MevJit_Sandwich, JitLiquiditySandwich),
@@ -132,6 +133,7 @@ impl ClickhouseBuffered {
             (EthereumPools, ProtocolInfoClickhouse),
             (BrontesTree, TransactionRoot),
             (BrontesBlock_Analysis, BlockAnalysis),
+            (BrontesBlock_Analysis_Rollups, BlockAnalysisRollup),
             (BrontesRun_Id, RunId)
         );
 
@@ -430,6 +430,10 @@ CREATE TABLE brontes.block_analysis ON CLUSTER eth_cluster0
     `liquidated_biggest_arb_revenue_amt`   Nullable(Float64),
     `total_usd_liquidated` Float64,
 
+    `oracle_liquidation_bundle_count`  UInt64,
+    `oracle_liquidation_total_profit`  Float64,
+    `oracle_liquidation_total_revenue` Float64,
+
     `builder_profit_usd`     Float64,
     `builder_profit_eth`     Float64,
     `builder_revenue_usd`    Float64,
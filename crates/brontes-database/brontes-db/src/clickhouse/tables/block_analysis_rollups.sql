@@ -0,0 +1,153 @@
+CREATE TABLE brontes.block_analysis_rollups ON CLUSTER eth_cluster0
+(
+    `bucket`            LowCardinality(String),
+    `bucket_start`      UInt64,
+    `start_block`       UInt64,
+    `end_block`         UInt64,
+    `block_count`       UInt64,
+    `average_eth_price` Float64,
+
+    `all` Tuple(
+        `bundle_count`               UInt64,
+        `total_profit`               Float64,
+        `total_revenue`              Float64,
+        `top_searcher_profit`        Nullable(String),
+        `top_searcher_profit_amt`    Nullable(Float64),
+        `top_searcher_revenue`       Nullable(String),
+        `top_searcher_revenue_amt`   Nullable(Float64),
+        `searcher_count`             UInt64,
+        `top_fund_profit`            Nullable(String),
+        `top_fund_profit_amt`        Nullable(Float64),
+        `fund_count`                 UInt64,
+        `most_arbed_pool_profit`     Nullable(String),
+        `most_arbed_pool_profit_amt` Nullable(Float64),
+        `most_arbed_dex_profit`      Nullable(String),
+        `most_arbed_dex_profit_amt`  Nullable(Float64),
+        `biggest_arb_profit`         Nullable(String),
+        `biggest_arb_profit_amt`     Nullable(Float64)
+    ),
+    `atomic` Tuple(
+        `bundle_count`               UInt64,
+        `total_profit`               Float64,
+        `total_revenue`              Float64,
+        `top_searcher_profit`        Nullable(String),
+        `top_searcher_profit_amt`    Nullable(Float64),
+        `top_searcher_revenue`       Nullable(String),
+        `top_searcher_revenue_amt`   Nullable(Float64),
+        `searcher_count`             UInt64,
+        `top_fund_profit`            Nullable(String),
+        `top_fund_profit_amt`        Nullable(Float64),
+        `fund_count`                 UInt64,
+        `most_arbed_pool_profit`     Nullable(String),
+        `most_arbed_pool_profit_amt` Nullable(Float64),
+        `most_arbed_dex_profit`      Nullable(String),
+        `most_arbed_dex_profit_amt`  Nullable(Float64),
+        `biggest_arb_profit`         Nullable(String),
+        `biggest_arb_profit_amt`     Nullable(Float64)
+    ),
+    `sandwich` Tuple(
+        `bundle_count`               UInt64,
+        `total_profit`               Float64,
+        `total_revenue`              Float64,
+        `top_searcher_profit`        Nullable(String),
+        `top_searcher_profit_amt`    Nullable(Float64),
+        `top_searcher_revenue`       Nullable(String),
+        `top_searcher_revenue_amt`   Nullable(Float64),
+        `searcher_count`             UInt64,
+        `top_fund_profit`            Nullable(String),
+        `top_fund_profit_amt`        Nullable(Float64),
+        `fund_count`                 UInt64,
+        `most_arbed_pool_profit`     Nullable(String),
+        `most_arbed_pool_profit_amt` Nullable(Float64),
+        `most_arbed_dex_profit`      Nullable(String),
+        `most_arbed_dex_profit_amt`  Nullable(Float64),
+        `biggest_arb_profit`         Nullable(String),
+        `biggest_arb_profit_amt`     Nullable(Float64)
+    ),
+    `jit` Tuple(
+        `bundle_count`               UInt64,
+        `total_profit`               Float64,
+        `total_revenue`              Float64,
+        `top_searcher_profit`        Nullable(String),
+        `top_searcher_profit_amt`    Nullable(Float64),
+        `top_searcher_revenue`       Nullable(String),
+        `top_searcher_revenue_amt`   Nullable(Float64),
+        `searcher_count`             UInt64,
+        `top_fund_profit`            Nullable(String),
+        `top_fund_profit_amt`        Nullable(Float64),
+        `fund_count`                 UInt64,
+        `most_arbed_pool_profit`     Nullable(String),
+        `most_arbed_pool_profit_amt` Nullable(Float64),
+        `most_arbed_dex_profit`      Nullable(String),
+        `most_arbed_dex_profit_amt`  Nullable(Float64),
+        `biggest_arb_profit`         Nullable(String),
+        `biggest_arb_profit_amt`     Nullable(Float64)
+    ),
+    `jit_sandwich` Tuple(
+        `bundle_count`               UInt64,
+        `total_profit`               Float64,
+        `total_revenue`              Float64,
+        `top_searcher_profit`        Nullable(String),
+        `top_searcher_profit_amt`    Nullable(Float64),
+        `top_searcher_revenue`       Nullable(String),
+        `top_searcher_revenue_amt`   Nullable(Float64),
+        `searcher_count`             UInt64,
+        `top_fund_profit`            Nullable(String),
+        `top_fund_profit_amt`        Nullable(Float64),
+        `fund_count`                 UInt64,
+        `most_arbed_pool_profit`     Nullable(String),
+        `most_arbed_pool_profit_amt` Nullable(Float64),
+        `most_arbed_dex_profit`      Nullable(String),
+        `most_arbed_dex_profit_amt`  Nullable(Float64),
+        `biggest_arb_profit`         Nullable(String),
+        `biggest_arb_profit_amt`     Nullable(Float64)
+    ),
+    `cex_dex` Tuple(
+        `bundle_count`               UInt64,
+        `total_profit`               Float64,
+        `total_revenue`              Float64,
+        `top_searcher_profit`        Nullable(String),
+        `top_searcher_profit_amt`    Nullable(Float64),
+        `top_searcher_revenue`       Nullable(String),
+        `top_searcher_revenue_amt`   Nullable(Float64),
+        `searcher_count`             UInt64,
+        `top_fund_profit`            Nullable(String),
+        `top_fund_profit_amt`        Nullable(Float64),
+        `fund_count`                 UInt64,
+        `most_arbed_pool_profit`     Nullable(String),
+        `most_arbed_pool_profit_amt` Nullable(Float64),
+        `most_arbed_dex_profit`      Nullable(String),
+        `most_arbed_dex_profit_amt`  Nullable(Float64),
+        `biggest_arb_profit`         Nullable(String),
+        `biggest_arb_profit_amt`     Nullable(Float64)
+    ),
+    `liquidation` Tuple(
+        `bundle_count`               UInt64,
+        `total_profit`               Float64,
+        `total_revenue`              Float64,
+        `top_searcher_profit`        Nullable(String),
+        `top_searcher_profit_amt`    Nullable(Float64),
+        `top_searcher_revenue`       Nullable(String),
+        `top_searcher_revenue_amt`   Nullable(Float64),
+        `searcher_count`             UInt64,
+        `top_fund_profit`            Nullable(String),
+        `top_fund_profit_amt`        Nullable(Float64),
+        `fund_count`                 UInt64,
+        `most_arbed_pool_profit`     Nullable(String),
+        `most_arbed_pool_profit_amt` Nullable(Float64),
+        `most_arbed_dex_profit`      Nullable(String),
+        `most_arbed_dex_profit_amt`  Nullable(Float64),
+        `biggest_arb_profit`         Nullable(String),
+        `biggest_arb_profit_amt`     Nullable(Float64)
+    ),
+
+    `total_usd_liquidated`   Float64,
+    `builder_profit_usd`     Float64,
+    `builder_revenue_usd`    Float64,
+    `builder_mev_profit_usd` Float64,
+    `proposer_profit_usd`    Float64,
+    `last_updated` UInt64 DEFAULT now()
+)
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/brontes/block_analysis_rollups', '{replica}', last_updated)
+PRIMARY KEY (`bucket`, `bucket_start`)
+ORDER BY (`bucket`, `bucket_start`)
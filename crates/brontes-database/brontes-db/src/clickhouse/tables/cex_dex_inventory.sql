@@ -0,0 +1,39 @@
+CREATE TABLE mev.cex_dex_inventory ON CLUSTER eth_cluster0
+(
+    `eoa` String,
+    `block_number` UInt64,
+    `start_block` UInt64,
+    `swaps` Nested(
+        `tx_hash` String,
+        `trace_idx` UInt64,
+        `from` String,
+        `recipient` String,
+        `pool` String,
+        `token_in` Tuple(String, String),
+        `token_out` Tuple(String, String),
+        `amount_in` Tuple(UInt256, UInt256),
+        `amount_out` Tuple(UInt256, UInt256)
+    ),
+    `gas_details` Nested(
+        `tx_hash` String,
+        `coinbase_transfer` Nullable(UInt128),
+        `priority_fee` UInt128,
+        `gas_used` UInt128,
+        `effective_gas_price` UInt128
+    ),
+    `markouts` Array(Tuple(
+        `horizon` UInt64,
+        `positions` Array(Tuple(
+            `token` String,
+            `amount` Float64,
+            `price_maker` Float64,
+            `price_taker` Float64
+        )),
+        `pnl_maker` Float64,
+        `pnl_taker` Float64
+    )),
+    `run_id` UInt64
+)
+ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/cex_dex_inventory', '{replica}')
+PRIMARY KEY (`block_number`, `eoa`)
+ORDER BY (`block_number`, `eoa`)
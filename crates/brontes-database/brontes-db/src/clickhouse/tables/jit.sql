@@ -49,6 +49,13 @@ CREATE TABLE mev.jit ON CLUSTER eth_cluster0
         `gas_used` UInt128,
         `effective_gas_price` UInt128
     ),
+    `victim_losses` Nested(
+        `tx_hash` String,
+        `victim_eoa` String,
+        `token` Tuple(String, String),
+        `amount_lost` Float64,
+        `amount_lost_usd` Float64
+    ),
     `run_id` UInt64
 ) 
 ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/jit', '{replica}')
@@ -2,6 +2,7 @@ CREATE TABLE mev.liquidations ON CLUSTER eth_cluster0
 (
     `liquidation_tx_hash` String,
     `block_number` UInt64,
+    `trigger` String,
     `liquidation_swaps` Nested(
         `trace_idx` UInt64,
         `from` String,
@@ -0,0 +1,281 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+use alloy_primitives::{Address, Bytes};
+use brontes_libmdbx::{RO, RW};
+use brontes_types::{
+    db::{dex::make_filter_key_range, traces::TxTracesInner},
+    structured_trace::{TraceActions, TxTrace},
+    FastHashMap, FastHashSet,
+};
+use eyre::WrapErr;
+use reth_db::{
+    table::{Compress, Encode},
+    DatabaseError,
+};
+use reth_primitives::{BlockId, BlockNumberOrTag, Header, B256};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    implementation::compressed_wrappers::tx::CompressedLibmdbxTx, tables::*,
+    types::CompressedTable, LibmdbxReadWriter,
+};
+
+const INDEX_FILE: &str = "index.json";
+
+/// A row of a libmdbx table, encoded the same way libmdbx stores it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureRow {
+    pub table: Tables,
+    pub key:   Bytes,
+    pub value: Bytes,
+}
+
+/// The result of an `eth_call` made while the block was classified
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedEthCall {
+    pub block:  Option<u64>,
+    pub to:     Option<Address>,
+    pub input:  Bytes,
+    pub output: Bytes,
+}
+
+/// The block an `eth_call` was made at. Calls at a tag aren't tied to a
+/// block, so they are recorded without one
+pub fn eth_call_block(block_id: Option<BlockId>) -> Option<u64> {
+    match block_id {
+        Some(BlockId::Number(BlockNumberOrTag::Number(block))) => Some(block),
+        _ => None,
+    }
+}
+
+/// Everything needed to replay a block without a node or clickhouse: the
+/// header, every libmdbx row that tracing, classification and the
+/// inspectors read for the block and the `eth_call`s classification made.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockFixture {
+    pub block_number: u64,
+    pub block_hash:   B256,
+    pub header:       Header,
+    pub tx_hashes:    Vec<B256>,
+    pub rows:         Vec<FixtureRow>,
+    #[serde(default)]
+    pub eth_calls:    Vec<RecordedEthCall>,
+}
+
+impl BlockFixture {
+    pub fn file_path(dir: impl AsRef<Path>, block_number: u64) -> PathBuf {
+        dir.as_ref().join(format!("{block_number}.json"))
+    }
+
+    pub fn read(dir: impl AsRef<Path>, block_number: u64) -> eyre::Result<Self> {
+        let path = Self::file_path(dir, block_number);
+        let file = File::open(&path)
+            .wrap_err_with(|| format!("no fixture found at {}", path.display()))?;
+
+        serde_json::from_reader(BufReader::new(file))
+            .wrap_err_with(|| format!("invalid fixture at {}", path.display()))
+    }
+
+    /// Writes the fixture and adds its transactions to the index of the dir
+    pub fn write(&self, dir: impl AsRef<Path>) -> eyre::Result<PathBuf> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let path = Self::file_path(dir, self.block_number);
+        serde_json::to_writer(BufWriter::new(File::create(&path)?), self)?;
+
+        let mut index = FixtureIndex::read(dir)?;
+        index.0.extend(
+            self.tx_hashes
+                .iter()
+                .map(|tx_hash| (*tx_hash, self.block_number)),
+        );
+        index.write(dir)?;
+
+        Ok(path)
+    }
+}
+
+/// Maps the transactions of every fixture in a dir to their block, so that
+/// fixtures can be looked up by tx hash.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FixtureIndex(pub FastHashMap<B256, u64>);
+
+impl FixtureIndex {
+    pub fn read(dir: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = dir.as_ref().join(INDEX_FILE);
+        if !path.exists() {
+            return Ok(Self::default())
+        }
+
+        serde_json::from_reader(BufReader::new(File::open(&path)?))
+            .wrap_err_with(|| format!("invalid fixture index at {}", path.display()))
+    }
+
+    pub fn write(&self, dir: impl AsRef<Path>) -> eyre::Result<()> {
+        let file = File::create(dir.as_ref().join(INDEX_FILE))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+
+        Ok(())
+    }
+}
+
+impl LibmdbxReadWriter {
+    /// Captures the rows of a block into a fixture. The metadata of the block
+    /// needs to be in the db. `cex_trade_blocks` is the window of blocks the
+    /// cex trades are loaded for.
+    pub fn record_fixture(
+        &self,
+        block_hash: B256,
+        header: Header,
+        mut traces: Vec<TxTrace>,
+        cex_trade_blocks: RangeInclusive<u64>,
+        eth_calls: Vec<RecordedEthCall>,
+    ) -> eyre::Result<BlockFixture> {
+        let block_number = header.number;
+        traces.sort_by_key(|trace| trace.tx_index);
+
+        self.db.view_db(|tx| {
+            let mut rows = vec![encode_row::<TxTraces>(
+                Tables::TxTraces,
+                block_number,
+                TxTracesInner::new(Some(traces.clone())),
+            )];
+
+            if !push_row::<BlockInfo>(tx, Tables::BlockInfo, block_number, &mut rows)? {
+                tracing::warn!(%block_number, "no block info found, fixture has no metadata");
+            }
+            push_row::<CexPrice>(tx, Tables::CexPrice, block_number, &mut rows)?;
+            push_row::<PoolCreationBlocks>(
+                tx,
+                Tables::PoolCreationBlocks,
+                block_number,
+                &mut rows,
+            )?;
+            for block in cex_trade_blocks {
+                push_row::<CexTrades>(tx, Tables::CexTrades, block, &mut rows)?;
+            }
+
+            let (start_key, end_key) = make_filter_key_range(block_number);
+            for entry in tx
+                .cursor_read::<DexPrice>()?
+                .walk_range(start_key..=end_key)?
+            {
+                let entry = entry?;
+                rows.push(encode_row::<DexPrice>(Tables::DexPrice, entry.0, entry.1));
+            }
+
+            // every address that shows up in the block, the classifier and the
+            // inspectors only ever look these up
+            let mut addresses = FastHashSet::default();
+            addresses.insert(header.beneficiary);
+            for trace in traces.iter().flat_map(|tx| &tx.trace) {
+                addresses.insert(trace.get_from_addr());
+                addresses.insert(trace.get_to_address());
+                addresses.insert(trace.get_msg_sender());
+                if trace.is_create() {
+                    addresses.insert(trace.get_create_output());
+                }
+                addresses.extend(trace.logs.iter().map(|log| log.address));
+            }
+            addresses.remove(&Address::ZERO);
+
+            let mut tokens = FastHashSet::default();
+            for address in &addresses {
+                if let Some(info) = tx.get::<AddressToProtocolInfo>(*address)? {
+                    tokens.extend(info.get_tokens());
+                    push_row::<AddressToProtocolInfo>(
+                        tx,
+                        Tables::AddressToProtocolInfo,
+                        *address,
+                        &mut rows,
+                    )?;
+                }
+                push_row::<AddressMeta>(tx, Tables::AddressMeta, *address, &mut rows)?;
+                push_row::<SearcherEOAs>(tx, Tables::SearcherEOAs, *address, &mut rows)?;
+                push_row::<SearcherContracts>(tx, Tables::SearcherContracts, *address, &mut rows)?;
+                push_row::<Builder>(tx, Tables::Builder, *address, &mut rows)?;
+            }
+
+            for token in addresses.union(&tokens) {
+                push_row::<TokenDecimals>(tx, Tables::TokenDecimals, *token, &mut rows)?;
+            }
+
+            Ok(BlockFixture {
+                block_number,
+                block_hash,
+                header,
+                tx_hashes: traces.iter().map(|trace| trace.tx_hash).collect(),
+                rows,
+                eth_calls,
+            })
+        })
+    }
+
+    /// Writes the rows of a fixture into the db
+    pub fn load_fixture(&self, fixture: &BlockFixture) -> eyre::Result<()> {
+        self.db
+            .update_db(|tx| fixture.rows.iter().try_for_each(|row| put_row(tx, row)))??;
+
+        Ok(())
+    }
+}
+
+/// Pushes the row for the key if it exists, returning whether it did
+fn push_row<T>(
+    tx: &CompressedLibmdbxTx<RO>,
+    table: Tables,
+    key: T::Key,
+    rows: &mut Vec<FixtureRow>,
+) -> eyre::Result<bool>
+where
+    T: CompressedTable,
+    T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+{
+    let Some(value) = tx.get::<T>(key.clone())? else { return Ok(false) };
+    rows.push(encode_row::<T>(table, key, value));
+
+    Ok(true)
+}
+
+fn encode_row<T>(table: Tables, key: T::Key, value: T::DecompressedValue) -> FixtureRow
+where
+    T: CompressedTable,
+    T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+{
+    let value: T::Value = value.into();
+    FixtureRow {
+        table,
+        key: Bytes::from(Into::<Vec<u8>>::into(key.encode())),
+        value: Bytes::from(Into::<Vec<u8>>::into(value.compress())),
+    }
+}
+
+fn put_row(tx: &CompressedLibmdbxTx<RW>, row: &FixtureRow) -> Result<(), DatabaseError> {
+    let (key, value) = (row.key.as_ref(), row.value.to_vec());
+
+    match row.table {
+        Tables::TokenDecimals => tx.put_bytes::<TokenDecimals>(key, value),
+        Tables::AddressToProtocolInfo => tx.put_bytes::<AddressToProtocolInfo>(key, value),
+        Tables::CexPrice => tx.put_bytes::<CexPrice>(key, value),
+        Tables::BlockInfo => tx.put_bytes::<BlockInfo>(key, value),
+        Tables::DexPrice => tx.put_bytes::<DexPrice>(key, value),
+        Tables::PoolCreationBlocks => tx.put_bytes::<PoolCreationBlocks>(key, value),
+        Tables::MevBlocks => tx.put_bytes::<MevBlocks>(key, value),
+        Tables::TxTraces => tx.put_bytes::<TxTraces>(key, value),
+        Tables::Builder => tx.put_bytes::<Builder>(key, value),
+        Tables::AddressMeta => tx.put_bytes::<AddressMeta>(key, value),
+        Tables::SearcherEOAs => tx.put_bytes::<SearcherEOAs>(key, value),
+        Tables::SearcherContracts => tx.put_bytes::<SearcherContracts>(key, value),
+        Tables::InitializedState => tx.put_bytes::<InitializedState>(key, value),
+        Tables::CexTrades => tx.put_bytes::<CexTrades>(key, value),
+        Tables::BlockAnalysisRollups => tx.put_bytes::<BlockAnalysisRollups>(key, value),
+        Tables::SubGraphs => tx.put_bytes::<SubGraphs>(key, value),
+        Tables::PoolState => tx.put_bytes::<PoolState>(key, value),
+        Tables::UniswapV4PoolKeys => tx.put_bytes::<UniswapV4PoolKeys>(key, value),
+    }
+}
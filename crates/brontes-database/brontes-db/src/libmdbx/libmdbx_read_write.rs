@@ -2,12 +2,13 @@ use std::{ops::RangeInclusive, path::Path, sync::Arc};
 
 use alloy_primitives::Address;
 use brontes_metrics::db_reads::LibmdbxMetrics;
-use brontes_pricing::Protocol;
+use brontes_pricing::{checkpoint::PricingCheckpoint, Protocol};
 use brontes_types::{
     constants::{ETH_ADDRESS, WETH_ADDRESS},
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
+        block_analysis_rollup::BlockAnalysisRollup,
         builder::BuilderInfo,
         cex::{quotes::CexPriceMap, trades::CexTradeMap},
         dex::{make_filter_key_range, DexPrices, DexQuotes},
@@ -19,7 +20,8 @@ use brontes_types::{
         mev_block::MevBlockWithClassified,
         searcher::SearcherInfo,
         token_info::{TokenInfo, TokenInfoWithAddress},
-        traits::{DBWriter, LibmdbxReader},
+        traits::{DBWriter, LibmdbxReader, MissingBlockData},
+        uniswap_v4_pool_key::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -35,7 +37,10 @@ use itertools::Itertools;
 use malachite::Rational;
 use reth_db::table::{Compress, Encode};
 use reth_interfaces::db::LogLevel;
-use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedSender},
+    Notify,
+};
 use tracing::{info, instrument};
 
 use super::{
@@ -90,6 +95,15 @@ pub trait LibmdbxInit: LibmdbxReader + DBWriter {
     ) -> eyre::Result<StateToInitialize>;
 
     fn get_db_range(&self) -> eyre::Result<(u64, u64)>;
+
+    /// Stores a checkpoint of the graph state of the dex pricer
+    fn save_pricing_checkpoint(&self, checkpoint: PricingCheckpoint) -> eyre::Result<()>;
+
+    /// The latest dex pricer checkpoint that resumes at or before `block`
+    fn load_pricing_checkpoint(&self, block: u64) -> eyre::Result<Option<PricingCheckpoint>>;
+
+    /// All stored uniswap v4 pool keys
+    fn load_uniswap_v4_pool_keys(&self) -> eyre::Result<Vec<UniswapV4PoolKey>>;
 }
 
 #[derive(Clone)]
@@ -127,6 +141,26 @@ impl LibmdbxReadWriter {
         })
     }
 
+    /// Opens the database in read only mode. No writer task is spawned, so
+    /// all writes will error.
+    pub fn init_db_read_only<P: AsRef<Path>>(
+        path: P,
+        log_level: Option<LogLevel>,
+        metrics: bool,
+    ) -> eyre::Result<Self> {
+        // 5 gb total
+        let memory_per_table_mb = 1_000;
+        let (tx, _) = unbounded_channel();
+        let db = Arc::new(Libmdbx::init_db_read_only(path, log_level)?);
+
+        Ok(Self {
+            db,
+            tx,
+            metrics: metrics.then(LibmdbxMetrics::default),
+            cache: ReadWriteCache::new(memory_per_table_mb, metrics),
+        })
+    }
+
     pub fn init_db_tests<P: AsRef<Path>>(path: P) -> eyre::Result<Self> {
         // 5 gb total
         let memory_per_table_mb = 1_000;
@@ -348,6 +382,58 @@ impl LibmdbxInit for LibmdbxReadWriter {
 
         Ok((start_block, end_block))
     }
+
+    fn save_pricing_checkpoint(&self, checkpoint: PricingCheckpoint) -> eyre::Result<()> {
+        // the pool states are written first as a checkpoint is only loaded when
+        // both tables have it
+        self.db
+            .write_table::<PoolState, PoolStateData>(&[PoolStateData::new(
+                checkpoint.block,
+                checkpoint.pool_states,
+            )])?;
+        self.db
+            .write_table::<SubGraphs, SubGraphsData>(&[SubGraphsData::new(
+                checkpoint.block,
+                checkpoint.sub_graphs,
+            )])?;
+
+        Ok(())
+    }
+
+    fn load_pricing_checkpoint(&self, block: u64) -> eyre::Result<Option<PricingCheckpoint>> {
+        self.db.view_db(|tx| {
+            let mut cursor = tx.cursor_read::<SubGraphs>()?;
+            // seek lands on the first checkpoint at or after the block
+            let mut entry = match cursor.seek(block)? {
+                Some(entry) => Some(entry),
+                None => cursor.last()?,
+            };
+
+            while let Some(row) = entry {
+                let (key, sub_graphs) = (row.0, row.1);
+                if key <= block {
+                    if let Some(pool_states) = tx.get::<PoolState>(key)? {
+                        return Ok(Some(PricingCheckpoint { block: key, sub_graphs, pool_states }))
+                    }
+                }
+                entry = cursor.prev()?;
+            }
+
+            Ok(None)
+        })
+    }
+
+    fn load_uniswap_v4_pool_keys(&self) -> eyre::Result<Vec<UniswapV4PoolKey>> {
+        self.db.view_db(|tx| {
+            let mut cursor = tx.cursor_read::<UniswapV4PoolKeys>()?;
+            let mut keys = Vec::new();
+            for entry in cursor.walk(None)? {
+                keys.push(entry?.1);
+            }
+
+            Ok(keys)
+        })
+    }
 }
 
 #[derive(Debug, Default)]
@@ -413,6 +499,10 @@ impl LibmdbxReader for LibmdbxReadWriter {
         self.fetch_trades(block)
     }
 
+    fn get_cex_quotes(&self, block: u64) -> eyre::Result<CexPriceMap> {
+        self.fetch_cex_quotes(block)
+    }
+
     fn has_dex_quotes(&self, block_num: u64) -> eyre::Result<bool> {
         self.db.view_db(|tx| {
             let Some(state) = tx.get::<InitializedState>(block_num)? else { return Ok(false) };
@@ -424,11 +514,8 @@ impl LibmdbxReader for LibmdbxReadWriter {
     fn load_trace(&self, block_num: u64) -> eyre::Result<Vec<TxTrace>> {
         self.db.view_db(|tx| {
             tx.get::<TxTraces>(block_num)?
-                .ok_or_else(|| eyre::eyre!("missing trace for block: {}", block_num))
-                .map(|i| {
-                    i.traces
-                        .ok_or_else(|| eyre::eyre!("missing trace for block: {}", block_num))
-                })?
+                .and_then(|i| i.traces)
+                .ok_or_else(|| MissingBlockData { table: "trace", block: block_num }.into())
         })
     }
 
@@ -487,6 +574,12 @@ impl LibmdbxReader for LibmdbxReadWriter {
         .into_metadata(cex_quotes, None, None, None))
     }
 
+    fn get_block_timestamp(&self, block_num: u64) -> eyre::Result<u64> {
+        self.fetch_block_metadata(block_num)
+            .map(|meta| meta.block_timestamp)
+            .map_err(|e| eyre::eyre!("failed to get block info: {e:?}"))
+    }
+
     #[brontes_macros::metrics_call(ptr=metrics,scope,db_read,"metadata")]
     fn get_metadata(&self, block_num: u64, quote_asset: Address) -> eyre::Result<Metadata> {
         let block_meta = self.fetch_block_metadata(block_num)?;
@@ -901,6 +994,48 @@ impl LibmdbxReader for LibmdbxReadWriter {
             |cursor| Ok(cursor.next().map(|inner| inner.map(|i| (i.0, i.1)))?),
         )
     }
+
+    #[instrument(level = "error", skip_all)]
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>> {
+        self.db.export_db(
+            None,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<TokenDecimals>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| {
+                Ok(cursor.next().map(|inner| {
+                    inner.map(|i| TokenInfoWithAddress { address: i.0, inner: i.1 })
+                })?)
+            },
+        )
+    }
+
+    #[instrument(level = "error", skip_all)]
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>> {
+        self.db.export_db(
+            None,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<AddressToProtocolInfo>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| Ok(cursor.next().map(|inner| inner.map(|i| (i.0, i.1)))?),
+        )
+    }
 }
 
 impl DBWriter for LibmdbxReadWriter {
@@ -1058,6 +1193,12 @@ impl DBWriter for LibmdbxReadWriter {
         )?)
     }
 
+    async fn insert_uniswap_v4_pool_key(&self, key: UniswapV4PoolKey) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::UniswapV4PoolKey { key }.stamp())?)
+    }
+
     async fn save_traces(&self, block: u64, traces: Vec<TxTrace>) -> eyre::Result<()> {
         Ok(self
             .tx
@@ -1087,6 +1228,39 @@ impl DBWriter for LibmdbxReadWriter {
     ) -> eyre::Result<()> {
         Ok(())
     }
+
+    async fn write_block_analysis_rollups(
+        &self,
+        rollups: Vec<BlockAnalysisRollup>,
+    ) -> eyre::Result<()> {
+        let data = rollups
+            .into_iter()
+            .map(|rollup| BlockAnalysisRollupsData::new(rollup.key(), rollup))
+            .collect_vec();
+        self.db
+            .write_table::<BlockAnalysisRollups, BlockAnalysisRollupsData>(&data)?;
+
+        Ok(())
+    }
+
+    async fn delete_block_data(&self, block_number: u64) -> eyre::Result<()> {
+        // wait for the delete to land so that a re-trace of the block doesn't read
+        // the stale traces
+        let done = Arc::new(Notify::new());
+        self.tx
+            .send(WriterMessage::DeleteBlockData { block_number, done: done.clone() }.stamp())?;
+        done.notified().await;
+
+        // the searcher stats and pools of the block were rolled back in the db
+        self.cache
+            .searcher_eoa(false, |handle| handle.invalidate_all());
+        self.cache
+            .searcher_contract(false, |handle| handle.invalidate_all());
+        self.cache
+            .protocol_info(false, |handle| handle.invalidate_all());
+
+        Ok(())
+    }
 }
 
 impl LibmdbxReadWriter {
@@ -1228,7 +1402,7 @@ impl LibmdbxReadWriter {
     pub fn fetch_trades(&self, block: u64) -> eyre::Result<CexTradeMap> {
         self.db.view_db(|tx| {
             tx.get::<CexTrades>(block)?
-                .ok_or_else(|| eyre::eyre!("no cex trades"))
+                .ok_or_else(|| MissingBlockData { table: "cex trades", block }.into())
                 .inspect_err(|_| {
                     let _ = self.init_state_updating(
                         block,
@@ -12,15 +12,16 @@ use brontes_types::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
         builder::BuilderInfo,
-        dex::{make_key, DexQuoteWithIndex, DexQuotes},
+        dex::{make_filter_key_range, make_key, DexQuoteWithIndex, DexQuotes},
         initialized_state::{DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
         mev_block::MevBlockWithClassified,
         pool_creation_block::PoolsToAddresses,
         searcher::SearcherInfo,
         token_info::TokenInfo,
         traces::TxTracesInner,
+        uniswap_v4_pool_key::UniswapV4PoolKey,
     },
-    mev::{Bundle, MevBlock},
+    mev::{Bundle, MevBlock, MevType},
     structured_trace::TxTrace,
     FastHashMap, Protocol, UnboundedYapperReceiver,
 };
@@ -93,10 +94,17 @@ pub enum WriterMessage {
         curve_lp_token:  Option<Address>,
         classifier_name: Protocol,
     },
+    UniswapV4PoolKey {
+        key: UniswapV4PoolKey,
+    },
     Traces {
         block:  u64,
         traces: Vec<TxTrace>,
     },
+    DeleteBlockData {
+        block_number: u64,
+        done:         Arc<Notify>,
+    },
     Init(InitTables, Arc<Notify>),
 }
 
@@ -242,6 +250,10 @@ impl LibmdbxWriter {
                 self.insert_pool(block, address, &tokens, curve_lp_token, classifier_name)?;
                 "pool"
             }
+            WriterMessage::UniswapV4PoolKey { key } => {
+                self.insert_uniswap_v4_pool_key(key)?;
+                "uniswapv4poolkey"
+            }
             WriterMessage::Traces { block, traces } => {
                 self.save_traces(block, traces)?;
                 "traces"
@@ -283,6 +295,12 @@ impl LibmdbxWriter {
                 self.write_searcher_contract_info(searcher_contract, *searcher_info)?;
                 "searchercontractinfo"
             }
+            WriterMessage::DeleteBlockData { block_number, done } => {
+                let res = self.delete_block_data(block_number);
+                done.notify_one();
+                res?;
+                "deleteblockdata"
+            }
             WriterMessage::Init(init, not) => {
                 init.write_data(self.db.clone())?;
                 not.notify_one();
@@ -514,6 +532,20 @@ impl LibmdbxWriter {
         })
     }
 
+    #[instrument(
+        target = "libmdbx_read_write::insert_uniswap_v4_pool_key",
+        skip_all,
+        level = "warn"
+    )]
+    fn insert_uniswap_v4_pool_key(&self, key: UniswapV4PoolKey) -> eyre::Result<()> {
+        self.instrumented_write::<UniswapV4PoolKeys, UniswapV4PoolKeysData>(&[
+            UniswapV4PoolKeysData::new(key.pool_address(), key),
+        ])
+        .expect("libmdbx write failure");
+
+        Ok(())
+    }
+
     #[instrument(target = "libmdbx_read_write::save_traces", skip_all, level = "warn")]
     fn save_traces(&mut self, block: u64, traces: Vec<TxTrace>) -> eyre::Result<()> {
         let data = TxTracesData::new(block, TxTracesInner { traces: Some(traces) }).into_key_val();
@@ -542,6 +574,95 @@ impl LibmdbxWriter {
         Ok(())
     }
 
+    /// removes all per-block data that was derived from the block's contents.
+    /// used when a block gets reorged out so that it can be re-processed.
+    ///
+    /// Searcher stats accumulate over all blocks, so the bundles of the block
+    /// are reverted out of them, and the pools discovered in the block are
+    /// removed so that they are discovered again if they are still part of the
+    /// canonical chain. Pricing checkpoints taken at or after the block are
+    /// dropped as well.
+    #[instrument(target = "libmdbx_read_write::delete_block_data", skip_all, level = "warn")]
+    fn delete_block_data(&mut self, block_number: u64) -> eyre::Result<()> {
+        // flush anything still queued so we don't re-insert stale rows after the
+        // delete
+        self.insert_remaining();
+
+        let (start_key, end_key) = make_filter_key_range(block_number);
+        self.db.update_db(|tx| {
+            if let Some(mev_block) = tx.get::<MevBlocks>(block_number)? {
+                // the same bundles the searcher stats are updated with
+                for header in mev_block
+                    .mev
+                    .iter()
+                    .map(|bundle| &bundle.header)
+                    .filter(|header| {
+                        !matches!(
+                            header.mev_type,
+                            MevType::Unknown | MevType::SearcherTx | MevType::CexDexInventory
+                        )
+                    })
+                {
+                    if let Some(mut info) = tx.get::<SearcherEOAs>(header.eoa)? {
+                        info.revert_bundle(header);
+                        tx.put::<SearcherEOAs>(header.eoa, info)?;
+                    }
+                    let Some(contract) = header.mev_contract else { continue };
+                    if let Some(mut info) = tx.get::<SearcherContracts>(contract)? {
+                        info.revert_bundle(header);
+                        tx.put::<SearcherContracts>(contract, info)?;
+                    }
+                }
+                tx.delete::<MevBlocks>(block_number, None)?;
+            }
+
+            if let Some(pools) = tx.get::<PoolCreationBlocks>(block_number)? {
+                for pool in pools.0 {
+                    let Some(info) = tx.get::<AddressToProtocolInfo>(pool)? else { continue };
+                    if info.init_block != block_number {
+                        continue
+                    }
+                    tx.delete::<AddressToProtocolInfo>(pool, None)?;
+                    if info.protocol == Protocol::UniswapV4 {
+                        tx.delete::<UniswapV4PoolKeys>(pool, None)?;
+                    }
+                }
+                tx.delete::<PoolCreationBlocks>(block_number, None)?;
+            }
+
+            tx.delete::<TxTraces>(block_number, None)?;
+
+            let mut cursor = tx.cursor_write::<DexPrice>()?;
+            let mut walker = cursor.walk_range(start_key..=end_key)?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+
+            // pricing checkpoints from the block on hold the state of the orphaned
+            // chain, a range resuming from one would be priced with it
+            let mut cursor = tx.cursor_write::<SubGraphs>()?;
+            let mut walker = cursor.walk_range(block_number..)?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+            let mut cursor = tx.cursor_write::<PoolState>()?;
+            let mut walker = cursor.walk_range(block_number..)?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+
+            if let Some(mut state) = tx.get::<InitializedState>(block_number)? {
+                state.apply_reset_key(DEX_PRICE_FLAG);
+                state.apply_reset_key(TRACE_FLAG);
+                tx.put::<InitializedState>(block_number, state)?;
+            }
+
+            Ok::<_, DatabaseError>(())
+        })??;
+
+        Ok(())
+    }
+
     #[instrument(target = "libmdbx_read_write::init_state_updating", skip_all, level = "warn")]
     fn init_state_updating(&mut self, block: u64, flag: u16) -> eyre::Result<()> {
         let tx = self.db.ro_tx()?;
@@ -14,6 +14,7 @@ pub mod cache_middleware;
 pub use cache_middleware::*;
 
 pub mod cex_utils;
+pub mod fixture;
 pub mod libmdbx_writer;
 
 pub mod initialize;
@@ -104,6 +105,27 @@ impl Libmdbx {
         Ok(this)
     }
 
+    /// Opens up an existing database at the specified path in read only mode.
+    /// The tables are expected to already exist.
+    pub fn init_db_read_only<P: AsRef<Path>>(
+        path: P,
+        log_level: Option<LogLevel>,
+    ) -> eyre::Result<Self> {
+        let rpath = path.as_ref();
+        if is_database_empty(rpath) {
+            eyre::bail!("no database found at {}", rpath.display())
+        }
+        check_db_version_file(rpath)?;
+
+        let db = DatabaseEnv::open(
+            rpath,
+            DatabaseEnvKind::RO,
+            DatabaseArguments::new(ClientVersion::default()).with_log_level(log_level),
+        )?;
+
+        Ok(Self(db))
+    }
+
     /// Creates all the defined tables, opens if already created
     fn create_tables(&self) -> Result<(), DatabaseError> {
         let tx = CompressedLibmdbxTx::new_rw_tx(&self.0)?;
@@ -6,10 +6,15 @@ use std::{
     sync::Arc,
 };
 
+use brontes_pricing::checkpoint::{
+    PoolStatesCheckpoint, PoolStatesCheckpointRedefined, SubGraphsCheckpoint,
+    SubGraphsCheckpointRedefined,
+};
 use brontes_types::{
     db::{
         address_metadata::{AddressMetadata, AddressMetadataRedefined},
         address_to_protocol_info::{ProtocolInfo, ProtocolInfoRedefined},
+        block_analysis_rollup::{BlockAnalysisRollup, BlockAnalysisRollupRedefined, RollupKey},
         builder::{BuilderInfo, BuilderInfoRedefined},
         cex::{
             quotes::{CexPriceMap, CexPriceMapRedefined},
@@ -28,6 +33,7 @@ use brontes_types::{
         token_info::TokenInfo,
         traces::{TxTracesInner, TxTracesInnerRedefined},
         traits::LibmdbxReader,
+        uniswap_v4_pool_key::{UniswapV4PoolKey, UniswapV4PoolKeyRedefined},
     },
     serde_utils::*,
     traits::TracingProvider,
@@ -58,7 +64,7 @@ use super::{
     CompressedTable,
 };
 
-pub const NUM_TABLES: usize = 14;
+pub const NUM_TABLES: usize = 18;
 
 macro_rules! tables {
     ($($table:ident),*) => {
@@ -276,7 +282,13 @@ impl Tables {
                     )
                     .await
             }
-            Tables::SearcherEOAs | Tables::SearcherContracts | Tables::InitializedState => Ok(()),
+            Tables::SearcherEOAs
+            | Tables::SearcherContracts
+            | Tables::InitializedState
+            | Tables::BlockAnalysisRollups
+            | Tables::SubGraphs
+            | Tables::PoolState
+            | Tables::UniswapV4PoolKeys => Ok(()),
             _ => unimplemented!("'initialize_table' not implemented for {:?}", self),
         }
     }
@@ -373,6 +385,13 @@ impl Tables {
             Self::MevBlocks => exporter.export_mev_blocks().await,
             Self::SearcherContracts | Self::SearcherEOAs => exporter.export_searcher_info().await,
             Self::Builder => exporter.export_builder_info().await,
+            Self::TxTraces => exporter.export_tx_traces().await,
+            Self::DexPrice => exporter.export_dex_prices().await,
+            Self::CexPrice => exporter.export_cex_quotes().await,
+            Self::CexTrades => exporter.export_cex_trades().await,
+            Self::TokenDecimals => exporter.export_token_info().await,
+            Self::AddressToProtocolInfo => exporter.export_protocol_info().await,
+            Self::PoolCreationBlocks => exporter.export_pool_creation_blocks().await,
             _ => unreachable!("Parquet export not yet supported for this table"),
         }
     }
@@ -537,7 +556,11 @@ tables!(
     SearcherEOAs,
     SearcherContracts,
     InitializedState,
-    CexTrades
+    CexTrades,
+    BlockAnalysisRollups,
+    SubGraphs,
+    PoolState,
+    UniswapV4PoolKeys
 );
 
 /// Must be in this order when defining
@@ -1031,3 +1054,77 @@ compressed_table!(
         }
     }
 );
+
+compressed_table!(
+    Table BlockAnalysisRollups {
+        Data {
+            key: RollupKey,
+            value: BlockAnalysisRollup,
+            compressed_value: BlockAnalysisRollupRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table SubGraphs {
+        Data {
+            key: u64,
+            value: SubGraphsCheckpoint,
+            compressed_value: SubGraphsCheckpointRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table PoolState {
+        Data {
+            key: u64,
+            value: PoolStatesCheckpoint,
+            compressed_value: PoolStatesCheckpointRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table UniswapV4PoolKeys {
+        #[serde_as]
+        Data {
+            #[serde(with = "address_string")]
+            key: Address,
+            value: UniswapV4PoolKey,
+            compressed_value: UniswapV4PoolKeyRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
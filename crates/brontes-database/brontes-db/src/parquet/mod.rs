@@ -3,9 +3,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use arrow::record_batch::RecordBatch;
+use arrow::{error::ArrowError, record_batch::RecordBatch};
 use brontes_types::{
-    db::traits::LibmdbxReader,
+    db::traits::{LibmdbxReader, MissingBlockData},
     mev::{BundleData, MevType},
 };
 use chrono::Local;
@@ -16,7 +16,7 @@ use parquet::{
     basic::Compression,
     file::properties::WriterProperties,
 };
-use tracing::error;
+use tracing::{error, info};
 
 use crate::Tables;
 
@@ -27,7 +27,10 @@ mod bundle_header;
 mod mev_block;
 mod mev_data;
 mod normalized_actions;
+mod prices;
+mod protocol_info;
 mod searcher;
+mod traces;
 pub mod utils;
 
 use address_meta::address_metadata_to_record_batch;
@@ -35,13 +38,23 @@ use builder::builder_info_to_record_batch;
 use bundle_header::bundle_headers_to_record_batch;
 use mev_block::mev_block_to_record_batch;
 use mev_data::*;
+use prices::{cex_quotes_to_record_batch, cex_trades_to_record_batch, dex_prices_to_record_batch};
+use protocol_info::{
+    pool_creation_to_record_batch, protocol_info_to_record_batch, token_info_to_record_batch,
+};
 use searcher::searcher_info_to_record_batch;
+use traces::tx_traces_to_record_batch;
+
+/// Amount of blocks written to a single file when exporting tables that are
+/// keyed by block
+pub const DEFAULT_PARTITION_SIZE: u64 = 100_000;
 
 pub struct ParquetExporter<DB: LibmdbxReader> {
-    pub start_block:   Option<u64>,
-    pub end_block:     Option<u64>,
-    pub base_dir_path: Option<String>,
-    pub db:            &'static DB,
+    pub start_block:    Option<u64>,
+    pub end_block:      Option<u64>,
+    pub base_dir_path:  Option<String>,
+    pub partition_size: u64,
+    pub db:             &'static DB,
 }
 
 impl<DB> ParquetExporter<DB>
@@ -52,9 +65,10 @@ where
         start_block: Option<u64>,
         end_block: Option<u64>,
         base_dir_path: Option<String>,
+        partition_size: u64,
         db: &'static DB,
     ) -> Self {
-        Self { start_block, end_block, base_dir_path, db }
+        Self { start_block, end_block, base_dir_path, partition_size, db }
     }
 
     pub async fn export_mev_blocks(&self) -> Result<(), Error> {
@@ -334,6 +348,197 @@ where
 
         Ok(())
     }
+
+    pub async fn export_token_info(&self) -> Result<(), Error> {
+        let token_info = self
+            .db
+            .fetch_all_token_info()
+            .wrap_err("Failed to query token decimals table")?;
+
+        if token_info.is_empty() {
+            error!("Token decimals table is empty.");
+            return Err(Error::msg("No token info"))
+        }
+
+        let token_info_batch = token_info_to_record_batch(token_info)
+            .wrap_err("Failed to convert Token Info to record batch")?;
+
+        write_parquet(
+            token_info_batch,
+            get_path(self.base_dir_path.clone(), Tables::TokenDecimals, None)?,
+        )
+        .await
+        .wrap_err("Failed to write token info to parquet file")
+    }
+
+    pub async fn export_protocol_info(&self) -> Result<(), Error> {
+        let protocol_info = self
+            .db
+            .fetch_all_protocol_info()
+            .wrap_err("Failed to query address to protocol info table")?;
+
+        if protocol_info.is_empty() {
+            error!("Address to protocol info table is empty.");
+            return Err(Error::msg("No protocol info"))
+        }
+
+        let protocol_info_batch = protocol_info_to_record_batch(protocol_info)
+            .wrap_err("Failed to convert Protocol Info to record batch")?;
+
+        write_parquet(
+            protocol_info_batch,
+            get_path(self.base_dir_path.clone(), Tables::AddressToProtocolInfo, None)?,
+        )
+        .await
+        .wrap_err("Failed to write protocol info to parquet file")
+    }
+
+    pub async fn export_tx_traces(&self) -> Result<(), Error> {
+        self.export_block_partitions(
+            Tables::TxTraces,
+            |db, block| skip_missing(db.load_trace(block)),
+            |traces| {
+                tx_traces_to_record_batch(traces.into_iter().flat_map(|(_, trace)| trace).collect())
+            },
+        )
+        .await
+    }
+
+    pub async fn export_dex_prices(&self) -> Result<(), Error> {
+        self.export_block_partitions(
+            Tables::DexPrice,
+            |db, block| {
+                db.get_dex_quotes(block)
+                    .map(|quotes| (!quotes.0.is_empty()).then_some(quotes))
+            },
+            dex_prices_to_record_batch,
+        )
+        .await
+    }
+
+    pub async fn export_cex_quotes(&self) -> Result<(), Error> {
+        self.export_block_partitions(
+            Tables::CexPrice,
+            // a block without quotes is read as an empty map
+            |db, block| {
+                db.get_cex_quotes(block)
+                    .map(|quotes| (!quotes.quotes.is_empty()).then_some(quotes))
+            },
+            cex_quotes_to_record_batch,
+        )
+        .await
+    }
+
+    pub async fn export_cex_trades(&self) -> Result<(), Error> {
+        self.export_block_partitions(
+            Tables::CexTrades,
+            |db, block| skip_missing(db.get_cex_trades(block)),
+            cex_trades_to_record_batch,
+        )
+        .await
+    }
+
+    pub async fn export_pool_creation_blocks(&self) -> Result<(), Error> {
+        for (start_block, end_block) in self.block_partitions()? {
+            // the range is exclusive of the end block
+            let mut pools = self
+                .db
+                .protocols_created_range(start_block, end_block + 1)
+                .wrap_err("Failed to query pool creation blocks table")?
+                .into_iter()
+                .collect::<Vec<_>>();
+
+            if pools.is_empty() {
+                info!(start_block, end_block, "no pools created in partition");
+                continue
+            }
+            pools.sort_unstable_by_key(|(block, _)| *block);
+
+            let batch = pool_creation_to_record_batch(pools)
+                .wrap_err("Failed to convert Pool Creation Blocks to record batch")?;
+            write_parquet(
+                batch,
+                get_partition_path(
+                    self.base_dir_path.clone(),
+                    Tables::PoolCreationBlocks,
+                    start_block,
+                    end_block,
+                )?,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Exports a table that is keyed by block, writing a file per partition of
+    /// blocks. Blocks `fetch` returns `None` for aren't in the db and are
+    /// skipped, any error fails the export.
+    async fn export_block_partitions<T, F, B>(
+        &self,
+        table: Tables,
+        fetch: F,
+        to_record_batch: B,
+    ) -> Result<(), Error>
+    where
+        F: Fn(&DB, u64) -> Result<Option<T>>,
+        B: Fn(Vec<(u64, T)>) -> Result<RecordBatch, ArrowError>,
+    {
+        for (start_block, end_block) in self.block_partitions()? {
+            let mut rows = Vec::new();
+            for block in start_block..=end_block {
+                if let Some(data) = fetch(self.db, block)
+                    .wrap_err_with(|| format!("Failed to fetch {table} for block {block}"))?
+                {
+                    rows.push((block, data));
+                }
+            }
+
+            if rows.is_empty() {
+                info!(%table, start_block, end_block, "no data found in partition");
+                continue
+            }
+
+            let batch = to_record_batch(rows)
+                .wrap_err_with(|| format!("Failed to convert {table} data to record batch"))?;
+            write_parquet(
+                batch,
+                get_partition_path(self.base_dir_path.clone(), table, start_block, end_block)?,
+            )
+            .await
+            .wrap_err_with(|| format!("Failed to write {table} to parquet file"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Splits the export range into inclusive ranges of `partition_size`
+    /// blocks. The range defaults to ending at the most recent block in the
+    /// db.
+    fn block_partitions(&self) -> Result<Vec<(u64, u64)>> {
+        let start_block = self
+            .start_block
+            .ok_or_else(|| Error::msg("A start block is required to export block tables"))?;
+        let end_block = match self.end_block {
+            Some(end_block) => end_block,
+            None => self.db.get_most_recent_block()?,
+        };
+        let partition_size = self.partition_size.max(1);
+
+        Ok((start_block..=end_block)
+            .step_by(partition_size as usize)
+            .map(|start| (start, (start + partition_size - 1).min(end_block)))
+            .collect())
+    }
+}
+
+/// Reads a block that isn't in the db as `None`, other errors are returned
+fn skip_missing<T>(res: Result<T>) -> Result<Option<T>> {
+    match res {
+        Result::Ok(data) => Ok(Some(data)),
+        Err(e) if e.downcast_ref::<MissingBlockData>().is_some() => Ok(None),
+        Err(e) => Err(e),
+    }
 }
 
 async fn write_parquet(record_batch: RecordBatch, file_path: PathBuf) -> Result<()> {
@@ -386,9 +591,7 @@ pub fn get_path(
     batch_type: Tables,
     mev_type: Option<MevType>,
 ) -> Result<PathBuf> {
-    let base_path = custom_path
-        .as_deref()
-        .unwrap_or("../brontes-notebook/data/brontes-exports");
+    let base_path = custom_path.as_deref().unwrap_or(DEFAULT_EXPORT_DIR);
 
     let mut path = PathBuf::from(base_path);
     path.push(batch_type.get_default_path());
@@ -402,6 +605,25 @@ pub fn get_path(
     create_file_path(path)
 }
 
+/// The file for a partition of blocks, e.g
+/// "data_exports/dex_prices/03-19/19000000-19099999.parquet"
+pub fn get_partition_path(
+    custom_path: Option<String>,
+    batch_type: Tables,
+    start_block: u64,
+    end_block: u64,
+) -> Result<PathBuf> {
+    let base_path = custom_path.as_deref().unwrap_or(DEFAULT_EXPORT_DIR);
+    let date_str = Local::now().format("%m-%d").to_string();
+
+    let dir_path = PathBuf::from(base_path)
+        .join(batch_type.get_default_path())
+        .join(date_str);
+    std::fs::create_dir_all(&dir_path)?;
+
+    Ok(dir_path.join(format!("{start_block}-{end_block}.parquet")))
+}
+
 pub fn create_file_path<P: AsRef<Path>>(base_dir: P) -> Result<PathBuf> {
     let now = Local::now();
     let date_str = now.format("%m-%d").to_string();
@@ -424,12 +646,44 @@ impl Tables {
             Tables::SearcherEOAs => DEFAULT_SEARCHER_INFO_DIR,
             Tables::SearcherContracts => DEFAULT_SEARCHER_INFO_DIR,
             Tables::Builder => DEFAULT_BUILDER_INFO_DIR,
+            Tables::TxTraces => DEFAULT_TX_TRACES_DIR,
+            Tables::DexPrice => DEFAULT_DEX_PRICE_DIR,
+            Tables::CexPrice => DEFAULT_CEX_QUOTES_DIR,
+            Tables::CexTrades => DEFAULT_CEX_TRADES_DIR,
+            Tables::TokenDecimals => DEFAULT_TOKEN_INFO_DIR,
+            Tables::AddressToProtocolInfo => DEFAULT_PROTOCOL_INFO_DIR,
+            Tables::PoolCreationBlocks => DEFAULT_POOL_CREATION_DIR,
             _ => panic!("Unsupported table type"),
         }
     }
 }
+pub const DEFAULT_EXPORT_DIR: &str = "../brontes-notebook/data/brontes-exports";
 pub const DEFAULT_SEARCHER_STATS: &str = "searcher_stats";
 pub const DEFAULT_BLOCK_DIR: &str = "mev";
 pub const DEFAULT_METADATA_DIR: &str = "address_metadata";
 pub const DEFAULT_SEARCHER_INFO_DIR: &str = "searcher_info";
 pub const DEFAULT_BUILDER_INFO_DIR: &str = "builder-info";
+pub const DEFAULT_TX_TRACES_DIR: &str = "tx_traces";
+pub const DEFAULT_DEX_PRICE_DIR: &str = "dex_prices";
+pub const DEFAULT_CEX_QUOTES_DIR: &str = "cex_quotes";
+pub const DEFAULT_CEX_TRADES_DIR: &str = "cex_trades";
+pub const DEFAULT_TOKEN_INFO_DIR: &str = "token_info";
+pub const DEFAULT_PROTOCOL_INFO_DIR: &str = "protocol_info";
+pub const DEFAULT_POOL_CREATION_DIR: &str = "pool_creation_blocks";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_missing() {
+        assert_eq!(skip_missing(Ok(1)).unwrap(), Some(1));
+
+        let missing: Result<u64> = Err(MissingBlockData { table: "trace", block: 1 }.into());
+        assert_eq!(skip_missing(missing).unwrap(), None);
+
+        // only a missing block is skipped, a failed read fails the export
+        let failed: Result<u64> = Err(eyre::eyre!("mdbx read failed"));
+        assert!(skip_missing(failed).is_err());
+    }
+}
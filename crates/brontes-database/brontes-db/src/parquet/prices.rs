@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{BooleanBuilder, Float64Builder, StringBuilder, UInt64Builder},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    db::{
+        cex::{quotes::CexPriceMap, trades::CexTradeMap},
+        dex::DexQuotes,
+    },
+    ToFloatNearest,
+};
+
+/// One row per block, tx and pair priced in the tx
+pub fn dex_prices_to_record_batch(
+    dex_quotes: Vec<(u64, DexQuotes)>,
+) -> Result<RecordBatch, ArrowError> {
+    let mut block_number_builder = UInt64Builder::new();
+    let mut tx_idx_builder = UInt64Builder::new();
+    let mut token0_builder = StringBuilder::new();
+    let mut token1_builder = StringBuilder::new();
+    let mut pre_state_builder = Float64Builder::new();
+    let mut post_state_builder = Float64Builder::new();
+    let mut pool_liquidity_builder = Float64Builder::new();
+    let mut goes_through0_builder = StringBuilder::new();
+    let mut goes_through1_builder = StringBuilder::new();
+    let mut is_transfer_builder = BooleanBuilder::new();
+
+    for (block_number, quotes) in dex_quotes {
+        for (tx_idx, prices) in quotes.0.into_iter().enumerate() {
+            let Some(prices) = prices else { continue };
+
+            for (pair, price) in prices {
+                block_number_builder.append_value(block_number);
+                tx_idx_builder.append_value(tx_idx as u64);
+                token0_builder.append_value(pair.0.to_string());
+                token1_builder.append_value(pair.1.to_string());
+                pre_state_builder.append_value(price.pre_state.to_float());
+                post_state_builder.append_value(price.post_state.to_float());
+                pool_liquidity_builder.append_value(price.pool_liquidity.to_float());
+                goes_through0_builder.append_value(price.goes_through.0.to_string());
+                goes_through1_builder.append_value(price.goes_through.1.to_string());
+                is_transfer_builder.append_value(price.is_transfer);
+            }
+        }
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_idx", DataType::UInt64, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("pre_state_price", DataType::Float64, false),
+        Field::new("post_state_price", DataType::Float64, false),
+        Field::new("pool_liquidity", DataType::Float64, false),
+        Field::new("goes_through_token0", DataType::Utf8, false),
+        Field::new("goes_through_token1", DataType::Utf8, false),
+        Field::new("is_transfer", DataType::Boolean, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_builder.finish()),
+            Arc::new(tx_idx_builder.finish()),
+            Arc::new(token0_builder.finish()),
+            Arc::new(token1_builder.finish()),
+            Arc::new(pre_state_builder.finish()),
+            Arc::new(post_state_builder.finish()),
+            Arc::new(pool_liquidity_builder.finish()),
+            Arc::new(goes_through0_builder.finish()),
+            Arc::new(goes_through1_builder.finish()),
+            Arc::new(is_transfer_builder.finish()),
+        ],
+    )
+}
+
+/// One row per block, exchange, pair and quote
+pub fn cex_quotes_to_record_batch(
+    cex_quotes: Vec<(u64, CexPriceMap)>,
+) -> Result<RecordBatch, ArrowError> {
+    let mut block_number_builder = UInt64Builder::new();
+    let mut exchange_builder = StringBuilder::new();
+    let mut token0_builder = StringBuilder::new();
+    let mut token1_builder = StringBuilder::new();
+    let mut timestamp_builder = UInt64Builder::new();
+    let mut bid_price_builder = Float64Builder::new();
+    let mut ask_price_builder = Float64Builder::new();
+    let mut bid_amount_builder = Float64Builder::new();
+    let mut ask_amount_builder = Float64Builder::new();
+
+    for (block_number, price_map) in cex_quotes {
+        for (exchange, pairs) in price_map.quotes {
+            for (pair, quotes) in pairs {
+                for quote in quotes {
+                    block_number_builder.append_value(block_number);
+                    exchange_builder.append_value(exchange.to_string());
+                    token0_builder.append_value(pair.0.to_string());
+                    token1_builder.append_value(pair.1.to_string());
+                    timestamp_builder.append_value(quote.timestamp);
+                    bid_price_builder.append_value(quote.price.0.to_float());
+                    ask_price_builder.append_value(quote.price.1.to_float());
+                    bid_amount_builder.append_value(quote.amount.0.to_float());
+                    ask_amount_builder.append_value(quote.amount.1.to_float());
+                }
+            }
+        }
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("bid_price", DataType::Float64, false),
+        Field::new("ask_price", DataType::Float64, false),
+        Field::new("bid_amount", DataType::Float64, false),
+        Field::new("ask_amount", DataType::Float64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_builder.finish()),
+            Arc::new(exchange_builder.finish()),
+            Arc::new(token0_builder.finish()),
+            Arc::new(token1_builder.finish()),
+            Arc::new(timestamp_builder.finish()),
+            Arc::new(bid_price_builder.finish()),
+            Arc::new(ask_price_builder.finish()),
+            Arc::new(bid_amount_builder.finish()),
+            Arc::new(ask_amount_builder.finish()),
+        ],
+    )
+}
+
+/// One row per block, exchange, pair and trade
+pub fn cex_trades_to_record_batch(
+    cex_trades: Vec<(u64, CexTradeMap)>,
+) -> Result<RecordBatch, ArrowError> {
+    let mut block_number_builder = UInt64Builder::new();
+    let mut exchange_builder = StringBuilder::new();
+    let mut token0_builder = StringBuilder::new();
+    let mut token1_builder = StringBuilder::new();
+    let mut timestamp_builder = UInt64Builder::new();
+    let mut price_builder = Float64Builder::new();
+    let mut amount_builder = Float64Builder::new();
+
+    for (block_number, trade_map) in cex_trades {
+        for (exchange, pairs) in trade_map.0 {
+            for (pair, trades) in pairs {
+                for trade in trades {
+                    block_number_builder.append_value(block_number);
+                    exchange_builder.append_value(exchange.to_string());
+                    token0_builder.append_value(pair.0.to_string());
+                    token1_builder.append_value(pair.1.to_string());
+                    timestamp_builder.append_value(trade.timestamp);
+                    price_builder.append_value(trade.price.to_float());
+                    amount_builder.append_value(trade.amount.to_float());
+                }
+            }
+        }
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("price", DataType::Float64, false),
+        Field::new("amount", DataType::Float64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_builder.finish()),
+            Arc::new(exchange_builder.finish()),
+            Arc::new(token0_builder.finish()),
+            Arc::new(token1_builder.finish()),
+            Arc::new(timestamp_builder.finish()),
+            Arc::new(price_builder.finish()),
+            Arc::new(amount_builder.finish()),
+        ],
+    )
+}
@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use arrow::{
+    array::{StringBuilder, UInt64Builder, UInt8Builder},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    db::{address_to_protocol_info::ProtocolInfo, token_info::TokenInfoWithAddress},
+    pair::Pair,
+    Protocol,
+};
+use itertools::Itertools;
+
+use super::utils::{build_string_array, build_uint64_array, get_string_array_from_owned};
+
+pub fn token_info_to_record_batch(
+    token_info: Vec<TokenInfoWithAddress>,
+) -> Result<RecordBatch, ArrowError> {
+    let mut address_builder = StringBuilder::new();
+    let mut symbol_builder = StringBuilder::new();
+    let mut decimals_builder = UInt8Builder::with_capacity(token_info.len());
+
+    for info in token_info {
+        address_builder.append_value(info.address.to_string());
+        symbol_builder.append_value(&info.inner.symbol);
+        decimals_builder.append_value(info.inner.decimals);
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("address", DataType::Utf8, false),
+        Field::new("symbol", DataType::Utf8, false),
+        Field::new("decimals", DataType::UInt8, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(address_builder.finish()),
+            Arc::new(symbol_builder.finish()),
+            Arc::new(decimals_builder.finish()),
+        ],
+    )
+}
+
+pub fn protocol_info_to_record_batch(
+    protocol_info: Vec<(Address, ProtocolInfo)>,
+) -> Result<RecordBatch, ArrowError> {
+    let address_array = build_string_array(
+        protocol_info
+            .iter()
+            .map(|info| info.0.to_string())
+            .collect_vec(),
+    );
+    let protocol_array = build_string_array(
+        protocol_info
+            .iter()
+            .map(|info| info.1.protocol.to_string())
+            .collect_vec(),
+    );
+    let token0_array = build_string_array(
+        protocol_info
+            .iter()
+            .map(|info| info.1.token0.to_string())
+            .collect_vec(),
+    );
+    let token1_array = build_string_array(
+        protocol_info
+            .iter()
+            .map(|info| info.1.token1.to_string())
+            .collect_vec(),
+    );
+    let token2_array = get_string_array_from_owned(
+        protocol_info
+            .iter()
+            .map(|info| info.1.token2.map(|token| token.to_string()))
+            .collect_vec(),
+    );
+    let token3_array = get_string_array_from_owned(
+        protocol_info
+            .iter()
+            .map(|info| info.1.token3.map(|token| token.to_string()))
+            .collect_vec(),
+    );
+    let token4_array = get_string_array_from_owned(
+        protocol_info
+            .iter()
+            .map(|info| info.1.token4.map(|token| token.to_string()))
+            .collect_vec(),
+    );
+    let curve_lp_token_array = get_string_array_from_owned(
+        protocol_info
+            .iter()
+            .map(|info| info.1.curve_lp_token.map(|token| token.to_string()))
+            .collect_vec(),
+    );
+    let init_block_array = build_uint64_array(
+        protocol_info
+            .iter()
+            .map(|info| info.1.init_block)
+            .collect_vec(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("address", DataType::Utf8, false),
+        Field::new("protocol", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("token2", DataType::Utf8, true),
+        Field::new("token3", DataType::Utf8, true),
+        Field::new("token4", DataType::Utf8, true),
+        Field::new("curve_lp_token", DataType::Utf8, true),
+        Field::new("init_block", DataType::UInt64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(address_array),
+            Arc::new(protocol_array),
+            Arc::new(token0_array),
+            Arc::new(token1_array),
+            Arc::new(token2_array),
+            Arc::new(token3_array),
+            Arc::new(token4_array),
+            Arc::new(curve_lp_token_array),
+            Arc::new(init_block_array),
+        ],
+    )
+}
+
+/// One row per pool, keyed by the block it was created in
+pub fn pool_creation_to_record_batch(
+    pools: Vec<(u64, Vec<(Address, Protocol, Pair)>)>,
+) -> Result<RecordBatch, ArrowError> {
+    let mut block_number_builder = UInt64Builder::new();
+    let mut address_builder = StringBuilder::new();
+    let mut protocol_builder = StringBuilder::new();
+    let mut token0_builder = StringBuilder::new();
+    let mut token1_builder = StringBuilder::new();
+
+    for (block_number, created) in pools {
+        for (address, protocol, pair) in created {
+            block_number_builder.append_value(block_number);
+            address_builder.append_value(address.to_string());
+            protocol_builder.append_value(protocol.to_string());
+            token0_builder.append_value(pair.0.to_string());
+            token1_builder.append_value(pair.1.to_string());
+        }
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("address", DataType::Utf8, false),
+        Field::new("protocol", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_builder.finish()),
+            Arc::new(address_builder.finish()),
+            Arc::new(protocol_builder.finish()),
+            Arc::new(token0_builder.finish()),
+            Arc::new(token1_builder.finish()),
+        ],
+    )
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{BooleanBuilder, ListBuilder, StringBuilder, UInt64Builder},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::structured_trace::{TraceActions, TxTrace};
+use reth_rpc_types::trace::parity::{Action, TraceOutput};
+
+use super::utils::u128_to_binary_array;
+
+/// Flattens the traces so that there is a row for every call frame
+pub fn tx_traces_to_record_batch(traces: Vec<TxTrace>) -> Result<RecordBatch, ArrowError> {
+    let frame_count = traces.iter().map(|tx| tx.trace.len()).sum::<usize>();
+
+    let mut block_number_builder = UInt64Builder::with_capacity(frame_count);
+    let mut tx_hash_builder = StringBuilder::new();
+    let mut tx_index_builder = UInt64Builder::with_capacity(frame_count);
+    let mut trace_idx_builder = UInt64Builder::with_capacity(frame_count);
+    let mut trace_address_builder = ListBuilder::new(UInt64Builder::new());
+    let mut action_type_builder = StringBuilder::new();
+    let mut call_type_builder = StringBuilder::new();
+    let mut from_builder = StringBuilder::new();
+    let mut to_builder = StringBuilder::new();
+    let mut msg_sender_builder = StringBuilder::new();
+    let mut value_builder = StringBuilder::new();
+    let mut gas_builder = UInt64Builder::with_capacity(frame_count);
+    let mut frame_gas_used_builder = UInt64Builder::with_capacity(frame_count);
+    let mut input_builder = StringBuilder::new();
+    let mut output_builder = StringBuilder::new();
+    let mut created_address_builder = StringBuilder::new();
+    let mut error_builder = StringBuilder::new();
+    let mut subtraces_builder = UInt64Builder::with_capacity(frame_count);
+    let mut log_address_builder = ListBuilder::new(StringBuilder::new());
+    let mut log_topics_builder = ListBuilder::new(StringBuilder::new());
+    let mut log_data_builder = ListBuilder::new(StringBuilder::new());
+    let mut tx_gas_used = Vec::with_capacity(frame_count);
+    let mut effective_price = Vec::with_capacity(frame_count);
+    let mut is_success_builder = BooleanBuilder::with_capacity(frame_count);
+
+    for tx in &traces {
+        for frame in &tx.trace {
+            block_number_builder.append_value(tx.block_number);
+            tx_hash_builder.append_value(format!("{:?}", tx.tx_hash));
+            tx_index_builder.append_value(tx.tx_index);
+            trace_idx_builder.append_value(frame.trace_idx);
+
+            trace_address_builder.values().append_slice(
+                &frame
+                    .get_trace_address()
+                    .into_iter()
+                    .map(|a| a as u64)
+                    .collect::<Vec<_>>(),
+            );
+            trace_address_builder.append(true);
+
+            let (action_type, call_type, gas) = match &frame.trace.action {
+                Action::Call(call) => {
+                    ("call", Some(format!("{:?}", call.call_type)), Some(call.gas.to::<u64>()))
+                }
+                Action::Create(create) => ("create", None, Some(create.gas.to::<u64>())),
+                Action::Selfdestruct(_) => ("selfdestruct", None, None),
+                Action::Reward(_) => ("reward", None, None),
+            };
+            action_type_builder.append_value(action_type);
+            call_type_builder.append_option(call_type);
+            gas_builder.append_option(gas);
+
+            from_builder.append_value(frame.get_from_addr().to_string());
+            to_builder.append_value(frame.get_to_address().to_string());
+            msg_sender_builder.append_value(frame.get_msg_sender().to_string());
+            value_builder.append_value(frame.get_msg_value().to_string());
+            input_builder.append_value(frame.get_calldata().to_string());
+
+            match &frame.trace.result {
+                Some(TraceOutput::Call(call)) => {
+                    frame_gas_used_builder.append_value(call.gas_used.to::<u64>());
+                    output_builder.append_value(call.output.to_string());
+                    created_address_builder.append_null();
+                }
+                Some(TraceOutput::Create(create)) => {
+                    frame_gas_used_builder.append_value(create.gas_used.to::<u64>());
+                    output_builder.append_null();
+                    created_address_builder.append_value(create.address.to_string());
+                }
+                None => {
+                    frame_gas_used_builder.append_null();
+                    output_builder.append_null();
+                    created_address_builder.append_null();
+                }
+            }
+
+            error_builder.append_option(frame.trace.error.as_deref());
+            subtraces_builder.append_value(frame.trace.subtraces as u64);
+
+            for log in &frame.logs {
+                log_address_builder
+                    .values()
+                    .append_value(log.address.to_string());
+                log_topics_builder.values().append_value(
+                    log.topics()
+                        .iter()
+                        .map(|topic| format!("{:?}", topic))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                );
+                log_data_builder
+                    .values()
+                    .append_value(log.data.data.to_string());
+            }
+            log_address_builder.append(true);
+            log_topics_builder.append(true);
+            log_data_builder.append(true);
+
+            tx_gas_used.push(tx.gas_used);
+            effective_price.push(tx.effective_price);
+            is_success_builder.append_value(tx.is_success);
+        }
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_hash", DataType::Utf8, false),
+        Field::new("tx_index", DataType::UInt64, false),
+        Field::new("trace_idx", DataType::UInt64, false),
+        Field::new(
+            "trace_address",
+            DataType::List(Arc::new(Field::new("item", DataType::UInt64, true))),
+            false,
+        ),
+        Field::new("action_type", DataType::Utf8, false),
+        Field::new("call_type", DataType::Utf8, true),
+        Field::new("from", DataType::Utf8, false),
+        Field::new("to", DataType::Utf8, false),
+        Field::new("msg_sender", DataType::Utf8, false),
+        Field::new("value", DataType::Utf8, false),
+        Field::new("gas", DataType::UInt64, true),
+        Field::new("frame_gas_used", DataType::UInt64, true),
+        Field::new("input", DataType::Utf8, false),
+        Field::new("output", DataType::Utf8, true),
+        Field::new("created_address", DataType::Utf8, true),
+        Field::new("error", DataType::Utf8, true),
+        Field::new("subtraces", DataType::UInt64, false),
+        Field::new(
+            "log_addresses",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        Field::new(
+            "log_topics",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        Field::new(
+            "log_data",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        Field::new("gas_used", DataType::Binary, false),
+        Field::new("effective_price", DataType::Binary, false),
+        Field::new("is_success", DataType::Boolean, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_builder.finish()),
+            Arc::new(tx_hash_builder.finish()),
+            Arc::new(tx_index_builder.finish()),
+            Arc::new(trace_idx_builder.finish()),
+            Arc::new(trace_address_builder.finish()),
+            Arc::new(action_type_builder.finish()),
+            Arc::new(call_type_builder.finish()),
+            Arc::new(from_builder.finish()),
+            Arc::new(to_builder.finish()),
+            Arc::new(msg_sender_builder.finish()),
+            Arc::new(value_builder.finish()),
+            Arc::new(gas_builder.finish()),
+            Arc::new(frame_gas_used_builder.finish()),
+            Arc::new(input_builder.finish()),
+            Arc::new(output_builder.finish()),
+            Arc::new(created_address_builder.finish()),
+            Arc::new(error_builder.finish()),
+            Arc::new(subtraces_builder.finish()),
+            Arc::new(log_address_builder.finish()),
+            Arc::new(log_topics_builder.finish()),
+            Arc::new(log_data_builder.finish()),
+            Arc::new(u128_to_binary_array(tx_gas_used)),
+            Arc::new(u128_to_binary_array(effective_price)),
+            Arc::new(is_success_builder.finish()),
+        ],
+    )
+}
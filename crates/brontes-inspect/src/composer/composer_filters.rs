@@ -1,12 +1,14 @@
 use brontes_types::mev::{compose_sandwich_jit, Bundle, MevType};
-use lazy_static::lazy_static;
+
+use super::rules::CompositionRule;
 
 /// Defines rules for composing multiple child MEV types into a single, complex
 /// parent MEV type.
 ///
-/// This macro creates a static reference (`MEV_COMPOSABILITY_FILTER`) that maps
-/// a list of child MEV types to each parent MEV type along with a composition
-/// function. The composition function is used to combine instances of the child
+/// This macro creates `default_composition_rules`, the rules the composer uses
+/// unless others are loaded with [`init_mev_rules`](super::init_mev_rules).
+/// Each rule maps a list of child MEV types to a parent MEV type, the parent
+/// MEV type needs a composition function that combines instances of the child
 /// MEV types into a new instance of the parent MEV type.
 ///
 /// # Usage
@@ -20,27 +22,24 @@ use lazy_static::lazy_static;
 #[macro_export]
 macro_rules! mev_composability {
     ($($($child_mev_type:ident),+ => $parent_mev_type:ident;)+) => {
-        lazy_static! {
-            pub static ref MEV_COMPOSABILITY_FILTER:
-                &'static [(MevType, ComposeFunction, Vec<MevType>)] = {
-                &*Box::leak(Box::new([
-                    $((
-                        MevType::$parent_mev_type,
-                        get_compose_fn(MevType::$parent_mev_type),
-                        vec![$(MevType::$child_mev_type),+],
-                    ),)+
-                ]))
-            };
+        pub fn default_composition_rules() -> Vec<CompositionRule> {
+            vec![
+                $(CompositionRule {
+                    parent:   MevType::$parent_mev_type,
+                    children: vec![$(MevType::$child_mev_type),+],
+                },)+
+            ]
         }
     };
 }
 
 pub type ComposeFunction = Box<dyn Fn(Vec<Bundle>) -> Option<Bundle> + Send + Sync>;
 
-pub fn get_compose_fn(mev_type: MevType) -> ComposeFunction {
+/// Returns none if the mev type can't be composed from other mev types
+pub fn get_compose_fn(mev_type: MevType) -> Option<ComposeFunction> {
     match mev_type {
-        MevType::JitSandwich => Box::new(compose_sandwich_jit),
-        _ => unreachable!("This mev type does not have a compose function"),
+        MevType::JitSandwich => Some(Box::new(compose_sandwich_jit)),
+        _ => None,
     }
 }
 
@@ -6,15 +6,17 @@ use brontes_types::{
     normalized_actions::Action,
     BlockTree,
 };
-use lazy_static::lazy_static;
+
+use super::rules::PrecedenceRule;
 
 /// Defines precedence rules among different MEV types for the purpose of
 /// deduplication.
 ///
-/// This macro creates a static reference (`MEV_DEDUPLICATION_FILTER`) that maps
-/// a list of subordinate MEV types to each dominant MEV type. These rules are
-/// used to determine which MEV types should be considered for deduplication
-/// when multiple types are present for overlapping transactions.
+/// This macro creates `default_precedence_rules`, the rules the composer uses
+/// unless others are loaded with [`init_mev_rules`](super::init_mev_rules).
+/// Each rule maps a list of subordinate MEV types to a dominant MEV type. These
+/// rules are used to determine which MEV types should be considered for
+/// deduplication when multiple types are present for overlapping transactions.
 ///
 /// # Usage
 /// ```ignore
@@ -38,17 +40,13 @@ use lazy_static::lazy_static;
 #[macro_export]
 macro_rules! define_mev_precedence {
     ($($($subordinate_mev_type:ident),+ => $dominant_mev_type:ident;)+) => {
-        lazy_static! {
-            pub static ref MEV_DEDUPLICATION_FILTER:
-                &'static [(MevType, FilterFn, Vec<MevType>)] = {
-                &*Box::leak(Box::new([
-                    $((
-                        MevType::$dominant_mev_type,
-                        get_filter_fn(MevType::$dominant_mev_type),
-                        vec![$(MevType::$subordinate_mev_type),+],
-                    ),)+
-                ]))
-            };
+        pub fn default_precedence_rules() -> Vec<PrecedenceRule> {
+            vec![
+                $(PrecedenceRule {
+                    dominant:     MevType::$dominant_mev_type,
+                    subordinates: vec![$(MevType::$subordinate_mev_type),+],
+                },)+
+            ]
         }
     };
 }
@@ -9,18 +9,17 @@
 //! ## Key Components
 //! - `Composer`: A struct that orchestrates specialized inspectors. It waits
 //!   for all results and then proceeds to compose and deduplicate MEV data.
-//! - `MEV_COMPOSABILITY_FILTER` and `MEV_DEDUPLICATION_FILTER`: These filters,
-//!   defined using the `mev_composability` and `define_mev_precedence` macros,
-//!   respectively, establish rules for composing multiple MEV types and setting
-//!   precedence among them for deduplication.
+//! - `MevRules`: The rules for composing multiple MEV types and setting
+//!   precedence among them for deduplication. The defaults are defined using
+//!   the `mev_composability` and `define_mev_precedence` macros, other rules
+//!   can be loaded from a TOML file with `init_mev_rules`.
 //! - Utility Functions: A collection of functions designed to assist in the
 //!   composition and deduplication processes of MEV data.
 //!
 //! ## Usage
 //! The `Composer` struct is central to this module. It processes a list of
 //! `Inspector` futures to extract MEV data, which is then composed and
-//! deduplicated based on the rules defined in the `MEV_COMPOSABILITY_FILTER`
-//! and `MEV_DEDUPLICATION_FILTER`.
+//! deduplicated based on the configured `MevRules`.
 //!
 //! ### Example
 //! ```ignore
@@ -29,7 +28,7 @@
 //! ```
 use std::sync::Arc;
 
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 use brontes_types::{
     db::{block_analysis::BlockAnalysis, traits::LibmdbxReader},
     mev::Mev,
@@ -40,6 +39,7 @@ use tracing::{span, Level};
 
 mod composer_filters;
 mod mev_filters;
+mod rules;
 mod utils;
 use brontes_types::{
     db::metadata::Metadata,
@@ -47,12 +47,14 @@ use brontes_types::{
     normalized_actions::Action,
     tree::BlockTree,
 };
-use composer_filters::{ComposeFunction, MEV_COMPOSABILITY_FILTER};
-use mev_filters::{FilterFn, MEV_DEDUPLICATION_FILTER};
+use composer_filters::ComposeFunction;
+use mev_filters::FilterFn;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rules::mev_filters;
+pub use rules::{init_mev_rules, CompositionRule, MevRules, PrecedenceRule};
 use utils::{
-    build_mev_header, filter_and_count_bundles, find_mev_with_matching_tx_hashes, sort_mev_by_type,
-    try_deduping_mev,
+    build_mev_header, filter_and_count_bundles, find_mev_with_matching_tx_hashes, is_reported,
+    sort_mev_by_type, try_deduping_mev,
 };
 
 const DISCOVERY_PRIORITY_FEE_MULTIPLIER: f64 = 2.0;
@@ -68,10 +70,54 @@ pub struct ComposerResults {
     pub block_analysis:    BlockAnalysis,
 }
 
+/// Why a bundle found by an inspector isn't part of the results of the block
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleRemoval {
+    /// composed together with other bundles into a bundle of the given type
+    Composed { into: MevType },
+    /// overlaps with a bundle of a type that takes precedence over it
+    Deduplicated { by: MevType, dominant_tx_hash: B256 },
+    /// overlaps with a bundle of the same type that was kept instead
+    Duplicate,
+    /// sandwiches and atomic arbs need to be profitable to be reported
+    Unprofitable,
+}
+
+#[derive(Debug)]
+pub struct ExplainedResults {
+    /// everything the inspectors found, before composition and deduplication
+    pub inspector_results: Vec<Bundle>,
+    pub removed:           Vec<(Bundle, BundleRemoval)>,
+    pub results:           ComposerResults,
+}
+
 pub fn run_block_inspection<DB: LibmdbxReader>(
     orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
     data: MultiBlockData,
     db: &'static DB,
+) -> ComposerResults {
+    inspect_block(orchestra, data, db, None)
+}
+
+/// Same as [`run_block_inspection`] but keeps track of every bundle that is
+/// dropped along the way and why
+pub fn explain_block_inspection<DB: LibmdbxReader>(
+    orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
+    data: MultiBlockData,
+    db: &'static DB,
+) -> ExplainedResults {
+    let mut explanation = (vec![], vec![]);
+    let results = inspect_block(orchestra, data, db, Some(&mut explanation));
+    let (inspector_results, removed) = explanation;
+
+    ExplainedResults { inspector_results, removed, results }
+}
+
+fn inspect_block<DB: LibmdbxReader>(
+    orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
+    data: MultiBlockData,
+    db: &'static DB,
+    explanation: Option<&mut (Vec<Bundle>, Vec<(Bundle, BundleRemoval)>)>,
 ) -> ComposerResults {
     let this_data = data.get_most_recent_block().clone();
     let BlockData { metadata, tree } = this_data;
@@ -82,8 +128,20 @@ pub fn run_block_inspection<DB: LibmdbxReader>(
 
     let quote_token = orchestra[0].get_quote_token();
 
-    let (block_details, mev_details) =
-        on_orchestra_resolution(tree, possible_mev_txes, metadata, classified_mev, quote_token, db);
+    let removed = explanation.map(|(inspector_results, removed)| {
+        inspector_results.extend(classified_mev.iter().cloned());
+        removed
+    });
+
+    let (block_details, mev_details) = on_orchestra_resolution(
+        tree,
+        possible_mev_txes,
+        metadata,
+        classified_mev,
+        quote_token,
+        db,
+        removed,
+    );
 
     let block_analysis = BlockAnalysis::new(&block_details, &mev_details);
 
@@ -104,10 +162,10 @@ fn run_inspectors(
         .flat_map(|inspector| {
             let window = inspector.block_window();
             // not sufficient size yet
-            if data.blocks < window {
+            if data.blocks < inspector.min_block_window() {
                 return vec![]
             };
-            let data = data.split_to_size(window);
+            let data = data.split_to_size(window.min(data.blocks));
             let span =
                 span!(Level::ERROR, "Inspector", inspector = %inspector.get_id(),block=&metadata.block_num);
 
@@ -141,16 +199,26 @@ fn on_orchestra_resolution<DB: LibmdbxReader>(
     orchestra_data: Vec<Bundle>,
     quote_token: Address,
     db: &'static DB,
+    mut removed: Option<&mut Vec<(Bundle, BundleRemoval)>>,
 ) -> (MevBlock, Vec<Bundle>) {
     let mut sorted_mev = sort_mev_by_type(orchestra_data);
 
-    MEV_COMPOSABILITY_FILTER
+    let filters = mev_filters();
+
+    filters
+        .composability
         .iter()
         .for_each(|(parent_mev_type, compose_fn, child_mev_type)| {
-            try_compose_mev(parent_mev_type, child_mev_type, compose_fn, &mut sorted_mev);
+            try_compose_mev(
+                parent_mev_type,
+                child_mev_type,
+                compose_fn,
+                &mut sorted_mev,
+                removed.as_deref_mut(),
+            );
         });
 
-    MEV_DEDUPLICATION_FILTER.iter().for_each(
+    filters.deduplication.iter().for_each(
         |(dominant_mev_type, extra_filter_fn, subordinate_mev_type)| {
             deduplicate_mev(
                 tree.clone(),
@@ -159,6 +227,7 @@ fn on_orchestra_resolution<DB: LibmdbxReader>(
                 extra_filter_fn,
                 subordinate_mev_type,
                 &mut sorted_mev,
+                removed.as_deref_mut(),
             );
         },
     );
@@ -167,7 +236,9 @@ fn on_orchestra_resolution<DB: LibmdbxReader>(
     // per mev_type
     let sorted_mev = sorted_mev
         .into_iter()
-        .map(|(mev_type, bundles)| (mev_type, SharedInspectorUtils::<DB>::dedup_bundles(bundles)))
+        .map(|(mev_type, bundles)| {
+            (mev_type, dedup_mev_type::<DB>(mev_type, bundles, removed.as_deref_mut()))
+        })
         .collect();
 
     let (mev_count, mut filtered_bundles) = filter_and_count_bundles(sorted_mev);
@@ -187,6 +258,32 @@ fn on_orchestra_resolution<DB: LibmdbxReader>(
     (header, filtered_bundles)
 }
 
+/// Deduplicates the bundles of a single mev type, recording the duplicates
+/// and the bundles that won't be reported because they are unprofitable
+fn dedup_mev_type<DB: LibmdbxReader>(
+    mev_type: MevType,
+    bundles: Vec<Bundle>,
+    removed: Option<&mut Vec<(Bundle, BundleRemoval)>>,
+) -> Vec<Bundle> {
+    let Some(removed) = removed else { return SharedInspectorUtils::<DB>::dedup_bundles(bundles) };
+
+    let deduped = SharedInspectorUtils::<DB>::dedup_bundles(bundles.clone());
+    removed.extend(
+        bundles
+            .into_iter()
+            .filter(|bundle| !deduped.contains(bundle))
+            .map(|bundle| (bundle, BundleRemoval::Duplicate)),
+    );
+    removed.extend(
+        deduped
+            .iter()
+            .filter(|bundle| !is_reported(mev_type, bundle))
+            .map(|bundle| (bundle.clone(), BundleRemoval::Unprofitable)),
+    );
+
+    deduped
+}
+
 fn deduplicate_mev<DB: LibmdbxReader>(
     tree: Arc<BlockTree<Action>>,
     db: &'static DB,
@@ -194,12 +291,14 @@ fn deduplicate_mev<DB: LibmdbxReader>(
     extra_filter_function: &FilterFn,
     subordinate_mev_types: &[MevType],
     sorted_mev: &mut FastHashMap<MevType, Vec<Bundle>>,
+    mut removed: Option<&mut Vec<(Bundle, BundleRemoval)>>,
 ) {
     let Some(dominant_mev_list) = sorted_mev.get(dominant_mev_type) else { return };
 
     let mut indexes = Vec::new();
 
     for dominate_mev in dominant_mev_list {
+        let dominant_tx_hash = dominate_mev.header.tx_hash;
         let hashes = dominate_mev.data.mev_transaction_hashes();
 
         for &sub_mev_type in subordinate_mev_types {
@@ -215,18 +314,25 @@ fn deduplicate_mev<DB: LibmdbxReader>(
                     extra_filter_function,
                     &hashes,
                 )
-                .zip(vec![sub_mev_type].into_iter().cycle()),
+                .map(move |index| (index, sub_mev_type, dominant_tx_hash)),
             )
         }
     }
 
     indexes
         .into_iter()
-        .unique()
+        .unique_by(|(index, mev_type, _)| (*index, *mev_type))
         .sorted_unstable_by(|a, b| b.0.cmp(&a.0))
-        .for_each(|(index, mev_type)| {
+        .for_each(|(index, mev_type, dominant_tx_hash)| {
             let Some(mev_list) = sorted_mev.get_mut(&mev_type) else { return };
-            mev_list.remove(index);
+            let bundle = mev_list.remove(index);
+
+            if let Some(removed) = removed.as_deref_mut() {
+                removed.push((
+                    bundle,
+                    BundleRemoval::Deduplicated { by: *dominant_mev_type, dominant_tx_hash },
+                ));
+            }
         });
 }
 
@@ -255,6 +361,7 @@ fn try_compose_mev(
     child_mev_type: &[MevType],
     compose: &ComposeFunction,
     sorted_mev: &mut FastHashMap<MevType, Vec<Bundle>>,
+    mut removed: Option<&mut Vec<(Bundle, BundleRemoval)>>,
 ) {
     let first_mev_type = child_mev_type[0];
     let mut removal_indices: FastHashMap<MevType, Vec<usize>> = FastHashMap::default();
@@ -305,7 +412,11 @@ fn try_compose_mev(
         if let Some(mev_list) = sorted_mev.get_mut(&mev_type) {
             for &index in indices.iter().sorted_unstable().rev() {
                 if mev_list.len() > index {
-                    mev_list.remove(index);
+                    let bundle = mev_list.remove(index);
+
+                    if let Some(removed) = removed.as_deref_mut() {
+                        removed.push((bundle, BundleRemoval::Composed { into: *parent_mev_type }));
+                    }
                 }
             }
         }
@@ -315,6 +426,11 @@ fn try_compose_mev(
 #[cfg(test)]
 pub mod tests {
     use alloy_primitives::hex;
+    use brontes_core::{test_utils::get_db_handle, LibmdbxReadWriter};
+    use brontes_types::mev::{
+        AtomicArb, BundleData, BundleHeader, CexDex, JitLiquidity, JitLiquiditySandwich, Sandwich,
+    };
+    use reth_primitives::Header;
 
     use super::*;
     use crate::{
@@ -322,6 +438,196 @@ pub mod tests {
         Inspectors,
     };
 
+    fn hash(byte: u8) -> B256 {
+        B256::with_last_byte(byte)
+    }
+
+    fn bundle(mev_type: MevType, tx_hash: B256, profit_usd: f64, data: BundleData) -> Bundle {
+        Bundle {
+            header: BundleHeader { tx_hash, profit_usd, mev_type, ..Default::default() },
+            data,
+        }
+    }
+
+    fn sandwich(frontrun: u8, victim: u8, backrun: u8) -> Bundle {
+        bundle(
+            MevType::Sandwich,
+            hash(frontrun),
+            10.0,
+            BundleData::Sandwich(Sandwich {
+                frontrun_tx_hash: vec![hash(frontrun)],
+                victim_swaps_tx_hashes: vec![vec![hash(victim)]],
+                backrun_tx_hash: hash(backrun),
+                ..Default::default()
+            }),
+        )
+    }
+
+    fn jit(mint: u8, burn: u8) -> Bundle {
+        bundle(
+            MevType::Jit,
+            hash(mint),
+            10.0,
+            BundleData::Jit(JitLiquidity {
+                frontrun_mint_tx_hash: hash(mint),
+                backrun_burn_tx_hash: hash(burn),
+                ..Default::default()
+            }),
+        )
+    }
+
+    fn atomic_arb(tx: u8, profit_usd: f64) -> Bundle {
+        bundle(
+            MevType::AtomicArb,
+            hash(tx),
+            profit_usd,
+            BundleData::AtomicArb(AtomicArb { tx_hash: hash(tx), ..Default::default() }),
+        )
+    }
+
+    #[test]
+    fn test_composed_removals() {
+        let composed_sandwich = sandwich(1, 2, 3);
+        let composed_jit = jit(1, 3);
+        let lone_jit = jit(7, 8);
+
+        let mut sorted_mev = FastHashMap::default();
+        sorted_mev.insert(MevType::Sandwich, vec![composed_sandwich.clone()]);
+        sorted_mev.insert(MevType::Jit, vec![composed_jit.clone(), lone_jit.clone()]);
+
+        let compose: ComposeFunction = Box::new(|bundles| {
+            assert_eq!(bundles.len(), 2);
+            Some(bundle(
+                MevType::JitSandwich,
+                bundles[0].header.tx_hash,
+                20.0,
+                BundleData::JitSandwich(JitLiquiditySandwich::default()),
+            ))
+        });
+
+        let mut removed = vec![];
+        try_compose_mev(
+            &MevType::JitSandwich,
+            &[MevType::Sandwich, MevType::Jit],
+            &compose,
+            &mut sorted_mev,
+            Some(&mut removed),
+        );
+
+        let into = MevType::JitSandwich;
+        assert_eq!(removed.len(), 2);
+        assert!(removed.contains(&(composed_sandwich, BundleRemoval::Composed { into })));
+        assert!(removed.contains(&(composed_jit, BundleRemoval::Composed { into })));
+
+        assert!(sorted_mev[&MevType::Sandwich].is_empty());
+        assert_eq!(sorted_mev[&MevType::Jit], vec![lone_jit]);
+        assert_eq!(sorted_mev[&MevType::JitSandwich].len(), 1);
+    }
+
+    #[test]
+    fn test_composition_failure_removes_nothing() {
+        let mut sorted_mev = FastHashMap::default();
+        sorted_mev.insert(MevType::Sandwich, vec![sandwich(1, 2, 3)]);
+        sorted_mev.insert(MevType::Jit, vec![jit(1, 3)]);
+
+        let compose: ComposeFunction = Box::new(|_| None);
+
+        let mut removed = vec![];
+        try_compose_mev(
+            &MevType::JitSandwich,
+            &[MevType::Sandwich, MevType::Jit],
+            &compose,
+            &mut sorted_mev,
+            Some(&mut removed),
+        );
+
+        assert!(removed.is_empty());
+        assert_eq!(sorted_mev[&MevType::Sandwich].len(), 1);
+        assert_eq!(sorted_mev[&MevType::Jit].len(), 1);
+        assert!(!sorted_mev.contains_key(&MevType::JitSandwich));
+    }
+
+    #[brontes_macros::test]
+    async fn test_deduplicated_removals() {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+        let tree = Arc::new(BlockTree::new(Header::default(), 0));
+
+        let cex_dex = bundle(
+            MevType::CexDexTrades,
+            hash(1),
+            10.0,
+            BundleData::CexDex(CexDex { tx_hash: hash(1), ..Default::default() }),
+        );
+        let overlapping_arb = atomic_arb(1, 5.0);
+        let other_arb = atomic_arb(2, 5.0);
+
+        let mut sorted_mev = FastHashMap::default();
+        sorted_mev.insert(MevType::CexDexTrades, vec![cex_dex]);
+        sorted_mev.insert(MevType::AtomicArb, vec![overlapping_arb.clone(), other_arb.clone()]);
+
+        let mut removed = vec![];
+        deduplicate_mev(
+            tree,
+            db,
+            &MevType::CexDexTrades,
+            &None,
+            &[MevType::AtomicArb],
+            &mut sorted_mev,
+            Some(&mut removed),
+        );
+
+        assert_eq!(
+            removed,
+            vec![(
+                overlapping_arb,
+                BundleRemoval::Deduplicated {
+                    by:               MevType::CexDexTrades,
+                    dominant_tx_hash: hash(1),
+                }
+            )]
+        );
+        assert_eq!(sorted_mev[&MevType::AtomicArb], vec![other_arb]);
+        assert_eq!(sorted_mev[&MevType::CexDexTrades].len(), 1);
+    }
+
+    #[test]
+    fn test_duplicate_and_unprofitable_removals() {
+        // same tx, the bundle with the lower profit is kept
+        let kept = atomic_arb(1, 5.0);
+        let duplicate = atomic_arb(1, 8.0);
+        let unprofitable = atomic_arb(2, -1.0);
+        let mut unpriced = atomic_arb(3, 0.0);
+        unpriced.header.no_pricing_calculated = true;
+
+        let mut removed = vec![];
+        let deduped = dedup_mev_type::<LibmdbxReadWriter>(
+            MevType::AtomicArb,
+            vec![kept.clone(), duplicate.clone(), unprofitable.clone(), unpriced.clone()],
+            Some(&mut removed),
+        );
+
+        assert_eq!(deduped.len(), 3);
+        assert!(deduped.contains(&kept));
+        assert!(deduped.contains(&unpriced));
+        assert_eq!(
+            removed,
+            vec![
+                (duplicate, BundleRemoval::Duplicate),
+                (unprofitable, BundleRemoval::Unprofitable)
+            ]
+        );
+
+        // other mev types are reported regardless of their profit
+        let mut removed = vec![];
+        let liquidation = bundle(MevType::Liquidation, hash(4), -1.0, BundleData::default());
+        dedup_mev_type::<LibmdbxReadWriter>(
+            MevType::Liquidation,
+            vec![liquidation],
+            Some(&mut removed),
+        );
+        assert!(removed.is_empty());
+    }
+
     #[brontes_macros::test]
     pub async fn test_jit_sandwich() {
         let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.2).await;
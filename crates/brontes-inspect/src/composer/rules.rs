@@ -0,0 +1,208 @@
+use std::{path::Path, sync::OnceLock};
+
+use brontes_types::{mev::MevType, FastHashSet};
+use itertools::Itertools;
+use serde::{Deserialize, Deserializer, Serialize};
+use strum::IntoEnumIterator;
+
+use super::{
+    composer_filters::{default_composition_rules, get_compose_fn, ComposeFunction},
+    mev_filters::{default_precedence_rules, get_filter_fn, FilterFn},
+};
+
+static MEV_FILTERS: OnceLock<MevFilters> = OnceLock::new();
+
+/// The precedence and composition rules the composer applies to the bundles
+/// found by the inspectors. Defaults to the rules defined in
+/// `mev_filters.rs` and `composer_filters.rs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MevRules {
+    #[serde(default)]
+    pub precedence:  Vec<PrecedenceRule>,
+    #[serde(default)]
+    pub composition: Vec<CompositionRule>,
+}
+
+/// Bundles of the subordinate types that overlap with a bundle of the
+/// dominant type are removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrecedenceRule {
+    #[serde(deserialize_with = "deserialize_mev_type")]
+    pub dominant:     MevType,
+    #[serde(deserialize_with = "deserialize_mev_types")]
+    pub subordinates: Vec<MevType>,
+}
+
+/// Overlapping bundles of all the child types are composed into a single
+/// bundle of the parent type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompositionRule {
+    #[serde(deserialize_with = "deserialize_mev_type")]
+    pub parent:   MevType,
+    #[serde(deserialize_with = "deserialize_mev_types")]
+    pub children: Vec<MevType>,
+}
+
+impl Default for MevRules {
+    fn default() -> Self {
+        Self { precedence: default_precedence_rules(), composition: default_composition_rules() }
+    }
+}
+
+impl MevRules {
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("failed to read mev rules at {}: {e}", path.display()))?;
+
+        Self::from_toml(&config)
+    }
+
+    pub fn from_toml(config: &str) -> eyre::Result<Self> {
+        let rules: Self = toml::from_str(config)?;
+        rules.validate()?;
+
+        Ok(rules)
+    }
+
+    pub fn validate(&self) -> eyre::Result<()> {
+        for rule in &self.precedence {
+            if rule.subordinates.is_empty() {
+                eyre::bail!("precedence rule for {} has no subordinate mev types", rule.dominant)
+            }
+            if rule.subordinates.contains(&rule.dominant) {
+                eyre::bail!("{} can't take precedence over itself", rule.dominant)
+            }
+        }
+
+        let mut parents = FastHashSet::default();
+        for rule in &self.composition {
+            if rule.children.len() < 2 {
+                eyre::bail!(
+                    "composition rule for {} needs at least two child mev types",
+                    rule.parent
+                )
+            }
+            if rule.children.contains(&rule.parent) {
+                eyre::bail!("{} can't be composed from itself", rule.parent)
+            }
+            if get_compose_fn(rule.parent).is_none() {
+                eyre::bail!("there is no way to compose a {} bundle", rule.parent)
+            }
+            if !parents.insert(rule.parent) {
+                eyre::bail!("{} has more than one composition rule", rule.parent)
+            }
+        }
+
+        Ok(())
+    }
+
+    fn into_filters(self) -> MevFilters {
+        MevFilters {
+            deduplication: self
+                .precedence
+                .into_iter()
+                .map(|rule| (rule.dominant, get_filter_fn(rule.dominant), rule.subordinates))
+                .collect(),
+            composability: self
+                .composition
+                .into_iter()
+                .map(|rule| {
+                    let compose = get_compose_fn(rule.parent).expect("validated");
+                    (rule.parent, compose, rule.children)
+                })
+                .collect(),
+        }
+    }
+}
+
+pub(crate) struct MevFilters {
+    pub deduplication: Vec<(MevType, FilterFn, Vec<MevType>)>,
+    pub composability: Vec<(MevType, ComposeFunction, Vec<MevType>)>,
+}
+
+/// Sets the rules used by the composer. Needs to be called before any block is
+/// inspected, otherwise the default rules are used.
+pub fn init_mev_rules(rules: MevRules) -> eyre::Result<()> {
+    rules.validate()?;
+    MEV_FILTERS
+        .set(rules.into_filters())
+        .map_err(|_| eyre::eyre!("mev rules were already initialized"))
+}
+
+pub(crate) fn mev_filters() -> &'static MevFilters {
+    MEV_FILTERS.get_or_init(|| MevRules::default().into_filters())
+}
+
+fn parse_mev_type(name: &str) -> Result<MevType, String> {
+    MevType::iter()
+        .find(|mev_type| mev_type.as_ref() == name)
+        .ok_or_else(|| {
+            format!(
+                "unknown mev type {name}, expected one of: {}",
+                MevType::iter()
+                    .map(|mev_type| mev_type.to_string())
+                    .join(", ")
+            )
+        })
+}
+
+fn deserialize_mev_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<MevType, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    parse_mev_type(&name).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_mev_types<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<MevType>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|name| parse_mev_type(name).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules_round_trip() {
+        let rules = MevRules::default();
+        rules.validate().unwrap();
+
+        let config = toml::to_string(&rules).unwrap();
+        assert_eq!(MevRules::from_toml(&config).unwrap(), rules);
+
+        let example = concat!(env!("CARGO_MANIFEST_DIR"), "/../../config/mev_rules.toml");
+        assert_eq!(MevRules::from_file(example).unwrap(), rules);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let unknown_type = r#"
+            [[precedence]]
+            dominant = "AtomicArbs"
+            subordinates = ["CexDexTrades"]
+        "#;
+        assert!(MevRules::from_toml(unknown_type).is_err());
+
+        let no_compose_fn = r#"
+            [[composition]]
+            parent = "AtomicArb"
+            children = ["Sandwich", "Jit"]
+        "#;
+        assert!(MevRules::from_toml(no_compose_fn).is_err());
+
+        let keep_both = r#"
+            [[precedence]]
+            dominant = "Sandwich"
+            subordinates = ["AtomicArb", "CexDexTrades"]
+        "#;
+        let rules = MevRules::from_toml(keep_both).unwrap();
+        assert!(rules.composition.is_empty());
+        assert_eq!(
+            rules.precedence[0].subordinates,
+            vec![MevType::AtomicArb, MevType::CexDexTrades]
+        );
+    }
+}
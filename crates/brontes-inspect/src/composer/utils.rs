@@ -146,6 +146,15 @@ pub(crate) fn try_deduping_mev<'a>(
         })
 }
 
+/// Sandwiches and atomic arbs are only reported if they were profitable
+pub(crate) fn is_reported(mev_type: MevType, bundle: &Bundle) -> bool {
+    if matches!(mev_type, MevType::Sandwich | MevType::AtomicArb) {
+        bundle.header.profit_usd > 0.0 || bundle.header.no_pricing_calculated
+    } else {
+        true
+    }
+}
+
 pub fn filter_and_count_bundles(
     sorted_mev: FastHashMap<MevType, Vec<Bundle>>,
 ) -> (MevCount, Vec<Bundle>) {
@@ -155,13 +164,7 @@ pub fn filter_and_count_bundles(
     for (mev_type, bundles) in sorted_mev {
         let filtered_bundles: Vec<Bundle> = bundles
             .into_iter()
-            .filter(|bundle| {
-                if matches!(mev_type, MevType::Sandwich | MevType::AtomicArb) {
-                    bundle.header.profit_usd > 0.0 || bundle.header.no_pricing_calculated
-                } else {
-                    true
-                }
-            })
+            .filter(|bundle| is_reported(mev_type, bundle))
             .collect();
 
         // Update  for this MEV type
@@ -191,7 +194,7 @@ fn update_mev_count(mev_count: &mut MevCount, mev_type: MevType, count: u64) {
         MevType::AtomicArb => mev_count.atomic_backrun_count = Some(count),
         MevType::Liquidation => mev_count.liquidation_count = Some(count),
         MevType::SearcherTx => mev_count.searcher_tx_count = Some(count),
-        MevType::Unknown => (),
+        MevType::CexDexInventory | MevType::Unknown => (),
     }
 }
 
@@ -77,10 +77,14 @@ use brontes_types::{
     },
     mev::{Bundle, BundleData},
     normalized_actions::Action,
+    traits::BlockSimulator,
     tree::BlockTree,
     MultiBlockData,
 };
-use cex_dex::{markout::CexDexMarkoutInspector, quotes::CexDexQuotesInspector};
+use cex_dex::{
+    inventory::CexDexInventoryInspector, markout::CexDexMarkoutInspector,
+    quotes::CexDexQuotesInspector,
+};
 use jit::JitCexDex;
 use liquidations::LiquidationInspector;
 use sandwich::SandwichInspector;
@@ -94,6 +98,11 @@ pub trait Inspector: Send + Sync {
     fn block_window(&self) -> usize {
         1
     }
+    /// the amount of blocks needed before the inspector is run, defaults to
+    /// the full block window
+    fn min_block_window(&self) -> usize {
+        self.block_window()
+    }
     /// Used for log span so we know which errors come from which inspector
     fn get_id(&self) -> &str;
     fn inspect_block(&self, data: MultiBlockData) -> Self::Result;
@@ -112,6 +121,7 @@ pub enum Inspectors {
     SearcherActivity,
     CexDexMarkout,
     JitCexDex,
+    CexDexInventory,
 }
 
 type DynMevInspector = &'static (dyn Inspector<Result = Vec<Bundle>> + 'static);
@@ -123,6 +133,8 @@ impl Inspectors {
         db: &'static DB,
         cex_exchanges: &[CexExchange],
         trade_config: CexDexTradeConfig,
+        sandwich_block_window: usize,
+        simulator: Option<&'static dyn BlockSimulator>,
         metrics: Option<OutlierMetrics>,
     ) -> DynMevInspector {
         match &self {
@@ -130,7 +142,8 @@ impl Inspectors {
                 static_object(AtomicArbInspector::new(quote_token, db, metrics)) as DynMevInspector
             }
             Self::Jit => {
-                static_object(JitInspector::new(quote_token, db, metrics)) as DynMevInspector
+                static_object(JitInspector::new(quote_token, db, metrics).with_simulator(simulator))
+                    as DynMevInspector
             }
 
             Self::CexDex => static_object(CexDexQuotesInspector::new(
@@ -140,9 +153,11 @@ impl Inspectors {
                 trade_config.quote_offset_from_block_us,
                 metrics,
             )) as DynMevInspector,
-            Self::Sandwich => {
-                static_object(SandwichInspector::new(quote_token, db, metrics)) as DynMevInspector
-            }
+            Self::Sandwich => static_object(
+                SandwichInspector::new(quote_token, db, metrics)
+                    .with_block_window(sandwich_block_window)
+                    .with_simulator(simulator),
+            ) as DynMevInspector,
             Self::Liquidations => {
                 static_object(LiquidationInspector::new(quote_token, db, metrics))
                     as DynMevInspector
@@ -165,8 +180,15 @@ impl Inspectors {
                     trade_config,
                     metrics.clone(),
                 ),
-                jit:     JitInspector::new(quote_token, db, metrics),
+                jit:     JitInspector::new(quote_token, db, metrics).with_simulator(simulator),
             }) as DynMevInspector,
+            Self::CexDexInventory => static_object(CexDexInventoryInspector::new(
+                quote_token,
+                db,
+                cex_exchanges,
+                trade_config,
+                metrics,
+            )) as DynMevInspector,
         }
     }
 }
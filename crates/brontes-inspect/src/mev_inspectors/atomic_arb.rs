@@ -8,7 +8,7 @@ use brontes_types::{
     mev::{AtomicArb, AtomicArbType, Bundle, BundleData, MevType},
     normalized_actions::{
         accounting::ActionAccounting, Action, NormalizedEthTransfer, NormalizedSwap,
-        NormalizedTransfer,
+        NormalizedTransfer, NormalizedWrap,
     },
     BlockData, FastHashSet, IntoZip, MultiBlockData, ToFloatNearest, TreeBase, TreeCollector,
     TreeSearchBuilder, TxInfo,
@@ -59,6 +59,7 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
                     Action::is_swap,
                     Action::is_transfer,
                     Action::is_eth_transfer,
+                    Action::is_wrap,
                     Action::is_nested_action,
                 ]))
                 .t_full_map(|(tree, v)| {
@@ -67,7 +68,12 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
                         tree.get_tx_info_batch(&tx_hashes, self.utils.db),
                         v.into_iter().map(|v| {
                             self.utils
-                                .flatten_nested_actions_default(v.into_iter())
+                                .flatten_nested_actions(v.into_iter(), &|action| {
+                                    action.is_swap()
+                                        || action.is_transfer()
+                                        || action.is_eth_transfer()
+                                        || action.is_wrap()
+                                })
                                 .collect::<Vec<_>>()
                         }),
                     )
@@ -86,10 +92,11 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
                         metadata.clone(),
                         actions
                             .into_iter()
-                            .split_actions::<(Vec<_>, Vec<_>, Vec<_>), _>((
+                            .split_actions::<(Vec<_>, Vec<_>, Vec<_>, Vec<_>), _>((
                                 Action::try_swaps_merged,
                                 Action::try_transfer,
                                 Action::try_eth_transfer,
+                                Action::try_wrap,
                             )),
                     )
                 })
@@ -109,10 +116,15 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
         trees: Vec<Arc<BlockTree<Action>>>,
         info: TxInfo,
         metadata: Arc<Metadata>,
-        data: (Vec<NormalizedSwap>, Vec<NormalizedTransfer>, Vec<NormalizedEthTransfer>),
+        data: (
+            Vec<NormalizedSwap>,
+            Vec<NormalizedTransfer>,
+            Vec<NormalizedEthTransfer>,
+            Vec<NormalizedWrap>,
+        ),
     ) -> Option<Bundle> {
         tracing::trace!(?info, "trying atomic");
-        let (mut swaps, transfers, eth_transfers) = data;
+        let (mut swaps, transfers, eth_transfers, wraps) = data;
         let mev_addresses: FastHashSet<Address> = info.collect_address_set_for_accounting();
 
         let mut ignore_addresses = mev_addresses.clone();
@@ -129,6 +141,7 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
             .into_iter()
             .map(Action::from)
             .chain(eth_transfers.into_iter().map(Action::from))
+            .chain(wraps.into_iter().map(Action::from))
             .chain(info.get_total_eth_value().iter().cloned().map(Action::from))
             .account_for_actions();
 
@@ -0,0 +1,476 @@
+use std::sync::Arc;
+
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::inspectors::OutlierMetrics;
+use brontes_types::{
+    db::cex::{
+        trades::{config::CexDexTradeConfig, CexTradeMap},
+        CexExchange,
+    },
+    mev::{Bundle, BundleData, CexDexInventory, InventoryMarkout, InventoryPosition, MevType},
+    normalized_actions::{
+        accounting::{ActionAccounting, AddressDeltas},
+        Action, NormalizedSwap,
+    },
+    pair::Pair,
+    BlockData, FastHashMap, MultiBlockData, ToFloatNearest, TreeCollector, TreeSearchBuilder,
+    TxInfo,
+};
+use itertools::{multizip, Itertools};
+use malachite::{
+    num::{
+        arithmetic::traits::Reciprocal,
+        basic::traits::{One, Zero},
+    },
+    Rational,
+};
+use reth_primitives::{Address, B256};
+
+use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+
+/// Accumulates the dex swaps of labelled cex-dex searchers over
+/// [`CexDexTradeConfig::inventory_window_blocks`] blocks and marks the net
+/// inventory out against the cex at each of the configured horizons. Unlike
+/// the markout inspector, which prices every tx on its own, this assumes the
+/// searcher only hedges its net position.
+pub struct CexDexInventoryInspector<'db, DB: LibmdbxReader> {
+    utils:         SharedInspectorUtils<'db, DB>,
+    trade_config:  CexDexTradeConfig,
+    cex_exchanges: Vec<CexExchange>,
+}
+
+/// The txs of a single searcher within the window.
+#[derive(Default)]
+struct SearcherTxs {
+    tx_info: Vec<TxInfo>,
+    swaps:   Vec<Vec<NormalizedSwap>>,
+    deltas:  Vec<AddressDeltas>,
+}
+
+impl<'db, DB: LibmdbxReader> CexDexInventoryInspector<'db, DB> {
+    pub fn new(
+        quote: Address,
+        db: &'db DB,
+        cex_exchanges: &[CexExchange],
+        trade_config: CexDexTradeConfig,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        Self {
+            utils: SharedInspectorUtils::new(quote, db, metrics),
+            trade_config,
+            cex_exchanges: cex_exchanges.to_owned(),
+        }
+    }
+}
+
+impl<DB: LibmdbxReader> Inspector for CexDexInventoryInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    fn get_id(&self) -> &str {
+        "CexDexInventory"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.utils.quote
+    }
+
+    fn block_window(&self) -> usize {
+        self.trade_config.inventory_window_blocks.max(1)
+    }
+
+    fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
+        let metadata = &data.get_most_recent_block().metadata;
+
+        // every block is only counted towards a single window
+        if !is_window_end(metadata.block_num, self.trade_config.inventory_window_blocks) {
+            return vec![]
+        }
+
+        if metadata.cex_trades.is_none() {
+            tracing::warn!("no cex trades for block: {}", metadata.block_num);
+            return vec![]
+        }
+
+        self.utils
+            .get_metrics()
+            .map(|m| m.run_inspector(MevType::CexDexInventory, || self.inspect_window(&data)))
+            .unwrap_or_else(|| self.inspect_window(&data))
+    }
+}
+
+impl<DB: LibmdbxReader> CexDexInventoryInspector<'_, DB> {
+    fn inspect_window(&self, data: &MultiBlockData) -> Vec<Bundle> {
+        let window = data
+            .per_block_data
+            .len()
+            .min(self.trade_config.inventory_window_blocks);
+        let blocks = &data.per_block_data[data.per_block_data.len() - window..];
+        let metadata = data.get_most_recent_block().metadata.clone();
+        let start_block = blocks[0].metadata.block_num;
+
+        let mut searchers: FastHashMap<Address, SearcherTxs> = FastHashMap::default();
+
+        for BlockData { tree, .. } in blocks {
+            let (hashes, actions): (Vec<_>, Vec<_>) = tree
+                .clone()
+                .collect_all(TreeSearchBuilder::default().with_actions([
+                    Action::is_swap,
+                    Action::is_transfer,
+                    Action::is_eth_transfer,
+                ]))
+                .unzip();
+
+            let tx_info = tree.get_tx_info_batch(&hashes, self.utils.db);
+
+            for (actions, tx_info) in multizip((actions, tx_info)) {
+                let Some(tx_info) = tx_info else { continue };
+                if !tx_info.is_labelled_searcher_of_type(MevType::CexDexTrades) {
+                    continue
+                }
+
+                let swaps = self
+                    .utils
+                    .flatten_nested_actions(actions.clone().into_iter(), &|action| action.is_swap())
+                    .split_actions::<Vec<NormalizedSwap>, _>(Action::try_swaps_merged);
+
+                if swaps.is_empty() {
+                    continue
+                }
+
+                let deltas = actions
+                    .into_iter()
+                    .chain(
+                        tx_info
+                            .get_total_eth_value()
+                            .iter()
+                            .cloned()
+                            .map(Action::from),
+                    )
+                    .account_for_actions();
+
+                let searcher = searchers.entry(tx_info.eoa).or_default();
+                searcher.tx_info.push(tx_info);
+                searcher.swaps.push(swaps);
+                searcher.deltas.push(deltas);
+            }
+        }
+
+        searchers
+            .into_values()
+            .filter_map(|searcher| self.build_bundle(searcher, start_block, metadata.clone()))
+            .sorted_by_key(|bundle| (bundle.header.tx_index, bundle.header.eoa))
+            .collect()
+    }
+
+    fn build_bundle(
+        &self,
+        searcher: SearcherTxs,
+        start_block: u64,
+        metadata: Arc<Metadata>,
+    ) -> Option<Bundle> {
+        let SearcherTxs { tx_info, swaps, deltas } = searcher;
+        let last_tx = tx_info.last()?;
+
+        let tx_swaps = tx_info
+            .iter()
+            .zip(&swaps)
+            .flat_map(|(info, swaps)| swaps.iter().map(|swap| (info.tx_hash, swap.clone())))
+            .collect_vec();
+
+        let inventory = net_inventory(tx_swaps.iter().map(|(_, swap)| swap));
+        let cex_trades = metadata.cex_trades.as_ref()?;
+
+        let markouts = self
+            .trade_config
+            .inventory_markout_horizons
+            .iter()
+            .filter_map(|horizon| {
+                let timestamp = metadata.microseconds_block_timestamp() + horizon * 1_000_000;
+
+                mark_out(*horizon, &inventory, |token| {
+                    token_quote_price(
+                        cex_trades,
+                        self.trade_config,
+                        &self.cex_exchanges,
+                        self.utils.quote,
+                        token,
+                        timestamp,
+                        &tx_swaps,
+                    )
+                })
+            })
+            .collect_vec();
+
+        let gas_details = tx_info.iter().map(|info| info.gas_details).collect_vec();
+        let gas_paid = metadata.get_gas_price_usd(
+            gas_details.iter().map(|gas| gas.gas_paid()).sum(),
+            self.utils.quote,
+        );
+
+        // the balance deltas and profit use the prices of the first horizon
+        let first = markouts.first();
+        let price_map = first
+            .map(|markout| {
+                markout
+                    .positions
+                    .iter()
+                    .map(|position| (position.token, position.price_taker.clone()))
+                    .collect::<FastHashMap<_, _>>()
+            })
+            .unwrap_or_default();
+        let profit_usd = first
+            .map(|markout| (&markout.pnl_taker - gas_paid).to_float())
+            .unwrap_or_default();
+
+        let tx_hashes = tx_info.iter().map(|info| info.tx_hash).collect_vec();
+        let header = self.utils.build_bundle_header(
+            deltas,
+            tx_hashes.clone(),
+            last_tx,
+            profit_usd,
+            &gas_details,
+            metadata.clone(),
+            MevType::CexDexInventory,
+            markouts.is_empty(),
+            |_, token, amount| Some(price_map.get(&token)? * amount),
+        );
+
+        Some(Bundle {
+            header,
+            data: BundleData::CexDexInventory(CexDexInventory {
+                eoa: last_tx.eoa,
+                block_number: metadata.block_num,
+                start_block,
+                tx_hashes,
+                swaps,
+                gas_details,
+                markouts,
+            }),
+        })
+    }
+}
+
+/// Windows are aligned to the block number so that every block belongs to
+/// exactly one window, regardless of where the range started.
+fn is_window_end(block_number: u64, window: usize) -> bool {
+    window != 0 && (block_number + 1) % window as u64 == 0
+}
+
+/// Nets the amounts bought and sold per token. Tokens the searcher ended up
+/// flat in are dropped.
+fn net_inventory<'a>(
+    swaps: impl Iterator<Item = &'a NormalizedSwap>,
+) -> FastHashMap<Address, Rational> {
+    let mut inventory: FastHashMap<Address, Rational> = FastHashMap::default();
+    for swap in swaps {
+        *inventory
+            .entry(swap.token_in.address)
+            .or_insert(Rational::ZERO) -= &swap.amount_in;
+        *inventory
+            .entry(swap.token_out.address)
+            .or_insert(Rational::ZERO) += &swap.amount_out;
+    }
+    inventory.retain(|_, amount| *amount != Rational::ZERO);
+
+    inventory
+}
+
+/// Values the inventory with the given maker and taker prices. Returns `None`
+/// if any of the tokens can't be priced, as the pnl would be meaningless.
+fn mark_out(
+    horizon: u64,
+    inventory: &FastHashMap<Address, Rational>,
+    price: impl Fn(Address) -> Option<(Rational, Rational)>,
+) -> Option<InventoryMarkout> {
+    let positions = inventory
+        .iter()
+        .sorted_by_key(|(token, _)| **token)
+        .map(|(token, amount)| {
+            let (price_maker, price_taker) = price(*token)?;
+
+            Some(InventoryPosition {
+                token: *token,
+                amount: amount.clone(),
+                price_maker,
+                price_taker,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let (pnl_maker, pnl_taker) =
+        positions
+            .iter()
+            .fold((Rational::ZERO, Rational::ZERO), |(maker, taker), position| {
+                (
+                    maker + &position.amount * &position.price_maker,
+                    taker + &position.amount * &position.price_taker,
+                )
+            });
+
+    Some(InventoryMarkout { horizon, positions, pnl_maker, pnl_taker })
+}
+
+/// The maker and taker cex price of the token in the quote token at the
+/// given timestamp. The price is taken from the trades of the token itself,
+/// the last swap of the token is only passed along for logging.
+fn token_quote_price(
+    cex_trades: &CexTradeMap,
+    trade_config: CexDexTradeConfig,
+    cex_exchanges: &[CexExchange],
+    quote: Address,
+    token: Address,
+    timestamp: u64,
+    swaps: &[(B256, NormalizedSwap)],
+) -> Option<(Rational, Rational)> {
+    if token == quote {
+        return Some((Rational::ONE, Rational::ONE))
+    }
+
+    let (tx_hash, swap) = swaps
+        .iter()
+        .rev()
+        .find(|(_, swap)| swap.token_in.address == token || swap.token_out.address == token)?;
+
+    let price = cex_trades
+        .calculate_time_window_vwam(
+            trade_config,
+            cex_exchanges,
+            Pair(token, quote),
+            &Rational::ONE,
+            timestamp,
+            true,
+            swap,
+            *tx_hash,
+        )?
+        .global;
+
+    if price.price_maker == Rational::ZERO || price.price_taker == Rational::ZERO {
+        return None
+    }
+
+    Some((price.price_maker.reciprocal(), price.price_taker.reciprocal()))
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::db::{cex::trades::CexTrades, token_info::TokenInfoWithAddress};
+
+    use super::*;
+
+    const QUOTE: Address = Address::with_last_byte(1);
+    const TOKEN_A: Address = Address::with_last_byte(2);
+    const TOKEN_B: Address = Address::with_last_byte(3);
+    const TIMESTAMP: u64 = 1_700_000_000_000_000;
+
+    fn swap(
+        token_in: Address,
+        amount_in: u64,
+        token_out: Address,
+        amount_out: u64,
+    ) -> NormalizedSwap {
+        NormalizedSwap {
+            token_in: TokenInfoWithAddress { address: token_in, ..Default::default() },
+            token_out: TokenInfoWithAddress { address: token_out, ..Default::default() },
+            amount_in: Rational::from(amount_in),
+            amount_out: Rational::from(amount_out),
+            ..Default::default()
+        }
+    }
+
+    fn trades(price: u64) -> Vec<CexTrades> {
+        // the walker starts next to the block timestamp, so it needs trades on
+        // both sides of it
+        [TIMESTAMP - 20_000, TIMESTAMP, TIMESTAMP + 20_000, TIMESTAMP + 40_000]
+            .into_iter()
+            .map(|timestamp| CexTrades {
+                exchange: CexExchange::Deribit,
+                timestamp,
+                price: Rational::from(price),
+                amount: Rational::from(10),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_window_end() {
+        assert!(is_window_end(9, 5));
+        assert!(is_window_end(14, 5));
+        assert!(!is_window_end(10, 5));
+        assert!(is_window_end(10, 1));
+        assert!(!is_window_end(9, 0));
+    }
+
+    #[test]
+    fn test_net_inventory() {
+        let swaps = [
+            swap(QUOTE, 100, TOKEN_A, 10),
+            swap(TOKEN_A, 4, TOKEN_B, 2),
+            swap(TOKEN_B, 2, QUOTE, 50),
+        ];
+
+        let inventory = net_inventory(swaps.iter());
+
+        assert_eq!(inventory.len(), 2);
+        assert_eq!(inventory[&QUOTE], Rational::from(-50));
+        assert_eq!(inventory[&TOKEN_A], Rational::from(6));
+        // flat in token b
+        assert!(!inventory.contains_key(&TOKEN_B));
+    }
+
+    #[test]
+    fn test_mark_out() {
+        let inventory = net_inventory([swap(QUOTE, 100, TOKEN_A, 10)].iter());
+
+        let markout = mark_out(60, &inventory, |token| match token {
+            QUOTE => Some((Rational::ONE, Rational::ONE)),
+            TOKEN_A => Some((Rational::from(11), Rational::from(9))),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!(markout.horizon, 60);
+        assert_eq!(markout.positions.len(), 2);
+        assert_eq!(markout.pnl_maker, Rational::from(10));
+        assert_eq!(markout.pnl_taker, Rational::from(-10));
+
+        // a single unpriced token invalidates the markout
+        let quote_only =
+            |token: Address| (token == QUOTE).then_some((Rational::ONE, Rational::ONE));
+        assert!(mark_out(60, &inventory, quote_only).is_none());
+    }
+
+    #[test]
+    fn test_prices_each_token_from_its_own_trades() {
+        let cex_trades = CexTradeMap(
+            [(
+                CexExchange::Deribit,
+                [(Pair(TOKEN_A, QUOTE), trades(2)), (Pair(TOKEN_B, QUOTE), trades(5))]
+                    .into_iter()
+                    .collect(),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let swaps = vec![
+            (B256::with_last_byte(1), swap(QUOTE, 20, TOKEN_A, 10)),
+            (B256::with_last_byte(2), swap(QUOTE, 50, TOKEN_B, 10)),
+        ];
+        let price = |token| {
+            token_quote_price(
+                &cex_trades,
+                CexDexTradeConfig::default(),
+                &[CexExchange::Deribit],
+                QUOTE,
+                token,
+                TIMESTAMP,
+                &swaps,
+            )
+        };
+
+        assert_eq!(price(TOKEN_A), Some((Rational::from(2), Rational::from(2))));
+        assert_eq!(price(TOKEN_B), Some((Rational::from(5), Rational::from(5))));
+        assert_eq!(price(QUOTE), Some((Rational::ONE, Rational::ONE)));
+        // never swapped, so there is nothing to log the lookup against
+        assert_eq!(price(Address::with_last_byte(4)), None);
+    }
+}
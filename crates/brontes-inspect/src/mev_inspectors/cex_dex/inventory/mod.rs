@@ -0,0 +1,3 @@
+mod cex_dex_inventory;
+
+pub use cex_dex_inventory::CexDexInventoryInspector;
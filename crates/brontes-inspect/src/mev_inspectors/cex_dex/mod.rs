@@ -1,2 +1,3 @@
+pub mod inventory;
 pub mod markout;
 pub mod quotes;
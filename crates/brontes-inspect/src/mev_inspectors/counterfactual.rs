@@ -0,0 +1,342 @@
+//! Re-executes the blocks of a bundle without its mev txs to find what the
+//! victims of the bundle would have received if it wasn't included.
+
+use std::sync::Arc;
+
+use alloy_primitives::{b256, Address, Log, TxHash, B256, U256};
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_types::{
+    db::{dex::PriceAt, metadata::Metadata},
+    mev::VictimLossAmount,
+    normalized_actions::NormalizedSwap,
+    traits::{BlockSimulator, SimulatedTx},
+    FastHashMap, ToScaledRational, TxInfo,
+};
+use itertools::Itertools;
+use malachite::{num::basic::traits::Zero, Rational};
+
+use crate::shared_utils::SharedInspectorUtils;
+
+const TRANSFER_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
+    /// Re-executes the blocks of the victims without the mev txs and compares
+    /// the amount of the output token each victim receives with the amount it
+    /// actually received.
+    ///
+    /// Victims that don't have a mev tx before them in their block are
+    /// skipped, removing the mev txs of a previous block would require
+    /// re-executing every block in between.
+    pub fn victim_losses<'a>(
+        &self,
+        mev_txs: impl IntoIterator<Item = &'a TxInfo>,
+        victims: impl IntoIterator<Item = (&'a TxInfo, &'a [NormalizedSwap])>,
+        metadata: &Arc<Metadata>,
+    ) -> Vec<VictimLossAmount> {
+        let Some(simulator) = self.simulator else { return vec![] };
+
+        let mev_txs = mev_txs.into_iter().collect_vec();
+        let victims_by_block = victims
+            .into_iter()
+            .filter(|(victim, _)| {
+                mev_txs.iter().any(|mev| {
+                    mev.block_number == victim.block_number && mev.tx_index < victim.tx_index
+                })
+            })
+            .into_group_map_by(|(victim, _)| victim.block_number);
+
+        victims_by_block
+            .into_iter()
+            .sorted_by_key(|(block, _)| *block)
+            .flat_map(|(block, victims)| {
+                let removed = mev_txs
+                    .iter()
+                    .filter(|mev| mev.block_number == block)
+                    .map(|mev| mev.tx_hash)
+                    .collect_vec();
+                let last_victim = victims
+                    .iter()
+                    .map(|(victim, _)| victim.tx_index)
+                    .max()
+                    .unwrap_or_default();
+
+                let simulated = match simulate_without(simulator, block, &removed, last_victim) {
+                    Ok(simulated) => simulated,
+                    Err(e) => {
+                        tracing::debug!(
+                            target: "brontes_inspect::counterfactual",
+                            %block,
+                            err = %e,
+                            "failed to re-execute block without the mev txs"
+                        );
+                        return vec![]
+                    }
+                };
+
+                victims
+                    .into_iter()
+                    .filter_map(|(victim, swaps)| {
+                        self.victim_loss(victim, swaps, simulated.get(&victim.tx_hash)?, metadata)
+                    })
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    fn victim_loss(
+        &self,
+        victim: &TxInfo,
+        swaps: &[NormalizedSwap],
+        simulated: &SimulatedTx,
+        metadata: &Arc<Metadata>,
+    ) -> Option<VictimLossAmount> {
+        // the victim's tx would have reverted, so there is nothing to compare with
+        if !simulated.is_success {
+            return None
+        }
+
+        // the token the victim ends up with is the output of the last swap
+        let last_swap = swaps.last()?;
+        let token = last_swap.token_out.clone();
+        let recipient = last_swap.recipient;
+
+        let received = swaps
+            .iter()
+            .filter(|swap| swap.token_out.address == token.address && swap.recipient == recipient)
+            .fold(Rational::ZERO, |acc, swap| acc + &swap.amount_out);
+
+        let transfers = simulated
+            .logs
+            .iter()
+            .filter_map(decode_transfer)
+            .filter(|(transfer_token, to, _)| *transfer_token == token.address && *to == recipient)
+            .collect_vec();
+
+        // native eth outputs and outputs sent through another contract don't
+        // emit a transfer to the recipient, so we can't tell what it would have
+        // received
+        if transfers.is_empty() {
+            return None
+        }
+
+        let counterfactual = transfers
+            .into_iter()
+            .fold(Rational::ZERO, |acc, (_, _, amount)| {
+                acc + amount.to_scaled_rational(token.decimals)
+            });
+
+        let token_amount_lost = counterfactual - received;
+        // we only have the pricing of the most recent block
+        let tx_index =
+            if victim.block_number == metadata.block_num { victim.tx_index as usize } else { 0 };
+        let amount_lost_usd = self
+            .get_token_value_dex(
+                tx_index,
+                PriceAt::Before,
+                token.address,
+                &token_amount_lost,
+                metadata,
+            )
+            .unwrap_or_default();
+
+        Some(VictimLossAmount {
+            tx_hash: victim.tx_hash,
+            victim_eoa: victim.eoa,
+            token,
+            token_amount_lost,
+            amount_lost_usd,
+        })
+    }
+}
+
+/// Executes the txs of the block up to and including `last_tx_index`,
+/// skipping the removed txs
+fn simulate_without(
+    simulator: &dyn BlockSimulator,
+    block: u64,
+    removed: &[TxHash],
+    last_tx_index: u64,
+) -> eyre::Result<FastHashMap<TxHash, SimulatedTx>> {
+    let tx_hashes = simulator
+        .block_tx_hashes(block)?
+        .into_iter()
+        .take(last_tx_index as usize + 1)
+        .filter(|tx_hash| !removed.contains(tx_hash))
+        .collect_vec();
+
+    Ok(simulator
+        .simulate_block(block, &tx_hashes)?
+        .into_iter()
+        .map(|tx| (tx.tx_hash, tx))
+        .collect())
+}
+
+/// Returns the token, recipient and amount of an erc20 transfer log
+fn decode_transfer(log: &Log) -> Option<(Address, Address, U256)> {
+    let topics = log.topics();
+    if topics.len() != 3 || topics[0] != TRANSFER_TOPIC {
+        return None
+    }
+
+    let to = Address::from_slice(&topics[2][12..]);
+    let amount = U256::try_from_be_slice(&log.data.data[..])?;
+
+    Some((log.address, to, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Bytes, LogData};
+    use brontes_core::test_utils::get_db_handle;
+    use brontes_types::{
+        constants::USDT_ADDRESS, db::token_info::TokenInfoWithAddress, GasDetails,
+    };
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct StubSimulator {
+        tx_hashes: Vec<TxHash>,
+        /// usdt each tx receives when simulated
+        received:  FastHashMap<TxHash, (Address, u64)>,
+        reverted:  Vec<TxHash>,
+    }
+
+    impl BlockSimulator for StubSimulator {
+        fn block_tx_hashes(&self, _: u64) -> eyre::Result<Vec<TxHash>> {
+            Ok(self.tx_hashes.clone())
+        }
+
+        fn simulate_block(&self, _: u64, tx_hashes: &[TxHash]) -> eyre::Result<Vec<SimulatedTx>> {
+            Ok(tx_hashes
+                .iter()
+                .map(|tx_hash| SimulatedTx {
+                    tx_hash:    *tx_hash,
+                    is_success: !self.reverted.contains(tx_hash),
+                    gas_used:   0,
+                    logs:       self
+                        .received
+                        .get(tx_hash)
+                        .map(|(to, amount)| transfer_log(USDT_ADDRESS, *to, *amount))
+                        .into_iter()
+                        .collect(),
+                })
+                .collect())
+        }
+    }
+
+    fn transfer_log(token: Address, to: Address, amount: u64) -> Log {
+        Log {
+            address: token,
+            data:    LogData::new_unchecked(
+                vec![TRANSFER_TOPIC, B256::ZERO, to.into_word()],
+                Bytes::from(U256::from(amount).to_be_bytes_vec()),
+            ),
+        }
+    }
+
+    fn tx_info(tx_index: u64, eoa: Address) -> TxInfo {
+        TxInfo::new(
+            1,
+            tx_index,
+            eoa,
+            None,
+            None,
+            B256::with_last_byte(tx_index as u8),
+            GasDetails::default(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            None,
+            vec![],
+        )
+    }
+
+    /// a victim swap that received `amount_out` usdt
+    fn swap_to(recipient: Address, amount_out: u64) -> NormalizedSwap {
+        NormalizedSwap {
+            recipient,
+            token_out: TokenInfoWithAddress::usdt(),
+            amount_out: Rational::from(amount_out),
+            ..Default::default()
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_victim_losses() {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+
+        let front = tx_info(0, Address::with_last_byte(1));
+        let victim = tx_info(1, Address::with_last_byte(2));
+        let no_transfer = tx_info(2, Address::with_last_byte(3));
+        let reverted = tx_info(3, Address::with_last_byte(4));
+        let back = tx_info(4, Address::with_last_byte(1));
+
+        let simulator = StubSimulator {
+            tx_hashes: vec![
+                front.tx_hash,
+                victim.tx_hash,
+                no_transfer.tx_hash,
+                reverted.tx_hash,
+                back.tx_hash,
+            ],
+            // usdt has 6 decimals, the victim would have received 105 usdt
+            received:  FastHashMap::from_iter([
+                (victim.tx_hash, (victim.eoa, 105_000_000)),
+                (reverted.tx_hash, (reverted.eoa, 1_000_000)),
+            ]),
+            reverted:  vec![reverted.tx_hash],
+        };
+        let utils =
+            SharedInspectorUtils::new(USDT_ADDRESS, db, None).with_simulator(Some(&simulator));
+
+        let victim_swaps = [swap_to(victim.eoa, 100)];
+        let no_transfer_swaps = [swap_to(no_transfer.eoa, 100)];
+        let reverted_swaps = [swap_to(reverted.eoa, 100)];
+
+        let losses = utils.victim_losses(
+            [&front, &back],
+            [
+                (&victim, &victim_swaps[..]),
+                (&no_transfer, &no_transfer_swaps[..]),
+                (&reverted, &reverted_swaps[..]),
+            ],
+            &Arc::new(Metadata::default()),
+        );
+
+        assert_eq!(losses.len(), 1);
+        assert_eq!(losses[0].tx_hash, victim.tx_hash);
+        assert_eq!(losses[0].victim_eoa, victim.eoa);
+        assert_eq!(losses[0].token_amount_lost, Rational::from(5));
+        // usdt is the quote so it is valued one to one
+        assert_eq!(losses[0].amount_lost_usd, Rational::from(5));
+    }
+
+    #[brontes_macros::test]
+    async fn test_victim_losses_skips_victims_before_mev() {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+
+        let victim = tx_info(0, Address::with_last_byte(2));
+        let backrun = tx_info(1, Address::with_last_byte(1));
+
+        let simulator = StubSimulator {
+            tx_hashes: vec![victim.tx_hash, backrun.tx_hash],
+            received:  FastHashMap::from_iter([(victim.tx_hash, (victim.eoa, 105_000_000))]),
+            reverted:  vec![],
+        };
+        let utils =
+            SharedInspectorUtils::new(USDT_ADDRESS, db, None).with_simulator(Some(&simulator));
+
+        let victim_swaps = [swap_to(victim.eoa, 100)];
+        let losses = utils.victim_losses(
+            [&backrun],
+            [(&victim, &victim_swaps[..])],
+            &Arc::new(Metadata::default()),
+        );
+
+        assert!(losses.is_empty());
+    }
+}
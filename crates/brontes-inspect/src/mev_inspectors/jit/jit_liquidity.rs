@@ -10,6 +10,7 @@ use brontes_types::{
     normalized_actions::{
         accounting::ActionAccounting, NormalizedBurn, NormalizedCollect, NormalizedMint,
     },
+    traits::BlockSimulator,
     ActionIter, BlockData, FastHashMap, FastHashSet, GasDetails, MultiBlockData, ToFloatNearest,
     TreeSearchBuilder, TxInfo,
 };
@@ -31,6 +32,13 @@ impl<'db, DB: LibmdbxReader> JitInspector<'db, DB> {
     pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
         Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
     }
+
+    /// re-executes the blocks of found jits without the mint and burn to
+    /// calculate how the victims were affected
+    pub fn with_simulator(mut self, simulator: Option<&'db dyn BlockSimulator>) -> Self {
+        self.utils = self.utils.with_simulator(simulator);
+        self
+    }
 }
 
 impl<DB: LibmdbxReader> Inspector for JitInspector<'_, DB> {
@@ -231,9 +239,9 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
             .unzip();
 
         let (victim_hashes, victim_gas_details): (Vec<_>, Vec<_>) = victim_info
-            .into_iter()
+            .iter()
             .flatten()
-            .map(|info| info.split_to_storage_info())
+            .map(|info| info.clone().split_to_storage_info())
             .unzip();
 
         let bribe = self.get_bribes(metadata.clone(), &gas_details);
@@ -272,7 +280,7 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
             },
         );
 
-        let jit_details = self.build_jit_type(
+        let mut jit_details = self.build_jit_type(
             hashes,
             gas_details,
             metadata.block_num,
@@ -283,6 +291,14 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
             victim_gas_details,
             &victim_actions,
         )?;
+        jit_details.victim_losses = self.utils.victim_losses(
+            &info_set,
+            victim_info
+                .iter()
+                .flatten()
+                .zip(jit_details.victim_swaps.iter().map(Vec::as_slice)),
+            &metadata,
+        );
 
         Some(vec![Bundle { header, data: BundleData::Jit(jit_details) }])
     }
@@ -338,6 +354,7 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
                         .collect_vec()
                 })
                 .unwrap_or(burns),
+            victim_losses: vec![],
         })
     }
 
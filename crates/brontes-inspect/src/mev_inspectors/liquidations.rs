@@ -5,12 +5,14 @@ use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
     db::dex::PriceAt,
     mev::{Bundle, BundleData, Liquidation, MevType},
-    normalized_actions::{accounting::ActionAccounting, Action},
+    normalized_actions::{
+        accounting::ActionAccounting, Action, NormalizedLiquidation, NormalizedOracleUpdate,
+    },
     ActionIter, BlockData, FastHashSet, MultiBlockData, ToFloatNearest, TreeSearchBuilder, TxInfo,
 };
-use itertools::multizip;
+use itertools::{multizip, Itertools};
 use malachite::{num::basic::traits::Zero, Rational};
-use reth_primitives::{b256, Address};
+use reth_primitives::{Address, B256};
 
 use super::{MAX_PROFIT, MIN_PROFIT};
 use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
@@ -41,6 +43,21 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
         let BlockData { metadata, tree } = block;
 
         let ex = || {
+            let oracle_updates = tree
+                .clone()
+                .collect_all(TreeSearchBuilder::default().with_action(Action::is_oracle_update))
+                .filter_map(|(tx_hash, updates)| {
+                    let position = tree.get_root(tx_hash)?.position as u64;
+                    Some(
+                        updates
+                            .into_iter()
+                            .filter_map(Action::try_oracle_update)
+                            .map(move |update| (position, tx_hash, update)),
+                    )
+                })
+                .flatten()
+                .collect_vec();
+
             let (tx, liq): (Vec<_>, Vec<_>) = tree
                 .clone()
                 .collect_all(TreeSearchBuilder::default().with_actions([
@@ -61,7 +78,7 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
                         .flatten_nested_actions_default(liq.into_iter())
                         .collect::<Vec<_>>();
 
-                    self.calculate_liquidation(info, metadata.clone(), actions)
+                    self.calculate_liquidation(info, metadata.clone(), actions, &oracle_updates)
                 })
                 .collect::<Vec<_>>()
         };
@@ -78,6 +95,7 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
         info: TxInfo,
         metadata: Arc<Metadata>,
         actions: Vec<Action>,
+        oracle_updates: &[(u64, B256, NormalizedOracleUpdate)],
     ) -> Option<Bundle> {
         let (swaps, liqs): (Vec<_>, Vec<_>) = actions
             .clone()
@@ -89,6 +107,9 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
             return None
         }
 
+        let oracle_update_tx =
+            oracle_trigger(info.tx_index, &liqs, oracle_updates).unwrap_or_default();
+
         let mev_addresses: FastHashSet<Address> = info.collect_address_set_for_accounting();
 
         let deltas = actions
@@ -146,7 +167,7 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
         let new_liquidation = Liquidation {
             block_number:        metadata.block_num,
             liquidation_tx_hash: info.tx_hash,
-            trigger:             b256!(),
+            trigger:             oracle_update_tx,
             liquidation_swaps:   swaps,
             liquidations:        liqs,
             gas_details:         info.gas_details,
@@ -156,16 +177,181 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
     }
 }
 
+/// Finds the last oracle update before the liquidation in the block that
+/// prices one of the liquidated assets, which is the update the liquidation
+/// backruns
+fn oracle_trigger(
+    tx_index: u64,
+    liquidations: &[NormalizedLiquidation],
+    oracle_updates: &[(u64, B256, NormalizedOracleUpdate)],
+) -> Option<B256> {
+    let assets = liquidations
+        .iter()
+        .flat_map(|liq| [liq.collateral_asset.address, liq.debt_asset.address])
+        .collect_vec();
+
+    oracle_updates
+        .iter()
+        .rev()
+        .filter(|(position, ..)| *position < tx_index)
+        .find(|(.., update)| update.may_price_any(&assets))
+        .map(|(_, tx_hash, _)| *tx_hash)
+}
+
 #[cfg(test)]
 mod tests {
 
     use alloy_primitives::hex;
+    use brontes_core::test_utils::get_db_handle;
+    use brontes_types::{
+        constants::WETH_ADDRESS,
+        db::{
+            dex::{DexPrices, DexQuotes},
+            token_info::TokenInfoWithAddress,
+        },
+        normalized_actions::NormalizedTransfer,
+        pair::Pair,
+        FastHashMap, GasDetails, Protocol,
+    };
 
+    use super::*;
     use crate::{
         test_utils::{InspectorTestUtils, InspectorTxRunConfig, USDC_ADDRESS},
         Inspectors,
     };
 
+    /// Runs a liquidation in which the searcher's contract repays 2000 usdc of
+    /// debt for 1.1 weth of collateral, with weth at 2000 usdc
+    async fn run_liquidation(protocol: Protocol) -> Bundle {
+        let db = get_db_handle(tokio::runtime::Handle::current()).await;
+        let inspector = LiquidationInspector::new(USDC_ADDRESS, db, None);
+
+        let eoa = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(2);
+        let pool = Address::with_last_byte(3);
+        let debtor = Address::with_last_byte(4);
+
+        let info = TxInfo::new(
+            1,
+            0,
+            eoa,
+            Some(contract),
+            None,
+            B256::with_last_byte(1),
+            GasDetails::default(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            None,
+            vec![],
+        );
+        let liquidation = NormalizedLiquidation {
+            protocol,
+            trace_index: 1,
+            pool,
+            liquidator: contract,
+            debtor,
+            collateral_asset: TokenInfoWithAddress::weth(),
+            debt_asset: TokenInfoWithAddress::usdc(),
+            covered_debt: Rational::from(2000),
+            liquidated_collateral: Rational::from_unsigneds(11u32, 10u32),
+            ..Default::default()
+        };
+        let transfer = |trace_index, from, to, token, amount| {
+            Action::Transfer(NormalizedTransfer {
+                trace_index,
+                from,
+                to,
+                token,
+                amount,
+                ..Default::default()
+            })
+        };
+        let actions = vec![
+            Action::Liquidation(liquidation),
+            transfer(2, contract, pool, TokenInfoWithAddress::usdc(), Rational::from(2000)),
+            transfer(
+                3,
+                pool,
+                contract,
+                TokenInfoWithAddress::weth(),
+                Rational::from_unsigneds(11u32, 10u32),
+            ),
+        ];
+
+        let weth_price = DexPrices {
+            pre_state:             Rational::from(2000),
+            post_state:            Rational::from(2000),
+            pool_liquidity:        Rational::from(1_000_000),
+            goes_through:          Pair(WETH_ADDRESS, USDC_ADDRESS),
+            is_transfer:           false,
+            first_hop_connections: 1,
+        };
+        let mut prices = FastHashMap::default();
+        prices.insert(Pair(WETH_ADDRESS, USDC_ADDRESS), weth_price);
+        let metadata =
+            Arc::new(Metadata::default().into_full_metadata(DexQuotes(vec![Some(prices)])));
+
+        inspector
+            .calculate_liquidation(info, metadata, actions, &[])
+            .unwrap()
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v3_liquidation_profit() {
+        let bundle = run_liquidation(Protocol::CompoundV3).await;
+
+        assert_eq!(bundle.header.mev_type, MevType::Liquidation);
+        assert!((bundle.header.profit_usd - 200.0).abs() < 1e-9);
+        let BundleData::Liquidation(liquidation) = bundle.data else { panic!("not a liquidation") };
+        assert_eq!(liquidation.liquidations.len(), 1);
+        assert_eq!(liquidation.liquidations[0].protocol, Protocol::CompoundV3);
+    }
+
+    #[brontes_macros::test]
+    async fn test_morpho_blue_liquidation_profit() {
+        let bundle = run_liquidation(Protocol::MorphoBlue).await;
+
+        assert!((bundle.header.profit_usd - 200.0).abs() < 1e-9);
+        let BundleData::Liquidation(liquidation) = bundle.data else { panic!("not a liquidation") };
+        assert_eq!(liquidation.liquidations[0].protocol, Protocol::MorphoBlue);
+        assert_eq!(liquidation.liquidations[0].liquidator, Address::with_last_byte(2));
+    }
+
+    #[test]
+    fn test_oracle_trigger() {
+        let weth: Address = hex!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").into();
+        let wbtc: Address = hex!("2260fac5e5542a773aa44fbcfedf7c193bc2c599").into();
+        let token = |address| TokenInfoWithAddress { address, ..Default::default() };
+        let update = |position: u64, asset| {
+            (
+                position,
+                B256::with_last_byte(position as u8),
+                NormalizedOracleUpdate { asset, ..Default::default() },
+            )
+        };
+
+        let liquidation = NormalizedLiquidation {
+            collateral_asset: token(weth),
+            debt_asset: token(USDC_ADDRESS),
+            ..Default::default()
+        };
+        let updates = vec![update(1, Some(token(weth))), update(3, Some(token(wbtc)))];
+
+        // the wbtc update doesn't price the liquidated assets
+        assert_eq!(
+            oracle_trigger(5, &[liquidation.clone()], &updates),
+            Some(B256::with_last_byte(1))
+        );
+        // updates after the liquidation aren't backrun
+        assert_eq!(oracle_trigger(1, &[liquidation.clone()], &updates), None);
+        // an update of an unknown feed could price anything
+        let updates = vec![update(1, Some(token(weth))), update(2, None)];
+        assert_eq!(oracle_trigger(5, &[liquidation], &updates), Some(B256::with_last_byte(2)));
+    }
+
     #[brontes_macros::test]
     async fn test_aave_v3_liquidation() {
         let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 6.0).await;
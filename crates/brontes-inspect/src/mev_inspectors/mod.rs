@@ -1,5 +1,6 @@
 pub mod atomic_arb;
 pub mod cex_dex;
+pub mod counterfactual;
 
 pub mod jit;
 pub mod liquidations;
@@ -14,6 +14,7 @@ use brontes_types::{
     normalized_actions::{
         accounting::ActionAccounting, Action, NormalizedSwap, NormalizedTransfer,
     },
+    traits::BlockSimulator,
     tree::{collect_address_set_for_accounting, BlockTree, GasDetails},
     ActionIter, BlockData, FastHashMap, FastHashSet, IntoZipTree, MultiBlockData, ToFloatNearest,
     TreeBase, TreeCollector, TreeIter, TreeSearchBuilder, TxInfo, UnzipPadded,
@@ -36,19 +37,51 @@ type VictimSetActions = Option<Vec<Vec<(Vec<NormalizedSwap>, Vec<NormalizedTrans
 const MAX_PRICE_DIFF: Rational = Rational::const_from_unsigneds(995, 1000);
 const MAX_NON_SWAP_FRONTRUN: Rational = Rational::const_from_unsigned(5000);
 
+/// by default we look for sandwiches that have the frontrun at the end of the
+/// previous block and the backrun at the top of the current block
+pub const DEFAULT_SANDWICH_BLOCK_WINDOW: usize = 2;
+
 pub struct SandwichInspector<'db, DB: LibmdbxReader> {
-    utils: SharedInspectorUtils<'db, DB>,
+    utils:        SharedInspectorUtils<'db, DB>,
+    block_window: usize,
 }
 
 impl<'db, DB: LibmdbxReader> SandwichInspector<'db, DB> {
     pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
-        Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
+        Self {
+            utils:        SharedInspectorUtils::new(quote, db, metrics),
+            block_window: DEFAULT_SANDWICH_BLOCK_WINDOW,
+        }
+    }
+
+    /// sets the amount of blocks a sandwich can span. A window of 1 only
+    /// looks for sandwiches within a single block
+    pub fn with_block_window(mut self, block_window: usize) -> Self {
+        self.block_window = block_window.max(1);
+        self
+    }
+
+    /// re-executes the blocks of found sandwiches without the frontruns and
+    /// backrun to calculate the losses of the victims
+    pub fn with_simulator(mut self, simulator: Option<&'db dyn BlockSimulator>) -> Self {
+        self.utils = self.utils.with_simulator(simulator);
+        self
     }
 }
 
 impl<DB: LibmdbxReader> Inspector for SandwichInspector<'_, DB> {
     type Result = Vec<Bundle>;
 
+    fn block_window(&self) -> usize {
+        self.block_window
+    }
+
+    // single block sandwiches don't need the previous blocks, so we don't
+    // want to miss them while the window is filling up
+    fn min_block_window(&self) -> usize {
+        1
+    }
+
     fn get_id(&self) -> &str {
         "Sandwich"
     }
@@ -58,32 +91,38 @@ impl<DB: LibmdbxReader> Inspector for SandwichInspector<'_, DB> {
     }
 
     fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
-        let BlockData { metadata, tree } = data.get_most_recent_block();
+        let execution = || {
+            let BlockData { metadata, tree } = data.get_most_recent_block();
+
+            let mut res = self.inspect_block_inner(tree.clone(), metadata.clone());
+            res.extend(self.inspect_block_boundaries(&data));
+            res
+        };
 
         self.utils
             .get_metrics()
-            .map(|m| {
-                m.run_inspector(MevType::Sandwich, || {
-                    self.inspect_block_inner(tree.clone(), metadata.clone())
-                })
-            })
-            .unwrap_or_else(|| self.inspect_block_inner(tree.clone(), metadata.clone()))
+            .map(|m| m.run_inspector(MevType::Sandwich, execution))
+            .unwrap_or_else(&execution)
     }
 }
 
 impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
+    fn search_args() -> TreeSearchBuilder<Action> {
+        TreeSearchBuilder::default().with_actions([
+            Action::is_swap,
+            Action::is_transfer,
+            Action::is_eth_transfer,
+            Action::is_nested_action,
+        ])
+    }
+
     fn inspect_block_inner(
         &self,
         tree: Arc<BlockTree<Action>>,
         metadata: Arc<Metadata>,
     ) -> Vec<Bundle> {
         tracing::trace!("starting sandwich");
-        let search_args = TreeSearchBuilder::default().with_actions([
-            Action::is_swap,
-            Action::is_transfer,
-            Action::is_eth_transfer,
-            Action::is_nested_action,
-        ]);
+        let search_args = Self::search_args();
 
         self.get_possible_sandwich(tree.clone())
             .into_iter()
@@ -99,6 +138,78 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             .collect::<Vec<_>>()
     }
 
+    /// Looks for sandwiches where the frontrun lands in one of the previous
+    /// blocks of the window and the backrun lands in the most recent block.
+    /// Sandwiches that are fully contained in a previous block were already
+    /// found when that block was inspected.
+    fn inspect_block_boundaries(&self, data: &MultiBlockData) -> Vec<Bundle> {
+        let Some((BlockData { metadata, .. }, _)) = data.per_block_data.split_last() else {
+            return vec![]
+        };
+        let trees = data
+            .per_block_data
+            .iter()
+            .map(|block| block.tree.clone())
+            .collect_vec();
+        let search_args = Self::search_args();
+
+        (0..trees.len().saturating_sub(1))
+            .filter_map(|start| self.get_possible_sandwich_with_info_across_blocks(&trees[start..]))
+            .flat_map(|(tree, possible_sandwiches)| {
+                possible_sandwiches
+                    .into_iter()
+                    .filter_map(|ps| {
+                        self.collect_baseline_sandwich_data(
+                            tree.clone(),
+                            search_args.clone(),
+                            ps,
+                            metadata.clone(),
+                        )
+                    })
+                    .flatten()
+                    .collect_vec()
+            })
+            .collect::<Vec<_>>()
+    }
+
+    /// Finds the sandwiches spanning the given blocks and fetches the tx info
+    /// of their transactions.
+    fn get_possible_sandwich_with_info_across_blocks(
+        &self,
+        trees: &[Arc<BlockTree<Action>>],
+    ) -> Option<(Arc<BlockTree<Action>>, Vec<PossibleSandwichWithTxInfo>)> {
+        let (tree, possible_sandwiches) = get_possible_sandwich_across_blocks(trees)?;
+
+        let tx_set = possible_sandwiches
+            .iter()
+            .flat_map(|ps| {
+                ps.possible_frontruns
+                    .iter()
+                    .chain(std::iter::once(&ps.possible_backrun))
+                    .chain(ps.victims.iter().flatten())
+                    .copied()
+            })
+            .unique()
+            .collect_vec();
+
+        // tx info is fetched from the original trees so that each transaction
+        // keeps the block it was included in
+        let tx_info_map = trees
+            .iter()
+            .flat_map(|tree| tree.get_tx_info_batch(&tx_set, self.utils.db))
+            .flatten()
+            .map(|info| (info.tx_hash, info))
+            .collect::<FastHashMap<_, _>>();
+
+        Some((
+            tree,
+            possible_sandwiches
+                .into_iter()
+                .filter_map(|ps| PossibleSandwichWithTxInfo::from_ps(ps, &tx_info_map))
+                .collect_vec(),
+        ))
+    }
+
     fn collect_baseline_sandwich_data(
         &self,
         tree: Arc<BlockTree<Action>>,
@@ -312,6 +423,11 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         // ensure valid pricing
         let mut has_dex_price = true;
         for (swaps, info) in front_run_swaps.iter().zip(&possible_front_runs_info) {
+            // we only have the pricing of the backrun block. For frontruns in a
+            // previous block we use the prices at the top of the backrun block
+            // as they are the closest to the state the frontrun executed on.
+            let tx_index =
+                if info.block_number == metadata.block_num { info.tx_index as usize } else { 0 };
             has_dex_price &= self.utils.valid_pricing(
                 metadata.clone(),
                 swaps,
@@ -323,7 +439,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
                             .map(|(k, _)| k)
                     })
                     .unique(),
-                info.tx_index as usize,
+                tx_index,
                 MAX_PRICE_DIFF,
                 MevType::Sandwich,
             );
@@ -420,9 +536,23 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         );
 
         let victim_swaps = victim_swaps.into_iter().map(|(s, _)| s).collect_vec();
+        let victim_losses = self.utils.victim_losses(
+            possible_front_runs_info
+                .iter()
+                .chain(std::iter::once(&backrun_info)),
+            victim_info
+                .iter()
+                .flatten()
+                .zip(victim_swaps.iter().map(Vec::as_slice)),
+            &metadata,
+        );
 
         let sandwich = Sandwich {
             block_number: metadata.block_num,
+            frontrun_block_number: possible_front_runs_info
+                .first()
+                .map(|info| info.block_number)
+                .unwrap_or(metadata.block_num),
             frontrun_tx_hash,
             frontrun_gas_details,
             frontrun_swaps: front_run_swaps,
@@ -432,6 +562,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             backrun_tx_hash: backrun_info.tx_hash,
             backrun_swaps: back_run_swaps,
             backrun_gas_details: backrun_info.gas_details,
+            victim_losses,
         };
         tracing::debug!("{:#?}\n{:#?}", header, sandwich);
 
@@ -1066,6 +1197,117 @@ fn get_possible_sandwich_duplicate_senders(tree: Arc<BlockTree<Action>>) -> Vec<
     possible_sandwiches.into_values().collect()
 }
 
+/// Pairs the transaction of a searcher in the first block with their
+/// transaction in the last block. We only consider searchers that have a
+/// single transaction in both blocks and none in the blocks in between,
+/// otherwise we can't tell the pair apart from single block sandwiches.
+///
+/// As the rest of the inspector works on a single tree, the transactions
+/// between the frontrun and backrun are merged into one tree.
+fn get_possible_sandwich_across_blocks(
+    trees: &[Arc<BlockTree<Action>>],
+) -> Option<(Arc<BlockTree<Action>>, Vec<PossibleSandwich>)> {
+    let (first, rest) = trees.split_first()?;
+    let (last, between) = rest.split_last()?;
+
+    let in_between = between
+        .iter()
+        .flat_map(|tree| tree.tx_roots.iter())
+        .flat_map(|root| [root.head.address, root.get_to_address()])
+        .collect::<FastHashSet<_>>();
+
+    let first_searchers = get_single_tx_searchers(first);
+    let last_searchers = get_single_tx_searchers(last);
+
+    let mut frontrun_start = first.tx_roots.len();
+    let mut backrun_end = 0;
+
+    let possible_sandwiches = first_searchers
+        .into_iter()
+        .filter(|(searcher, _)| !in_between.contains(searcher))
+        .filter_map(|(searcher, frontrun_idx)| {
+            Some((frontrun_idx, *last_searchers.get(&searcher)?))
+        })
+        .unique()
+        .filter_map(|(frontrun_idx, backrun_idx)| {
+            let frontrun = &first.tx_roots[frontrun_idx];
+            let backrun = &last.tx_roots[backrun_idx];
+
+            let victims = first.tx_roots[frontrun_idx + 1..]
+                .iter()
+                .chain(between.iter().flat_map(|tree| tree.tx_roots.iter()))
+                .chain(last.tx_roots[..backrun_idx].iter())
+                .filter(|root| !root.get_root_action().is_revert())
+                .map(|root| root.tx_hash)
+                .collect_vec();
+
+            if victims.is_empty() || victims.len() > 30 {
+                return None
+            }
+
+            frontrun_start = frontrun_start.min(frontrun_idx);
+            backrun_end = backrun_end.max(backrun_idx + 1);
+
+            Some(PossibleSandwich {
+                eoa:                   frontrun.head.address,
+                possible_frontruns:    vec![frontrun.tx_hash],
+                possible_backrun:      backrun.tx_hash,
+                mev_executor_contract: frontrun.get_to_address(),
+                victims:               vec![victims],
+            })
+        })
+        .collect_vec();
+
+    if possible_sandwiches.is_empty() {
+        return None
+    }
+
+    let tx_roots = first.tx_roots[frontrun_start..]
+        .iter()
+        .chain(between.iter().flat_map(|tree| tree.tx_roots.iter()))
+        .chain(last.tx_roots[..backrun_end].iter())
+        .cloned()
+        .collect_vec();
+    let tree = Arc::new(BlockTree {
+        tx_roots,
+        header: last.header.clone(),
+        priority_fee_std_dev: last.priority_fee_std_dev,
+        avg_priority_fee: last.avg_priority_fee,
+    });
+
+    Some((tree, possible_sandwiches))
+}
+
+/// Maps each eoa and contract that is only called once in the block to the
+/// index of that transaction.
+fn get_single_tx_searchers(tree: &BlockTree<Action>) -> FastHashMap<Address, usize> {
+    let mut searchers: FastHashMap<Address, Option<usize>> = FastHashMap::default();
+
+    for (idx, root) in tree.tx_roots.iter().enumerate() {
+        if root.get_root_action().is_revert() {
+            continue
+        }
+
+        for searcher in [root.head.address, root.get_to_address()] {
+            match searchers.entry(searcher) {
+                Entry::Vacant(v) => {
+                    v.insert(Some(idx));
+                }
+                Entry::Occupied(mut o) => {
+                    if *o.get() != Some(idx) {
+                        o.insert(None);
+                    }
+                }
+            }
+        }
+    }
+
+    searchers
+        .into_iter()
+        .filter_map(|(searcher, idx)| Some((searcher, idx?)))
+        .collect()
+}
+
 /// This function iterates through the block tree to identify potential
 /// sandwiches by looking for a contract that is involved in multiple
 /// transactions within a block.
@@ -1529,4 +1771,241 @@ mod tests {
 
         inspector_util.run_inspector(config, None).await.unwrap();
     }
+
+    #[brontes_macros::test]
+    async fn test_default_block_window() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+        let inspector =
+            SandwichInspector::new(USDC_ADDRESS, inspector_util.classifier_inspector.libmdbx, None);
+
+        assert_eq!(inspector.block_window(), DEFAULT_SANDWICH_BLOCK_WINDOW);
+        assert_eq!(inspector.block_window(), 2);
+        // single block sandwiches are searched while the window is filling up
+        assert_eq!(inspector.min_block_window(), 1);
+        assert_eq!(inspector.with_block_window(0).block_window(), 1);
+    }
+
+    #[brontes_macros::test]
+    async fn test_two_block_sandwich() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        // the frontrun and victim of `test_low_profit_sandwich1` land in the
+        // block before the backrun
+        let config = InspectorTxRunConfig::new(Inspectors::Sandwich)
+            .with_dex_prices()
+            .with_mev_tx_hashes(vec![
+                hex!("73003ef0efa2d7fea8b54418d58c529fe02dfa7f074c792f608c52028671c0ee").into(),
+                hex!("9a52628d5f1b4129ee85768cf96477824c158ebce48b4331ab4f89de28a39ef1").into(),
+                hex!("a46bfbd85fbcaf8450879d73f27436bf942078e5762af68bc10757745b5e1c9a").into(),
+            ])
+            .with_txes_in_previous_block(2)
+            .needs_tokens(vec![
+                WETH_ADDRESS,
+                hex!("8390a1da07e376ef7add4be859ba74fb83aa02d5").into(),
+            ])
+            .with_gas_paid_usd(16.57)
+            .with_expected_profit_usd(0.001);
+
+        inspector_util
+            .run_inspector(
+                config,
+                Some(Box::new(|bundle: &Bundle| {
+                    let BundleData::Sandwich(ref sando) = bundle.data else {
+                        panic!("expected a sandwich");
+                    };
+                    assert_eq!(sando.block_span(), 2);
+                    assert_eq!(sando.frontrun_block_number + 1, sando.block_number);
+                    assert_eq!(
+                        sando.frontrun_tx_hash,
+                        vec![B256::from(hex!(
+                            "73003ef0efa2d7fea8b54418d58c529fe02dfa7f074c792f608c52028671c0ee"
+                        ))]
+                    );
+                    assert_eq!(
+                        sando.backrun_tx_hash,
+                        B256::from(hex!(
+                            "a46bfbd85fbcaf8450879d73f27436bf942078e5762af68bc10757745b5e1c9a"
+                        ))
+                    );
+                })),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[brontes_macros::test]
+    async fn test_single_block_sandwich_with_block_window() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        // the previous block is empty, the sandwich must only be found once
+        let config = InspectorTxRunConfig::new(Inspectors::Sandwich)
+            .with_dex_prices()
+            .with_mev_tx_hashes(vec![
+                hex!("73003ef0efa2d7fea8b54418d58c529fe02dfa7f074c792f608c52028671c0ee").into(),
+                hex!("9a52628d5f1b4129ee85768cf96477824c158ebce48b4331ab4f89de28a39ef1").into(),
+                hex!("a46bfbd85fbcaf8450879d73f27436bf942078e5762af68bc10757745b5e1c9a").into(),
+            ])
+            .with_txes_in_previous_block(0)
+            .needs_tokens(vec![
+                WETH_ADDRESS,
+                hex!("8390a1da07e376ef7add4be859ba74fb83aa02d5").into(),
+            ])
+            .with_gas_paid_usd(16.57)
+            .with_expected_profit_usd(0.001);
+
+        inspector_util
+            .run_inspector(
+                config,
+                Some(Box::new(|bundle: &Bundle| {
+                    let BundleData::Sandwich(ref sando) = bundle.data else {
+                        panic!("expected a sandwich");
+                    };
+                    assert_eq!(sando.block_span(), 1);
+                    assert_eq!(sando.frontrun_block_number, sando.block_number);
+                })),
+            )
+            .await
+            .unwrap();
+    }
+
+    mod across_blocks {
+        use brontes_types::{
+            normalized_actions::NormalizedEthTransfer,
+            tree::{Node, NodeData, Root},
+        };
+        use reth_primitives::Header;
+
+        use super::super::*;
+
+        const SEARCHER: Address = Address::with_last_byte(1);
+        const SEARCHER_CONTRACT: Address = Address::with_last_byte(2);
+
+        fn tx(tx_hash: u8, from: Address, to: Address) -> Root<Action> {
+            let action =
+                Action::EthTransfer(NormalizedEthTransfer { from, to, ..Default::default() });
+
+            Root {
+                head: Node::new(0, from, vec![]),
+                position: 0,
+                tx_hash: B256::with_last_byte(tx_hash),
+                private: false,
+                gas_details: GasDetails::default(),
+                total_msg_value_transfers: vec![],
+                data_store: NodeData(vec![Some(vec![action])]),
+            }
+        }
+
+        fn searcher_tx(tx_hash: u8) -> Root<Action> {
+            tx(tx_hash, SEARCHER, SEARCHER_CONTRACT)
+        }
+
+        fn victim_tx(tx_hash: u8) -> Root<Action> {
+            tx(
+                tx_hash,
+                Address::with_last_byte(100 + tx_hash),
+                Address::with_last_byte(200 + tx_hash),
+            )
+        }
+
+        fn block(number: u64, tx_roots: Vec<Root<Action>>) -> Arc<BlockTree<Action>> {
+            Arc::new(BlockTree {
+                tx_roots,
+                header: Header { number, ..Default::default() },
+                priority_fee_std_dev: 0.0,
+                avg_priority_fee: 0.0,
+            })
+        }
+
+        fn tx_hashes(tree: &BlockTree<Action>) -> Vec<B256> {
+            tree.tx_roots.iter().map(|root| root.tx_hash).collect()
+        }
+
+        #[test]
+        fn test_pairs_frontrun_with_backrun_in_next_block() {
+            let trees = [
+                block(10, vec![victim_tx(1), searcher_tx(2), victim_tx(3)]),
+                block(11, vec![victim_tx(4), searcher_tx(5), victim_tx(6)]),
+            ];
+
+            let (tree, possible_sandwiches) = get_possible_sandwich_across_blocks(&trees).unwrap();
+
+            assert_eq!(
+                possible_sandwiches,
+                vec![PossibleSandwich {
+                    eoa:                   SEARCHER,
+                    possible_frontruns:    vec![B256::with_last_byte(2)],
+                    possible_backrun:      B256::with_last_byte(5),
+                    mev_executor_contract: SEARCHER_CONTRACT,
+                    victims:               vec![vec![
+                        B256::with_last_byte(3),
+                        B256::with_last_byte(4)
+                    ]],
+                }]
+            );
+
+            // the merged tree spans from the frontrun to the backrun and takes
+            // the header of the backrun block
+            assert_eq!(tx_hashes(&tree), [2, 3, 4, 5].map(B256::with_last_byte).to_vec());
+            assert_eq!(tree.header.number, 11);
+        }
+
+        #[test]
+        fn test_merges_blocks_in_between() {
+            let trees = [
+                block(10, vec![searcher_tx(1)]),
+                block(11, vec![victim_tx(2), victim_tx(3)]),
+                block(12, vec![searcher_tx(4), victim_tx(5)]),
+            ];
+
+            let (tree, possible_sandwiches) = get_possible_sandwich_across_blocks(&trees).unwrap();
+
+            assert_eq!(possible_sandwiches.len(), 1);
+            assert_eq!(
+                possible_sandwiches[0].victims,
+                vec![vec![B256::with_last_byte(2), B256::with_last_byte(3)]]
+            );
+            assert_eq!(tx_hashes(&tree), [1, 2, 3, 4].map(B256::with_last_byte).to_vec());
+            assert_eq!(tree.header.number, 12);
+        }
+
+        #[test]
+        fn test_no_pair_without_victims() {
+            let trees = [
+                block(10, vec![victim_tx(1), searcher_tx(2)]),
+                block(11, vec![searcher_tx(3), victim_tx(4)]),
+            ];
+
+            assert!(get_possible_sandwich_across_blocks(&trees).is_none());
+        }
+
+        #[test]
+        fn test_no_pair_when_searcher_trades_in_between() {
+            let trees = [
+                block(10, vec![searcher_tx(1), victim_tx(2)]),
+                block(11, vec![victim_tx(3), searcher_tx(4)]),
+                block(12, vec![victim_tx(5), searcher_tx(6)]),
+            ];
+
+            assert!(get_possible_sandwich_across_blocks(&trees).is_none());
+        }
+
+        #[test]
+        fn test_no_pair_with_multiple_searcher_txes() {
+            // with two txes in the first block the searcher could be running a
+            // single block sandwich, so we don't pair it across blocks
+            let trees = [
+                block(10, vec![searcher_tx(1), victim_tx(2), searcher_tx(3), victim_tx(4)]),
+                block(11, vec![victim_tx(5), searcher_tx(6)]),
+            ];
+
+            assert!(get_possible_sandwich_across_blocks(&trees).is_none());
+        }
+
+        #[test]
+        fn test_no_pair_within_a_single_block() {
+            let trees = [block(10, vec![searcher_tx(1), victim_tx(2), searcher_tx(3)])];
+
+            assert!(get_possible_sandwich_across_blocks(&trees).is_none());
+        }
+    }
 }
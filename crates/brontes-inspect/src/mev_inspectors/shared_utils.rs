@@ -18,6 +18,7 @@ use brontes_types::{
         NormalizedTransfer,
     },
     pair::Pair,
+    traits::BlockSimulator,
     utils::ToFloatNearest,
     ActionIter, FastHashMap, FastHashSet, GasDetails, TxInfo,
 };
@@ -36,14 +37,22 @@ const LOW_LIQ_TH: Rational = Rational::const_from_unsigned(50_000u64);
 
 #[derive(Debug)]
 pub struct SharedInspectorUtils<'db, DB: LibmdbxReader> {
-    pub(crate) quote: Address,
-    pub(crate) db:    &'db DB,
-    pub metrics:      Option<OutlierMetrics>,
+    pub(crate) quote:     Address,
+    pub(crate) db:        &'db DB,
+    pub metrics:          Option<OutlierMetrics>,
+    /// used to re-execute blocks without the mev txs, victim losses aren't
+    /// calculated if it isn't set
+    pub(crate) simulator: Option<&'db dyn BlockSimulator>,
 }
 
 impl<'db, DB: LibmdbxReader> SharedInspectorUtils<'db, DB> {
     pub fn new(quote_address: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
-        SharedInspectorUtils { quote: quote_address, db, metrics }
+        SharedInspectorUtils { quote: quote_address, db, metrics, simulator: None }
+    }
+
+    pub fn with_simulator(mut self, simulator: Option<&'db dyn BlockSimulator>) -> Self {
+        self.simulator = simulator;
+        self
     }
 }
 type TokenDeltas = FastHashMap<Address, Rational>;
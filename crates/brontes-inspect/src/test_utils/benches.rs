@@ -12,7 +12,7 @@ use brontes_types::{
 use criterion::{black_box, Criterion};
 
 use super::InspectorTestUtilsError;
-use crate::{composer::run_block_inspection, Inspectors};
+use crate::{composer::run_block_inspection, sandwich::DEFAULT_SANDWICH_BLOCK_WINDOW, Inspectors};
 
 pub struct InspectorBenchUtils {
     classifier_inspector: ClassifierTestUtils,
@@ -48,6 +48,8 @@ impl InspectorBenchUtils {
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
+                    DEFAULT_SANDWICH_BLOCK_WINDOW,
+                    None,
                     None,
                 )
             })
@@ -99,6 +101,8 @@ impl InspectorBenchUtils {
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
+            DEFAULT_SANDWICH_BLOCK_WINDOW,
+            None,
             None,
         );
 
@@ -162,6 +166,8 @@ impl InspectorBenchUtils {
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
+            DEFAULT_SANDWICH_BLOCK_WINDOW,
+            None,
             None,
         );
 
@@ -216,6 +222,8 @@ impl InspectorBenchUtils {
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
+            DEFAULT_SANDWICH_BLOCK_WINDOW,
+            None,
             None,
         );
 
@@ -263,6 +271,8 @@ impl InspectorBenchUtils {
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
+                    DEFAULT_SANDWICH_BLOCK_WINDOW,
+                    None,
                     None,
                 )
             })
@@ -322,6 +332,8 @@ impl InspectorBenchUtils {
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
+                    DEFAULT_SANDWICH_BLOCK_WINDOW,
+                    None,
                     None,
                 )
             })
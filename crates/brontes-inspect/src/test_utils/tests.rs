@@ -38,7 +38,10 @@ use brontes_types::{
 };
 use thiserror::Error;
 
-use crate::{composer::run_block_inspection, shared_utils::SharedInspectorUtils, Inspectors};
+use crate::{
+    composer::run_block_inspection, sandwich::DEFAULT_SANDWICH_BLOCK_WINDOW,
+    shared_utils::SharedInspectorUtils, Inspectors,
+};
 
 type StateTests = Option<Box<dyn for<'a> Fn(&'a Bundle)>>;
 
@@ -168,6 +171,8 @@ impl InspectorTestUtils {
                 CexExchange::Kucoin,
             ],
             CexDexTradeConfig::default(),
+            DEFAULT_SANDWICH_BLOCK_WINDOW,
+            None,
             None,
         );
         let data = BlockData { metadata: metadata.into(), tree: tree.into() };
@@ -258,11 +263,17 @@ impl InspectorTestUtils {
                 CexExchange::Upbit,
             ],
             cex_trade_config,
+            DEFAULT_SANDWICH_BLOCK_WINDOW,
+            None,
             None,
         );
 
-        let data = BlockData { metadata: metadata.into(), tree: tree.into() };
-        let multi = MultiBlockData { per_block_data: vec![data], blocks: 1 };
+        let multi = if let Some(txes) = config.txes_in_previous_block {
+            split_into_previous_block(tree, metadata, txes)
+        } else {
+            let data = BlockData { metadata: metadata.into(), tree: tree.into() };
+            MultiBlockData { per_block_data: vec![data], blocks: 1 }
+        };
         let results = inspector.inspect_block(multi);
         let mut results = SharedInspectorUtils::<LibmdbxReadWriter>::dedup_bundles(results);
 
@@ -367,6 +378,8 @@ impl InspectorTestUtils {
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
+                    DEFAULT_SANDWICH_BLOCK_WINDOW,
+                    None,
                     None,
                 )
             })
@@ -443,6 +456,7 @@ pub struct InspectorTxRunConfig {
     pub needs_dex_prices: bool,
     pub needs_tokens: Vec<Address>,
     pub use_block_time_weights_for_cex_pricing: bool,
+    pub txes_in_previous_block: Option<usize>,
 }
 
 impl InspectorTxRunConfig {
@@ -457,6 +471,7 @@ impl InspectorTxRunConfig {
             needs_tokens: Vec::new(),
             needs_dex_prices: false,
             use_block_time_weights_for_cex_pricing: false,
+            txes_in_previous_block: None,
         }
     }
 
@@ -506,6 +521,43 @@ impl InspectorTxRunConfig {
         self.use_block_time_weights_for_cex_pricing = true;
         self
     }
+
+    /// Moves the first `txes` of the tree into the block before it, so the
+    /// inspector runs over two blocks. With zero txes the previous block is
+    /// empty.
+    pub fn with_txes_in_previous_block(mut self, txes: usize) -> Self {
+        self.txes_in_previous_block = Some(txes);
+        self
+    }
+}
+
+fn split_into_previous_block(
+    mut tree: BlockTree<Action>,
+    mut metadata: Metadata,
+    txes: usize,
+) -> MultiBlockData {
+    let current_roots = tree.tx_roots.split_off(txes);
+    let mut previous_tree = BlockTree {
+        tx_roots:             std::mem::replace(&mut tree.tx_roots, current_roots),
+        header:               tree.header.clone(),
+        priority_fee_std_dev: tree.priority_fee_std_dev,
+        avg_priority_fee:     tree.avg_priority_fee,
+    };
+    previous_tree.header.number -= 1;
+
+    // the metadata falls back to the default when it isn't in the db, so the
+    // block numbers are taken from the trees
+    metadata.block_metadata.block_num = tree.header.number;
+    let mut previous_metadata = metadata.clone();
+    previous_metadata.block_metadata.block_num = previous_tree.header.number;
+
+    MultiBlockData {
+        per_block_data: vec![
+            BlockData { metadata: previous_metadata.into(), tree: previous_tree.into() },
+            BlockData { metadata: metadata.into(), tree: tree.into() },
+        ],
+        blocks:         2,
+    }
 }
 
 #[derive(Debug, Clone)]
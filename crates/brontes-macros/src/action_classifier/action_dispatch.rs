@@ -56,8 +56,14 @@ impl ActionDispatch {
                             )> {
 
 
-                            let protocol_byte = db_tx.get_protocol(call_info.target_address)
-                                .ok()?.to_byte();
+                            // contracts of registered forks are classified as their base protocol
+                            let protocol_byte = match db_tx.get_protocol(call_info.target_address) {
+                                Ok(protocol) => protocol.to_byte(),
+                                Err(_) => crate::forks::fork_registry()
+                                    .fork_of_contract(call_info.target_address)?
+                                    .base
+                                    .to_byte(),
+                            };
 
                             if call_info.call_data.len() < 4 {
                                 return None
@@ -183,6 +183,18 @@ pub fn discovery_dispatch(input: TokenStream) -> syn::Result<TokenStream> {
                                 }
                             )*
                             _ => {
+                                let registry = crate::forks::fork_registry();
+                                if let Some(fork) = registry.fork_of_factory(factory) {
+                                    ::tracing::trace!(target: "brontes_classifier::discovery", ?deployed_address, ?factory, fork=fork.name, "fork factory match found");
+                                    return crate::forks::discover_fork_pools(
+                                        fork,
+                                        tracer,
+                                        deployed_address,
+                                        trace_idx,
+                                        parent_calldata,
+                                    ).await
+                                }
+
                                 ::tracing::trace!(target: "brontes_classifier::discovery", ?deployed_address, ?factory, ?key, "no match found");
                                 Vec::new()
                             }
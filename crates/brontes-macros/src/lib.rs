@@ -78,6 +78,12 @@ use crate::action_classifier::{ActionDispatch, ActionMacro};
 /// you can do something like ..(Transfer | SpecialTransfer) or ..(Transfer |
 /// SpecialTransfer)*
 ///
+/// # Action Type
+/// if the action a call results in depends on the call itself (e.g a single
+/// function that both adds and removes liquidity), use `Action` as the call
+/// type. The closure then returns a
+/// `brontes_types::normalized_actions::Action` instead of the normalized
+/// struct.
 ///
 /// the fields `call_data`, `return_data` and `log_data` are only put into the
 /// closure if specified they are always in this order, for example if you put
@@ -134,6 +140,9 @@ pub fn action_impl(input: TokenStream) -> TokenStream {
 ///
 /// action_dispatch!(ClassifierDispatch, UniswapV2swapCall);
 /// ```
+/// Calls to addresses that aren't in the database fall back to the base
+/// protocol of the fork registered for the address, see
+/// `brontes_classifier::forks`.
 pub fn action_dispatch(input: TokenStream) -> TokenStream {
     parse_macro_input!(input as ActionDispatch)
         .expand()
@@ -191,6 +200,9 @@ pub fn curve_discovery_impl(input: TokenStream) -> TokenStream {
 /// ```ignore
 /// discovery_dispatch!(<DispatchStructName>, [discovery_impl_name..],);
 /// ```
+/// Factories without a discovery classifier fall back to the discovery of the
+/// base protocol of the fork registered for the factory, see
+/// `brontes_classifier::forks`.
 pub fn discovery_dispatch(input: TokenStream) -> TokenStream {
     discovery_classifier::discovery_dispatch(input.into())
         .unwrap_or_else(syn::Error::into_compile_error)
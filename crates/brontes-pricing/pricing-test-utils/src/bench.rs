@@ -114,8 +114,9 @@ impl BrontesPricingBencher {
             .unwrap()
             .2;
 
-        let pair_1 = pool_pair.0;
-        c.bench_function(bench_name, move |b| b.iter(|| black_box(state.price(pair_1).unwrap())));
+        c.bench_function(bench_name, move |b| {
+            b.iter(|| black_box(state.price(pool_pair.0, pool_pair.1).unwrap()))
+        });
 
         Ok(())
     }
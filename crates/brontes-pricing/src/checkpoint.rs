@@ -0,0 +1,125 @@
+//! Checkpoints of the finalized graph state of the
+//! [`BrontesBatchPricer`](crate::BrontesBatchPricer). The pricer periodically
+//! emits a [`PricingCheckpoint`] which is stored in libmdbx, so that a range
+//! can resume from the subgraphs and pool states of the checkpoint instead of
+//! rebuilding them from scratch.
+
+use alloy_primitives::Address;
+use brontes_types::{
+    db::redefined_types::{malachite::RationalRedefined, primitives::AddressRedefined},
+    implement_table_value_codecs_with_zc,
+    pair::{Pair, PairRedefined},
+    price_graph_types::{PoolPairInfoDirection, PoolPairInformation, SubGraphEdge},
+    Protocol,
+};
+use malachite::Rational;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{PoolState, PoolStateRedefined};
+
+/// Amount of blocks between two checkpoints of the pricer
+pub const PRICING_CHECKPOINT_INTERVAL: u64 = 1_000;
+
+/// The finalized graph state of the pricer at the start of `block`, i.e. after
+/// all the state transitions of `block - 1` are applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingCheckpoint {
+    pub block:       u64,
+    pub sub_graphs:  SubGraphsCheckpoint,
+    pub pool_states: PoolStatesCheckpoint,
+}
+
+/// All subgraphs of the subgraph registry
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SubGraphsCheckpoint {
+    pub sub_graphs: Vec<SubGraphCheckpoint>,
+}
+
+implement_table_value_codecs_with_zc!(SubGraphsCheckpointRedefined);
+
+/// The finalized state of the pools the subgraphs of the registry use
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct PoolStatesCheckpoint {
+    pub states: Vec<PoolState>,
+}
+
+implement_table_value_codecs_with_zc!(PoolStatesCheckpointRedefined);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SubGraphCheckpoint {
+    /// pair the subgraph is registered under
+    pub pair:                   Pair,
+    /// first hop the subgraph is registered under
+    pub goes_through:           Pair,
+    pub subgraph_pair:          Pair,
+    pub complete_pair:          Pair,
+    pub must_go_through:        Pair,
+    pub extends_to:             Option<Pair>,
+    /// the token of every node, ordered by node index
+    pub tokens:                 Vec<Address>,
+    /// the edges between the nodes, ordered by edge index
+    pub connections:            Vec<SubGraphConnection>,
+    pub start_nodes_liq:        Vec<NodeLiquidity>,
+    pub start_node:             u16,
+    pub end_node:               u16,
+    pub last_block_for_pricing: u64,
+    pub remove_at:              Option<u64>,
+}
+
+/// The pools that go from the `from` node to the `to` node of a subgraph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SubGraphConnection {
+    pub from:  u16,
+    pub to:    u16,
+    pub edges: Vec<SubGraphEdgeCheckpoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SubGraphEdgeCheckpoint {
+    pub pool_addr:  Address,
+    #[redefined(same_fields)]
+    pub dex_type:   Protocol,
+    pub token_0:    Address,
+    pub token_1:    Address,
+    pub token_0_in: bool,
+}
+
+impl SubGraphEdgeCheckpoint {
+    pub fn new(edge: &SubGraphEdge) -> Self {
+        Self {
+            pool_addr:  edge.pool_addr,
+            dex_type:   edge.dex_type,
+            token_0:    edge.token_0,
+            token_1:    edge.token_1,
+            token_0_in: edge.token_0_in,
+        }
+    }
+
+    /// the pool info is leaked the same way the edges of the
+    /// [`AllPairGraph`](crate::AllPairGraph) are
+    pub fn into_edge(self) -> SubGraphEdge {
+        let info = Box::leak(Box::new(PoolPairInformation::new(
+            self.pool_addr,
+            self.dex_type,
+            self.token_0,
+            self.token_1,
+        )));
+
+        SubGraphEdge::new(PoolPairInfoDirection::new(info, self.token_0_in))
+    }
+}
+
+/// Liquidity of a start node when the subgraph was last verified
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NodeLiquidity {
+    pub token:     Address,
+    pub liquidity: Rational,
+}
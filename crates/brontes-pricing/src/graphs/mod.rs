@@ -27,6 +27,7 @@ pub use self::{
 };
 use super::PoolUpdate;
 use crate::{
+    checkpoint::PricingCheckpoint,
     types::{PairWithFirstPoolHop, PoolState},
     Protocol,
 };
@@ -100,6 +101,24 @@ impl GraphManager {
         self.graph_state = state;
     }
 
+    /// The finalized subgraphs and the state they depend on. Needs to be
+    /// taken right after `block - 1` is finalized.
+    pub fn checkpoint(&self, block: u64) -> PricingCheckpoint {
+        PricingCheckpoint {
+            block,
+            sub_graphs: self.sub_graph_registry.checkpoint(),
+            pool_states: self.graph_state.checkpoint(),
+        }
+    }
+
+    pub fn restore_checkpoint(&mut self, checkpoint: PricingCheckpoint) {
+        let dependents = self
+            .sub_graph_registry
+            .restore_checkpoint(checkpoint.sub_graphs);
+        self.graph_state
+            .restore_checkpoint(checkpoint.pool_states, &dependents);
+    }
+
     pub fn add_pool(&mut self, pair: Pair, pool_addr: Address, dex: Protocol, block: u64) {
         self.all_pair_graph.add_node(pair, pool_addr, dex, block);
     }
@@ -13,7 +13,10 @@ use malachite::{
 };
 
 use super::{subgraph::PairSubGraph, PoolState};
-use crate::types::{PairWithFirstPoolHop, ProtocolState};
+use crate::{
+    checkpoint::SubGraphsCheckpoint,
+    types::{PairWithFirstPoolHop, ProtocolState},
+};
 
 /// Manages subgraphs in the BrontesBatchPricer module, crucial for DEX pricing.
 ///
@@ -118,6 +121,50 @@ impl SubGraphRegistry {
         removals
     }
 
+    /// The active subgraphs, the pending subgraphs aren't finalized yet and
+    /// are left out.
+    pub fn checkpoint(&self) -> SubGraphsCheckpoint {
+        SubGraphsCheckpoint {
+            sub_graphs: self
+                .sub_graphs
+                .iter()
+                .flat_map(|(pair, gts)| {
+                    gts.iter()
+                        .map(|(gt, graph)| graph.to_checkpoint(*pair, *gt))
+                })
+                .collect(),
+        }
+    }
+
+    /// Inserts the subgraphs of the checkpoint and returns the amount of edges
+    /// of each pool, which is the amount of dependents of its state.
+    pub fn restore_checkpoint(
+        &mut self,
+        checkpoint: SubGraphsCheckpoint,
+    ) -> FastHashMap<Address, u64> {
+        let mut dependents = FastHashMap::default();
+        for sub_graph in checkpoint.sub_graphs {
+            let (pair, gt) = (sub_graph.pair, sub_graph.goes_through);
+            let graph = PairSubGraph::from_checkpoint(sub_graph);
+            graph.get_all_pools().flatten().for_each(|edge| {
+                *dependents.entry(edge.pool_addr).or_default() += 1;
+            });
+
+            if self
+                .sub_graphs
+                .entry(pair)
+                .or_default()
+                .insert(gt, graph)
+                .is_none()
+            {
+                self.metrics
+                    .as_ref()
+                    .inspect(|m| m.active_subgraphs.increment(1.0));
+            }
+        }
+        dependents
+    }
+
     pub fn mark_future_use(&self, pair: Pair, gt: Pair, block: u64) {
         // we unwrap as this should never fail.
         let Some(graph) = self.sub_graphs.get(&pair.ordered()) else { return };
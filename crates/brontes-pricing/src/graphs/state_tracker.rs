@@ -7,6 +7,7 @@ use itertools::Itertools;
 use tracing::debug;
 
 use crate::{
+    checkpoint::PoolStatesCheckpoint,
     types::{PoolState, PoolUpdate},
     PoolPairInfoDirection, SubGraphEdge,
 };
@@ -198,6 +199,40 @@ impl StateTracker {
         state.state.increment_state(update);
     }
 
+    /// The finalized state of all pools
+    pub fn checkpoint(&self) -> PoolStatesCheckpoint {
+        PoolStatesCheckpoint {
+            states: self
+                .finalized_edge_state
+                .values()
+                .map(|state| state.state.clone())
+                .collect(),
+        }
+    }
+
+    /// Inserts the finalized state of the checkpoint for the pools that have
+    /// dependents, the state of the other pools is no longer needed.
+    pub fn restore_checkpoint(
+        &mut self,
+        checkpoint: PoolStatesCheckpoint,
+        dependents: &FastHashMap<Address, u64>,
+    ) {
+        for state in checkpoint.states {
+            let pool = state.address();
+            let Some(dependents) = dependents.get(&pool).copied() else { continue };
+
+            if self
+                .finalized_edge_state
+                .insert(pool, StateWithDependencies { state, dependents })
+                .is_none()
+            {
+                self.metrics
+                    .as_ref()
+                    .inspect(|m| m.active_state.increment(1.0));
+            }
+        }
+    }
+
     pub fn new_state_for_verification(&mut self, address: Address, state: StateWithDependencies) {
         self.verification_edge_state
             .entry(address)
@@ -29,7 +29,11 @@ use petgraph::{
 };
 use tracing::error;
 
-use crate::{types::ProtocolState, Pair};
+use crate::{
+    checkpoint::{NodeLiquidity, SubGraphCheckpoint, SubGraphConnection, SubGraphEdgeCheckpoint},
+    types::ProtocolState,
+    Pair,
+};
 
 pub struct VerificationOutcome {
     pub should_requery: bool,
@@ -167,6 +171,96 @@ impl PairSubGraph {
         }
     }
 
+    /// The subgraph registered under `pair` and `goes_through` in a form that
+    /// can be stored. Nodes and edges keep their index so that the restored
+    /// graph is identical.
+    pub fn to_checkpoint(&self, pair: Pair, goes_through: Pair) -> SubGraphCheckpoint {
+        let mut tokens = vec![Address::ZERO; self.graph.node_count()];
+        for (token, index) in &self.token_to_index {
+            tokens[*index as usize] = *token;
+        }
+
+        let connections = self
+            .graph
+            .raw_edges()
+            .iter()
+            .map(|edge| SubGraphConnection {
+                from:  edge.source().index() as u16,
+                to:    edge.target().index() as u16,
+                edges: edge
+                    .weight
+                    .iter()
+                    .map(SubGraphEdgeCheckpoint::new)
+                    .collect(),
+            })
+            .collect();
+
+        SubGraphCheckpoint {
+            pair,
+            goes_through,
+            subgraph_pair: self.pair,
+            complete_pair: self.complete_pair,
+            must_go_through: self.must_go_through,
+            extends_to: self.extends_to,
+            tokens,
+            connections,
+            start_nodes_liq: self
+                .start_nodes_liq
+                .iter()
+                .map(|(token, liquidity)| NodeLiquidity {
+                    token:     *token,
+                    liquidity: liquidity.clone(),
+                })
+                .collect(),
+            start_node: self.start_node,
+            end_node: self.end_node,
+            last_block_for_pricing: self.last_block_for_pricing.load(SeqCst),
+            remove_at: self.remove_at,
+        }
+    }
+
+    pub fn from_checkpoint(checkpoint: SubGraphCheckpoint) -> Self {
+        let mut graph = DiGraph::<(), Vec<SubGraphEdge>, u16>::with_capacity(
+            checkpoint.tokens.len(),
+            checkpoint.connections.len(),
+        );
+        let token_to_index = checkpoint
+            .tokens
+            .into_iter()
+            .map(|token| (token, graph.add_node(()).index() as u16))
+            .collect();
+
+        graph.extend_with_edges(checkpoint.connections.into_iter().map(|connection| {
+            (
+                connection.from,
+                connection.to,
+                connection
+                    .edges
+                    .into_iter()
+                    .map(SubGraphEdgeCheckpoint::into_edge)
+                    .collect_vec(),
+            )
+        }));
+
+        Self {
+            pair: checkpoint.subgraph_pair,
+            complete_pair: checkpoint.complete_pair,
+            must_go_through: checkpoint.must_go_through,
+            graph,
+            token_to_index,
+            extends_to: checkpoint.extends_to,
+            start_nodes_liq: checkpoint
+                .start_nodes_liq
+                .into_iter()
+                .map(|node| (node.token, node.liquidity))
+                .collect(),
+            start_node: checkpoint.start_node,
+            end_node: checkpoint.end_node,
+            last_block_for_pricing: Arc::new(AtomicU64::new(checkpoint.last_block_for_pricing)),
+            remove_at: checkpoint.remove_at,
+        }
+    }
+
     pub fn should_use_for_new(&self) -> bool {
         self.remove_at.is_none()
     }
@@ -202,7 +296,7 @@ impl PairSubGraph {
             .edge_weights()
             .flat_map(|weight| {
                 weight.iter().filter_map(|edge| {
-                    let (r0, r1) = state.get(&edge.pool_addr)?.tvl(edge.token_0);
+                    let (r0, r1) = state.get(&edge.pool_addr)?.tvl(edge.token_0, edge.token_1);
                     let tvl_added = r0 + r1;
 
                     Some((edge.pool_addr, tvl_added))
@@ -222,7 +316,10 @@ impl PairSubGraph {
                 weight
                     .iter()
                     .map(|edge| {
-                        let (r0, r1) = state.get(&edge.pool_addr).unwrap().tvl(edge.token_0);
+                        let (r0, r1) = state
+                            .get(&edge.pool_addr)
+                            .unwrap()
+                            .tvl(edge.token_0, edge.token_1);
                         let tvl_added = r0 + r1;
                         let start_tvl = self.start_nodes_liq.get(&edge.pool_addr).unwrap();
 
@@ -499,14 +596,18 @@ impl PairSubGraph {
                         continue;
                     };
 
-                    let Ok(pool_price) =
-                        pool_state.price(info.get_token_with_direction(is_outgoing))
-                    else {
+                    let Ok(pool_price) = pool_state.price(
+                        info.get_token_with_direction(is_outgoing),
+                        info.get_token_with_direction(!is_outgoing),
+                    ) else {
                         Self::bad_state(pair, info, Rational::ZERO, &mut removal_map.removal_state);
                         continue;
                     };
 
-                    let (t0, t1) = pool_state.tvl(info.get_token_with_direction(is_outgoing));
+                    let (t0, t1) = pool_state.tvl(
+                        info.get_token_with_direction(is_outgoing),
+                        info.get_token_with_direction(!is_outgoing),
+                    );
                     let liq0 = prev_price.clone().reciprocal() * &t0;
 
                     let goes_through_arg = if ignore_goes_through {
@@ -893,7 +994,7 @@ impl PairSubGraph {
 
                 for pool in pools {
                     let Some(pool_e) = state.get(&pool.pool_addr) else { continue };
-                    let (_, quote) = pool_e.tvl(pool.get_base_token());
+                    let (_, quote) = pool_e.tvl(pool.get_base_token(), pool.get_quote_token());
                     if min_liq > quote {
                         min_liq = quote;
                     }
@@ -955,11 +1056,13 @@ impl PairSubGraph {
                         continue;
                     };
 
-                    let Ok(pool_price) = pool_state.price(info.get_base_token()) else {
+                    let Ok(pool_price) =
+                        pool_state.price(info.get_base_token(), info.get_quote_token())
+                    else {
                         continue;
                     };
 
-                    let (t0, t1) = pool_state.tvl(info.get_base_token());
+                    let (t0, t1) = pool_state.tvl(info.get_base_token(), info.get_quote_token());
 
                     let t0xt1 = &t0 * &t1;
                     pxw += pool_price * &t0xt1;
@@ -1097,11 +1200,15 @@ pub mod test {
     }
 
     impl ProtocolState for MockPoolState {
-        fn price(&self, _base: Address) -> Result<Rational, crate::errors::ArithmeticError> {
+        fn price(
+            &self,
+            _base: Address,
+            _quote: Address,
+        ) -> Result<Rational, crate::errors::ArithmeticError> {
             Ok(self.price.clone())
         }
 
-        fn tvl(&self, _base: Address) -> (Rational, Rational) {
+        fn tvl(&self, _base: Address, _quote: Address) -> (Rational, Rational) {
             self.tvl.clone()
         }
     }
@@ -1175,4 +1282,30 @@ pub mod test {
 
         assert_eq!(price, Rational::from_unsigneds(1usize, 390usize))
     }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        addresses!(t0, t1, t2, t3, t4);
+        let graph = make_simple_graph();
+        let pair = Pair(t0, t4);
+        let gt = Pair(t0, t1);
+
+        let checkpoint = graph.to_checkpoint(pair, gt);
+        assert_eq!(checkpoint.tokens.len(), 5);
+        assert_eq!(checkpoint.connections.len(), 4);
+
+        let restored = PairSubGraph::from_checkpoint(checkpoint.clone());
+        assert_eq!(restored.to_checkpoint(pair, gt), checkpoint);
+
+        // the restored graph prices through the same path
+        let mut state_map = FastHashMap::default();
+        let states = [t0, t1, t2, t3].map(|_| {
+            MockPoolState::new(Rational::from(2), Rational::from(10_000), Rational::from(10_000))
+        });
+        for (token, state) in [t0, t1, t2, t3].into_iter().zip(&states) {
+            state_map.insert(token, state);
+        }
+
+        assert_eq!(restored.fetch_price(&state_map), graph.fetch_price(&state_map));
+    }
 }
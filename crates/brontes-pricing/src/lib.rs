@@ -28,6 +28,7 @@ use brontes_types::{
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::graphs::StateWithDependencies;
+pub mod checkpoint;
 pub mod function_call_bench;
 mod graphs;
 pub mod protocols;
@@ -52,6 +53,7 @@ use brontes_types::{
     traits::TracingProvider,
     FastHashMap, FastHashSet,
 };
+use checkpoint::{PricingCheckpoint, PRICING_CHECKPOINT_INTERVAL};
 use futures::Stream;
 pub use graphs::{
     AllPairGraph, GraphManager, StateTracker, SubGraphRegistry, SubgraphVerifier,
@@ -65,6 +67,7 @@ use malachite::{
 use protocols::lazy::{LazyExchangeLoader, LazyResult, LoadResult};
 pub use protocols::{Protocol, *};
 use subgraph_query::*;
+use tokio::sync::mpsc::UnboundedSender;
 use tracing::{debug, error, info};
 use types::{DexPriceMsg, PairWithFirstPoolHop, PoolUpdate};
 
@@ -109,6 +112,8 @@ pub struct BrontesBatchPricer<T: TracingProvider> {
     skip_pricing:    VecDeque<u64>,
     /// metrics
     metrics:         Option<DexPricingMetrics>,
+    /// where the checkpoints of the finalized graph state are sent to
+    checkpoint_tx:   Option<UnboundedSender<PricingCheckpoint>>,
 }
 
 impl<T: TracingProvider> BrontesBatchPricer<T> {
@@ -142,9 +147,17 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
             skip_pricing: VecDeque::new(),
             needs_more_data,
             metrics,
+            checkpoint_tx: None,
         }
     }
 
+    /// Sends a checkpoint of the finalized graph state every
+    /// [`PRICING_CHECKPOINT_INTERVAL`] blocks.
+    pub fn with_checkpoints(mut self, checkpoint_tx: UnboundedSender<PricingCheckpoint>) -> Self {
+        self.checkpoint_tx = Some(checkpoint_tx);
+        self
+    }
+
     pub fn current_block_processing(&self) -> u64 {
         self.completed_block
     }
@@ -1047,6 +1060,7 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         // prune dead subgraphs
         self.graph_manager
             .prune_dead_subgraphs(self.completed_block);
+        self.try_checkpoint(block);
 
         self.metrics
             .as_ref()
@@ -1054,6 +1068,35 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         self.should_return().then_some((block, res))
     }
 
+    /// Sends a checkpoint of the state at the start of the next block if it is
+    /// on the checkpoint interval. The pool updates of blocks that skip
+    /// pricing are applied as soon as they are received, so when a later
+    /// skipped block was already received the finalized state is ahead and
+    /// the checkpoint is skipped.
+    fn try_checkpoint(&mut self, block: u64) {
+        let Some(checkpoint_tx) = self.checkpoint_tx.as_ref() else { return };
+        if (block + 1) % PRICING_CHECKPOINT_INTERVAL != 0 {
+            return
+        }
+
+        if self
+            .skip_pricing
+            .iter()
+            .any(|skipped| *skipped > block && *skipped <= self.current_block)
+        {
+            debug!(block, "finalized state is ahead of the block, skipping checkpoint");
+            return
+        }
+
+        if checkpoint_tx
+            .send(self.graph_manager.checkpoint(block + 1))
+            .is_err()
+        {
+            error!(block, "checkpoint receiver dropped, disabling checkpoints");
+            self.checkpoint_tx = None;
+        }
+    }
+
     // checks skip
     fn should_return(&mut self) -> bool {
         // remove ones lower than completed
@@ -0,0 +1,173 @@
+pub mod stable;
+pub mod weighted;
+
+use std::sync::Arc;
+
+use alloy_primitives::{hex, Address};
+use alloy_sol_macro::sol;
+use brontes_types::{traits::TracingProvider, ToScaledRational};
+use malachite::Rational;
+pub use stable::{BalancerStablePool, BalancerStablePoolRedefined};
+pub use weighted::{BalancerWeightedPool, BalancerWeightedPoolRedefined};
+
+use super::make_call_request;
+use crate::{errors::AmmError, types::PoolVariants, Protocol};
+
+pub const BALANCER_V2_VAULT: Address =
+    Address::new(hex!("BA12222222228d8Ba445958a75a0704d566BF2C8"));
+
+sol!(
+    interface IBalancerV2Pool {
+        function getPoolId() external view returns (bytes32);
+        function getNormalizedWeights() external view returns (uint256[]);
+        function getAmplificationParameter() external view returns (
+            uint256 value,
+            bool isUpdating,
+            uint256 precision
+        );
+        function getScalingFactors() external view returns (uint256[]);
+    }
+);
+
+sol!(
+    interface IBalancerV2Vault {
+        function getPoolTokens(bytes32 poolId) external view returns (
+            address[] tokens,
+            uint256[] balances,
+            uint256 lastChangeBlock
+        );
+    }
+);
+
+sol!(
+    interface IBalancerV1Pool {
+        function getCurrentTokens() external view returns (address[]);
+        function getBalance(address token) external view returns (uint256);
+        function getNormalizedWeight(address token) external view returns (uint256);
+    }
+);
+
+sol!(
+    interface IBalancerV1SmartPool {
+        function bPool() external view returns (address);
+    }
+);
+
+sol!(
+    interface IBalancerErc20 {
+        function decimals() external view returns (uint8);
+    }
+);
+
+/// Balancer V2 pools all live in the vault and share the same swap, join and
+/// exit actions, so we can't tell from the protocol alone what math the pool
+/// uses. We check for weights first and fallback to the stable math.
+pub async fn load_v2_pool<T: TracingProvider>(
+    address: Address,
+    provider: Arc<T>,
+    block: u64,
+) -> Result<PoolVariants, AmmError> {
+    match BalancerWeightedPool::new_load_on_block(
+        address,
+        Protocol::BalancerV2,
+        provider.clone(),
+        block,
+    )
+    .await
+    {
+        Ok(pool) => Ok(PoolVariants::BalancerWeighted(Box::new(pool))),
+        Err(_) => Ok(PoolVariants::BalancerStable(Box::new(
+            BalancerStablePool::new_load_on_block(address, provider, block).await?,
+        ))),
+    }
+}
+
+/// the indexes of the base and quote tokens. Pools can hold more than two
+/// tokens, so both are searched for in all of them.
+pub(crate) fn quote_index(
+    tokens: &[Address],
+    base: Address,
+    quote: Address,
+) -> Option<(usize, usize)> {
+    let base = tokens.iter().position(|t| *t == base)?;
+    let quote = tokens.iter().position(|t| *t == quote)?;
+
+    (base != quote).then_some((base, quote))
+}
+
+/// the token the base is quoted in when no quote is given, this is the other
+/// token of the pools first pair
+pub(crate) fn default_quote(tokens: &[Address], base: Address) -> Address {
+    if tokens.first() == Some(&base) {
+        tokens[1]
+    } else {
+        tokens[0]
+    }
+}
+
+/// Fetches the tokens registered for the pool in the vault along with their
+/// balances scaled by decimals, in the order the vault returns them.
+/// Composable pools register their own lp token, which is kept so that the
+/// indexes match the pools on-chain arrays.
+pub(crate) async fn fetch_v2_pool_tokens<T: TracingProvider>(
+    address: Address,
+    provider: &Arc<T>,
+    block: u64,
+) -> Result<(Vec<Address>, Vec<Rational>, Vec<u8>), AmmError> {
+    let pool_id =
+        make_call_request(IBalancerV2Pool::getPoolIdCall::new(()), provider, address, Some(block))
+            .await?
+            ._0;
+
+    let res = make_call_request(
+        IBalancerV2Vault::getPoolTokensCall::new((pool_id,)),
+        provider,
+        BALANCER_V2_VAULT,
+        Some(block),
+    )
+    .await?;
+
+    let mut balances = Vec::with_capacity(res.tokens.len());
+    let mut decimals = Vec::with_capacity(res.tokens.len());
+
+    for (token, balance) in res.tokens.iter().zip(res.balances) {
+        let dec = fetch_decimals(*token, provider, block).await?;
+        balances.push(balance.to_scaled_rational(dec));
+        decimals.push(dec);
+    }
+
+    if res.tokens.iter().filter(|token| **token != address).count() < 2 {
+        return Err(AmmError::NoStateError(address))
+    }
+
+    Ok((res.tokens, balances, decimals))
+}
+
+pub(crate) async fn fetch_decimals<T: TracingProvider>(
+    token: Address,
+    provider: &Arc<T>,
+    block: u64,
+) -> Result<u8, AmmError> {
+    Ok(make_call_request(IBalancerErc20::decimalsCall::new(()), provider, token, Some(block))
+        .await?
+        ._0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_index_searches_all_tokens() {
+        let tokens =
+            [Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3)];
+
+        assert_eq!(quote_index(&tokens, tokens[2], tokens[1]), Some((2, 1)));
+        assert_eq!(quote_index(&tokens, tokens[0], tokens[2]), Some((0, 2)));
+        assert_eq!(quote_index(&tokens, tokens[0], tokens[0]), None);
+        assert_eq!(quote_index(&tokens, tokens[0], Address::with_last_byte(4)), None);
+
+        assert_eq!(default_quote(&tokens, tokens[0]), tokens[1]);
+        assert_eq!(default_quote(&tokens, tokens[2]), tokens[0]);
+    }
+}
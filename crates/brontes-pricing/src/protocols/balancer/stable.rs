@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log};
+use async_trait::async_trait;
+use brontes_types::{
+    db::redefined_types::{malachite::RationalRedefined, primitives::AddressRedefined},
+    normalized_actions::Action,
+    traits::TracingProvider,
+    ToFloatNearest, ToScaledRational,
+};
+use malachite::{
+    num::basic::traits::{One, Zero},
+    Rational,
+};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::{default_quote, fetch_v2_pool_tokens, quote_index, IBalancerV2Pool};
+use crate::{
+    curve::stable::spot_price,
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request,
+    protocols::apply_action,
+    UpdatableProtocol,
+};
+
+/// State for Balancer V2 stable, meta stable and composable stable pools. These
+/// use the same invariant as Curve's StableSwap.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BalancerStablePool {
+    pub address:         Address,
+    /// the tokens registered in the vault, in the vaults order. For composable
+    /// pools this includes the pools own lp token, so that the indexes match
+    /// the on-chain balances and scaling factors.
+    pub tokens:          Vec<Address>,
+    /// balances scaled by the tokens decimals
+    pub balances:        Vec<Rational>,
+    /// the rate of each token from the pools rate providers, 1 if the token
+    /// has none
+    pub scaling_factors: Vec<Rational>,
+    pub amp:             f64,
+}
+
+#[async_trait]
+impl UpdatableProtocol for BalancerStablePool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    /// the tokens that can be priced, this excludes the lp token of
+    /// composable pools
+    fn tokens(&self) -> Vec<Address> {
+        self.tokens
+            .iter()
+            .copied()
+            .filter(|token| *token != self.address)
+            .collect()
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        self.calculate_price_in(base_token, default_quote(&self.tokens(), base_token))
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        let tokens = &self.tokens;
+        apply_action(self.address, &mut self.balances, action, |token, amount| {
+            Some((tokens.iter().position(|t| *t == token)?, amount.clone()))
+        })
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+}
+
+impl BalancerStablePool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        provider: Arc<T>,
+        block: u64,
+    ) -> Result<Self, AmmError> {
+        let (tokens, balances, decimals) = fetch_v2_pool_tokens(address, &provider, block).await?;
+
+        let amp = make_call_request(
+            IBalancerV2Pool::getAmplificationParameterCall::new(()),
+            &provider,
+            address,
+            Some(block),
+        )
+        .await?;
+        let amp =
+            (amp.value.to_scaled_rational(0) / amp.precision.to_scaled_rational(0)).to_float();
+
+        // scaling factors are `10 ** (36 - decimals) * rate / 1e18`
+        let scaling_factors = match make_call_request(
+            IBalancerV2Pool::getScalingFactorsCall::new(()),
+            &provider,
+            address,
+            Some(block),
+        )
+        .await
+        {
+            Ok(res) => res
+                ._0
+                .into_iter()
+                .zip(decimals.iter())
+                .map(|(factor, dec)| factor.to_scaled_rational(36 - dec))
+                .collect(),
+            Err(_) => vec![Rational::ONE; tokens.len()],
+        };
+
+        let pool = Self { address, tokens, balances, scaling_factors, amp };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        self.tokens().len() >= 2
+            && self.tokens.len() == self.balances.len()
+            && self.tokens.len() == self.scaling_factors.len()
+            && self.amp > 0.0
+            && self
+                .invariant_indexes()
+                .all(|i| self.balances[i] > Rational::ZERO)
+    }
+
+    /// the price of the base token in terms of the quote token
+    pub fn calculate_price_in(
+        &self,
+        base_token: Address,
+        quote_token: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let (base, quote) = quote_index(&self.tokens, base_token, quote_token)
+            .filter(|(base, quote)| {
+                self.tokens[*base] != self.address && self.tokens[*quote] != self.address
+            })
+            .ok_or(ArithmeticError::TokenNotInPool(base_token))?;
+
+        // the lp token of composable pools isn't part of the invariant
+        let xp = self
+            .invariant_indexes()
+            .map(|i| (&self.balances[i] * &self.scaling_factors[i]).to_float())
+            .collect::<Vec<_>>();
+
+        if xp.iter().any(|x| *x <= 0.0) {
+            return Err(ArithmeticError::BalancerDivZero)
+        }
+
+        let price = spot_price(
+            &xp,
+            self.amp * xp.len() as f64,
+            self.invariant_index(base),
+            self.invariant_index(quote),
+        )
+        .ok_or(ArithmeticError::InvariantNotConverged)?;
+        let price = price * self.scaling_factors[base].clone().to_float()
+            / self.scaling_factors[quote].clone().to_float();
+
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let Some((base, quote)) = quote_index(&self.tokens, base, quote) else {
+            let mut tokens = self.invariant_indexes();
+            let (t0, t1) = (tokens.next().unwrap_or(0), tokens.next().unwrap_or(1));
+            return (self.balances[t0].clone(), self.balances[t1].clone())
+        };
+
+        (self.balances[base].clone(), self.balances[quote].clone())
+    }
+
+    fn invariant_indexes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.tokens.len()).filter(|i| self.tokens[*i] != self.address)
+    }
+
+    /// maps an index into the vaults token list to the index in the invariant,
+    /// which skips the lp token of composable pools
+    fn invariant_index(&self, idx: usize) -> usize {
+        match self.tokens.iter().position(|token| *token == self.address) {
+            Some(lp_idx) if lp_idx < idx => idx - 1,
+            _ => idx,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composable_pool_skips_lp_token() {
+        let pool_address = Address::with_last_byte(2);
+        let wsteth = Address::with_last_byte(1);
+        let reth = Address::with_last_byte(3);
+        let sfrxeth = Address::with_last_byte(4);
+
+        // the lp token sits between the other tokens and has a huge pre-minted
+        // balance, this shouldn't move the price of the other tokens
+        let pool = BalancerStablePool {
+            address:         pool_address,
+            tokens:          vec![wsteth, pool_address, reth, sfrxeth],
+            balances:        vec![
+                Rational::from(1_000),
+                Rational::from(2_596_148_429_267_413u64),
+                Rational::from(1_000),
+                Rational::from(500),
+            ],
+            scaling_factors: vec![Rational::from(1); 4],
+            amp:             1500.0,
+        };
+        let plain = BalancerStablePool {
+            address:         pool_address,
+            tokens:          vec![wsteth, reth, sfrxeth],
+            balances:        vec![
+                Rational::from(1_000),
+                Rational::from(1_000),
+                Rational::from(500),
+            ],
+            scaling_factors: vec![Rational::from(1); 3],
+            amp:             1500.0,
+        };
+
+        assert!(pool.data_is_populated());
+        assert_eq!(pool.tokens(), vec![wsteth, reth, sfrxeth]);
+
+        // wsteth and reth are balanced
+        let reth_in_wsteth = pool.calculate_price_in(reth, wsteth).unwrap().to_float();
+        assert!((reth_in_wsteth - 1.0).abs() < 1e-9, "price={reth_in_wsteth}");
+
+        // pairs past the lp token match the same pool without it
+        let sfrxeth_in_reth = pool.calculate_price_in(sfrxeth, reth).unwrap().to_float();
+        let expected = plain.calculate_price_in(sfrxeth, reth).unwrap().to_float();
+        assert!(sfrxeth_in_reth > 1.0);
+        assert!((sfrxeth_in_reth - expected).abs() < 1e-9);
+
+        assert_eq!(pool.get_tvl(sfrxeth, reth), (Rational::from(500), Rational::from(1_000)));
+        assert!(pool.calculate_price_in(pool_address, reth).is_err());
+    }
+}
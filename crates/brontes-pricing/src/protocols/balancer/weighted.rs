@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log};
+use async_trait::async_trait;
+use brontes_types::{
+    db::redefined_types::{malachite::RationalRedefined, primitives::AddressRedefined},
+    normalized_actions::Action,
+    traits::TracingProvider,
+    ToScaledRational,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    default_quote, fetch_decimals, fetch_v2_pool_tokens, quote_index, IBalancerV1Pool,
+    IBalancerV1SmartPool, IBalancerV2Pool,
+};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request,
+    protocols::apply_action,
+    Protocol, UpdatableProtocol,
+};
+
+/// State for Balancer V1 pools and Balancer V2 weighted pools.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BalancerWeightedPool {
+    pub address:  Address,
+    #[redefined(same_fields)]
+    pub protocol: Protocol,
+    pub tokens:   Vec<Address>,
+    /// balances scaled by the tokens decimals
+    pub balances: Vec<Rational>,
+    /// normalized weights, these sum to 1
+    pub weights:  Vec<Rational>,
+}
+
+#[async_trait]
+impl UpdatableProtocol for BalancerWeightedPool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.tokens.clone()
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        self.calculate_price_in(base_token, default_quote(&self.tokens, base_token))
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        let tokens = &self.tokens;
+        apply_action(self.address, &mut self.balances, action, |token, amount| {
+            Some((tokens.iter().position(|t| *t == token)?, amount.clone()))
+        })
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+}
+
+impl BalancerWeightedPool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        provider: Arc<T>,
+        block: u64,
+    ) -> Result<Self, AmmError> {
+        let (tokens, balances, weights) = match protocol {
+            Protocol::BalancerV1 => Self::load_v1(address, &provider, block).await?,
+            // smart pools hold their liquidity in a regular v1 pool
+            Protocol::BalancerV1CRP => {
+                let bpool = make_call_request(
+                    IBalancerV1SmartPool::bPoolCall::new(()),
+                    &provider,
+                    address,
+                    Some(block),
+                )
+                .await?
+                ._0;
+
+                Self::load_v1(bpool, &provider, block).await?
+            }
+            Protocol::BalancerV2 => Self::load_v2(address, &provider, block).await?,
+            _ => return Err(AmmError::UnsupportedProtocol),
+        };
+
+        let pool = Self { address, protocol, tokens, balances, weights };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    async fn load_v1<T: TracingProvider>(
+        address: Address,
+        provider: &Arc<T>,
+        block: u64,
+    ) -> Result<(Vec<Address>, Vec<Rational>, Vec<Rational>), AmmError> {
+        let tokens = make_call_request(
+            IBalancerV1Pool::getCurrentTokensCall::new(()),
+            provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+
+        let mut balances = Vec::with_capacity(tokens.len());
+        let mut weights = Vec::with_capacity(tokens.len());
+        for token in &tokens {
+            let dec = fetch_decimals(*token, provider, block).await?;
+            let balance = make_call_request(
+                IBalancerV1Pool::getBalanceCall::new((*token,)),
+                provider,
+                address,
+                Some(block),
+            )
+            .await?
+            ._0;
+            let weight = make_call_request(
+                IBalancerV1Pool::getNormalizedWeightCall::new((*token,)),
+                provider,
+                address,
+                Some(block),
+            )
+            .await?
+            ._0;
+
+            balances.push(balance.to_scaled_rational(dec));
+            weights.push(weight.to_scaled_rational(18));
+        }
+
+        Ok((tokens, balances, weights))
+    }
+
+    async fn load_v2<T: TracingProvider>(
+        address: Address,
+        provider: &Arc<T>,
+        block: u64,
+    ) -> Result<(Vec<Address>, Vec<Rational>, Vec<Rational>), AmmError> {
+        let (mut tokens, mut balances, _) = fetch_v2_pool_tokens(address, provider, block).await?;
+
+        // managed pools register their lp token, it isn't part of the invariant
+        let lp_idx = tokens.iter().position(|token| *token == address);
+        if let Some(idx) = lp_idx {
+            tokens.remove(idx);
+            balances.remove(idx);
+        }
+
+        let mut weights = make_call_request(
+            IBalancerV2Pool::getNormalizedWeightsCall::new(()),
+            provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+
+        // managed pools return the weights including the lp token
+        if weights.len() != tokens.len() {
+            if let Some(idx) = lp_idx.filter(|idx| *idx < weights.len()) {
+                weights.remove(idx);
+            }
+        }
+
+        Ok((
+            tokens,
+            balances,
+            weights
+                .into_iter()
+                .map(|w| w.to_scaled_rational(18))
+                .collect(),
+        ))
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        self.tokens.len() >= 2
+            && self.tokens.len() == self.balances.len()
+            && self.tokens.len() == self.weights.len()
+            && self.weights.iter().all(|w| *w > Rational::ZERO)
+    }
+
+    /// the spot price of the weighted invariant without fees:
+    /// `(balance_quote / weight_quote) / (balance_base / weight_base)`
+    pub fn calculate_price_in(
+        &self,
+        base_token: Address,
+        quote_token: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let (base, quote) = quote_index(&self.tokens, base_token, quote_token)
+            .ok_or(ArithmeticError::TokenNotInPool(base_token))?;
+
+        if self.balances[base] == Rational::ZERO || self.weights[quote] == Rational::ZERO {
+            return Err(ArithmeticError::BalancerDivZero)
+        }
+
+        Ok((&self.balances[quote] / &self.weights[quote])
+            / (&self.balances[base] / &self.weights[base]))
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let Some((base, quote)) = quote_index(&self.tokens, base, quote) else {
+            return (self.balances[0].clone(), self.balances[1].clone())
+        };
+
+        (self.balances[base].clone(), self.balances[quote].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_weighted_spot_price() {
+        let bal = Address::new(hex!("ba100000625a3754423978a60c9317c58a424e3d"));
+        let weth = Address::new(hex!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"));
+
+        // 80/20 BAL/WETH
+        let pool = BalancerWeightedPool {
+            address:  Address::ZERO,
+            protocol: Protocol::BalancerV2,
+            tokens:   vec![bal, weth],
+            balances: vec![Rational::from(4_000_000), Rational::from(1_000)],
+            weights:  vec![
+                Rational::from_signeds(8i64, 10i64),
+                Rational::from_signeds(2i64, 10i64),
+            ],
+        };
+
+        // (1000 / 0.2) / (4_000_000 / 0.8) = 0.001
+        assert_eq!(pool.calculate_price(bal).unwrap(), Rational::from_signeds(1i64, 1000i64));
+        assert_eq!(pool.calculate_price(weth).unwrap(), Rational::from(1000));
+    }
+}
@@ -0,0 +1,279 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log, U256};
+use async_trait::async_trait;
+use brontes_types::{
+    db::redefined_types::{malachite::RationalRedefined, primitives::AddressRedefined},
+    normalized_actions::Action,
+    traits::TracingProvider,
+    ToFloatNearest, ToScaledRational,
+};
+use malachite::{
+    num::basic::traits::{One, Zero},
+    Rational,
+};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    coin_index, default_quote, fetch_balance, fetch_coins, fetch_decimals, quote_index,
+    ICurveCryptoPool, ICurvePool, ICurveTriCryptoPool,
+};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request,
+    protocols::apply_action,
+    Protocol, UpdatableProtocol,
+};
+
+const A_MULTIPLIER: f64 = 10_000.0;
+const MAX_ITERATIONS: usize = 255;
+
+/// State for Curve CryptoSwap (two coin) and TriCrypto pools.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct CurveCryptoPool {
+    pub address:     Address,
+    #[redefined(same_fields)]
+    pub protocol:    Protocol,
+    pub coins:       Vec<Address>,
+    /// balances scaled by the coins decimals
+    pub balances:    Vec<Rational>,
+    /// price of each coin in terms of the first coin, the first entry is
+    /// always 1
+    pub price_scale: Vec<Rational>,
+    /// `A * n^n`
+    pub ann:         f64,
+    pub gamma:       f64,
+}
+
+#[async_trait]
+impl UpdatableProtocol for CurveCryptoPool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.coins.clone()
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        self.calculate_price_in(base_token, default_quote(&self.coins, base_token))
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        let coins = &self.coins;
+        apply_action(self.address, &mut self.balances, action, |token, amount| {
+            Some((coin_index(coins, token)?, amount.clone()))
+        })
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+}
+
+impl CurveCryptoPool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        provider: Arc<T>,
+        block: u64,
+    ) -> Result<Self, AmmError> {
+        let coins = fetch_coins(address, &provider, block).await?;
+
+        let mut balances = Vec::with_capacity(coins.len());
+        for (i, coin) in coins.iter().enumerate() {
+            let dec = fetch_decimals(*coin, &provider, block).await?;
+            balances.push(fetch_balance(address, i, dec, &provider, block).await?);
+        }
+
+        let mut price_scale = vec![Rational::ONE];
+        if protocol == Protocol::CurveTriCryptoPool {
+            for k in 0..coins.len() - 1 {
+                price_scale.push(
+                    make_call_request(
+                        ICurveTriCryptoPool::price_scaleCall::new((U256::from(k),)),
+                        &provider,
+                        address,
+                        Some(block),
+                    )
+                    .await?
+                    ._0
+                    .to_scaled_rational(18),
+                );
+            }
+        } else {
+            price_scale.push(
+                make_call_request(
+                    ICurveCryptoPool::price_scaleCall::new(()),
+                    &provider,
+                    address,
+                    Some(block),
+                )
+                .await?
+                ._0
+                .to_scaled_rational(18),
+            );
+        }
+
+        // crypto pools return `A * n^n * A_MULTIPLIER`
+        let ann = make_call_request(ICurvePool::ACall::new(()), &provider, address, Some(block))
+            .await?
+            ._0
+            .to_scaled_rational(0)
+            .to_float()
+            / A_MULTIPLIER;
+
+        let gamma = make_call_request(
+            ICurveCryptoPool::gammaCall::new(()),
+            &provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0
+        .to_scaled_rational(18)
+        .to_float();
+
+        let pool = Self { address, protocol, coins, balances, price_scale, ann, gamma };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        self.coins.len() == self.balances.len()
+            && self.coins.len() == self.price_scale.len()
+            && self.ann > 0.0
+            && self.gamma > 0.0
+            && self.balances.iter().all(|b| *b > Rational::ZERO)
+    }
+
+    /// the price of the base coin in terms of the quote coin
+    pub fn calculate_price_in(
+        &self,
+        base_token: Address,
+        quote_token: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let (base, quote) = quote_index(&self.coins, base_token, quote_token)
+            .ok_or(ArithmeticError::TokenNotInPool(base_token))?;
+        let xp = self.xp()?;
+
+        let price = spot_price(&xp, self.ann, self.gamma, base, quote)
+            .ok_or(ArithmeticError::InvariantNotConverged)?;
+        let price = price * self.price_scale[base].clone().to_float()
+            / self.price_scale[quote].clone().to_float();
+
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let Some((base, quote)) = quote_index(&self.coins, base, quote) else {
+            return (self.balances[0].clone(), self.balances[1].clone())
+        };
+
+        (self.balances[base].clone(), self.balances[quote].clone())
+    }
+
+    /// balances in terms of the first coin
+    fn xp(&self) -> Result<Vec<f64>, ArithmeticError> {
+        let xp = self
+            .balances
+            .iter()
+            .zip(self.price_scale.iter())
+            .map(|(balance, scale)| (balance * scale).to_float())
+            .collect::<Vec<_>>();
+
+        if xp.iter().any(|x| *x <= 0.0) {
+            return Err(ArithmeticError::YIsZero)
+        }
+
+        Ok(xp)
+    }
+}
+
+/// The CryptoSwap invariant normalized by `D^n`, with `x = xp / D`:
+///
+/// `K * (sum(x) - 1) + prod(x) - (1 / n)^n`
+///
+/// where `K0 = prod(x) * n^n` and `K = A * K0 * gamma^2 / (gamma + 1 - K0)^2`
+fn invariant(x: &[f64], ann: f64, gamma: f64) -> f64 {
+    let n = x.len() as f64;
+    let n_pow_n = n.powi(x.len() as i32);
+    let prod: f64 = x.iter().product();
+    let sum: f64 = x.iter().sum();
+
+    let k0 = prod * n_pow_n;
+    let k = (ann / n_pow_n) * k0 * gamma.powi(2) / (gamma + 1.0 - k0).powi(2);
+
+    k * (sum - 1.0) + prod - 1.0 / n_pow_n
+}
+
+/// Solves for D with newton's method, using the geometric mean of the balances
+/// as the starting point as the pool contracts do.
+pub(crate) fn get_d(xp: &[f64], ann: f64, gamma: f64) -> Option<f64> {
+    let n = xp.len() as f64;
+    let f = |d: f64| invariant(&xp.iter().map(|x| x / d).collect::<Vec<_>>(), ann, gamma);
+
+    let mut d = n * xp.iter().product::<f64>().powf(1.0 / n);
+    for _ in 0..MAX_ITERATIONS {
+        let h = d * 1e-7;
+        let derivative = (f(d + h) - f(d - h)) / (2.0 * h);
+        if derivative == 0.0 || !derivative.is_finite() {
+            return None
+        }
+
+        let step = f(d) / derivative;
+        d -= step;
+
+        if step.abs() <= d * 1e-14 {
+            return Some(d)
+        }
+    }
+
+    None
+}
+
+/// The marginal amount of coin `j` received for coin `i` in terms of the first
+/// coin. Given the invariant F, this is `dF/dx_i / dF/dx_j`.
+pub(crate) fn spot_price(xp: &[f64], ann: f64, gamma: f64, i: usize, j: usize) -> Option<f64> {
+    let d = get_d(xp, ann, gamma)?;
+    let x = xp.iter().map(|x| x / d).collect::<Vec<_>>();
+
+    let partial = |k: usize| {
+        let h = x[k] * 1e-6;
+        let mut up = x.clone();
+        let mut down = x.clone();
+        up[k] += h;
+        down[k] -= h;
+
+        (invariant(&up, ann, gamma) - invariant(&down, ann, gamma)) / (2.0 * h)
+    };
+
+    let price = partial(i) / partial(j);
+    price.is_finite().then_some(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balanced_pool_is_at_price_scale() {
+        // typical tricrypto params
+        let ann = 1707629.0 / A_MULTIPLIER;
+        let gamma = 0.0000118;
+        let xp = [10_000_000.0, 10_000_000.0, 10_000_000.0];
+
+        let d = get_d(&xp, ann, gamma).unwrap();
+        assert!((d - 30_000_000.0).abs() / d < 1e-9, "d={d}");
+
+        let price = spot_price(&xp, ann, gamma, 1, 0).unwrap();
+        assert!((price - 1.0).abs() < 1e-6, "price={price}");
+    }
+}
@@ -0,0 +1,224 @@
+pub mod crypto;
+pub mod stable;
+
+use std::sync::Arc;
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_macro::sol;
+use brontes_types::{
+    constants::{ETH_ADDRESS, WETH_ADDRESS},
+    traits::TracingProvider,
+    ToScaledRational,
+};
+pub use crypto::{CurveCryptoPool, CurveCryptoPoolRedefined};
+use malachite::Rational;
+pub use stable::{CurveStablePool, CurveStablePoolRedefined};
+
+use super::make_call_request;
+use crate::{errors::AmmError, Protocol};
+
+/// max amount of coins a curve pool can hold
+pub const MAX_COINS: usize = 8;
+
+sol!(
+    interface ICurvePool {
+        function coins(uint256 i) external view returns (address);
+        function balances(uint256 i) external view returns (uint256);
+        function A() external view returns (uint256);
+        function A_precise() external view returns (uint256);
+        function base_pool() external view returns (address);
+        function get_virtual_price() external view returns (uint256);
+        function stored_rates() external view returns (uint256[]);
+    }
+);
+
+// older pools (3pool, compound, etc) index coins with a int128
+sol!(
+    interface ICurvePoolLegacy {
+        function coins(int128 i) external view returns (address);
+        function balances(int128 i) external view returns (uint256);
+    }
+);
+
+sol!(
+    interface ICurveCryptoPool {
+        function gamma() external view returns (uint256);
+        function price_scale() external view returns (uint256);
+    }
+);
+
+sol!(
+    interface ICurveTriCryptoPool {
+        function price_scale(uint256 k) external view returns (uint256);
+    }
+);
+
+sol!(
+    interface ICurveErc20 {
+        function decimals() external view returns (uint8);
+    }
+);
+
+pub(crate) fn is_metapool(protocol: Protocol) -> bool {
+    matches!(
+        protocol,
+        Protocol::CurveV1MetaPool
+            | Protocol::CurveV1MetapoolImpl
+            | Protocol::CurveV2MetaPool
+            | Protocol::CurveV2MetapoolImpl
+            | Protocol::CurvecrvUSDMetaPool
+            | Protocol::CurvecrvUSDMetapoolImpl
+    )
+}
+
+/// Curve pools that hold native eth use the placeholder address, the rest of
+/// the system might refer to it as weth.
+pub(crate) fn coin_index(coins: &[Address], token: Address) -> Option<usize> {
+    coins.iter().position(|coin| {
+        *coin == token
+            || (*coin == ETH_ADDRESS && token == WETH_ADDRESS)
+            || (*coin == WETH_ADDRESS && token == ETH_ADDRESS)
+    })
+}
+
+/// the indexes of the base and quote coins. Pools can hold more than two
+/// coins, so both are searched for in all of them.
+pub(crate) fn quote_index(
+    coins: &[Address],
+    base: Address,
+    quote: Address,
+) -> Option<(usize, usize)> {
+    let base = coin_index(coins, base)?;
+    let quote = coin_index(coins, quote)?;
+
+    (base != quote).then_some((base, quote))
+}
+
+/// the coin the base is quoted in when no quote is given, this is the other
+/// coin of the pools first pair
+pub(crate) fn default_quote(coins: &[Address], base: Address) -> Address {
+    if coin_index(coins, base) == Some(0) {
+        coins[1]
+    } else {
+        coins[0]
+    }
+}
+
+/// Fetches all of the coins of the pool. As the amount of coins isn't always
+/// known upfront, we query until the pool reverts.
+pub(crate) async fn fetch_coins<T: TracingProvider>(
+    address: Address,
+    provider: &Arc<T>,
+    block: u64,
+) -> Result<Vec<Address>, AmmError> {
+    let mut coins = Vec::new();
+
+    for i in 0..MAX_COINS {
+        let coin = match make_call_request(
+            ICurvePool::coinsCall::new((U256::from(i),)),
+            provider,
+            address,
+            Some(block),
+        )
+        .await
+        {
+            Ok(res) => res._0,
+            Err(_) => match make_call_request(
+                ICurvePoolLegacy::coinsCall::new((i as i128,)),
+                provider,
+                address,
+                Some(block),
+            )
+            .await
+            {
+                Ok(res) => res._0,
+                Err(_) => break,
+            },
+        };
+        coins.push(coin);
+    }
+
+    if coins.len() < 2 {
+        return Err(AmmError::NoStateError(address))
+    }
+
+    Ok(coins)
+}
+
+/// Fetches the balance of the coin at index `i` scaled by its decimals
+pub(crate) async fn fetch_balance<T: TracingProvider>(
+    address: Address,
+    i: usize,
+    decimals: u8,
+    provider: &Arc<T>,
+    block: u64,
+) -> Result<Rational, AmmError> {
+    let balance = match make_call_request(
+        ICurvePool::balancesCall::new((U256::from(i),)),
+        provider,
+        address,
+        Some(block),
+    )
+    .await
+    {
+        Ok(res) => res._0,
+        Err(_) => {
+            make_call_request(
+                ICurvePoolLegacy::balancesCall::new((i as i128,)),
+                provider,
+                address,
+                Some(block),
+            )
+            .await?
+            ._0
+        }
+    };
+
+    Ok(balance.to_scaled_rational(decimals))
+}
+
+pub(crate) async fn fetch_decimals<T: TracingProvider>(
+    coin: Address,
+    provider: &Arc<T>,
+    block: u64,
+) -> Result<u8, AmmError> {
+    if coin == ETH_ADDRESS {
+        return Ok(18)
+    }
+
+    Ok(make_call_request(ICurveErc20::decimalsCall::new(()), provider, coin, Some(block))
+        .await?
+        ._0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_index_searches_all_coins() {
+        let dai = Address::with_last_byte(1);
+        let usdc = Address::with_last_byte(2);
+        let usdt = Address::with_last_byte(3);
+        let coins = [dai, usdc, usdt];
+
+        assert_eq!(quote_index(&coins, dai, usdc), Some((0, 1)));
+        assert_eq!(quote_index(&coins, usdt, usdc), Some((2, 1)));
+        assert_eq!(quote_index(&coins, dai, usdt), Some((0, 2)));
+        assert_eq!(quote_index(&coins, dai, dai), None);
+        assert_eq!(quote_index(&coins, dai, Address::with_last_byte(4)), None);
+
+        assert_eq!(default_quote(&coins, dai), usdc);
+        assert_eq!(default_quote(&coins, usdt), dai);
+    }
+
+    #[test]
+    fn test_quote_index_matches_weth_to_eth() {
+        let usdt = Address::with_last_byte(1);
+        let wbtc = Address::with_last_byte(2);
+        let coins = [usdt, wbtc, ETH_ADDRESS];
+
+        assert_eq!(quote_index(&coins, WETH_ADDRESS, usdt), Some((2, 0)));
+        assert_eq!(quote_index(&coins, wbtc, WETH_ADDRESS), Some((1, 2)));
+    }
+}
@@ -0,0 +1,326 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log};
+use async_trait::async_trait;
+use brontes_types::{
+    db::redefined_types::{malachite::RationalRedefined, primitives::AddressRedefined},
+    normalized_actions::Action,
+    traits::TracingProvider,
+    ToFloatNearest, ToScaledRational,
+};
+use malachite::{
+    num::basic::traits::{One, Zero},
+    Rational,
+};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    coin_index, default_quote, fetch_balance, fetch_coins, fetch_decimals, is_metapool,
+    quote_index, ICurvePool,
+};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request,
+    protocols::apply_action,
+    Protocol, UpdatableProtocol,
+};
+
+const A_PRECISION: u64 = 100;
+const MAX_ITERATIONS: usize = 255;
+
+/// State for all Curve StableSwap style pools. This covers the base pools,
+/// plain pools, metapools and the crvUSD pools as they all share the same
+/// invariant.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct CurveStablePool {
+    pub address:          Address,
+    #[redefined(same_fields)]
+    pub protocol:         Protocol,
+    pub coins:            Vec<Address>,
+    /// balances scaled by the coins decimals
+    pub balances:         Vec<Rational>,
+    /// multiplier to get the normalized balance of each coin. this is 1 unless
+    /// the coin is the lp token of a metapool's base pool or the pool uses
+    /// rate oracles.
+    pub rate_multipliers: Vec<Rational>,
+    /// the coins of the base pool if this pool is a metapool
+    pub base_coins:       Vec<Address>,
+    pub amp:              f64,
+}
+
+#[async_trait]
+impl UpdatableProtocol for CurveStablePool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.coins.clone()
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        self.calculate_price_in(base_token, default_quote(&self.coins, base_token))
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        let coins = &self.coins;
+        let base_coins = &self.base_coins;
+        let lp_rate = self.rate_multipliers.last().cloned();
+
+        apply_action(self.address, &mut self.balances, action, |token, amount| {
+            if let Some(idx) = coin_index(coins, token) {
+                return Some((idx, amount.clone()))
+            }
+            // underlying swaps on metapools move the base pool lp token. we
+            // approximate the amount of lp using the virtual price.
+            coin_index(base_coins, token)?;
+            Some((coins.len() - 1, amount / lp_rate.as_ref()?))
+        })
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+}
+
+impl CurveStablePool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        provider: Arc<T>,
+        block: u64,
+    ) -> Result<Self, AmmError> {
+        let coins = fetch_coins(address, &provider, block).await?;
+
+        let mut balances = Vec::with_capacity(coins.len());
+        let mut decimals = Vec::with_capacity(coins.len());
+        for (i, coin) in coins.iter().enumerate() {
+            let dec = fetch_decimals(*coin, &provider, block).await?;
+            balances.push(fetch_balance(address, i, dec, &provider, block).await?);
+            decimals.push(dec);
+        }
+
+        let amp = match make_call_request(
+            ICurvePool::A_preciseCall::new(()),
+            &provider,
+            address,
+            Some(block),
+        )
+        .await
+        {
+            Ok(res) => res._0.to_scaled_rational(0) / Rational::from(A_PRECISION),
+            Err(_) => {
+                make_call_request(ICurvePool::ACall::new(()), &provider, address, Some(block))
+                    .await?
+                    ._0
+                    .to_scaled_rational(0)
+            }
+        }
+        .to_float();
+
+        let (rate_multipliers, base_coins) = if is_metapool(protocol) {
+            let base_pool = make_call_request(
+                ICurvePool::base_poolCall::new(()),
+                &provider,
+                address,
+                Some(block),
+            )
+            .await?
+            ._0;
+            let virtual_price = make_call_request(
+                ICurvePool::get_virtual_priceCall::new(()),
+                &provider,
+                base_pool,
+                Some(block),
+            )
+            .await?
+            ._0
+            .to_scaled_rational(18);
+
+            let mut rates = vec![Rational::ONE; coins.len()];
+            *rates.last_mut().unwrap() = virtual_price;
+
+            (rates, fetch_coins(base_pool, &provider, block).await?)
+        } else if let Ok(rates) = make_call_request(
+            ICurvePool::stored_ratesCall::new(()),
+            &provider,
+            address,
+            Some(block),
+        )
+        .await
+        {
+            // stableswap-ng pools store `10 ** (36 - decimals) * oracle_rate / 1e18`
+            let rates = rates
+                ._0
+                .into_iter()
+                .zip(decimals.iter())
+                .map(|(rate, dec)| rate.to_scaled_rational(36 - dec))
+                .collect();
+
+            (rates, vec![])
+        } else {
+            (vec![Rational::ONE; coins.len()], vec![])
+        };
+
+        let pool = Self { address, protocol, coins, balances, rate_multipliers, base_coins, amp };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        self.coins.len() == self.balances.len()
+            && self.coins.len() == self.rate_multipliers.len()
+            && self.amp > 0.0
+            && self.balances.iter().all(|b| *b > Rational::ZERO)
+    }
+
+    /// the price of the base coin in terms of the quote coin
+    pub fn calculate_price_in(
+        &self,
+        base_token: Address,
+        quote_token: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let (base, quote) = quote_index(&self.coins, base_token, quote_token)
+            .ok_or(ArithmeticError::TokenNotInPool(base_token))?;
+        let xp = self.xp()?;
+
+        let price = spot_price(&xp, self.ann(), base, quote)
+            .ok_or(ArithmeticError::InvariantNotConverged)?;
+        let price = price * self.rate_multipliers[base].clone().to_float()
+            / self.rate_multipliers[quote].clone().to_float();
+
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let Some((base, quote)) = quote_index(&self.coins, base, quote) else {
+            return (self.balances[0].clone(), self.balances[1].clone())
+        };
+
+        (self.balances[base].clone(), self.balances[quote].clone())
+    }
+
+    fn ann(&self) -> f64 {
+        self.amp * self.coins.len() as f64
+    }
+
+    /// balances normalized to the same unit of account
+    fn xp(&self) -> Result<Vec<f64>, ArithmeticError> {
+        let xp = self
+            .balances
+            .iter()
+            .zip(self.rate_multipliers.iter())
+            .map(|(balance, rate)| (balance * rate).to_float())
+            .collect::<Vec<_>>();
+
+        if xp.iter().any(|x| *x <= 0.0) {
+            return Err(ArithmeticError::YIsZero)
+        }
+
+        Ok(xp)
+    }
+}
+
+/// Solves for D using the same newton iteration the pool contracts use.
+///
+/// `A * n * sum(x) + D = A * n * D + D^(n+1) / (n^n * prod(x))`
+pub(crate) fn get_d(xp: &[f64], ann: f64) -> Option<f64> {
+    let n = xp.len() as f64;
+    let sum: f64 = xp.iter().sum();
+    if sum == 0.0 {
+        return Some(0.0)
+    }
+
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        let d_p = xp.iter().fold(d, |d_p, x| d_p * d / (x * n));
+        let d_prev = d;
+        d = (ann * sum + d_p * n) * d / ((ann - 1.0) * d + (n + 1.0) * d_p);
+
+        if (d - d_prev).abs() <= d * 1e-14 {
+            return Some(d)
+        }
+    }
+
+    None
+}
+
+/// The marginal amount of coin `j` received for coin `i` in normalized units.
+/// Given the invariant F, this is `dF/dx_i / dF/dx_j` where
+/// `dF/dx_k = A * n + D^(n+1) / (n^n * prod(x) * x_k)`.
+pub(crate) fn spot_price(xp: &[f64], ann: f64, i: usize, j: usize) -> Option<f64> {
+    let n = xp.len() as f64;
+    let d = get_d(xp, ann)?;
+    let d_p = xp.iter().fold(d, |d_p, x| d_p * d / (x * n));
+
+    let price = (ann + d_p / xp[i]) / (ann + d_p / xp[j]);
+    price.is_finite().then_some(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balanced_pool_is_at_peg() {
+        let xp = [1_000_000.0, 1_000_000.0, 1_000_000.0];
+        let price = spot_price(&xp, 2000.0 * 3.0, 0, 1).unwrap();
+        assert!((price - 1.0).abs() < 1e-12, "price={price}");
+
+        let d = get_d(&xp, 2000.0 * 3.0).unwrap();
+        assert!((d - 3_000_000.0).abs() < 1e-6, "d={d}");
+    }
+
+    #[test]
+    fn test_imbalanced_pool_prices_scarce_coin_higher() {
+        // more of coin 0 in the pool, so coin 1 should be worth more
+        let xp = [1_500_000.0, 500_000.0];
+        let ann = 100.0 * 2.0;
+
+        let price_0_in_1 = spot_price(&xp, ann, 0, 1).unwrap();
+        let price_1_in_0 = spot_price(&xp, ann, 1, 0).unwrap();
+
+        assert!(price_0_in_1 < 1.0);
+        assert!(price_1_in_0 > 1.0);
+        assert!((price_0_in_1 * price_1_in_0 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_prices_pairs_past_the_first_two_coins() {
+        let dai = Address::with_last_byte(1);
+        let usdc = Address::with_last_byte(2);
+        let usdt = Address::with_last_byte(3);
+        let pool = CurveStablePool {
+            coins: vec![dai, usdc, usdt],
+            balances: vec![
+                Rational::from(1_000_000),
+                Rational::from(1_000_000),
+                Rational::from(500_000),
+            ],
+            rate_multipliers: vec![Rational::from(1); 3],
+            amp: 2000.0,
+            ..Default::default()
+        };
+
+        let usdt_in_usdc = pool.calculate_price_in(usdt, usdc).unwrap().to_float();
+        let usdc_in_usdt = pool.calculate_price_in(usdc, usdt).unwrap().to_float();
+        assert!(usdt_in_usdc > 1.0, "price={usdt_in_usdc}");
+        assert!((usdt_in_usdc * usdc_in_usdt - 1.0).abs() < 1e-9);
+
+        // dai and usdc are balanced, so usdt is priced the same in both
+        let usdt_in_dai = pool.calculate_price_in(usdt, dai).unwrap().to_float();
+        assert!((usdt_in_dai - usdt_in_usdc).abs() < 1e-9);
+
+        assert_eq!(pool.get_tvl(usdt, usdc), (Rational::from(500_000), Rational::from(1_000_000)));
+        assert!(pool
+            .calculate_price_in(usdt, Address::with_last_byte(4))
+            .is_err());
+    }
+}
@@ -29,6 +29,8 @@ pub enum AmmError {
     UnrecognizedPoolCreatedEventLog,
     #[error("Error when syncing pool")]
     SyncError(Address),
+    #[error("Pool {0:?} can only be synced from logs")]
+    ActionSyncUnsupported(Address),
     #[error("Error when getting pool data")]
     PoolDataError,
     #[error("Arithmetic error")]
@@ -73,6 +75,12 @@ pub enum ArithmeticError {
     UniswapV3MathError(#[from] UniswapV3MathError),
     #[error("v2 div by zero")]
     UniV2DivZero,
+    #[error("Invariant did not converge")]
+    InvariantNotConverged,
+    #[error("balancer div by zero")]
+    BalancerDivZero,
+    #[error("Token {0:?} is not in the pool")]
+    TokenNotInPool(Address),
 }
 
 #[derive(Error, Debug)]
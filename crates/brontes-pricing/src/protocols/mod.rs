@@ -1,7 +1,11 @@
+pub mod balancer;
+pub mod curve;
 pub mod errors;
 pub mod lazy;
 pub mod uniswap_v2;
 pub mod uniswap_v3;
+pub mod uniswap_v4;
+pub mod wrapper;
 
 use std::{future::Future, sync::Arc};
 
@@ -9,15 +13,19 @@ use alloy_primitives::{Address, Log};
 use async_trait::async_trait;
 use brontes_types::{normalized_actions::Action, pair::Pair, traits::TracingProvider};
 pub use brontes_types::{queries::make_call_request, Protocol};
-use malachite::Rational;
+use malachite::{num::basic::traits::Zero, Rational};
 use tracing::{debug, warn};
 
 use crate::{
+    balancer::{load_v2_pool, BalancerWeightedPool},
+    curve::{CurveCryptoPool, CurveStablePool},
     lazy::{PoolFetchError, PoolFetchSuccess},
     protocols::errors::{AmmError, ArithmeticError},
     types::PairWithFirstPoolHop,
     uniswap_v2::UniswapV2Pool,
     uniswap_v3::UniswapV3Pool,
+    uniswap_v4::UniswapV4Pool,
+    wrapper::WrapperPool,
     LoadResult, PoolState,
 };
 
@@ -30,6 +38,64 @@ pub trait UpdatableProtocol {
     fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError>;
 }
 
+/// Applies the balance changes of a classified action to a pool that tracks
+/// its balances per token. `lookup` maps a token and amount to the index of the
+/// token in the pool and the amount in terms of that token.
+pub(crate) fn apply_action(
+    address: Address,
+    balances: &mut [Rational],
+    action: Action,
+    lookup: impl Fn(Address, &Rational) -> Option<(usize, Rational)>,
+) -> Result<(), AmmError> {
+    let deltas = match action {
+        Action::Swap(s) => vec![
+            (s.token_in.address, s.amount_in, true),
+            (s.token_out.address, s.amount_out, false),
+        ],
+        Action::SwapWithFee(s) => vec![
+            (s.swap.token_in.address, s.swap.amount_in, true),
+            (s.swap.token_out.address, s.swap.amount_out, false),
+        ],
+        Action::Mint(m) => m
+            .token
+            .into_iter()
+            .zip(m.amount)
+            .map(|(token, amount)| (token.address, amount, true))
+            .collect(),
+        Action::Burn(b) => b
+            .token
+            .into_iter()
+            .zip(b.amount)
+            .map(|(token, amount)| (token.address, amount, false))
+            .collect(),
+        // transfers into the pool get routed here as well, these are already
+        // accounted for by the swap / mint / burn
+        _ => return Ok(()),
+    };
+
+    let deltas = deltas
+        .into_iter()
+        .map(|(token, amount, is_add)| {
+            let (idx, amount) = lookup(token, &amount).ok_or(AmmError::SyncError(address))?;
+            Ok((idx, amount, is_add))
+        })
+        .collect::<Result<Vec<_>, AmmError>>()?;
+
+    for (idx, amount, is_add) in deltas {
+        let balance = &mut balances[idx];
+        if is_add {
+            *balance += amount;
+        } else {
+            *balance -= amount;
+            if *balance < Rational::ZERO {
+                *balance = Rational::ZERO;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub trait LoadState {
     fn has_state_updater(&self) -> bool;
     fn try_load_state<T: TracingProvider>(
@@ -52,6 +118,28 @@ impl LoadState for Protocol {
                 | Self::SushiSwapV3
                 | Self::PancakeSwapV2
                 | Self::PancakeSwapV3
+                | Self::CurveBasePool2
+                | Self::CurveBasePool3
+                | Self::CurveBasePool4
+                | Self::CurveV1MetaPool
+                | Self::CurveV1MetapoolImpl
+                | Self::CurveV2MetaPool
+                | Self::CurveV2MetapoolImpl
+                | Self::CurveV2PlainPool
+                | Self::CurveV2PlainPoolImpl
+                | Self::CurvecrvUSDMetaPool
+                | Self::CurvecrvUSDMetapoolImpl
+                | Self::CurvecrvUSDPlainPool
+                | Self::CurvecrvUSDPlainPoolImpl
+                | Self::CurveCryptoSwapPool
+                | Self::CurveTriCryptoPool
+                | Self::BalancerV1
+                | Self::BalancerV1CRP
+                | Self::BalancerV2
+                | Self::UniswapV4
+                | Self::LidoStETH
+                | Self::LidoWstETH
+                | Self::RocketPoolRETH
         )
     }
 
@@ -120,6 +208,196 @@ impl LoadState for Protocol {
                     res,
                 ))
             }
+            Self::CurveBasePool2
+            | Self::CurveBasePool3
+            | Self::CurveBasePool4
+            | Self::CurveV1MetaPool
+            | Self::CurveV1MetapoolImpl
+            | Self::CurveV2MetaPool
+            | Self::CurveV2MetapoolImpl
+            | Self::CurveV2PlainPool
+            | Self::CurveV2PlainPoolImpl
+            | Self::CurvecrvUSDMetaPool
+            | Self::CurvecrvUSDMetapoolImpl
+            | Self::CurvecrvUSDPlainPool
+            | Self::CurvecrvUSDPlainPoolImpl => {
+                let (pool, res) = if let Ok(pool) = CurveStablePool::new_load_on_block(
+                    address,
+                    self,
+                    provider.clone(),
+                    block_number - 1,
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        CurveStablePool::new_load_on_block(address, self, provider, block_number)
+                            .await
+                            .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, self, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::CurveStable(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::CurveCryptoSwapPool | Self::CurveTriCryptoPool => {
+                let (pool, res) = if let Ok(pool) = CurveCryptoPool::new_load_on_block(
+                    address,
+                    self,
+                    provider.clone(),
+                    block_number - 1,
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        CurveCryptoPool::new_load_on_block(address, self, provider, block_number)
+                            .await
+                            .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, self, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::CurveCrypto(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::BalancerV1 | Self::BalancerV1CRP => {
+                let (pool, res) = if let Ok(pool) = BalancerWeightedPool::new_load_on_block(
+                    address,
+                    self,
+                    provider.clone(),
+                    block_number - 1,
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        BalancerWeightedPool::new_load_on_block(
+                            address,
+                            self,
+                            provider,
+                            block_number,
+                        )
+                        .await
+                        .map_err(|e| {
+                            debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                            (address, self, block_number, pool_pair, fp, e)
+                        })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::BalancerWeighted(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::BalancerV2 => {
+                let (variant, res) = if let Ok(variant) =
+                    load_v2_pool(address, provider.clone(), block_number - 1).await
+                {
+                    (variant, LoadResult::Ok)
+                } else {
+                    (
+                        load_v2_pool(address, provider, block_number).await.map_err(|e| {
+                            debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                            (address, self, block_number, pool_pair, fp, e)
+                        })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((block_number, address, PoolState::new(variant, block_number), res))
+            }
+            Self::UniswapV4 => {
+                let (pool, res) = if let Ok(pool) =
+                    UniswapV4Pool::new_load_on_block(address, provider.clone(), block_number - 1)
+                        .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        UniswapV4Pool::new_load_on_block(address, provider, block_number)
+                            .await
+                            .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, self, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::UniswapV4(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::LidoStETH | Self::LidoWstETH | Self::RocketPoolRETH => {
+                let (pool, res) = if let Ok(pool) = WrapperPool::new_load_on_block(
+                    address,
+                    self,
+                    provider.clone(),
+                    block_number - 1,
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        WrapperPool::new_load_on_block(address, self, provider, block_number)
+                            .await
+                            .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, self, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::Wrapper(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
             rest => {
                 warn!(protocol=?rest, "no state updater is build for");
                 Err((address, self, block_number, pool_pair, fp, AmmError::UnsupportedProtocol))
@@ -8,11 +8,16 @@ use alloy_rlp::{RlpDecodable, RlpEncodable};
 use alloy_sol_macro::sol;
 use alloy_sol_types::SolEvent;
 use async_trait::async_trait;
-use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToScaledRational};
+use brontes_types::{
+    db::redefined_types::primitives::AddressRedefined, normalized_actions::Action,
+    traits::TracingProvider, ToScaledRational,
+};
 use malachite::{
     num::{arithmetic::traits::Pow, basic::traits::Zero},
     Natural, Rational,
 };
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
 use self::batch_request::get_v2_pool_data;
@@ -49,8 +54,19 @@ pub const SYNC_EVENT_SIGNATURE: B256 = FixedBytes([
 ]);
 
 #[derive(
-    Debug, Clone, Default, Serialize, Deserialize, RlpEncodable, RlpDecodable, Hash, PartialEq, Eq,
+    Debug,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    RlpEncodable,
+    RlpDecodable,
+    Hash,
+    PartialEq,
+    Eq,
+    Redefined,
 )]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct UniswapV2Pool {
     pub address:          Address,
     pub token_a:          Address,
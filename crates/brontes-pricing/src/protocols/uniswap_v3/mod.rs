@@ -7,9 +7,14 @@ use alloy_sol_macro::sol;
 use alloy_sol_types::{SolCall, SolEvent};
 use async_trait::async_trait;
 use brontes_types::{
-    normalized_actions::Action, traits::TracingProvider, FastHashMap, ToScaledRational,
+    db::redefined_types::primitives::{AddressRedefined, U256Redefined},
+    normalized_actions::Action,
+    traits::TracingProvider,
+    FastHashMap, ToScaledRational,
 };
 use malachite::Rational;
+use redefined::{Redefined, RedefinedConvert};
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
 use self::batch_request::get_v3_pool_data_batch_request;
@@ -143,7 +148,19 @@ pub struct UniswapV3Pool {
     pub reserve_1: U256,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, Hash, PartialEq, Eq)]
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    Hash,
+    PartialEq,
+    Eq,
+    rSerialize,
+    rDeserialize,
+    Archive,
+)]
 pub struct Info {
     pub liquidity_gross: u128,
     pub liquidity_net:   i128,
@@ -156,6 +173,74 @@ impl Info {
     }
 }
 
+#[derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive, Redefined)]
+#[redefined(UniswapV3Pool)]
+#[redefined_attr(to_source = "self.into_pool()", from_source = "UniswapV3PoolRedefined::new(src)")]
+pub struct UniswapV3PoolRedefined {
+    pub address:          AddressRedefined,
+    pub token_a:          AddressRedefined,
+    pub token_a_decimals: u8,
+    pub token_b:          AddressRedefined,
+    pub token_b_decimals: u8,
+    pub liquidity:        u128,
+    pub sqrt_price:       U256Redefined,
+    pub fee:              u32,
+    pub tick:             i32,
+    pub tick_spacing:     i32,
+    pub tick_bitmap:      Vec<(i16, U256Redefined)>,
+    pub ticks:            Vec<(i32, Info)>,
+    pub reserve_0:        U256Redefined,
+    pub reserve_1:        U256Redefined,
+}
+
+impl UniswapV3PoolRedefined {
+    fn new(pool: UniswapV3Pool) -> Self {
+        Self {
+            address:          AddressRedefined::from_source(pool.address),
+            token_a:          AddressRedefined::from_source(pool.token_a),
+            token_a_decimals: pool.token_a_decimals,
+            token_b:          AddressRedefined::from_source(pool.token_b),
+            token_b_decimals: pool.token_b_decimals,
+            liquidity:        pool.liquidity,
+            sqrt_price:       U256Redefined::from_source(pool.sqrt_price),
+            fee:              pool.fee,
+            tick:             pool.tick,
+            tick_spacing:     pool.tick_spacing,
+            tick_bitmap:      pool
+                .tick_bitmap
+                .into_iter()
+                .map(|(word, bitmap)| (word, U256Redefined::from_source(bitmap)))
+                .collect(),
+            ticks:            pool.ticks.into_iter().collect(),
+            reserve_0:        U256Redefined::from_source(pool.reserve_0),
+            reserve_1:        U256Redefined::from_source(pool.reserve_1),
+        }
+    }
+
+    fn into_pool(self) -> UniswapV3Pool {
+        UniswapV3Pool {
+            address:          self.address.to_source(),
+            token_a:          self.token_a.to_source(),
+            token_a_decimals: self.token_a_decimals,
+            token_b:          self.token_b.to_source(),
+            token_b_decimals: self.token_b_decimals,
+            liquidity:        self.liquidity,
+            sqrt_price:       self.sqrt_price.to_source(),
+            fee:              self.fee,
+            tick:             self.tick,
+            tick_spacing:     self.tick_spacing,
+            tick_bitmap:      self
+                .tick_bitmap
+                .into_iter()
+                .map(|(word, bitmap)| (word, bitmap.to_source()))
+                .collect(),
+            ticks:            self.ticks.into_iter().collect(),
+            reserve_0:        self.reserve_0.to_source(),
+            reserve_1:        self.reserve_1.to_source(),
+        }
+    }
+}
+
 #[async_trait]
 impl UpdatableProtocol for UniswapV3Pool {
     fn address(&self) -> Address {
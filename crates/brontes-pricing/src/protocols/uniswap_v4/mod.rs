@@ -0,0 +1,304 @@
+use std::{
+    cmp::Ordering,
+    sync::{Arc, OnceLock},
+};
+
+use alloy_primitives::{hex, keccak256, Address, Log, B256, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolEvent;
+use async_trait::async_trait;
+pub use brontes_types::db::uniswap_v4_pool_key::{pool_id_to_address, UniswapV4PoolKey};
+use brontes_types::{
+    db::redefined_types::primitives::{AddressRedefined, B256Redefined, U256Redefined},
+    normalized_actions::Action,
+    traits::TracingProvider,
+    ToScaledRational,
+};
+use dashmap::DashMap;
+use malachite::Rational;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::make_call_request;
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    uniswap_v3::uniswap_v3_math,
+    UpdatableProtocol,
+};
+
+/// The singleton that holds the state of all v4 pools.
+pub const POOL_MANAGER: Address = Address::new(hex!("000000000004444c5dc75cB358380D2e3dE08A90"));
+
+/// storage slot of the `pools` mapping in the pool manager
+const POOLS_SLOT: u64 = 6;
+/// offset of the liquidity from the start of a pool's state
+const LIQUIDITY_OFFSET: u64 = 3;
+
+sol!(
+    interface IUniswapV4PoolManager {
+        event Swap(
+            bytes32 indexed id,
+            address indexed sender,
+            int128 amount0,
+            int128 amount1,
+            uint160 sqrtPriceX96,
+            uint128 liquidity,
+            int24 tick,
+            uint24 fee
+        );
+        event ModifyLiquidity(
+            bytes32 indexed id,
+            address indexed sender,
+            int24 tickLower,
+            int24 tickUpper,
+            int256 liquidityDelta,
+            bytes32 salt
+        );
+    }
+);
+
+sol!(
+    interface IUniswapV4Erc20 {
+        function decimals() external view returns (uint8);
+    }
+);
+
+/// Given a v4 pool address is derived from the pool id, we need the pool key
+/// to go back to the pool id when loading state. Every call into the pool
+/// manager contains the key, so the classifier registers it here when it
+/// classifies an action for the pool. This happens before the pricing engine
+/// receives the update for the pool.
+///
+/// The classifier also persists the key in libmdbx when the pool is
+/// initialized, the registry is filled with the stored keys on startup so
+/// that pools that haven't been touched since can still be loaded.
+fn pool_keys() -> &'static DashMap<Address, UniswapV4PoolKey> {
+    static POOL_KEYS: OnceLock<DashMap<Address, UniswapV4PoolKey>> = OnceLock::new();
+    POOL_KEYS.get_or_init(DashMap::default)
+}
+
+/// registers the key of a pool, returning the address of the pool
+pub fn register_pool_key(key: UniswapV4PoolKey) -> Address {
+    let address = key.pool_address();
+    pool_keys().insert(address, key);
+
+    address
+}
+
+pub fn get_pool_key(address: Address) -> Option<UniswapV4PoolKey> {
+    pool_keys().get(&address).map(|key| *key)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct UniswapV4Pool {
+    pub address:          Address,
+    pub pool_id:          B256,
+    pub token_a:          Address,
+    pub token_a_decimals: u8,
+    pub token_b:          Address,
+    pub token_b_decimals: u8,
+    pub hooks:            Address,
+    pub fee:              u32,
+    pub tick_spacing:     i32,
+    pub liquidity:        u128,
+    pub sqrt_price:       U256,
+    pub tick:             i32,
+}
+
+#[async_trait]
+impl UpdatableProtocol for UniswapV4Pool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        vec![self.token_a, self.token_b]
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        if self.liquidity <= 10_000 {
+            return Err(ArithmeticError::UniswapV3MathError(
+                uniswap_v3_math::error::UniswapV3MathError::LiquidityTooLow(self.liquidity),
+            ))
+        }
+
+        let tick = uniswap_v3_math::tick_math::get_tick_at_sqrt_ratio(self.sqrt_price)?;
+        let shift = self.token_a_decimals as i8 - self.token_b_decimals as i8;
+        let price = match shift.cmp(&0) {
+            Ordering::Less => 1.0001_f64.powi(tick) / 10_f64.powi(-shift as i32),
+            Ordering::Greater => 1.0001_f64.powi(tick) * 10_f64.powi(shift as i32),
+            Ordering::Equal => 1.0001_f64.powi(tick),
+        };
+
+        let price = if base_token == self.token_a { price } else { 1.0 / price };
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+
+    fn sync_from_action(&mut self, _action: Action) -> Result<(), AmmError> {
+        Err(AmmError::ActionSyncUnsupported(self.address))
+    }
+
+    /// the pool manager emits the events of all pools, so we only apply the
+    /// ones for this pool.
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let event_signature = log.topics()[0];
+
+        if event_signature == IUniswapV4PoolManager::Swap::SIGNATURE_HASH {
+            let swap = IUniswapV4PoolManager::Swap::decode_log_data(&log, false)?;
+            if swap.id != self.pool_id {
+                return Ok(())
+            }
+
+            self.sqrt_price = U256::from(swap.sqrtPriceX96);
+            self.liquidity = swap.liquidity;
+            self.tick = swap.tick;
+        } else if event_signature == IUniswapV4PoolManager::ModifyLiquidity::SIGNATURE_HASH {
+            let modify = IUniswapV4PoolManager::ModifyLiquidity::decode_log_data(&log, false)?;
+            if modify.id != self.pool_id {
+                return Ok(())
+            }
+
+            // only liquidity of positions in range is active
+            if self.tick >= modify.tickLower && self.tick < modify.tickUpper {
+                let delta = i128::try_from(modify.liquidityDelta)
+                    .map_err(|_| ArithmeticError::U128ConversionError)?;
+                self.liquidity = self.liquidity.saturating_add_signed(delta);
+            }
+        } else {
+            Err(EventLogError::InvalidEventSignature)?
+        }
+
+        Ok(())
+    }
+}
+
+impl UniswapV4Pool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        provider: Arc<T>,
+        block: u64,
+    ) -> Result<Self, AmmError> {
+        let key = get_pool_key(address).ok_or(AmmError::NoStateError(address))?;
+        let pool_id = key.pool_id();
+
+        let mut buf = [0u8; 64];
+        buf[0..32].copy_from_slice(pool_id.as_slice());
+        buf[32..64].copy_from_slice(&U256::from(POOLS_SLOT).to_be_bytes::<32>());
+        let state_slot = keccak256(buf);
+
+        let slot0 = provider
+            .get_storage(Some(block), POOL_MANAGER, state_slot)
+            .await?
+            .unwrap_or_default();
+        let liquidity_slot =
+            B256::from(U256::from_be_bytes(state_slot.0) + U256::from(LIQUIDITY_OFFSET));
+        let liquidity = provider
+            .get_storage(Some(block), POOL_MANAGER, liquidity_slot)
+            .await?
+            .unwrap_or_default();
+
+        // slot0 is packed as | lp fee | protocol fee | tick | sqrt price |
+        let sqrt_price = slot0 & ((U256::from(1) << 160) - U256::from(1));
+        let tick = (((slot0 >> 160) & U256::from(0xffffff)).to::<u32>() << 8) as i32 >> 8;
+        let lp_fee = ((slot0 >> 208) & U256::from(0xffffff)).to::<u32>();
+        let liquidity = (liquidity & U256::from(u128::MAX)).to::<u128>();
+
+        let token_a = key.token0();
+        let token_b = key.token1();
+        let token_a_decimals = fetch_decimals(token_a, &provider, block).await?;
+        let token_b_decimals = fetch_decimals(token_b, &provider, block).await?;
+
+        let pool = Self {
+            address,
+            pool_id,
+            token_a,
+            token_a_decimals,
+            token_b,
+            token_b_decimals,
+            hooks: key.hooks,
+            fee: lp_fee,
+            tick_spacing: key.tick_spacing,
+            liquidity,
+            sqrt_price,
+            tick,
+        };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        !self.sqrt_price.is_zero()
+    }
+
+    /// v4 pools don't hold their own balances. We use the virtual reserves of
+    /// the active liquidity instead, `x = L / sqrt(P)` and `y = L * sqrt(P)`.
+    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+        let q96 = (U256::from(1) << 96).to_scaled_rational(0);
+        let sqrt_price = self.sqrt_price.to_scaled_rational(0) / &q96;
+        let liquidity = Rational::from(self.liquidity);
+
+        let (reserve_0, reserve_1) = if sqrt_price == 0 {
+            (Rational::from(0), Rational::from(0))
+        } else {
+            (&liquidity / &sqrt_price, liquidity * sqrt_price)
+        };
+        let reserve_0 = reserve_0.to_scaled_rational(self.token_a_decimals);
+        let reserve_1 = reserve_1.to_scaled_rational(self.token_b_decimals);
+
+        if self.token_a == base {
+            (reserve_0, reserve_1)
+        } else {
+            (reserve_1, reserve_0)
+        }
+    }
+}
+
+async fn fetch_decimals<T: TracingProvider>(
+    token: Address,
+    provider: &Arc<T>,
+    block: u64,
+) -> Result<u8, AmmError> {
+    Ok(make_call_request(IUniswapV4Erc20::decimalsCall::new(()), provider, token, Some(block))
+        .await?
+        ._0)
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::constants::WETH_ADDRESS;
+
+    use super::*;
+
+    #[test]
+    fn test_pool_key_registry() {
+        // ETH/USDC 0.05%
+        let key = UniswapV4PoolKey {
+            currency0:    Address::ZERO,
+            currency1:    Address::new(hex!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")),
+            fee:          500,
+            tick_spacing: 10,
+            hooks:        Address::ZERO,
+        };
+
+        let address = register_pool_key(key);
+        assert_eq!(address, pool_id_to_address(key.pool_id()));
+        assert_eq!(get_pool_key(address), Some(key));
+        assert_eq!(key.token0(), WETH_ADDRESS);
+    }
+
+    #[test]
+    fn test_sync_from_action_is_unsupported() {
+        let mut pool = UniswapV4Pool { address: Address::with_last_byte(1), ..Default::default() };
+
+        assert!(matches!(
+            pool.sync_from_action(Action::Revert),
+            Err(AmmError::ActionSyncUnsupported(address)) if address == Address::with_last_byte(1)
+        ));
+    }
+}
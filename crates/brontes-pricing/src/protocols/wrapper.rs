@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log};
+use alloy_sol_macro::sol;
+use async_trait::async_trait;
+use brontes_types::{
+    constants::{ETH_ADDRESS, STETH_ADDRESS, WETH_ADDRESS},
+    db::redefined_types::{malachite::RationalRedefined, primitives::AddressRedefined},
+    normalized_actions::Action,
+    traits::TracingProvider,
+    ToScaledRational,
+};
+use malachite::{
+    num::{
+        arithmetic::traits::Reciprocal,
+        basic::traits::{One, Zero},
+    },
+    Rational,
+};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request, Protocol, UpdatableProtocol,
+};
+
+sol!(
+    interface ILiquidStakingToken {
+        function totalSupply() external view returns (uint256);
+        function stEthPerToken() external view returns (uint256);
+        function getExchangeRate() external view returns (uint256);
+    }
+);
+
+/// State for contracts that mint a token in exchange for a deposit of
+/// another, such as liquid staking tokens and their wrappers. The price
+/// between the two tokens is the exchange rate of the contract, which only
+/// moves when it is used.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct WrapperPool {
+    pub address:    Address,
+    #[redefined(same_fields)]
+    pub protocol:   Protocol,
+    /// the deposited token, WETH when ETH is deposited
+    pub underlying: Address,
+    /// the token minted by the contract
+    pub wrapped:    Address,
+    /// the amount of `underlying` one `wrapped` is worth
+    pub rate:       Rational,
+    /// the supply of `wrapped`
+    pub supply:     Rational,
+}
+
+#[async_trait]
+impl UpdatableProtocol for WrapperPool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        vec![self.underlying, self.wrapped]
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        if base_token == self.wrapped {
+            Ok(self.rate.clone())
+        } else if base_token == self.underlying {
+            Ok((&self.rate).reciprocal())
+        } else {
+            Err(ArithmeticError::TokenNotInPool(base_token))
+        }
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        let Action::Wrap(wrap) = action else { return Ok(()) };
+        if wrap.amount_in == Rational::ZERO || wrap.amount_out == Rational::ZERO {
+            return Ok(())
+        }
+
+        if as_weth(wrap.token_in.address) == self.underlying {
+            self.rate = &wrap.amount_in / &wrap.amount_out;
+            self.supply += wrap.amount_out;
+        } else if wrap.token_in.address == self.wrapped {
+            self.rate = &wrap.amount_out / &wrap.amount_in;
+            self.supply -= wrap.amount_in;
+        } else {
+            return Err(AmmError::SyncError(self.address))
+        }
+
+        Ok(())
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+}
+
+impl WrapperPool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        provider: Arc<T>,
+        block: u64,
+    ) -> Result<Self, AmmError> {
+        let (underlying, rate) = match protocol {
+            Protocol::LidoStETH => (WETH_ADDRESS, Rational::ONE),
+            Protocol::LidoWstETH => (
+                STETH_ADDRESS,
+                make_call_request(
+                    ILiquidStakingToken::stEthPerTokenCall::new(()),
+                    &provider,
+                    address,
+                    Some(block),
+                )
+                .await?
+                ._0
+                .to_scaled_rational(18),
+            ),
+            Protocol::RocketPoolRETH => (
+                WETH_ADDRESS,
+                make_call_request(
+                    ILiquidStakingToken::getExchangeRateCall::new(()),
+                    &provider,
+                    address,
+                    Some(block),
+                )
+                .await?
+                ._0
+                .to_scaled_rational(18),
+            ),
+            _ => return Err(AmmError::UnsupportedProtocol),
+        };
+
+        let supply = make_call_request(
+            ILiquidStakingToken::totalSupplyCall::new(()),
+            &provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0
+        .to_scaled_rational(18);
+
+        let pool = Self { address, protocol, underlying, wrapped: address, rate, supply };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        self.rate > Rational::ZERO && self.supply > Rational::ZERO
+    }
+
+    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+        let underlying = &self.supply * &self.rate;
+        if base == self.wrapped {
+            (self.supply.clone(), underlying)
+        } else {
+            (underlying, self.supply.clone())
+        }
+    }
+}
+
+/// Pricing treats ETH as WETH
+fn as_weth(token: Address) -> Address {
+    if token == ETH_ADDRESS {
+        WETH_ADDRESS
+    } else {
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+    use brontes_types::{db::token_info::TokenInfoWithAddress, normalized_actions::NormalizedWrap};
+
+    use super::*;
+
+    #[test]
+    fn test_sync_from_wrap() {
+        let wst_eth = Address::new(hex!("7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0"));
+        let mut pool = WrapperPool {
+            address:    wst_eth,
+            protocol:   Protocol::LidoWstETH,
+            underlying: STETH_ADDRESS,
+            wrapped:    wst_eth,
+            rate:       Rational::from_unsigneds(11u32, 10u32),
+            supply:     Rational::from(100),
+        };
+
+        let st_eth = TokenInfoWithAddress { address: STETH_ADDRESS, ..Default::default() };
+        let wrapped = TokenInfoWithAddress { address: wst_eth, ..Default::default() };
+        let wrap = NormalizedWrap {
+            contract: wst_eth,
+            token_in: st_eth.clone(),
+            token_out: wrapped.clone(),
+            amount_in: Rational::from(12),
+            amount_out: Rational::from(10),
+            ..Default::default()
+        };
+        pool.sync_from_action(Action::Wrap(wrap)).unwrap();
+
+        assert_eq!(pool.rate, Rational::from_unsigneds(6u32, 5u32));
+        assert_eq!(pool.supply, Rational::from(110));
+        assert_eq!(pool.calculate_price(wst_eth).unwrap(), Rational::from_unsigneds(6u32, 5u32));
+        assert_eq!(
+            pool.calculate_price(STETH_ADDRESS).unwrap(),
+            Rational::from_unsigneds(5u32, 6u32)
+        );
+
+        let unwrap = NormalizedWrap {
+            contract: wst_eth,
+            token_in: wrapped,
+            token_out: st_eth,
+            amount_in: Rational::from(10),
+            amount_out: Rational::from(13),
+            ..Default::default()
+        };
+        pool.sync_from_action(Action::Wrap(unwrap)).unwrap();
+
+        assert_eq!(pool.rate, Rational::from_unsigneds(13u32, 10u32));
+        assert_eq!(pool.supply, Rational::from(100));
+    }
+}
@@ -2,15 +2,27 @@ use std::fmt::{Debug, Display};
 
 use alloy_primitives::{wrap_fixed_bytes, Address, FixedBytes, Log};
 use brontes_types::{
-    constants::WETH_ADDRESS,
+    constants::{ETH_ADDRESS, WETH_ADDRESS},
     normalized_actions::{pool::NormalizedPoolConfigUpdate, Action},
     pair::Pair,
 };
 use malachite::Rational;
+use redefined::{Redefined, RedefinedConvert};
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    errors::ArithmeticError, uniswap_v2::UniswapV2Pool, uniswap_v3::UniswapV3Pool, LoadState,
-    Protocol, UpdatableProtocol,
+    balancer::{
+        BalancerStablePool, BalancerStablePoolRedefined, BalancerWeightedPool,
+        BalancerWeightedPoolRedefined,
+    },
+    curve::{CurveCryptoPool, CurveCryptoPoolRedefined, CurveStablePool, CurveStablePoolRedefined},
+    errors::ArithmeticError,
+    uniswap_v2::{UniswapV2Pool, UniswapV2PoolRedefined},
+    uniswap_v3::{UniswapV3Pool, UniswapV3PoolRedefined},
+    uniswap_v4::{UniswapV4Pool, UniswapV4PoolRedefined},
+    wrapper::{WrapperPool, WrapperPoolRedefined},
+    LoadState, Protocol, UpdatableProtocol,
 };
 
 wrap_fixed_bytes!(extra_derives:[],
@@ -51,22 +63,26 @@ impl PairWithFirstPoolHop {
     }
 }
 
+/// The price and tvl of a pool for the given pair. Pools can hold more than
+/// two tokens, so the quote token is needed to know which side to price
+/// against.
 pub trait ProtocolState: Debug {
-    fn price(&self, base: Address) -> Result<Rational, ArithmeticError>;
-    fn tvl(&self, base: Address) -> (Rational, Rational);
+    fn price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError>;
+    fn tvl(&self, base: Address, quote: Address) -> (Rational, Rational);
 }
 
 impl ProtocolState for PoolState {
-    fn tvl(&self, base: Address) -> (Rational, Rational) {
-        self.get_tvl(base)
+    fn tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        self.get_tvl(base, quote)
     }
 
-    fn price(&self, base: Address) -> Result<Rational, ArithmeticError> {
-        self.get_price(base)
+    fn price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError> {
+        self.get_price(base, quote)
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct PoolState {
     variant:         PoolVariants,
     pub last_update: u64,
@@ -76,8 +92,8 @@ impl Debug for PoolState {
         f.debug_struct("Pool State")
             .field("addr", &self.address())
             .field("pair", &self.pair())
-            .field("tvl 0", &self.get_tvl(self.pair().0).0)
-            .field("tvl 1", &self.get_tvl(self.pair().0).1)
+            .field("tvl 0", &self.get_tvl(self.pair().0, self.pair().1).0)
+            .field("tvl 1", &self.get_tvl(self.pair().0, self.pair().1).1)
             .field("block", &self.last_update)
             .finish()
     }
@@ -92,6 +108,15 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(v) => Pair(v.token_a, v.token_b),
             PoolVariants::UniswapV3(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::UniswapV4(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::CurveStable(v) => Pair(v.coins[0], v.coins[1]),
+            PoolVariants::CurveCrypto(v) => Pair(v.coins[0], v.coins[1]),
+            PoolVariants::BalancerWeighted(v) => Pair(v.tokens[0], v.tokens[1]),
+            PoolVariants::BalancerStable(v) => {
+                let tokens = v.tokens();
+                Pair(tokens[0], tokens[1])
+            }
+            PoolVariants::Wrapper(v) => Pair(v.underlying, v.wrapped),
         }
     }
 
@@ -99,6 +124,12 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(_) => Protocol::UniswapV2,
             PoolVariants::UniswapV3(_) => Protocol::UniswapV3,
+            PoolVariants::UniswapV4(_) => Protocol::UniswapV4,
+            PoolVariants::CurveStable(v) => v.protocol,
+            PoolVariants::CurveCrypto(v) => v.protocol,
+            PoolVariants::BalancerWeighted(v) => v.protocol,
+            PoolVariants::BalancerStable(_) => Protocol::BalancerV2,
+            PoolVariants::Wrapper(v) => v.protocol,
         }
     }
 
@@ -108,43 +139,145 @@ impl PoolState {
             return
         }
         self.last_update = state.block;
-        self.variant.increment_state(state.logs);
+        self.variant.increment_state(state);
     }
 
     pub fn address(&self) -> Address {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.address(),
             PoolVariants::UniswapV3(v) => v.address(),
+            PoolVariants::UniswapV4(v) => v.address(),
+            PoolVariants::CurveStable(v) => v.address(),
+            PoolVariants::CurveCrypto(v) => v.address(),
+            PoolVariants::BalancerWeighted(v) => v.address(),
+            PoolVariants::BalancerStable(v) => v.address(),
+            PoolVariants::Wrapper(v) => v.address(),
         }
     }
 
-    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.get_tvl(base),
             PoolVariants::UniswapV3(v) => v.get_tvl(base),
+            PoolVariants::UniswapV4(v) => v.get_tvl(base),
+            PoolVariants::CurveStable(v) => v.get_tvl(base, quote),
+            PoolVariants::CurveCrypto(v) => v.get_tvl(base, quote),
+            PoolVariants::BalancerWeighted(v) => v.get_tvl(base, quote),
+            PoolVariants::BalancerStable(v) => v.get_tvl(base, quote),
+            PoolVariants::Wrapper(v) => v.get_tvl(base),
         }
     }
 
-    pub fn get_price(&self, base: Address) -> Result<Rational, ArithmeticError> {
+    pub fn get_price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError> {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.calculate_price(base),
             PoolVariants::UniswapV3(v) => v.calculate_price(base),
+            PoolVariants::UniswapV4(v) => v.calculate_price(base),
+            PoolVariants::CurveStable(v) => v.calculate_price_in(base, quote),
+            PoolVariants::CurveCrypto(v) => v.calculate_price_in(base, quote),
+            PoolVariants::BalancerWeighted(v) => v.calculate_price_in(base, quote),
+            PoolVariants::BalancerStable(v) => v.calculate_price_in(base, quote),
+            PoolVariants::Wrapper(v) => v.calculate_price(base),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PoolVariants {
     UniswapV2(Box<UniswapV2Pool>),
     UniswapV3(Box<UniswapV3Pool>),
+    UniswapV4(Box<UniswapV4Pool>),
+    CurveStable(Box<CurveStablePool>),
+    CurveCrypto(Box<CurveCryptoPool>),
+    BalancerWeighted(Box<BalancerWeightedPool>),
+    BalancerStable(Box<BalancerStablePool>),
+    Wrapper(Box<WrapperPool>),
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive, Redefined)]
+#[redefined(PoolVariants)]
+#[redefined_attr(
+    to_source = "self.into_variant()",
+    from_source = "PoolVariantsRedefined::new(src)"
+)]
+pub enum PoolVariantsRedefined {
+    UniswapV2(UniswapV2PoolRedefined),
+    UniswapV3(UniswapV3PoolRedefined),
+    UniswapV4(UniswapV4PoolRedefined),
+    CurveStable(CurveStablePoolRedefined),
+    CurveCrypto(CurveCryptoPoolRedefined),
+    BalancerWeighted(BalancerWeightedPoolRedefined),
+    BalancerStable(BalancerStablePoolRedefined),
+    Wrapper(WrapperPoolRedefined),
+}
+
+impl PoolVariantsRedefined {
+    fn new(variant: PoolVariants) -> Self {
+        match variant {
+            PoolVariants::UniswapV2(v) => Self::UniswapV2(RedefinedConvert::from_source(*v)),
+            PoolVariants::UniswapV3(v) => Self::UniswapV3(RedefinedConvert::from_source(*v)),
+            PoolVariants::UniswapV4(v) => Self::UniswapV4(RedefinedConvert::from_source(*v)),
+            PoolVariants::CurveStable(v) => Self::CurveStable(RedefinedConvert::from_source(*v)),
+            PoolVariants::CurveCrypto(v) => Self::CurveCrypto(RedefinedConvert::from_source(*v)),
+            PoolVariants::BalancerWeighted(v) => {
+                Self::BalancerWeighted(RedefinedConvert::from_source(*v))
+            }
+            PoolVariants::BalancerStable(v) => {
+                Self::BalancerStable(RedefinedConvert::from_source(*v))
+            }
+            PoolVariants::Wrapper(v) => Self::Wrapper(RedefinedConvert::from_source(*v)),
+        }
+    }
+
+    fn into_variant(self) -> PoolVariants {
+        match self {
+            Self::UniswapV2(v) => PoolVariants::UniswapV2(Box::new(v.to_source())),
+            Self::UniswapV3(v) => PoolVariants::UniswapV3(Box::new(v.to_source())),
+            Self::UniswapV4(v) => PoolVariants::UniswapV4(Box::new(v.to_source())),
+            Self::CurveStable(v) => PoolVariants::CurveStable(Box::new(v.to_source())),
+            Self::CurveCrypto(v) => PoolVariants::CurveCrypto(Box::new(v.to_source())),
+            Self::BalancerWeighted(v) => PoolVariants::BalancerWeighted(Box::new(v.to_source())),
+            Self::BalancerStable(v) => PoolVariants::BalancerStable(Box::new(v.to_source())),
+            Self::Wrapper(v) => PoolVariants::Wrapper(Box::new(v.to_source())),
+        }
+    }
 }
 
 impl PoolVariants {
-    fn increment_state(&mut self, logs: Vec<Log>) {
+    fn increment_state(&mut self, update: PoolUpdate) {
+        match self {
+            // curve, balancer and wrapper pools don't emit enough data in their logs
+            // to track the balances, so we use the classified action instead
+            PoolVariants::CurveStable(a) => {
+                let _ = a.sync_from_action(update.action);
+            }
+            PoolVariants::CurveCrypto(a) => {
+                let _ = a.sync_from_action(update.action);
+            }
+            PoolVariants::BalancerWeighted(a) => {
+                let _ = a.sync_from_action(update.action);
+            }
+            PoolVariants::BalancerStable(a) => {
+                let _ = a.sync_from_action(update.action);
+            }
+            PoolVariants::Wrapper(a) => {
+                let _ = a.sync_from_action(update.action);
+            }
+            _ => self.sync_from_logs(update.logs),
+        }
+    }
+
+    fn sync_from_logs(&mut self, logs: Vec<Log>) {
         for log in logs {
             let _ = match self {
                 PoolVariants::UniswapV3(a) => a.sync_from_log(log),
                 PoolVariants::UniswapV2(a) => a.sync_from_log(log),
+                PoolVariants::UniswapV4(a) => a.sync_from_log(log),
+                PoolVariants::CurveStable(_)
+                | PoolVariants::CurveCrypto(_)
+                | PoolVariants::BalancerWeighted(_)
+                | PoolVariants::BalancerStable(_)
+                | PoolVariants::Wrapper(_) => Ok(()),
             };
         }
     }
@@ -242,7 +375,21 @@ impl PoolUpdate {
             Action::Transfer(t) => Some(Pair(t.token.address, quote)),
             Action::EthTransfer(_) => Some(Pair(WETH_ADDRESS, quote)),
             Action::Liquidation(l) => Some(Pair(l.collateral_asset.address, l.debt_asset.address)),
+            Action::Supply(s) => Some(Pair(s.token.address, quote)),
+            Action::Withdraw(w) => Some(Pair(w.token.address, quote)),
+            Action::Loan(l) => Some(Pair(l.loaned_token.address, quote)),
+            Action::Repayment(r) => Some(Pair(r.repayed_token.address, quote)),
             Action::SwapWithFee(s) => Some(Pair(s.token_in.address, s.token_out.address)),
+            Action::OracleUpdate(_) => None,
+            Action::Wrap(w) => {
+                // eth is priced as weth
+                let [token_in, token_out] = [w.token_in.address, w.token_out.address]
+                    .map(|token| if token == ETH_ADDRESS { WETH_ADDRESS } else { token });
+                if token_in == token_out {
+                    return Some(Pair(token_in, quote))
+                }
+                Some(Pair(token_in, token_out))
+            }
             rest => {
                 tracing::debug!(?rest, "tried to get pair for action with no def");
                 None
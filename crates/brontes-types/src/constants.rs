@@ -40,6 +40,7 @@ pub const EURT_ADDRESS: Address = Address::new(hex!("c581b735a1688071a1746c968e0
 pub const LINK_ADDRESS: Address = Address::new(hex!("514910771af9ca656af840dff83e8264ecf986ca"));
 pub const UNI_TOKEN: Address = Address::new(hex!("1f9840a85d5af5bf1d1762f925bdaddc4201f984"));
 pub const XAUT_ADDRESS: Address = Address::new(hex!("68749665ff8d2d112fa859aa293f07a622782f38"));
+pub const STETH_ADDRESS: Address = Address::new(hex!("ae7ab96520DE3A18E5e111B5EaAb095312D7fE84"));
 
 /// The first block where the chainbound mempool data is available.
 pub const START_OF_CHAINBOUND_MEMPOOL_DATA: u64 = 17193367;
@@ -639,6 +639,11 @@ pub struct BlockAnalysis {
     pub liquidated_biggest_arb_revenue_amt:       Option<f64>,
     pub total_usd_liquidated:                     f64,
 
+    // liquidations that backrun an oracle update
+    pub oracle_liquidation_bundle_count:  u64,
+    pub oracle_liquidation_total_profit:  f64,
+    pub oracle_liquidation_total_revenue: f64,
+
     // builder
     pub builder_profit_usd:     f64,
     pub builder_profit_eth:     f64,
@@ -1170,6 +1175,7 @@ impl BlockAnalysis {
 
         let (liquidation_biggest_tx_rev, liquidation_biggest_rev) =
             Self::biggest_arb_revenue(|b| b == MevType::Liquidation, bundles).unzip();
+        let oracle_liquidations = Self::oracle_backrun_liquidations(bundles);
 
         Self {
             block_number: block.block_number,
@@ -1664,6 +1670,16 @@ impl BlockAnalysis {
             liquidated_biggest_arb_revenue:           liquidation_biggest_tx_rev,
             liquidated_biggest_arb_revenue_amt:       liquidation_biggest_rev,
 
+            oracle_liquidation_bundle_count:  oracle_liquidations.len() as u64,
+            oracle_liquidation_total_profit:  oracle_liquidations
+                .iter()
+                .map(|b| b.header.profit_usd)
+                .sum::<f64>(),
+            oracle_liquidation_total_revenue: oracle_liquidations
+                .iter()
+                .map(|b| b.header.profit_usd + b.header.bribe_usd)
+                .sum::<f64>(),
+
             builder_profit_usd:     block.builder_profit_usd,
             builder_profit_eth:     block.builder_eth_profit,
             builder_revenue_usd:    block.builder_profit_usd
@@ -1799,6 +1815,13 @@ impl BlockAnalysis {
             .max_by(|a, b| a.1.total_cmp(&b.1))
     }
 
+    fn oracle_backrun_liquidations(bundles: &[Bundle]) -> Vec<&Bundle> {
+        bundles
+            .iter()
+            .filter(|b| matches!(&b.data, BundleData::Liquidation(l) if l.is_oracle_backrun()))
+            .collect()
+    }
+
     fn total_revenue_by_type(mev_type: impl Fn(MevType) -> bool, bundles: &[Bundle]) -> f64 {
         bundles
             .iter()
@@ -2650,6 +2673,10 @@ impl Default for BlockAnalysis {
             liquidated_biggest_arb_revenue_amt: Default::default(),
             total_usd_liquidated: Default::default(),
 
+            oracle_liquidation_bundle_count:  Default::default(),
+            oracle_liquidation_total_profit:  Default::default(),
+            oracle_liquidation_total_revenue: Default::default(),
+
             builder_address:        Default::default(),
             builder_mev_profit_eth: Default::default(),
             builder_mev_profit_usd: Default::default(),
@@ -0,0 +1,483 @@
+use std::{collections::BTreeMap, hash::Hash, str::FromStr};
+
+use alloy_primitives::{wrap_fixed_bytes, Address, FixedBytes};
+use clickhouse::Row;
+use redefined::{self_convert_redefined, Redefined};
+use reth_db::DatabaseError;
+use reth_primitives::TxHash;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
+
+use crate::{
+    db::{
+        block_analysis::BlockAnalysis,
+        redefined_types::primitives::{AddressRedefined, TxHashRedefined},
+        searcher::Fund,
+    },
+    implement_table_value_codecs_with_zc,
+    serde_utils::{option_address, option_fund, option_protocol, option_txhash},
+    FastHashMap, Protocol,
+};
+
+/// Timestamp of the first beacon chain slot
+const BEACON_GENESIS_TIMESTAMP: u64 = 1_606_824_023;
+const SECONDS_PER_EPOCH: u64 = 12 * 32;
+
+/// The span of time a [`BlockAnalysisRollup`] covers
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    EnumString,
+    Display,
+    AsRefStr,
+    rSerialize,
+    rDeserialize,
+    Archive,
+)]
+#[strum(serialize_all = "lowercase")]
+#[repr(u8)]
+pub enum RollupBucket {
+    #[default]
+    Hour,
+    Day,
+    /// A beacon chain epoch, 32 slots of 12 seconds
+    Epoch,
+}
+
+impl RollupBucket {
+    /// The id of the bucket the block timestamp falls in. This is the
+    /// timestamp the hour or day starts at, or the epoch number.
+    pub fn bucket_of(&self, timestamp: u64) -> u64 {
+        match self {
+            Self::Hour => timestamp - timestamp % 3600,
+            Self::Day => timestamp - timestamp % 86400,
+            Self::Epoch => timestamp.saturating_sub(BEACON_GENESIS_TIMESTAMP) / SECONDS_PER_EPOCH,
+        }
+    }
+}
+
+impl Serialize for RollupBucket {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+impl<'de> Deserialize<'de> for RollupBucket {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bucket = String::deserialize(deserializer)?;
+        RollupBucket::from_str(&bucket).map_err(serde::de::Error::custom)
+    }
+}
+
+self_convert_redefined!(RollupBucket);
+
+wrap_fixed_bytes!(
+    extra_derives: [],
+    pub struct RollupKey<9>;
+);
+
+impl reth_db::table::Encode for RollupKey {
+    type Encoded = [u8; 9];
+
+    fn encode(self) -> Self::Encoded {
+        self.0 .0
+    }
+}
+
+impl reth_db::table::Decode for RollupKey {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        Ok(RollupKey::from_slice(value.as_ref()))
+    }
+}
+
+/// Keys are ordered by bucket kind first so that all rollups of a kind are
+/// next to each other
+pub fn make_rollup_key(bucket: RollupBucket, bucket_start: u64) -> RollupKey {
+    let bucket_byte = FixedBytes::new([bucket as u8]);
+    bucket_byte
+        .concat_const(bucket_start.to_be_bytes().into())
+        .into()
+}
+
+/// Totals of a mev type over the blocks of a rollup
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct MevTypeRollup {
+    pub bundle_count:               u64,
+    pub total_profit:               f64,
+    pub total_revenue:              f64,
+    #[serde(with = "option_address")]
+    pub top_searcher_profit:        Option<Address>,
+    pub top_searcher_profit_amt:    Option<f64>,
+    #[serde(with = "option_address")]
+    pub top_searcher_revenue:       Option<Address>,
+    pub top_searcher_revenue_amt:   Option<f64>,
+    pub searcher_count:             u64,
+    #[redefined(same_fields)]
+    #[serde(with = "option_fund")]
+    pub top_fund_profit:            Option<Fund>,
+    pub top_fund_profit_amt:        Option<f64>,
+    pub fund_count:                 u64,
+    #[serde(with = "option_address")]
+    pub most_arbed_pool_profit:     Option<Address>,
+    pub most_arbed_pool_profit_amt: Option<f64>,
+    #[redefined(same_fields)]
+    #[serde(with = "option_protocol")]
+    pub most_arbed_dex_profit:      Option<Protocol>,
+    pub most_arbed_dex_profit_amt:  Option<f64>,
+    #[serde(with = "option_txhash")]
+    pub biggest_arb_profit:         Option<TxHash>,
+    pub biggest_arb_profit_amt:     Option<f64>,
+}
+
+/// The [`BlockAnalysis`] of every block in an hour, day or epoch merged
+/// together. Searchers, funds, pools and dexes are ranked by their summed
+/// profit over the bucket.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Row, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BlockAnalysisRollup {
+    #[redefined(same_fields)]
+    pub bucket:                 RollupBucket,
+    /// The timestamp the hour or day starts at, or the epoch number
+    pub bucket_start:           u64,
+    pub start_block:            u64,
+    pub end_block:              u64,
+    pub block_count:            u64,
+    pub average_eth_price:      f64,
+    /// The top searchers, funds, pools and dexes of `all` are taken over the
+    /// mev types below
+    pub all:                    MevTypeRollup,
+    pub atomic:                 MevTypeRollup,
+    pub sandwich:               MevTypeRollup,
+    pub jit:                    MevTypeRollup,
+    pub jit_sandwich:           MevTypeRollup,
+    pub cex_dex:                MevTypeRollup,
+    pub liquidation:            MevTypeRollup,
+    pub total_usd_liquidated:   f64,
+    pub builder_profit_usd:     f64,
+    pub builder_revenue_usd:    f64,
+    pub builder_mev_profit_usd: f64,
+    pub proposer_profit_usd:    f64,
+}
+
+implement_table_value_codecs_with_zc!(BlockAnalysisRollupRedefined);
+
+impl BlockAnalysisRollup {
+    /// Merges the analyses, each paired with the timestamp of its block, into
+    /// one rollup per bucket ordered by bucket start
+    pub fn from_analyses(
+        bucket: RollupBucket,
+        analyses: impl IntoIterator<Item = (u64, BlockAnalysis)>,
+    ) -> Vec<Self> {
+        let mut buckets: BTreeMap<u64, RollupAccumulator> = BTreeMap::new();
+        for (timestamp, analysis) in analyses {
+            buckets
+                .entry(bucket.bucket_of(timestamp))
+                .or_default()
+                .add_block(&analysis);
+        }
+
+        buckets
+            .into_iter()
+            .map(|(bucket_start, acc)| acc.finish(bucket, bucket_start))
+            .collect()
+    }
+
+    pub fn key(&self) -> RollupKey {
+        make_rollup_key(self.bucket, self.bucket_start)
+    }
+}
+
+#[derive(Default)]
+struct RollupAccumulator {
+    start_block:            u64,
+    end_block:              u64,
+    block_count:            u64,
+    eth_price_sum:          f64,
+    all:                    MevTypeAccumulator,
+    atomic:                 MevTypeAccumulator,
+    sandwich:               MevTypeAccumulator,
+    jit:                    MevTypeAccumulator,
+    jit_sandwich:           MevTypeAccumulator,
+    cex_dex:                MevTypeAccumulator,
+    liquidation:            MevTypeAccumulator,
+    total_usd_liquidated:   f64,
+    builder_profit_usd:     f64,
+    builder_revenue_usd:    f64,
+    builder_mev_profit_usd: f64,
+    proposer_profit_usd:    f64,
+}
+
+/// Adds the fields shared by every mev type of the analysis, the pool and
+/// dex fields are added by the caller as liquidations don't have them
+macro_rules! add_mev_type {
+    ($acc:expr, $analysis:ident, $ty:ident, $biggest:ident) => {
+        paste::paste! {
+            $acc.add_totals(
+                $analysis.[<$ty _bundle_count>],
+                $analysis.[<$ty _total_profit>],
+                $analysis.[<$ty _total_revenue>],
+            );
+            add_amounts(
+                &mut $acc.searcher_profit,
+                &$analysis.[<$ty _searcher_eoa_all_profit>],
+                &$analysis.[<$ty _searcher_eoa_all_profit_amt>],
+            );
+            add_amounts(
+                &mut $acc.searcher_revenue,
+                &$analysis.[<$ty _searcher_eoa_all_revenue>],
+                &$analysis.[<$ty _searcher_eoa_all_revenue_amt>],
+            );
+            add_amounts(
+                &mut $acc.fund_profit,
+                &$analysis.[<$ty _fund_all_profit>],
+                &$analysis.[<$ty _fund_all_profit_amt>],
+            );
+            $acc.add_biggest_arb(
+                $analysis.[<$biggest _biggest_arb_profit>],
+                $analysis.[<$biggest _biggest_arb_profit_amt>],
+            );
+        }
+    };
+    ($acc:expr, $analysis:ident, $ty:ident) => {
+        add_mev_type!($acc, $analysis, $ty, $ty);
+        paste::paste! {
+            add_amounts(
+                &mut $acc.pool_profit,
+                &$analysis.[<$ty _arbed_pool_all_profit>],
+                &$analysis.[<$ty _arbed_pool_all_profit_amt>],
+            );
+            add_amounts(
+                &mut $acc.dex_profit,
+                &$analysis.[<$ty _arbed_dex_all_profit>],
+                &$analysis.[<$ty _arbed_dex_all_profit_amt>],
+            );
+        }
+    };
+}
+
+impl RollupAccumulator {
+    fn add_block(&mut self, analysis: &BlockAnalysis) {
+        if self.block_count == 0 || analysis.block_number < self.start_block {
+            self.start_block = analysis.block_number;
+        }
+        self.end_block = self.end_block.max(analysis.block_number);
+        self.block_count += 1;
+        self.eth_price_sum += analysis.eth_price;
+
+        self.all.add_totals(
+            analysis.all_bundle_count,
+            analysis.all_total_profit,
+            analysis.all_total_revenue,
+        );
+        self.all
+            .add_biggest_arb(analysis.all_biggest_arb_profit, analysis.all_biggest_arb_profit_amt);
+
+        add_mev_type!(self.atomic, analysis, atomic);
+        add_mev_type!(self.sandwich, analysis, sandwich);
+        add_mev_type!(self.jit, analysis, jit);
+        add_mev_type!(self.jit_sandwich, analysis, jit_sandwich);
+        add_mev_type!(self.cex_dex, analysis, cex_dex);
+        add_mev_type!(self.liquidation, analysis, liquidation, liquidated);
+
+        self.total_usd_liquidated += analysis.total_usd_liquidated;
+        self.builder_profit_usd += analysis.builder_profit_usd;
+        self.builder_revenue_usd += analysis.builder_revenue_usd;
+        self.builder_mev_profit_usd += analysis.builder_mev_profit_usd;
+        self.proposer_profit_usd += analysis.proposer_profit_usd.unwrap_or_default();
+    }
+
+    fn finish(mut self, bucket: RollupBucket, bucket_start: u64) -> BlockAnalysisRollup {
+        for ty in [
+            &self.atomic,
+            &self.sandwich,
+            &self.jit,
+            &self.jit_sandwich,
+            &self.cex_dex,
+            &self.liquidation,
+        ] {
+            self.all.merge_rankings(ty);
+        }
+
+        BlockAnalysisRollup {
+            bucket,
+            bucket_start,
+            start_block: self.start_block,
+            end_block: self.end_block,
+            block_count: self.block_count,
+            average_eth_price: self.eth_price_sum / self.block_count as f64,
+            all: self.all.finish(),
+            atomic: self.atomic.finish(),
+            sandwich: self.sandwich.finish(),
+            jit: self.jit.finish(),
+            jit_sandwich: self.jit_sandwich.finish(),
+            cex_dex: self.cex_dex.finish(),
+            liquidation: self.liquidation.finish(),
+            total_usd_liquidated: self.total_usd_liquidated,
+            builder_profit_usd: self.builder_profit_usd,
+            builder_revenue_usd: self.builder_revenue_usd,
+            builder_mev_profit_usd: self.builder_mev_profit_usd,
+            proposer_profit_usd: self.proposer_profit_usd,
+        }
+    }
+}
+
+#[derive(Default)]
+struct MevTypeAccumulator {
+    bundle_count:       u64,
+    total_profit:       f64,
+    total_revenue:      f64,
+    searcher_profit:    FastHashMap<Address, f64>,
+    searcher_revenue:   FastHashMap<Address, f64>,
+    fund_profit:        FastHashMap<Fund, f64>,
+    pool_profit:        FastHashMap<Address, f64>,
+    dex_profit:         FastHashMap<Protocol, f64>,
+    biggest_arb_profit: Option<(TxHash, f64)>,
+}
+
+impl MevTypeAccumulator {
+    fn add_totals(&mut self, bundle_count: u64, profit: f64, revenue: f64) {
+        self.bundle_count += bundle_count;
+        self.total_profit += profit;
+        self.total_revenue += revenue;
+    }
+
+    fn add_biggest_arb(&mut self, tx_hash: Option<TxHash>, profit: Option<f64>) {
+        let (Some(tx_hash), Some(profit)) = (tx_hash, profit) else { return };
+        if self
+            .biggest_arb_profit
+            .map_or(true, |(_, biggest)| profit > biggest)
+        {
+            self.biggest_arb_profit = Some((tx_hash, profit));
+        }
+    }
+
+    fn merge_rankings(&mut self, other: &Self) {
+        merge_amounts(&mut self.searcher_profit, &other.searcher_profit);
+        merge_amounts(&mut self.searcher_revenue, &other.searcher_revenue);
+        merge_amounts(&mut self.fund_profit, &other.fund_profit);
+        merge_amounts(&mut self.pool_profit, &other.pool_profit);
+        merge_amounts(&mut self.dex_profit, &other.dex_profit);
+    }
+
+    fn finish(self) -> MevTypeRollup {
+        let (top_searcher_profit, top_searcher_profit_amt) = top(&self.searcher_profit);
+        let (top_searcher_revenue, top_searcher_revenue_amt) = top(&self.searcher_revenue);
+        let (top_fund_profit, top_fund_profit_amt) = top(&self.fund_profit);
+        let (most_arbed_pool_profit, most_arbed_pool_profit_amt) = top(&self.pool_profit);
+        let (most_arbed_dex_profit, most_arbed_dex_profit_amt) = top(&self.dex_profit);
+        let (biggest_arb_profit, biggest_arb_profit_amt) = self.biggest_arb_profit.unzip();
+
+        MevTypeRollup {
+            bundle_count: self.bundle_count,
+            total_profit: self.total_profit,
+            total_revenue: self.total_revenue,
+            top_searcher_profit,
+            top_searcher_profit_amt,
+            top_searcher_revenue,
+            top_searcher_revenue_amt,
+            searcher_count: self.searcher_profit.len() as u64,
+            top_fund_profit,
+            top_fund_profit_amt,
+            fund_count: self.fund_profit.len() as u64,
+            most_arbed_pool_profit,
+            most_arbed_pool_profit_amt,
+            most_arbed_dex_profit,
+            most_arbed_dex_profit_amt,
+            biggest_arb_profit,
+            biggest_arb_profit_amt,
+        }
+    }
+}
+
+fn add_amounts<K: Hash + Eq + Copy>(map: &mut FastHashMap<K, f64>, keys: &[K], amounts: &[f64]) {
+    keys.iter().zip(amounts).for_each(|(key, amount)| {
+        *map.entry(*key).or_default() += amount;
+    });
+}
+
+fn merge_amounts<K: Hash + Eq + Copy>(map: &mut FastHashMap<K, f64>, other: &FastHashMap<K, f64>) {
+    other.iter().for_each(|(key, amount)| {
+        *map.entry(*key).or_default() += amount;
+    });
+}
+
+fn top<K: Copy>(map: &FastHashMap<K, f64>) -> (Option<K>, Option<f64>) {
+    map.iter()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(key, amount)| (*key, *amount))
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+
+    use super::*;
+
+    fn analysis(block_number: u64, searcher: Address, profit: f64) -> BlockAnalysis {
+        BlockAnalysis {
+            block_number,
+            eth_price: 2000.0,
+            all_bundle_count: 1,
+            all_total_profit: profit,
+            atomic_bundle_count: 1,
+            atomic_total_profit: profit,
+            atomic_searcher_eoa_all_profit: vec![searcher],
+            atomic_searcher_eoa_all_profit_amt: vec![profit],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bucket_of() {
+        // 2024-01-01 12:34:56 UTC
+        let timestamp = 1_704_112_496;
+        assert_eq!(RollupBucket::Hour.bucket_of(timestamp), 1_704_110_400);
+        assert_eq!(RollupBucket::Day.bucket_of(timestamp), 1_704_067_200);
+        assert_eq!(
+            RollupBucket::Epoch.bucket_of(BEACON_GENESIS_TIMESTAMP + SECONDS_PER_EPOCH * 3 + 1),
+            3
+        );
+    }
+
+    #[test]
+    fn test_rollup_by_day() {
+        let alice = Address::new(hex!("00000000000000000000000000000000000000a1"));
+        let bob = Address::new(hex!("00000000000000000000000000000000000000b0"));
+        let day = 1_704_067_200;
+
+        let rollups = BlockAnalysisRollup::from_analyses(
+            RollupBucket::Day,
+            [
+                (day + 12, analysis(100, bob, 30.0)),
+                (day + 24, analysis(101, alice, 20.0)),
+                (day + 36, analysis(102, alice, 20.0)),
+                (day + 86_400, analysis(103, bob, 5.0)),
+            ],
+        );
+
+        assert_eq!(rollups.len(), 2);
+        let first = &rollups[0];
+        assert_eq!(first.bucket_start, day);
+        assert_eq!((first.start_block, first.end_block, first.block_count), (100, 102, 3));
+        assert_eq!(first.all.total_profit, 70.0);
+        assert_eq!(first.atomic.bundle_count, 3);
+        // alice made less than bob in every block but more over the day
+        assert_eq!(first.atomic.top_searcher_profit, Some(alice));
+        assert_eq!(first.atomic.top_searcher_profit_amt, Some(40.0));
+        assert_eq!(first.all.top_searcher_profit, Some(alice));
+        assert_eq!(first.atomic.searcher_count, 2);
+
+        assert_eq!(rollups[1].bucket_start, day + 86_400);
+        assert_eq!(rollups[1].atomic.top_searcher_profit, Some(bob));
+    }
+}
@@ -1,3 +1,7 @@
+/// The horizons, in seconds after the block, that the inventory of labelled
+/// cex-dex searchers is marked out at by default.
+pub const DEFAULT_INVENTORY_MARKOUT_HORIZONS: [u64; 3] = [60, 300, 3600];
+
 #[derive(Debug, Clone, Copy)]
 pub struct CexDexTradeConfig {
     pub initial_vwap_pre_block_us:         u64,
@@ -19,6 +23,12 @@ pub struct CexDexTradeConfig {
     pub pre_decay_weight_op:               f64,
     pub post_decay_weight_op:              f64,
     pub quote_offset_from_block_us:        u64,
+    /// amount of blocks a labelled cex-dex searchers inventory is accumulated
+    /// over before being marked out, 0 disables the inventory markout
+    pub inventory_window_blocks:           usize,
+    /// seconds after the last block of the window that the inventory is
+    /// marked out at
+    pub inventory_markout_horizons:        &'static [u64],
 }
 
 impl Default for CexDexTradeConfig {
@@ -43,6 +53,8 @@ impl Default for CexDexTradeConfig {
             pre_decay_weight_op:               -0.0000003,
             post_decay_weight_op:              -0.00000012,
             quote_offset_from_block_us:        0,
+            inventory_window_blocks:           0,
+            inventory_markout_horizons:        &DEFAULT_INVENTORY_MARKOUT_HORIZONS,
         }
     }
 }
@@ -2,7 +2,13 @@ use redefined::Redefined;
 use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
-use crate::{implement_table_value_codecs_with_zc, mev::*};
+use crate::mev::*;
+
+/// Prefixes every row encoded with the current layout. Rows written before
+/// the sandwich and jit bundles gained new fields have no prefix and
+/// start with the zstd magic number instead, so they can't be mistaken for a
+/// versioned row.
+const MEV_BLOCK_VERSION: [u8; 4] = *b"mev\x01";
 
 #[derive(Debug, Default, Serialize, PartialEq, Deserialize, Clone, Redefined)]
 #[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
@@ -11,4 +17,356 @@ pub struct MevBlockWithClassified {
     pub mev:   Vec<Bundle>,
 }
 
-implement_table_value_codecs_with_zc!(MevBlockWithClassifiedRedefined);
+impl alloy_rlp::Encodable for MevBlockWithClassifiedRedefined {
+    fn encode(&self, out: &mut dyn bytes::BufMut) {
+        let encoded = rkyv::to_bytes::<_, 256>(self).unwrap();
+
+        out.put_slice(&encoded)
+    }
+}
+
+impl alloy_rlp::Decodable for MevBlockWithClassifiedRedefined {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let archived: &ArchivedMevBlockWithClassifiedRedefined =
+            unsafe { rkyv::archived_root::<Self>(&buf[..]) };
+
+        let this = rkyv::Deserialize::deserialize(archived, &mut rkyv::Infallible).unwrap();
+
+        Ok(this)
+    }
+}
+
+impl reth_db::table::Compress for MevBlockWithClassifiedRedefined {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<B: reth_primitives::bytes::BufMut + AsMut<[u8]>>(self, buf: &mut B) {
+        let mut encoded = Vec::new();
+        alloy_rlp::Encodable::encode(&self, &mut encoded);
+        let encoded_compressed = zstd::encode_all(&*encoded, 0).unwrap();
+
+        buf.put_slice(&MEV_BLOCK_VERSION);
+        buf.put_slice(&encoded_compressed);
+    }
+}
+
+impl reth_db::table::Decompress for MevBlockWithClassifiedRedefined {
+    fn decompress<B: AsRef<[u8]>>(value: B) -> Result<Self, reth_db::DatabaseError> {
+        let value = value.as_ref();
+
+        let Some(compressed) = value.strip_prefix(&MEV_BLOCK_VERSION) else {
+            return legacy::decompress(value)
+        };
+
+        let encoded_decompressed =
+            zstd::decode_all(compressed).map_err(|_| reth_db::DatabaseError::Decode)?;
+        let buf = &mut encoded_decompressed.as_slice();
+
+        alloy_rlp::Decodable::decode(buf).map_err(|_| reth_db::DatabaseError::Decode)
+    }
+}
+
+/// The layout of [`MevBlockWithClassified`] before the versioned encoding.
+/// Only the bundles whose fields changed since are redefined here, the rest
+/// still archive the same way.
+mod legacy {
+    use redefined::{Redefined, RedefinedConvert};
+    use reth_primitives::B256;
+    use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+    use serde::Serialize;
+
+    use super::{MevBlockWithClassified, MevBlockWithClassifiedRedefined};
+    use crate::{db::redefined_types::primitives::*, mev::*, normalized_actions::*, GasDetails};
+
+    pub(super) fn decompress(
+        value: &[u8],
+    ) -> Result<MevBlockWithClassifiedRedefined, reth_db::DatabaseError> {
+        let encoded_decompressed =
+            zstd::decode_all(value).map_err(|_| reth_db::DatabaseError::Decode)?;
+
+        let archived: &ArchivedMevBlockWithClassifiedV0Redefined = unsafe {
+            rkyv::archived_root::<MevBlockWithClassifiedV0Redefined>(&encoded_decompressed[..])
+        };
+        let legacy: MevBlockWithClassifiedV0Redefined =
+            rkyv::Deserialize::deserialize(archived, &mut rkyv::Infallible).unwrap();
+
+        Ok(MevBlockWithClassifiedRedefined::from_source(legacy.to_source().into()))
+    }
+
+    #[derive(Debug, PartialEq, Clone, Redefined)]
+    #[redefined_attr(derive(
+        Debug,
+        PartialEq,
+        Clone,
+        Serialize,
+        rSerialize,
+        rDeserialize,
+        Archive
+    ))]
+    pub(super) struct MevBlockWithClassifiedV0 {
+        pub block: MevBlock,
+        pub mev:   Vec<BundleV0>,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Redefined)]
+    #[redefined_attr(derive(
+        Debug,
+        PartialEq,
+        Clone,
+        Serialize,
+        rSerialize,
+        rDeserialize,
+        Archive
+    ))]
+    pub(super) struct BundleV0 {
+        pub header: BundleHeader,
+        pub data:   BundleDataV0,
+    }
+
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Debug, PartialEq, Clone, Redefined)]
+    #[redefined_attr(derive(
+        Debug,
+        PartialEq,
+        Clone,
+        Serialize,
+        rSerialize,
+        rDeserialize,
+        Archive
+    ))]
+    pub(super) enum BundleDataV0 {
+        Sandwich(SandwichV0),
+        AtomicArb(AtomicArb),
+        JitSandwich(JitLiquiditySandwich),
+        Jit(JitLiquidityV0),
+        CexDexQuote(CexDexQuote),
+        CexDex(CexDex),
+        Liquidation(Liquidation),
+        Unknown(SearcherTx),
+    }
+
+    #[derive(Debug, PartialEq, Clone, Redefined)]
+    #[redefined_attr(derive(
+        Debug,
+        PartialEq,
+        Clone,
+        Serialize,
+        rSerialize,
+        rDeserialize,
+        Archive
+    ))]
+    pub(super) struct SandwichV0 {
+        pub block_number:             u64,
+        pub frontrun_tx_hash:         Vec<B256>,
+        pub frontrun_swaps:           Vec<Vec<NormalizedSwap>>,
+        #[redefined(same_fields)]
+        pub frontrun_gas_details:     Vec<GasDetails>,
+        pub victim_swaps_tx_hashes:   Vec<Vec<B256>>,
+        pub victim_swaps:             Vec<Vec<NormalizedSwap>>,
+        #[redefined(same_fields)]
+        pub victim_swaps_gas_details: Vec<GasDetails>,
+        pub backrun_tx_hash:          B256,
+        pub backrun_swaps:            Vec<NormalizedSwap>,
+        #[redefined(same_fields)]
+        pub backrun_gas_details:      GasDetails,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Redefined)]
+    #[redefined_attr(derive(
+        Debug,
+        PartialEq,
+        Clone,
+        Serialize,
+        rSerialize,
+        rDeserialize,
+        Archive
+    ))]
+    pub(super) struct JitLiquidityV0 {
+        pub frontrun_mint_tx_hash: B256,
+        pub block_number: u64,
+        pub frontrun_mints: Vec<NormalizedMint>,
+        #[redefined(same_fields)]
+        pub frontrun_mint_gas_details: GasDetails,
+        pub victim_swaps_tx_hashes: Vec<B256>,
+        pub victim_swaps: Vec<Vec<NormalizedSwap>>,
+        pub victim_swaps_gas_details_tx_hashes: Vec<B256>,
+        #[redefined(same_fields)]
+        pub victim_swaps_gas_details: Vec<GasDetails>,
+        pub backrun_burn_tx_hash: B256,
+        pub backrun_burns: Vec<NormalizedBurn>,
+        #[redefined(same_fields)]
+        pub backrun_burn_gas_details: GasDetails,
+    }
+
+    impl From<MevBlockWithClassifiedV0> for MevBlockWithClassified {
+        fn from(value: MevBlockWithClassifiedV0) -> Self {
+            Self {
+                block: value.block,
+                mev:   value
+                    .mev
+                    .into_iter()
+                    .map(|bundle| Bundle { header: bundle.header, data: bundle.data.into() })
+                    .collect(),
+            }
+        }
+    }
+
+    impl From<BundleDataV0> for BundleData {
+        fn from(value: BundleDataV0) -> Self {
+            match value {
+                BundleDataV0::Sandwich(s) => BundleData::Sandwich(s.into()),
+                BundleDataV0::AtomicArb(a) => BundleData::AtomicArb(a),
+                BundleDataV0::JitSandwich(j) => BundleData::JitSandwich(j),
+                BundleDataV0::Jit(j) => BundleData::Jit(j.into()),
+                BundleDataV0::CexDexQuote(c) => BundleData::CexDexQuote(c),
+                BundleDataV0::CexDex(c) => BundleData::CexDex(c),
+                BundleDataV0::Liquidation(l) => BundleData::Liquidation(l),
+                BundleDataV0::Unknown(s) => BundleData::Unknown(s),
+            }
+        }
+    }
+
+    impl From<SandwichV0> for Sandwich {
+        fn from(value: SandwichV0) -> Self {
+            Self {
+                block_number:             value.block_number,
+                // legacy sandwiches were always contained in a single block
+                frontrun_block_number:    value.block_number,
+                frontrun_tx_hash:         value.frontrun_tx_hash,
+                frontrun_swaps:           value.frontrun_swaps,
+                frontrun_gas_details:     value.frontrun_gas_details,
+                victim_swaps_tx_hashes:   value.victim_swaps_tx_hashes,
+                victim_swaps:             value.victim_swaps,
+                victim_swaps_gas_details: value.victim_swaps_gas_details,
+                backrun_tx_hash:          value.backrun_tx_hash,
+                backrun_swaps:            value.backrun_swaps,
+                backrun_gas_details:      value.backrun_gas_details,
+                victim_losses:            vec![],
+            }
+        }
+    }
+
+    impl From<JitLiquidityV0> for JitLiquidity {
+        fn from(value: JitLiquidityV0) -> Self {
+            Self {
+                frontrun_mint_tx_hash: value.frontrun_mint_tx_hash,
+                block_number: value.block_number,
+                frontrun_mints: value.frontrun_mints,
+                frontrun_mint_gas_details: value.frontrun_mint_gas_details,
+                victim_swaps_tx_hashes: value.victim_swaps_tx_hashes,
+                victim_swaps: value.victim_swaps,
+                victim_swaps_gas_details_tx_hashes: value.victim_swaps_gas_details_tx_hashes,
+                victim_swaps_gas_details: value.victim_swaps_gas_details,
+                backrun_burn_tx_hash: value.backrun_burn_tx_hash,
+                backrun_burns: value.backrun_burns,
+                backrun_burn_gas_details: value.backrun_burn_gas_details,
+                victim_losses: vec![],
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn compress(value: MevBlockWithClassifiedV0) -> Vec<u8> {
+        let encoded =
+            rkyv::to_bytes::<_, 256>(&MevBlockWithClassifiedV0Redefined::from_source(value))
+                .unwrap();
+        zstd::encode_all(&*encoded, 0).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256};
+    use redefined::RedefinedConvert;
+    use reth_db::table::{Compress, Decompress};
+
+    use super::{legacy::*, *};
+    use crate::normalized_actions::NormalizedSwap;
+
+    fn swap(trace_index: u64) -> NormalizedSwap {
+        NormalizedSwap {
+            trace_index,
+            from: Address::with_last_byte(1),
+            recipient: Address::with_last_byte(1),
+            pool: Address::with_last_byte(2),
+            ..Default::default()
+        }
+    }
+
+    fn header(mev_type: MevType) -> BundleHeader {
+        BundleHeader {
+            block_number: 10,
+            tx_hash: B256::with_last_byte(3),
+            mev_type,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_current_layout_round_trip() {
+        let block = MevBlockWithClassified {
+            block: MevBlock { block_number: 10, ..Default::default() },
+            mev:   vec![Bundle {
+                header: header(MevType::Sandwich),
+                data:   BundleData::Sandwich(Sandwich {
+                    block_number: 10,
+                    frontrun_block_number: 9,
+                    frontrun_tx_hash: vec![B256::with_last_byte(1)],
+                    frontrun_swaps: vec![vec![swap(0)]],
+                    backrun_tx_hash: B256::with_last_byte(3),
+                    backrun_swaps: vec![swap(1)],
+                    ..Default::default()
+                }),
+            }],
+        };
+
+        let compressed = MevBlockWithClassifiedRedefined::from_source(block.clone()).compress();
+        assert!(compressed.starts_with(&MEV_BLOCK_VERSION));
+
+        let decompressed = MevBlockWithClassifiedRedefined::decompress(compressed).unwrap();
+        assert_eq!(decompressed.to_source(), block);
+    }
+
+    #[test]
+    fn test_legacy_layout_decodes() {
+        let legacy = MevBlockWithClassifiedV0 {
+            block: MevBlock { block_number: 10, ..Default::default() },
+            mev:   vec![
+                BundleV0 {
+                    header: header(MevType::Sandwich),
+                    data:   BundleDataV0::Sandwich(SandwichV0 {
+                        block_number:             10,
+                        frontrun_tx_hash:         vec![B256::with_last_byte(1)],
+                        frontrun_swaps:           vec![vec![swap(0)]],
+                        frontrun_gas_details:     vec![Default::default()],
+                        victim_swaps_tx_hashes:   vec![vec![B256::with_last_byte(2)]],
+                        victim_swaps:             vec![vec![swap(0)]],
+                        victim_swaps_gas_details: vec![Default::default()],
+                        backrun_tx_hash:          B256::with_last_byte(3),
+                        backrun_swaps:            vec![swap(1)],
+                        backrun_gas_details:      Default::default(),
+                    }),
+                },
+                BundleV0 {
+                    header: header(MevType::Liquidation),
+                    data:   BundleDataV0::Liquidation(Liquidation {
+                        liquidation_tx_hash: B256::with_last_byte(4),
+                        block_number: 10,
+                        ..Default::default()
+                    }),
+                },
+            ],
+        };
+
+        let decompressed =
+            MevBlockWithClassifiedRedefined::decompress(compress(legacy.clone())).unwrap();
+
+        assert_eq!(decompressed.to_source(), MevBlockWithClassified::from(legacy));
+
+        let BundleData::Sandwich(sandwich) = &decompressed.to_source().mev[0].data else {
+            panic!("expected a sandwich")
+        };
+        assert_eq!(sandwich.frontrun_block_number, 10);
+        assert_eq!(sandwich.block_span(), 1);
+        assert!(sandwich.victim_losses.is_empty());
+    }
+}
@@ -6,6 +6,7 @@ pub mod address_to_protocol_info;
 
 #[rustfmt::skip]
 pub mod block_analysis;
+pub mod block_analysis_rollup;
 pub mod block_times;
 pub mod builder;
 pub mod cex;
@@ -24,6 +25,7 @@ pub mod searcher;
 pub mod token_info;
 pub mod traces;
 pub mod traits;
+pub mod uniswap_v4_pool_key;
 
 /// This table is used to add run id inserts for each clickhouse table in order
 /// for us to not have to clear runs multiple times
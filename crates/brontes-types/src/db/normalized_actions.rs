@@ -151,12 +151,18 @@ pub enum ActionKind {
     Burn,
     Collect,
     Liquidation,
+    Supply,
+    Withdraw,
+    Loan,
+    Repayment,
     Unclassified,
     SelfDestruct,
     EthTransfer,
     NewPool,
     PoolConfigUpdate,
     Aggregator,
+    OracleUpdate,
+    Wrap,
     Revert,
 }
 
@@ -171,6 +177,10 @@ impl From<&Action> for ActionKind {
             Action::Burn(_) => ActionKind::Burn,
             Action::Transfer(_) => ActionKind::Transfer,
             Action::Liquidation(_) => ActionKind::Liquidation,
+            Action::Supply(_) => ActionKind::Supply,
+            Action::Withdraw(_) => ActionKind::Withdraw,
+            Action::Loan(_) => ActionKind::Loan,
+            Action::Repayment(_) => ActionKind::Repayment,
             Action::Collect(_) => ActionKind::Collect,
             Action::SelfDestruct(_) => ActionKind::SelfDestruct,
             Action::EthTransfer(_) => ActionKind::EthTransfer,
@@ -178,6 +188,8 @@ impl From<&Action> for ActionKind {
             Action::NewPool(_) => ActionKind::NewPool,
             Action::PoolConfigUpdate(_) => ActionKind::PoolConfigUpdate,
             Action::Aggregator(_) => ActionKind::Aggregator,
+            Action::OracleUpdate(_) => ActionKind::OracleUpdate,
+            Action::Wrap(_) => ActionKind::Wrap,
             Action::Revert => ActionKind::Revert,
         }
     }
@@ -74,7 +74,7 @@ impl SearcherInfo {
             MevType::AtomicArb => self.mev_count.atomic_backrun_count,
             MevType::Liquidation => self.mev_count.liquidation_count,
             MevType::SearcherTx => self.mev_count.searcher_tx_count,
-            MevType::Unknown => None,
+            MevType::CexDexInventory | MevType::Unknown => None,
         }
     }
 
@@ -172,6 +172,20 @@ impl SearcherInfo {
         self.mev_count.increment_count(header.mev_type);
         self.gas_bids.account_gas(header);
     }
+
+    /// reverts [`Self::update_with_bundle`], used when the block of the bundle
+    /// is reorged out
+    pub fn revert_bundle(&mut self, header: &BundleHeader) {
+        let reverted = BundleHeader {
+            profit_usd: -header.profit_usd,
+            bribe_usd: -header.bribe_usd,
+            mev_type: header.mev_type,
+            ..Default::default()
+        };
+        self.pnl.account_pnl(&reverted);
+        self.mev_count.decrement_count(header.mev_type);
+        self.gas_bids.account_gas(&reverted);
+    }
 }
 
 implement_table_value_codecs_with_zc!(SearcherInfoRedefined);
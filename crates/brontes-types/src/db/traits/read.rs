@@ -2,9 +2,14 @@ use alloy_primitives::Address;
 
 use crate::{
     db::{
-        address_metadata::AddressMetadata, address_to_protocol_info::ProtocolInfo,
-        builder::BuilderInfo, cex::trades::CexTradeMap, dex::DexQuotes, metadata::Metadata,
-        mev_block::MevBlockWithClassified, searcher::SearcherInfo,
+        address_metadata::AddressMetadata,
+        address_to_protocol_info::ProtocolInfo,
+        builder::BuilderInfo,
+        cex::{quotes::CexPriceMap, trades::CexTradeMap},
+        dex::DexQuotes,
+        metadata::Metadata,
+        mev_block::MevBlockWithClassified,
+        searcher::SearcherInfo,
         token_info::TokenInfoWithAddress,
     },
     pair::Pair,
@@ -14,6 +19,15 @@ use crate::{
 pub type AllSearcherInfo = (Vec<(Address, SearcherInfo)>, Vec<(Address, SearcherInfo)>);
 pub type ProtocolCreatedRange = FastHashMap<u64, Vec<(Address, Protocol, Pair)>>;
 
+/// The error of a read of a table keyed by block when the block has no entry,
+/// so that a missing block can be told apart from a failed read
+#[derive(Debug, thiserror::Error)]
+#[error("missing {table} for block: {block}")]
+pub struct MissingBlockData {
+    pub table: &'static str,
+    pub block: u64,
+}
+
 #[auto_impl::auto_impl(&, Box)]
 pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
     fn get_most_recent_block(&self) -> eyre::Result<u64>;
@@ -23,6 +37,8 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
         quote_asset: Address,
     ) -> eyre::Result<Metadata>;
 
+    fn get_block_timestamp(&self, block_num: u64) -> eyre::Result<u64>;
+
     fn has_dex_quotes(&self, block_num: u64) -> eyre::Result<bool>;
 
     fn try_fetch_searcher_info(
@@ -100,6 +116,8 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
 
     fn get_cex_trades(&self, block: u64) -> eyre::Result<CexTradeMap>;
 
+    fn get_cex_quotes(&self, block: u64) -> eyre::Result<CexPriceMap>;
+
     fn try_fetch_address_metadata(&self, address: Address)
         -> eyre::Result<Option<AddressMetadata>>;
 
@@ -113,6 +131,8 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
         self.try_fetch_token_info(address).map(|info| info.decimals)
     }
 
+    fn fetch_all_token_info(&self) -> eyre::Result<Vec<TokenInfoWithAddress>>;
+
     fn try_fetch_mev_blocks(
         &self,
         start_block: Option<u64>,
@@ -141,6 +161,8 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
 
     fn get_protocol_details(&self, address: Address) -> eyre::Result<ProtocolInfo>;
 
+    fn fetch_all_protocol_info(&self) -> eyre::Result<Vec<(Address, ProtocolInfo)>>;
+
     /// returns protocol details with the tokens sorted from smallest to
     /// biggest. This is needed as for some reason the tokens in the
     /// database for a given protocol don't seems to always be ordered
@@ -3,8 +3,9 @@ use futures::Future;
 
 use crate::{
     db::{
-        address_metadata::AddressMetadata, block_analysis::BlockAnalysis, builder::BuilderInfo,
-        dex::DexQuotes, searcher::SearcherInfo,
+        address_metadata::AddressMetadata, block_analysis::BlockAnalysis,
+        block_analysis_rollup::BlockAnalysisRollup, builder::BuilderInfo, dex::DexQuotes,
+        searcher::SearcherInfo, uniswap_v4_pool_key::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -26,6 +27,13 @@ pub trait DBWriter: Send + Unpin + 'static {
         self.inner().write_block_analysis(block_analysis)
     }
 
+    fn write_block_analysis_rollups(
+        &self,
+        rollups: Vec<BlockAnalysisRollup>,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_block_analysis_rollups(rollups)
+    }
+
     fn write_dex_quotes(
         &self,
         block_number: u64,
@@ -110,6 +118,15 @@ pub trait DBWriter: Send + Unpin + 'static {
             .insert_pool(block, address, tokens, curve_lp_token, classifier_name)
     }
 
+    /// stores the key of a uniswap v4 pool. v4 pools are only identified by
+    /// their id, so the key is needed to load their state
+    fn insert_uniswap_v4_pool_key(
+        &self,
+        key: UniswapV4PoolKey,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().insert_uniswap_v4_pool_key(key)
+    }
+
     fn insert_tree(
         &self,
         tree: BlockTree<Action>,
@@ -124,4 +141,14 @@ pub trait DBWriter: Send + Unpin + 'static {
     ) -> impl Future<Output = eyre::Result<()>> + Send {
         self.inner().save_traces(block, traces)
     }
+
+    /// removes all data derived from the given block (mev blocks, dex prices,
+    /// traces, discovered pools) and reverts its bundles out of the searcher
+    /// stats. used to roll back blocks that were reorged out
+    fn delete_block_data(
+        &self,
+        block_number: u64,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().delete_block_data(block_number)
+    }
 }
@@ -0,0 +1,67 @@
+use alloy_primitives::{keccak256, Address, B256};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::WETH_ADDRESS, db::redefined_types::primitives::AddressRedefined,
+    implement_table_value_codecs_with_zc,
+};
+
+/// The key that identifies a v4 pool. The pool id is the hash of this.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Redefined)]
+#[redefined_attr(derive(Debug, Clone, PartialEq, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct UniswapV4PoolKey {
+    pub currency0:    Address,
+    pub currency1:    Address,
+    pub fee:          u32,
+    pub tick_spacing: i32,
+    pub hooks:        Address,
+}
+
+implement_table_value_codecs_with_zc!(UniswapV4PoolKeyRedefined);
+
+impl UniswapV4PoolKey {
+    /// `keccak256(abi.encode(key))`
+    pub fn pool_id(&self) -> B256 {
+        let mut buf = [0u8; 160];
+        buf[12..32].copy_from_slice(self.currency0.as_slice());
+        buf[44..64].copy_from_slice(self.currency1.as_slice());
+        buf[92..96].copy_from_slice(&self.fee.to_be_bytes());
+        if self.tick_spacing.is_negative() {
+            buf[96..124].fill(0xff);
+        }
+        buf[124..128].copy_from_slice(&self.tick_spacing.to_be_bytes());
+        buf[140..160].copy_from_slice(self.hooks.as_slice());
+
+        keccak256(buf)
+    }
+
+    /// v4 pools don't have an address. As the rest of the system identifies
+    /// pools by their address, we use the first 20 bytes of the pool id.
+    pub fn pool_address(&self) -> Address {
+        pool_id_to_address(self.pool_id())
+    }
+
+    /// native eth is represented by the zero address in v4, we use weth in its
+    /// place so that these pools connect with the rest of the pricing graph.
+    pub fn token0(&self) -> Address {
+        native_to_weth(self.currency0)
+    }
+
+    pub fn token1(&self) -> Address {
+        native_to_weth(self.currency1)
+    }
+}
+
+pub fn pool_id_to_address(pool_id: B256) -> Address {
+    Address::from_slice(&pool_id[0..20])
+}
+
+fn native_to_weth(currency: Address) -> Address {
+    if currency.is_zero() {
+        WETH_ADDRESS
+    } else {
+        currency
+    }
+}
@@ -1,3 +1,4 @@
+pub mod tree;
 pub mod utils;
 
 //TODO: Will break this up into individual display impls within each type in
@@ -0,0 +1,161 @@
+use std::fmt;
+
+use alloy_primitives::{hex, Address};
+use colored::Colorize;
+use itertools::Itertools;
+use malachite::{num::basic::traits::Zero, Rational};
+
+use crate::{
+    normalized_actions::{accounting::AddressDeltas, Action},
+    structured_trace::TraceActions,
+    tree::{Node, Root},
+    utils::{ToFloatNearest, ToScaledRational},
+};
+
+/// Prints the call tree of a tx, one line per frame with the actions the frame
+/// was classified as
+pub fn display_tx_tree(root: &Root<Action>, f: &mut fmt::Formatter) -> fmt::Result {
+    display_node(root, &root.head, 0, f)
+}
+
+fn display_node(
+    root: &Root<Action>,
+    node: &Node,
+    depth: usize,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    let trace_address = format!("[{}]", node.trace_address.iter().join(","));
+
+    match root.data_store.get_ref(node.data) {
+        Some(actions) if !actions.is_empty() => {
+            for action in actions {
+                writeln!(
+                    f,
+                    "{indent}{} {} {}",
+                    trace_address.dimmed(),
+                    format!("{:?}", node.address).cyan(),
+                    format_action(action)
+                )?;
+            }
+        }
+        // the data of the frame was merged into a parent frame
+        _ => writeln!(
+            f,
+            "{indent}{} {} {}",
+            trace_address.dimmed(),
+            format!("{:?}", node.address).cyan(),
+            "merged into parent".dimmed()
+        )?,
+    }
+
+    node.inner
+        .iter()
+        .try_for_each(|inner| display_node(root, inner, depth + 1, f))
+}
+
+/// Single line description of an action
+pub fn format_action(action: &Action) -> String {
+    match action {
+        Action::Swap(swap) => swap.to_string(),
+        Action::SwapWithFee(swap) => swap.swap.to_string(),
+        Action::Mint(mint) => mint.to_string(),
+        Action::Burn(burn) => burn.to_string(),
+        Action::Collect(collect) => collect.to_string(),
+        Action::Liquidation(liquidation) => liquidation.to_string(),
+        Action::Supply(supply) => supply.to_string(),
+        Action::Withdraw(withdraw) => withdraw.to_string(),
+        Action::Loan(loan) => loan.to_string(),
+        Action::Repayment(repayment) => repayment.to_string(),
+        Action::OracleUpdate(update) => update.to_string(),
+        Action::Wrap(wrap) => wrap.to_string(),
+        Action::Transfer(transfer) => format!(
+            "Transfer {:.4} {} from {:?} to {:?}",
+            transfer.amount.clone().to_float(),
+            transfer.token.symbol,
+            transfer.from,
+            transfer.to
+        ),
+        Action::EthTransfer(transfer) => format!(
+            "Eth Transfer {:.4} ETH from {:?} to {:?}{}",
+            transfer.value.to_scaled_rational(18).to_float(),
+            transfer.from,
+            transfer.to,
+            if transfer.coinbase_transfer { " (coinbase transfer)" } else { "" }
+        ),
+        Action::FlashLoan(flash_loan) => {
+            format!("Flash Loan via {}", flash_loan.protocol.to_string().bold())
+        }
+        Action::Batch(batch) => format!("Batch via {}", batch.protocol.to_string().bold()),
+        Action::Aggregator(aggregator) => {
+            format!("Aggregator via {}", aggregator.protocol.to_string().bold())
+        }
+        Action::Unclassified(trace) => {
+            let call = trace
+                .decoded_data
+                .as_ref()
+                .map(|data| data.function_name.clone())
+                .unwrap_or_else(|| {
+                    let calldata = trace.get_calldata();
+                    format!("0x{}", hex::encode(calldata.get(..4).unwrap_or(&calldata)))
+                });
+
+            format!(
+                "{} {} from {:?}{}",
+                "Unclassified".yellow(),
+                call,
+                trace.msg_sender,
+                if trace.logs.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({} logs)", trace.logs.len())
+                }
+            )
+        }
+        Action::Revert => "Revert".red().to_string(),
+        action => format!("{} via {}", action_name(action), action.get_protocol()),
+    }
+}
+
+fn action_name(action: &Action) -> String {
+    let debug = format!("{action:?}");
+    debug
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Prints the token deltas of every address, resolving the symbol of each
+/// token with `symbol`
+pub fn display_token_deltas(
+    deltas: &AddressDeltas,
+    symbol: impl Fn(Address) -> Option<String>,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    for (address, tokens) in deltas.iter().sorted_by_key(|(address, _)| **address) {
+        let tokens = tokens
+            .iter()
+            .filter(|(_, amount)| **amount != Rational::ZERO)
+            .sorted_by_key(|(token, _)| **token)
+            .collect_vec();
+        if tokens.is_empty() {
+            continue
+        }
+
+        writeln!(f, "  {}", format!("{address:?}").cyan())?;
+        for (token, amount) in tokens {
+            let amount = amount.clone().to_float();
+            let amount = if amount.is_sign_negative() {
+                format!("{amount:.6}").red()
+            } else {
+                format!("+{amount:.6}").green()
+            };
+            let token = symbol(*token).unwrap_or_else(|| format!("{token:?}"));
+
+            writeln!(f, "    {amount} {token}")?;
+        }
+    }
+
+    Ok(())
+}
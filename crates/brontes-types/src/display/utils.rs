@@ -45,6 +45,14 @@ pub fn display_sandwich(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result
         }
     }
 
+    if sandwich_data.block_span() > 1 {
+        writeln!(
+            f,
+            "   - Block Span: {} - {}",
+            sandwich_data.frontrun_block_number, sandwich_data.block_number
+        )?;
+    }
+
     writeln!(f, "\n{}:", "Attacks".bright_yellow().underline())?;
     for (i, ((tx_hash, swaps), gas_details)) in sandwich_data
         .frontrun_tx_hash
@@ -105,6 +113,22 @@ pub fn display_sandwich(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result
                 if let Some(gas_details) = victim_gas_details {
                     gas_details.pretty_print_with_spaces(f, 16)?;
                 }
+
+                // Victim loss compared to the block without the sandwich
+                if let Some(loss) = sandwich_data
+                    .victim_losses
+                    .iter()
+                    .find(|loss| loss.tx_hash == *tx_hash)
+                {
+                    writeln!(
+                        f,
+                        "          - {}: {:.6} {} (${:.2})",
+                        "Loss".bright_blue(),
+                        loss.token_amount_lost.clone().to_float(),
+                        loss.token.symbol,
+                        loss.amount_lost_usd.clone().to_float()
+                    )?;
+                }
             }
         }
     }
@@ -501,12 +525,14 @@ pub fn display_liquidation(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Resu
         "Transaction".bright_blue(),
         format_etherscan_url(&liquidation_data.liquidation_tx_hash)
     )?;
-    writeln!(
-        f,
-        " - {}: {}",
-        "Trigger".bright_blue(),
-        format_etherscan_url(&liquidation_data.trigger)
-    )?;
+    if liquidation_data.is_oracle_backrun() {
+        writeln!(
+            f,
+            " - {}: {}",
+            "Oracle Update".bright_blue(),
+            format_etherscan_url(&liquidation_data.trigger)
+        )?;
+    }
 
     // Swaps Section
     writeln!(f, "\n{}\n", "Liquidation Swaps".bright_yellow().underline())?;
@@ -955,6 +981,34 @@ pub fn display_cex_dex_quotes(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::R
     Ok(())
 }
 
+pub fn display_cex_dex_inventory(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
+    let inventory = match &bundle.data {
+        BundleData::CexDexInventory(data) => data,
+        _ => return Err(fmt::Error),
+    };
+
+    writeln!(f, "\n{}", "Cex-Dex Inventory".bold().underline().purple())?;
+    writeln!(f, "   - EOA: {}", inventory.eoa)?;
+    writeln!(f, "   - Blocks: {} - {}", inventory.start_block, inventory.block_number)?;
+    writeln!(f, "   - Profit USD: {}", format!("{:.6}", bundle.header.profit_usd).green())?;
+    writeln!(f, "   - Bribe USD: {}", bundle.header.bribe_usd)?;
+
+    writeln!(f, "\n{}", "Swaps".bold().underline().bright_yellow())?;
+    for (tx_hash, swaps) in inventory.tx_hashes.iter().zip(&inventory.swaps) {
+        writeln!(f, "   - Tx: {}", format_etherscan_url(tx_hash))?;
+        for swap in swaps {
+            writeln!(f, "       {}", swap)?;
+        }
+    }
+
+    writeln!(f, "\n{}", "Markouts".bold().underline().bright_yellow())?;
+    for markout in &inventory.markouts {
+        write!(f, "{}", markout)?;
+    }
+
+    Ok(())
+}
+
 pub fn display_searcher_tx(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
     let ascii_header = indoc! {r#"
     
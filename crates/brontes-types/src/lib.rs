@@ -45,3 +45,16 @@ pub use protocol::*;
 pub mod channel_alerts;
 pub use channel_alerts::*;
 pub mod block_metadata;
+
+/// Dependencies of the exported macros, so that crates using them don't have to
+/// depend on them directly
+#[doc(hidden)]
+pub mod __private {
+    pub use alloy_rlp;
+    pub use bytes;
+    pub use paste;
+    pub use reth_db;
+    pub use reth_primitives;
+    pub use rkyv;
+    pub use zstd;
+}
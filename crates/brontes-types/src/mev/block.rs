@@ -234,6 +234,26 @@ impl MevCount {
             _ => {}
         }
     }
+
+    /// reverts [`Self::increment_count`]
+    pub fn decrement_count(&mut self, mev_type: MevType) {
+        self.bundle_count = self.bundle_count.saturating_sub(1);
+        let count = match mev_type {
+            MevType::CexDexTrades => &mut self.cex_dex_trade_count,
+            MevType::CexDexQuotes => &mut self.cex_dex_quote_count,
+            MevType::Sandwich => &mut self.sandwich_count,
+            MevType::AtomicArb => &mut self.atomic_backrun_count,
+            MevType::Jit => &mut self.jit_count,
+            MevType::JitSandwich => &mut self.jit_sandwich_count,
+            MevType::Liquidation => &mut self.liquidation_count,
+            MevType::SearcherTx => &mut self.searcher_tx_count,
+            MevType::JitCexDex => &mut self.jit_cex_dex_count,
+            _ => return,
+        };
+        if let Some(count) = count.as_mut() {
+            *count = count.saturating_sub(1);
+        }
+    }
 }
 self_convert_redefined!(MevCount);
 
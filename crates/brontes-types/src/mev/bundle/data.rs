@@ -32,6 +32,7 @@ pub enum BundleData {
     CexDex(CexDex),
     Liquidation(Liquidation),
     Unknown(SearcherTx),
+    CexDexInventory(CexDexInventory),
 }
 
 impl Default for BundleData {
@@ -51,6 +52,7 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.mev_type(),
             BundleData::Liquidation(m) => m.mev_type(),
             BundleData::Unknown(m) => m.mev_type(),
+            BundleData::CexDexInventory(m) => m.mev_type(),
         }
     }
 
@@ -64,6 +66,7 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.total_gas_paid(),
             BundleData::Liquidation(m) => m.total_gas_paid(),
             BundleData::Unknown(s) => s.total_gas_paid(),
+            BundleData::CexDexInventory(s) => s.total_gas_paid(),
         }
     }
 
@@ -77,6 +80,7 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.total_priority_fee_paid(base_fee),
             BundleData::Liquidation(m) => m.total_priority_fee_paid(base_fee),
             BundleData::Unknown(s) => s.total_priority_fee_paid(base_fee),
+            BundleData::CexDexInventory(s) => s.total_priority_fee_paid(base_fee),
         }
     }
 
@@ -90,6 +94,7 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.bribe(),
             BundleData::Liquidation(m) => m.bribe(),
             BundleData::Unknown(s) => s.bribe(),
+            BundleData::CexDexInventory(s) => s.bribe(),
         }
     }
 
@@ -103,6 +108,7 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.mev_transaction_hashes(),
             BundleData::Liquidation(m) => m.mev_transaction_hashes(),
             BundleData::Unknown(s) => s.mev_transaction_hashes(),
+            BundleData::CexDexInventory(s) => s.mev_transaction_hashes(),
         }
     }
 
@@ -116,6 +122,7 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.protocols(),
             BundleData::Liquidation(m) => m.protocols(),
             BundleData::Unknown(s) => s.protocols(),
+            BundleData::CexDexInventory(s) => s.protocols(),
         }
     }
 }
@@ -162,6 +169,12 @@ impl From<Liquidation> for BundleData {
     }
 }
 
+impl From<CexDexInventory> for BundleData {
+    fn from(value: CexDexInventory) -> Self {
+        Self::CexDexInventory(value)
+    }
+}
+
 impl Serialize for BundleData {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -176,6 +189,7 @@ impl Serialize for BundleData {
             BundleData::CexDexQuote(cex_dex) => cex_dex.serialize(serializer),
             BundleData::Liquidation(liquidation) => liquidation.serialize(serializer),
             BundleData::Unknown(s) => s.serialize(serializer),
+            BundleData::CexDexInventory(inventory) => inventory.serialize(serializer),
         }
     }
 }
@@ -191,6 +205,7 @@ impl InsertRow for BundleData {
             BundleData::CexDexQuote(cex_dex) => cex_dex.get_column_names(),
             BundleData::Liquidation(liquidation) => liquidation.get_column_names(),
             BundleData::Unknown(s) => s.get_column_names(),
+            BundleData::CexDexInventory(inventory) => inventory.get_column_names(),
         }
     }
 }
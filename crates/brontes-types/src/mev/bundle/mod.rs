@@ -68,6 +68,7 @@ impl fmt::Display for Bundle {
             MevType::Liquidation => display_liquidation(self, f)?,
             MevType::JitSandwich => display_jit_liquidity_sandwich(self, f)?,
             MevType::SearcherTx => display_searcher_tx(self, f)?,
+            MevType::CexDexInventory => display_cex_dex_inventory(self, f)?,
             MevType::Unknown => (),
         }
 
@@ -104,6 +105,7 @@ pub enum MevType {
     SearcherTx,
     #[default]
     Unknown,
+    CexDexInventory,
 }
 
 impl MevType {
@@ -119,6 +121,7 @@ impl MevType {
             MevType::CexDexRfq
             | MevType::CexDexTrades
             | MevType::CexDexQuotes
+            | MevType::CexDexInventory
             | MevType::JitCexDex => true,
         }
     }
@@ -134,6 +137,7 @@ impl MevType {
             MevType::Sandwich => "sandwich",
             MevType::JitSandwich => "jit-sandwich",
             MevType::SearcherTx => "searcher-tx",
+            MevType::CexDexInventory => "cex-dex-inventory",
             MevType::Liquidation => "liquidation",
             MevType::Unknown => "header",
         }
@@ -154,6 +158,7 @@ impl From<String> for MevType {
             "JitSandwich" => MevType::JitSandwich,
             "AtomicArb" => MevType::AtomicArb,
             "SearcherTx" => MevType::SearcherTx,
+            "CexDexInventory" => MevType::CexDexInventory,
             _ => MevType::Unknown,
         }
     }
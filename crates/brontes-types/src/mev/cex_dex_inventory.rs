@@ -0,0 +1,218 @@
+use std::{fmt, fmt::Debug};
+
+use ::clickhouse::DbRow;
+use ::serde::{
+    ser::{SerializeStruct, Serializer},
+    Deserialize, Serialize,
+};
+use ahash::HashSet;
+use colored::Colorize;
+use malachite::Rational;
+use redefined::Redefined;
+use reth_primitives::{Address, B256};
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde_with::serde_as;
+
+use super::{Mev, MevType};
+use crate::{
+    db::redefined_types::{malachite::RationalRedefined, primitives::*},
+    normalized_actions::*,
+    tree::ClickhouseVecGasDetails,
+    GasDetails, Protocol, ToFloatNearest,
+};
+
+/// The net dex inventory a labelled cex-dex searcher built up over a window of
+/// blocks, marked out against the cex at horizons after the last block of the
+/// window. Market makers net their positions across blocks instead of hedging
+/// every trade, so this gives a more realistic pnl than the per tx markout.
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct CexDexInventory {
+    pub eoa:          Address,
+    /// last block of the window
+    pub block_number: u64,
+    /// first block of the window
+    pub start_block:  u64,
+    pub tx_hashes:    Vec<B256>,
+    /// the dex swaps of each tx in `tx_hashes`
+    pub swaps:        Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub gas_details:  Vec<GasDetails>,
+    pub markouts:     Vec<InventoryMarkout>,
+}
+
+/// The inventory marked out at a single horizon.
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct InventoryMarkout {
+    /// seconds after the block timestamp
+    pub horizon:   u64,
+    pub positions: Vec<InventoryPosition>,
+    pub pnl_maker: Rational,
+    pub pnl_taker: Rational,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct InventoryPosition {
+    pub token:       Address,
+    /// net amount bought on dexes, negative if the searcher is short
+    pub amount:      Rational,
+    /// cex price of the token in the quote token at the horizon
+    pub price_maker: Rational,
+    pub price_taker: Rational,
+}
+
+impl Mev for CexDexInventory {
+    fn mev_type(&self) -> MevType {
+        MevType::CexDexInventory
+    }
+
+    fn total_gas_paid(&self) -> u128 {
+        self.gas_details.iter().map(|gas| gas.gas_paid()).sum()
+    }
+
+    fn total_priority_fee_paid(&self, base_fee: u128) -> u128 {
+        self.gas_details
+            .iter()
+            .map(|gas| gas.priority_fee_paid(base_fee))
+            .sum()
+    }
+
+    fn mev_transaction_hashes(&self) -> Vec<B256> {
+        self.tx_hashes.clone()
+    }
+
+    fn bribe(&self) -> u128 {
+        self.gas_details
+            .iter()
+            .filter_map(|gas| gas.coinbase_transfer)
+            .sum()
+    }
+
+    fn protocols(&self) -> HashSet<Protocol> {
+        self.swaps
+            .iter()
+            .flatten()
+            .map(|swap| swap.protocol)
+            .collect()
+    }
+}
+
+impl Serialize for CexDexInventory {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut ser_struct = serializer.serialize_struct("CexDexInventory", 18)?;
+        ser_struct.serialize_field("eoa", &format!("{:?}", self.eoa))?;
+        ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("start_block", &self.start_block)?;
+
+        let swaps: ClickhouseDoubleVecNormalizedSwap = (self.tx_hashes.clone(), self.swaps.clone())
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+        ser_struct.serialize_field("swaps.tx_hash", &swaps.tx_hash)?;
+        ser_struct.serialize_field("swaps.trace_idx", &swaps.trace_index)?;
+        ser_struct.serialize_field("swaps.from", &swaps.from)?;
+        ser_struct.serialize_field("swaps.recipient", &swaps.recipient)?;
+        ser_struct.serialize_field("swaps.pool", &swaps.pool)?;
+        ser_struct.serialize_field("swaps.token_in", &swaps.token_in)?;
+        ser_struct.serialize_field("swaps.token_out", &swaps.token_out)?;
+        ser_struct.serialize_field("swaps.amount_in", &swaps.amount_in)?;
+        ser_struct.serialize_field("swaps.amount_out", &swaps.amount_out)?;
+
+        let gas_details: ClickhouseVecGasDetails =
+            (self.tx_hashes.clone(), self.gas_details.clone()).into();
+        ser_struct.serialize_field("gas_details.tx_hash", &gas_details.tx_hash)?;
+        ser_struct
+            .serialize_field("gas_details.coinbase_transfer", &gas_details.coinbase_transfer)?;
+        ser_struct.serialize_field("gas_details.priority_fee", &gas_details.priority_fee)?;
+        ser_struct.serialize_field("gas_details.gas_used", &gas_details.gas_used)?;
+        ser_struct
+            .serialize_field("gas_details.effective_gas_price", &gas_details.effective_gas_price)?;
+
+        // inventory is signed, so unlike the rest of the row it is stored as floats
+        ser_struct.serialize_field(
+            "markouts",
+            &self
+                .markouts
+                .iter()
+                .map(|markout| {
+                    (
+                        markout.horizon,
+                        markout
+                            .positions
+                            .iter()
+                            .map(|p| {
+                                (
+                                    format!("{:?}", p.token),
+                                    p.amount.clone().to_float(),
+                                    p.price_maker.clone().to_float(),
+                                    p.price_taker.clone().to_float(),
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                        markout.pnl_maker.clone().to_float(),
+                        markout.pnl_taker.clone().to_float(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )?;
+
+        ser_struct.end()
+    }
+}
+
+impl DbRow for CexDexInventory {
+    const COLUMN_NAMES: &'static [&'static str] = &[
+        "eoa",
+        "block_number",
+        "start_block",
+        "swaps.tx_hash",
+        "swaps.trace_idx",
+        "swaps.from",
+        "swaps.recipient",
+        "swaps.pool",
+        "swaps.token_in",
+        "swaps.token_out",
+        "swaps.amount_in",
+        "swaps.amount_out",
+        "gas_details.tx_hash",
+        "gas_details.coinbase_transfer",
+        "gas_details.priority_fee",
+        "gas_details.gas_used",
+        "gas_details.effective_gas_price",
+        "markouts",
+    ];
+}
+
+impl fmt::Display for InventoryMarkout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "   - {}:",
+            format!("{}s markout", self.horizon)
+                .bold()
+                .underline()
+                .cyan()
+        )?;
+        for position in &self.positions {
+            writeln!(
+                f,
+                "       - {:?}: {:.6} @ Maker: {:.6}, Taker: {:.6}",
+                position.token,
+                position.amount.clone().to_float(),
+                position.price_maker.clone().to_float(),
+                position.price_taker.clone().to_float()
+            )?;
+        }
+        writeln!(
+            f,
+            "       - Maker PnL: {:.6}, Taker PnL: {:.6}",
+            self.pnl_maker.clone().to_float(),
+            self.pnl_taker.clone().to_float()
+        )
+    }
+}
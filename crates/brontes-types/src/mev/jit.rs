@@ -11,7 +11,7 @@ use serde::{
 };
 use serde_with::serde_as;
 
-use super::{Mev, MevType};
+use super::{ClickhouseVecVictimLossAmount, Mev, MevType, VictimLossAmount};
 use crate::{
     db::redefined_types::primitives::*, normalized_actions::*, tree::ClickhouseVecGasDetails,
     Protocol,
@@ -41,6 +41,10 @@ pub struct JitLiquidity {
     pub backrun_burns: Vec<NormalizedBurn>,
     #[redefined(same_fields)]
     pub backrun_burn_gas_details: GasDetails,
+    /// What each victim lost compared to the block being executed without the
+    /// jit mint and burn, negative when the jit liquidity improved the price
+    #[serde(default)]
+    pub victim_losses: Vec<VictimLossAmount>,
 }
 
 impl Mev for JitLiquidity {
@@ -83,7 +87,7 @@ impl Serialize for JitLiquidity {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("JitLiquidity", 31)?;
+        let mut ser_struct = serializer.serialize_struct("JitLiquidity", 36)?;
 
         // frontrun mint
         ser_struct.serialize_field(
@@ -173,6 +177,14 @@ impl Serialize for JitLiquidity {
 
         ser_struct.serialize_field("backrun_burn_gas_details", &(backrun_burn_gas_details))?;
 
+        let victim_losses: ClickhouseVecVictimLossAmount = self.victim_losses.clone().into();
+        ser_struct.serialize_field("victim_losses.tx_hash", &victim_losses.tx_hash)?;
+        ser_struct.serialize_field("victim_losses.victim_eoa", &victim_losses.victim_eoa)?;
+        ser_struct.serialize_field("victim_losses.token", &victim_losses.token)?;
+        ser_struct.serialize_field("victim_losses.amount_lost", &victim_losses.amount_lost)?;
+        ser_struct
+            .serialize_field("victim_losses.amount_lost_usd", &victim_losses.amount_lost_usd)?;
+
         ser_struct.end()
     }
 }
@@ -210,5 +222,10 @@ impl DbRow for JitLiquidity {
         "backrun_burns.tokens",
         "backrun_burns.amounts",
         "backrun_burn_gas_details",
+        "victim_losses.tx_hash",
+        "victim_losses.victim_eoa",
+        "victim_losses.token",
+        "victim_losses.amount_lost",
+        "victim_losses.amount_lost_usd",
     ];
 }
@@ -22,6 +22,8 @@ use crate::{display::utils::display_sandwich, normalized_actions::*, GasDetails}
 pub struct Liquidation {
     pub liquidation_tx_hash: B256,
     pub block_number:        u64,
+    /// The oracle update tx the liquidation backruns, zero if the liquidation
+    /// isn't preceded by an update of the liquidated assets in its block
     pub trigger:             B256,
     pub liquidation_swaps:   Vec<NormalizedSwap>,
     pub liquidations:        Vec<NormalizedLiquidation>,
@@ -29,6 +31,12 @@ pub struct Liquidation {
     pub gas_details:         GasDetails,
 }
 
+impl Liquidation {
+    pub fn is_oracle_backrun(&self) -> bool {
+        self.trigger != B256::ZERO
+    }
+}
+
 impl Mev for Liquidation {
     fn mev_type(&self) -> MevType {
         MevType::Liquidation
@@ -70,12 +78,13 @@ impl Serialize for Liquidation {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("Liquidation", 34)?;
+        let mut ser_struct = serializer.serialize_struct("Liquidation", 35)?;
 
         // frontrun
         ser_struct
             .serialize_field("liquidation_tx_hash", &format!("{:?}", self.liquidation_tx_hash))?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("trigger", &format!("{:?}", self.trigger))?;
 
         let liquidation_swaps: ClickhouseVecNormalizedSwap = self
             .liquidation_swaps
@@ -131,6 +140,7 @@ impl DbRow for Liquidation {
     const COLUMN_NAMES: &'static [&'static str] = &[
         "liquidation_tx_hash",
         "block_number",
+        "trigger",
         "liquidation_swaps.trace_idx",
         "liquidation_swaps.from",
         "liquidation_swaps.recipient",
@@ -14,7 +14,7 @@ use super::{Mev, MevType};
 use crate::{
     db::{redefined_types::primitives::*, token_info::TokenInfoWithAddress},
     normalized_actions::*,
-    ClickhouseVecGasDetails, Protocol,
+    ClickhouseVecGasDetails, Protocol, ToFloatNearest,
 };
 #[allow(unused_imports)]
 use crate::{
@@ -61,6 +61,10 @@ use crate::{
 #[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct Sandwich {
     pub block_number:             u64,
+    /// Block of the first frontrun. This is before `block_number` when the
+    /// sandwich spans multiple blocks.
+    #[serde(default)]
+    pub frontrun_block_number:    u64,
     /// Transaction hashes of the frontrunning transactions.
     /// Supports multiple transactions for complex sandwich scenarios.
     pub frontrun_tx_hash:         Vec<B256>,
@@ -88,19 +92,69 @@ pub struct Sandwich {
     /// Gas details for each backrunning transaction.
     #[redefined(same_fields)]
     pub backrun_gas_details:      GasDetails,
+    /// What each victim lost compared to the block being executed without the
+    /// frontruns and backrun. Empty if the block couldn't be re-executed.
+    #[serde(default)]
+    pub victim_losses:            Vec<VictimLossAmount>,
 }
 
-/// calcuation for the loss per user
-#[derive(Debug, Deserialize, PartialEq, Clone, Default)]
+impl Sandwich {
+    /// the amount of blocks the sandwich spans, 1 if it is contained in a
+    /// single block
+    pub fn block_span(&self) -> u64 {
+        self.block_number.saturating_sub(self.frontrun_block_number) + 1
+    }
+}
+
+/// calcuation for the loss per user, the difference between the amount of
+/// `token` the victim would have received if the mev txs weren't included and
+/// the amount it actually received. Negative if the victim got a better price
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct VictimLossAmount {
     pub tx_hash:           B256,
-    pub vicitim_eoa:       Address,
+    pub victim_eoa:        Address,
     pub token:             TokenInfoWithAddress,
     pub token_amount_lost: Rational,
     /// is zero if we don't have a price for the given token
     pub amount_lost_usd:   Rational,
 }
 
+pub struct ClickhouseVecVictimLossAmount {
+    pub tx_hash:         Vec<String>,
+    pub victim_eoa:      Vec<String>,
+    pub token:           Vec<(String, String)>,
+    pub amount_lost:     Vec<f64>,
+    pub amount_lost_usd: Vec<f64>,
+}
+
+impl From<Vec<VictimLossAmount>> for ClickhouseVecVictimLossAmount {
+    fn from(value: Vec<VictimLossAmount>) -> Self {
+        ClickhouseVecVictimLossAmount {
+            tx_hash:         value
+                .iter()
+                .map(|loss| format!("{:?}", loss.tx_hash))
+                .collect(),
+            victim_eoa:      value
+                .iter()
+                .map(|loss| format!("{:?}", loss.victim_eoa))
+                .collect(),
+            token:           value
+                .iter()
+                .map(|loss| loss.token.clickhouse_fmt())
+                .collect(),
+            amount_lost:     value
+                .iter()
+                .map(|loss| loss.token_amount_lost.clone().to_float())
+                .collect(),
+            amount_lost_usd: value
+                .iter()
+                .map(|loss| loss.amount_lost_usd.clone().to_float())
+                .collect(),
+        }
+    }
+}
+
 impl Mev for Sandwich {
     fn mev_type(&self) -> MevType {
         MevType::Sandwich
@@ -166,8 +220,9 @@ impl Serialize for Sandwich {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("Sandwich", 35)?;
+        let mut ser_struct = serializer.serialize_struct("Sandwich", 41)?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("frontrun_block_number", &self.frontrun_block_number)?;
 
         // frontrun
         ser_struct.serialize_field(
@@ -282,6 +337,14 @@ impl Serialize for Sandwich {
             &vec![self.backrun_gas_details.effective_gas_price],
         )?;
 
+        let victim_losses: ClickhouseVecVictimLossAmount = self.victim_losses.clone().into();
+        ser_struct.serialize_field("victim_losses.tx_hash", &victim_losses.tx_hash)?;
+        ser_struct.serialize_field("victim_losses.victim_eoa", &victim_losses.victim_eoa)?;
+        ser_struct.serialize_field("victim_losses.token", &victim_losses.token)?;
+        ser_struct.serialize_field("victim_losses.amount_lost", &victim_losses.amount_lost)?;
+        ser_struct
+            .serialize_field("victim_losses.amount_lost_usd", &victim_losses.amount_lost_usd)?;
+
         ser_struct.end()
     }
 }
@@ -289,6 +352,7 @@ impl Serialize for Sandwich {
 impl DbRow for Sandwich {
     const COLUMN_NAMES: &'static [&'static str] = &[
         "block_number",
+        "frontrun_block_number",
         "frontrun_tx_hash",
         "frontrun_swaps.tx_hash",
         "frontrun_swaps.trace_idx",
@@ -333,5 +397,10 @@ impl DbRow for Sandwich {
         "backrun_gas_details.priority_fee",
         "backrun_gas_details.gas_used",
         "backrun_gas_details.effective_gas_price",
+        "victim_losses.tx_hash",
+        "victim_losses.victim_eoa",
+        "victim_losses.token",
+        "victim_losses.amount_lost",
+        "victim_losses.amount_lost_usd",
     ];
 }
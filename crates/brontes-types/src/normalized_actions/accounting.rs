@@ -41,7 +41,8 @@ impl<IT: Iterator<Item = Action>> ActionAccounting for IT {
         let mut rem = vec![];
 
         for next in self {
-            if next.is_transfer() {
+            // eth transfers can be covered by an unwrap, so are applied last as well
+            if next.is_transfer() || next.is_eth_transfer() {
                 rem.push(next);
                 continue
             }
@@ -91,5 +92,81 @@ pub fn apply_delta<K: PartialEq + Hash + Eq>(
 
 #[cfg(test)]
 pub mod test {
-    // todo: add tests
+    use alloy_primitives::{hex, U256};
+
+    use super::*;
+    use crate::{
+        constants::{ETH_ADDRESS, WETH_ADDRESS},
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{
+            NormalizedEthTransfer, NormalizedSupply, NormalizedTransfer, NormalizedWrap,
+        },
+        Protocol,
+    };
+
+    #[test]
+    fn test_supply_covers_transfer() {
+        let user = Address::new(hex!("00000000000000000000000000000000000000aa"));
+        let pool = Address::new(hex!("87870bca3f3fd6335c3f4ce8392d69350b4fa4e2"));
+        let a_token = Address::new(hex!("98c23e9d8f34fefb1b7bd6a91b7ff122f4e16f5c"));
+        let usdc = TokenInfoWithAddress::usdc();
+
+        let supply = Action::Supply(NormalizedSupply {
+            pool,
+            from: user,
+            on_behalf_of: user,
+            token: usdc.clone(),
+            amount: Rational::from(100),
+            ..Default::default()
+        });
+        // the funds are held by the atoken, not the pool
+        let transfer = Action::Transfer(NormalizedTransfer {
+            from: user,
+            to: a_token,
+            token: usdc.clone(),
+            amount: Rational::from(100),
+            ..Default::default()
+        });
+
+        let deltas = vec![transfer, supply].into_iter().account_for_actions();
+
+        assert_eq!(deltas[&user][&usdc.address], Rational::from(-100));
+        assert_eq!(deltas[&pool][&usdc.address], Rational::from(100));
+        assert!(!deltas.contains_key(&a_token));
+    }
+
+    #[test]
+    fn test_unwrap_covers_eth_transfer() {
+        let user = Address::new(hex!("00000000000000000000000000000000000000aa"));
+        let eth = TokenInfoWithAddress {
+            inner:   TokenInfo { decimals: 18, symbol: "ETH".to_string() },
+            address: ETH_ADDRESS,
+        };
+        let weth = TokenInfoWithAddress::weth();
+
+        let unwrap = Action::Wrap(NormalizedWrap {
+            protocol: Protocol::WETH,
+            from: user,
+            recipient: user,
+            contract: WETH_ADDRESS,
+            token_in: weth.clone(),
+            token_out: eth,
+            amount_in: Rational::from(2),
+            amount_out: Rational::from(2),
+            ..Default::default()
+        });
+        // weth sends the eth back to the user
+        let eth_transfer = Action::EthTransfer(NormalizedEthTransfer {
+            from: WETH_ADDRESS,
+            to: user,
+            value: U256::from(2_000_000_000_000_000_000u128),
+            ..Default::default()
+        });
+
+        let deltas = vec![eth_transfer, unwrap].into_iter().account_for_actions();
+
+        assert_eq!(deltas[&user][&weth.address], Rational::from(-2));
+        assert_eq!(deltas[&user][&ETH_ADDRESS], Rational::from(2));
+        assert!(!deltas.contains_key(&WETH_ADDRESS));
+    }
 }
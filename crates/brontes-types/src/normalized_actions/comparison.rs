@@ -1,6 +1,11 @@
 use std::fmt::Debug;
 
-use super::{Action, NormalizedCollect, NormalizedMint, NormalizedSwap, NormalizedTransfer};
+use super::{
+    Action, NormalizedCollect, NormalizedEthTransfer, NormalizedLoan, NormalizedMint,
+    NormalizedRepayment, NormalizedSupply, NormalizedSwap, NormalizedTransfer, NormalizedWithdraw,
+    NormalizedWrap,
+};
+use crate::{constants::ETH_ADDRESS, ToScaledRational};
 
 impl<T: Sized + SubordinateAction<O>, O: ActionCmp<T>> ActionComparison<O> for T {}
 
@@ -20,7 +25,12 @@ impl ActionCmp<Action> for Action {
             Action::Swap(s) => s.is_superior_action(other),
             Action::Mint(m) => m.is_superior_action(other),
             Action::Collect(c) => c.is_superior_action(other),
+            Action::Supply(s) => s.is_superior_action(other),
+            Action::Withdraw(w) => w.is_superior_action(other),
+            Action::Loan(l) => l.is_superior_action(other),
+            Action::Repayment(r) => r.is_superior_action(other),
             Action::SwapWithFee(s) => s.swap.is_superior_action(other),
+            Action::Wrap(w) => w.is_superior_action(other),
             Action::FlashLoan(f) => f.child_actions.iter().any(|a| a.is_superior_action(other)),
             Action::Batch(b) => {
                 let user = b.user_swaps.iter().any(|b| b.is_superior_action(other));
@@ -116,3 +126,72 @@ impl ActionCmp<Action> for NormalizedCollect {
         }
     }
 }
+
+// the deposited token, or ETH, is sent to the wrapper and the wrapped token, or
+// ETH when unwrapping, is sent back to the recipient
+impl ActionCmp<NormalizedTransfer> for NormalizedWrap {
+    fn is_superior_action(&self, transfer: &NormalizedTransfer) -> bool {
+        (transfer.token == self.token_in
+            && transfer.to == self.contract
+            && &transfer.amount + &transfer.fee == self.amount_in)
+            || (transfer.token == self.token_out
+                && transfer.from == self.contract
+                && transfer.to == self.recipient
+                && transfer.amount == self.amount_out)
+    }
+}
+
+impl ActionCmp<NormalizedEthTransfer> for NormalizedWrap {
+    fn is_superior_action(&self, transfer: &NormalizedEthTransfer) -> bool {
+        let value = transfer.value.to_scaled_rational(18);
+
+        (self.token_in.address == ETH_ADDRESS
+            && transfer.from == self.from
+            && transfer.to == self.contract
+            && value == self.amount_in)
+            || (self.token_out.address == ETH_ADDRESS
+                && transfer.from == self.contract
+                && transfer.to == self.recipient
+                && value == self.amount_out)
+    }
+}
+
+impl ActionCmp<Action> for NormalizedWrap {
+    fn is_superior_action(&self, other: &Action) -> bool {
+        match other {
+            Action::Transfer(t) => self.is_superior_action(t),
+            Action::EthTransfer(t) => self.is_superior_action(t),
+            _ => false,
+        }
+    }
+}
+
+/// lending actions move a single token, the transfer of that token is covered
+/// by the action
+macro_rules! lending_action_cmp {
+    ($($action:ident: $token:ident, $amount:ident),*) => {
+        $(
+            impl ActionCmp<NormalizedTransfer> for $action {
+                fn is_superior_action(&self, transfer: &NormalizedTransfer) -> bool {
+                    transfer.amount.eq(&self.$amount) && transfer.token.eq(&self.$token)
+                }
+            }
+
+            impl ActionCmp<Action> for $action {
+                fn is_superior_action(&self, other: &Action) -> bool {
+                    match other {
+                        Action::Transfer(t) => self.is_superior_action(t),
+                        _ => false,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+lending_action_cmp!(
+    NormalizedSupply: token, amount,
+    NormalizedWithdraw: token, amount,
+    NormalizedLoan: loaned_token, loan_amount,
+    NormalizedRepayment: repayed_token, repayment_amount
+);
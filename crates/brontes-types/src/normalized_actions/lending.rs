@@ -1,33 +1,146 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 use alloy_primitives::U256;
 use clickhouse::Row;
+use colored::Colorize;
 use malachite::Rational;
 use reth_primitives::Address;
 use serde::{Deserialize, Serialize};
 
-use crate::{db::token_info::TokenInfoWithAddress, FastHashMap, Protocol};
+use super::accounting::{apply_delta, AddressDeltas, TokenAccounting};
+use crate::{db::token_info::TokenInfoWithAddress, Protocol, ToFloatNearest};
 
-#[derive(Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+/// Collateral supplied to a lending market. The `on_behalf_of` address is the
+/// account that is credited with the collateral.
+#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+pub struct NormalizedSupply {
+    pub protocol:     Protocol,
+    pub trace_index:  u64,
+    pub pool:         Address,
+    pub from:         Address,
+    pub on_behalf_of: Address,
+    pub token:        TokenInfoWithAddress,
+    pub amount:       Rational,
+    pub msg_value:    U256,
+}
+
+impl TokenAccounting for NormalizedSupply {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(self.from, self.token.address, -self.amount.clone(), delta_map);
+        apply_delta(self.pool, self.token.address, self.amount.clone(), delta_map);
+    }
+}
+
+/// Collateral withdrawn from a lending market by `from` and sent to
+/// `recipient`.
+#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+pub struct NormalizedWithdraw {
+    pub protocol:    Protocol,
+    pub trace_index: u64,
+    pub pool:        Address,
+    pub from:        Address,
+    pub recipient:   Address,
+    pub token:       TokenInfoWithAddress,
+    pub amount:      Rational,
+}
+
+impl TokenAccounting for NormalizedWithdraw {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(self.pool, self.token.address, -self.amount.clone(), delta_map);
+        apply_delta(self.recipient, self.token.address, self.amount.clone(), delta_map);
+    }
+}
+
+/// A borrow against the `borrower`'s collateral. The borrowed funds are sent
+/// to the `recipient`, which is not always the borrower (e.g aave credit
+/// delegation).
+#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
 pub struct NormalizedLoan {
     pub protocol:     Protocol,
     pub trace_index:  u64,
     pub lender:       Address,
     pub borrower:     Address,
+    pub recipient:    Address,
     pub loaned_token: TokenInfoWithAddress,
     pub loan_amount:  Rational,
-    pub collateral:   FastHashMap<TokenInfoWithAddress, Rational>,
     pub msg_value:    U256,
 }
 
-#[derive(Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+impl TokenAccounting for NormalizedLoan {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(self.lender, self.loaned_token.address, -self.loan_amount.clone(), delta_map);
+        apply_delta(self.recipient, self.loaned_token.address, self.loan_amount.clone(), delta_map);
+    }
+}
+
+/// A repayment of the `borrower`'s debt paid by the `payer`.
+#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
 pub struct NormalizedRepayment {
     pub protocol:         Protocol,
     pub trace_index:      u64,
     pub lender:           Address,
     pub borrower:         Address,
+    pub payer:            Address,
     pub repayed_token:    TokenInfoWithAddress,
     pub repayment_amount: Rational,
-    pub collateral:       FastHashMap<TokenInfoWithAddress, Rational>,
     pub msg_value:        U256,
 }
+
+impl TokenAccounting for NormalizedRepayment {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(
+            self.payer,
+            self.repayed_token.address,
+            -self.repayment_amount.clone(),
+            delta_map,
+        );
+        apply_delta(
+            self.lender,
+            self.repayed_token.address,
+            self.repayment_amount.clone(),
+            delta_map,
+        );
+    }
+}
+
+impl fmt::Display for NormalizedSupply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.protocol.to_string().bold();
+        let amount = format!("{:.4}", self.amount.clone().to_float()).green();
+        let token = self.token.inner.symbol.bold();
+
+        write!(f, "Supplied {} {} on {}", amount, token, protocol)
+    }
+}
+
+impl fmt::Display for NormalizedWithdraw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.protocol.to_string().bold();
+        let amount = format!("{:.4}", self.amount.clone().to_float()).red();
+        let token = self.token.inner.symbol.bold();
+
+        write!(f, "Withdrew {} {} from {}", amount, token, protocol)
+    }
+}
+
+impl fmt::Display for NormalizedLoan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.protocol.to_string().bold();
+        let amount = format!("{:.4}", self.loan_amount.clone().to_float()).red();
+        let token = self.loaned_token.inner.symbol.bold();
+        let borrower = format!("{}", self.borrower).cyan();
+
+        write!(f, "{} borrowed {} {} from {}", borrower, amount, token, protocol)
+    }
+}
+
+impl fmt::Display for NormalizedRepayment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.protocol.to_string().bold();
+        let amount = format!("{:.4}", self.repayment_amount.clone().to_float()).green();
+        let token = self.repayed_token.inner.symbol.bold();
+        let borrower = format!("{}", self.borrower).cyan();
+
+        write!(f, "Repaid {} {} of {}'s debt on {}", amount, token, borrower, protocol)
+    }
+}
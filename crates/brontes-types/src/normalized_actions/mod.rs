@@ -8,10 +8,12 @@ pub mod lending;
 pub mod liquidation;
 pub mod liquidity;
 pub mod multi_callframe;
+pub mod oracle;
 pub mod pool;
 pub mod self_destruct;
 pub mod swaps;
 pub mod transfer;
+pub mod wrap;
 use std::fmt::Debug;
 
 use ::clickhouse::DbRow;
@@ -26,11 +28,13 @@ pub use lending::*;
 pub use liquidation::*;
 pub use liquidity::*;
 pub use multi_callframe::*;
+pub use oracle::*;
 pub use pool::*;
 use reth_rpc_types::trace::parity::Action as TraceAction;
 pub use self_destruct::*;
 pub use swaps::*;
 pub use transfer::*;
+pub use wrap::*;
 
 use crate::{
     structured_trace::{TraceActions, TransactionTraceWithLogs},
@@ -90,6 +94,10 @@ impl NormalizedAction for Action {
             Self::Burn(b) => b.trace_index,
             Self::Transfer(t) => t.trace_index,
             Self::Liquidation(t) => t.trace_index,
+            Self::Supply(s) => s.trace_index,
+            Self::Withdraw(w) => w.trace_index,
+            Self::Loan(l) => l.trace_index,
+            Self::Repayment(r) => r.trace_index,
             Self::Collect(c) => c.trace_index,
             Self::SelfDestruct(c) => c.trace_index,
             Self::EthTransfer(e) => e.trace_index,
@@ -97,6 +105,8 @@ impl NormalizedAction for Action {
             Self::NewPool(p) => p.trace_index,
             Self::PoolConfigUpdate(p) => p.trace_index,
             Self::Aggregator(a) => a.trace_index,
+            Self::OracleUpdate(o) => o.trace_index,
+            Self::Wrap(w) => w.trace_index,
             Self::Revert => unreachable!("no trace index for revert"),
         }
     }
@@ -114,11 +124,17 @@ pub enum Action {
     Burn(NormalizedBurn),
     Collect(NormalizedCollect),
     Liquidation(NormalizedLiquidation),
+    Supply(NormalizedSupply),
+    Withdraw(NormalizedWithdraw),
+    Loan(NormalizedLoan),
+    Repayment(NormalizedRepayment),
     SelfDestruct(SelfdestructWithIndex),
     EthTransfer(NormalizedEthTransfer),
     NewPool(NormalizedNewPool),
     PoolConfigUpdate(NormalizedPoolConfigUpdate),
     Aggregator(NormalizedAggregator),
+    OracleUpdate(NormalizedOracleUpdate),
+    Wrap(NormalizedWrap),
     Unclassified(TransactionTraceWithLogs),
     Revert,
 }
@@ -135,12 +151,18 @@ impl InsertRow for Action {
             Action::Burn(_) => NormalizedBurn::COLUMN_NAMES,
             Action::Collect(_) => NormalizedCollect::COLUMN_NAMES,
             Action::Liquidation(_) => NormalizedLiquidation::COLUMN_NAMES,
+            Action::Supply(_) => NormalizedSupply::COLUMN_NAMES,
+            Action::Withdraw(_) => NormalizedWithdraw::COLUMN_NAMES,
+            Action::Loan(_) => NormalizedLoan::COLUMN_NAMES,
+            Action::Repayment(_) => NormalizedRepayment::COLUMN_NAMES,
             Action::SelfDestruct(_) => todo!("joe pls dome this"),
             Action::EthTransfer(_) => todo!("joe pls dome this"),
             Action::NewPool(_) => todo!(),
             Action::PoolConfigUpdate(_) => todo!(),
             Action::Unclassified(..) | Action::Revert => panic!(),
             Action::Aggregator(_) => NormalizedAggregator::COLUMN_NAMES,
+            Action::OracleUpdate(_) => NormalizedOracleUpdate::COLUMN_NAMES,
+            Action::Wrap(_) => NormalizedWrap::COLUMN_NAMES,
         }
     }
 }
@@ -161,8 +183,14 @@ impl serde::Serialize for Action {
             Action::Burn(b) => b.serialize(serializer),
             Action::Collect(c) => c.serialize(serializer),
             Action::Liquidation(c) => c.serialize(serializer),
+            Action::Supply(s) => s.serialize(serializer),
+            Action::Withdraw(w) => w.serialize(serializer),
+            Action::Loan(l) => l.serialize(serializer),
+            Action::Repayment(r) => r.serialize(serializer),
             Action::SelfDestruct(sd) => sd.serialize(serializer),
             Action::EthTransfer(et) => et.serialize(serializer),
+            Action::OracleUpdate(o) => o.serialize(serializer),
+            Action::Wrap(w) => w.serialize(serializer),
             Action::Unclassified(trace) => (trace).serialize(serializer),
             action => format!("{:?}", action).serialize(serializer),
             //action => unreachable!("no action serialization for {action:?}"),
@@ -218,14 +246,35 @@ impl Action {
                     from: a.from,
                     ..Default::default()
                 }),
+                Self::Supply(s) => (!s.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: s.msg_value,
+                    to: s.pool,
+                    from: s.from,
+                    ..Default::default()
+                }),
+                Self::Repayment(r) => (!r.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: r.msg_value,
+                    to: r.lender,
+                    from: r.payer,
+                    ..Default::default()
+                }),
+                Self::Wrap(w) => (!w.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: w.msg_value,
+                    to: w.contract,
+                    from: w.from,
+                    ..Default::default()
+                }),
                 Self::Mint(_) => None,
                 Self::Burn(_) => None,
+                Self::Withdraw(_) => None,
+                Self::Loan(_) => None,
                 Self::Transfer(_) => None,
                 Self::Collect(_) => None,
                 Self::SelfDestruct(_) => None,
                 Self::EthTransfer(_) => None,
                 Self::NewPool(_) => None,
                 Self::PoolConfigUpdate(_) => None,
+                Self::OracleUpdate(_) => None,
                 Self::Revert => None,
             };
         if res.is_some() {
@@ -251,6 +300,10 @@ impl Action {
             Self::Burn(b) => b.trace_index,
             Self::Transfer(t) => t.trace_index,
             Self::Liquidation(t) => t.trace_index,
+            Self::Supply(s) => s.trace_index,
+            Self::Withdraw(w) => w.trace_index,
+            Self::Loan(l) => l.trace_index,
+            Self::Repayment(r) => r.trace_index,
             Self::Collect(c) => c.trace_index,
             Self::SelfDestruct(c) => c.trace_index,
             Self::EthTransfer(e) => e.trace_index,
@@ -258,6 +311,8 @@ impl Action {
             Self::NewPool(p) => p.trace_index,
             Self::PoolConfigUpdate(p) => p.trace_index,
             Self::Aggregator(a) => a.trace_index,
+            Self::OracleUpdate(o) => o.trace_index,
+            Self::Wrap(w) => w.trace_index,
             Self::Revert => return None,
         })
     }
@@ -325,6 +380,10 @@ impl Action {
             Action::Transfer(t) => t.to,
             Action::Collect(c) => c.pool,
             Action::Liquidation(c) => c.pool,
+            Action::Supply(s) => s.pool,
+            Action::Withdraw(w) => w.pool,
+            Action::Loan(l) => l.lender,
+            Action::Repayment(r) => r.lender,
             Action::SelfDestruct(c) => c.get_refund_address(),
             Action::Unclassified(t) => match &t.trace.action {
                 reth_rpc_types::trace::parity::Action::Call(c) => c.to,
@@ -335,6 +394,8 @@ impl Action {
             Action::EthTransfer(t) => t.to,
             Action::NewPool(p) => p.pool_address,
             Action::PoolConfigUpdate(p) => p.pool_address,
+            Action::OracleUpdate(o) => o.oracle,
+            Action::Wrap(w) => w.contract,
             Action::Revert => Address::ZERO,
         }
     }
@@ -351,6 +412,10 @@ impl Action {
             Action::Transfer(t) => t.from,
             Action::Collect(c) => c.from,
             Action::Liquidation(c) => c.liquidator,
+            Action::Supply(s) => s.from,
+            Action::Withdraw(w) => w.from,
+            Action::Loan(l) => l.borrower,
+            Action::Repayment(r) => r.payer,
             Action::SelfDestruct(c) => c.get_address(),
             Action::Unclassified(t) => match &t.trace.action {
                 reth_rpc_types::trace::parity::Action::Call(c) => c.to,
@@ -362,6 +427,8 @@ impl Action {
             Action::Revert => unreachable!(),
             Action::NewPool(_) => Address::ZERO,
             Action::PoolConfigUpdate(_) => Address::ZERO,
+            Action::OracleUpdate(o) => o.from,
+            Action::Wrap(w) => w.from,
         }
     }
 
@@ -395,6 +462,22 @@ impl Action {
         matches!(self, Action::Liquidation(_))
     }
 
+    pub const fn is_supply(&self) -> bool {
+        matches!(self, Action::Supply(_))
+    }
+
+    pub const fn is_withdraw(&self) -> bool {
+        matches!(self, Action::Withdraw(_))
+    }
+
+    pub const fn is_loan(&self) -> bool {
+        matches!(self, Action::Loan(_))
+    }
+
+    pub const fn is_repayment(&self) -> bool {
+        matches!(self, Action::Repayment(_))
+    }
+
     pub const fn is_batch(&self) -> bool {
         matches!(self, Action::Batch(_))
     }
@@ -431,6 +514,14 @@ impl Action {
         matches!(self, Action::PoolConfigUpdate(_))
     }
 
+    pub const fn is_oracle_update(&self) -> bool {
+        matches!(self, Action::OracleUpdate(_))
+    }
+
+    pub const fn is_wrap(&self) -> bool {
+        matches!(self, Action::Wrap(_))
+    }
+
     pub const fn is_unclassified(&self) -> bool {
         matches!(self, Action::Unclassified(_))
     }
@@ -445,9 +536,15 @@ impl Action {
             Action::Burn(b) => b.protocol,
             Action::Collect(c) => c.protocol,
             Action::Liquidation(c) => c.protocol,
+            Action::Supply(s) => s.protocol,
+            Action::Withdraw(w) => w.protocol,
+            Action::Loan(l) => l.protocol,
+            Action::Repayment(r) => r.protocol,
             Action::NewPool(p) => p.protocol,
             Action::PoolConfigUpdate(p) => p.protocol,
             Action::Aggregator(a) => a.protocol,
+            Action::OracleUpdate(o) => o.protocol,
+            Action::Wrap(w) => w.protocol,
             _ => Protocol::Unknown,
         }
     }
@@ -523,10 +620,16 @@ extra_impls!(
     (Transfer, NormalizedTransfer),
     (EthTransfer, NormalizedEthTransfer),
     (Liquidation, NormalizedLiquidation),
+    (Supply, NormalizedSupply),
+    (Withdraw, NormalizedWithdraw),
+    (Loan, NormalizedLoan),
+    (Repayment, NormalizedRepayment),
     (FlashLoan, NormalizedFlashLoan),
     (Aggregator, NormalizedAggregator),
     (Batch, NormalizedBatch),
-    (NewPool, NormalizedNewPool)
+    (NewPool, NormalizedNewPool),
+    (OracleUpdate, NormalizedOracleUpdate),
+    (Wrap, NormalizedWrap)
 );
 
 /// Custom impl for itering over swaps and swap with fee
@@ -572,6 +675,10 @@ impl TokenAccounting for Action {
             Action::FlashLoan(flash_loan) => flash_loan.apply_token_deltas(delta_map),
             Action::Aggregator(aggregator) => aggregator.apply_token_deltas(delta_map),
             Action::Liquidation(liquidation) => liquidation.apply_token_deltas(delta_map),
+            Action::Supply(supply) => supply.apply_token_deltas(delta_map),
+            Action::Withdraw(withdraw) => withdraw.apply_token_deltas(delta_map),
+            Action::Loan(loan) => loan.apply_token_deltas(delta_map),
+            Action::Repayment(repayment) => repayment.apply_token_deltas(delta_map),
             Action::Batch(batch) => batch.apply_token_deltas(delta_map),
             Action::Burn(burn) => burn.apply_token_deltas(delta_map),
             Action::Mint(mint) => mint.apply_token_deltas(delta_map),
@@ -583,6 +690,8 @@ impl TokenAccounting for Action {
             Action::SelfDestruct(_self_destruct) => (),
             Action::NewPool(_new_pool) => (),
             Action::PoolConfigUpdate(_pool_update) => (),
+            Action::OracleUpdate(oracle_update) => oracle_update.apply_token_deltas(delta_map),
+            Action::Wrap(wrap) => wrap.apply_token_deltas(delta_map),
             Action::Revert => (), // No token deltas to apply for a revert
         }
     }
@@ -5,6 +5,10 @@ type ParseFn<V> = Box<dyn Fn(&mut V, Vec<(NodeDataIndex, V)>) -> Vec<NodeDataInd
 
 pub struct MultiCallFrameClassification<V: NormalizedAction> {
     pub trace_index:         u64,
+    /// Also collects the actions of the call-frames that come after the head
+    /// in the same parent call. Used for protocols where the action is
+    /// finished by a later call instead of a callback
+    pub search_siblings:     bool,
     pub tree_search_builder: TreeSearchBuilder<V>,
     pub parse_fn:            ParseFn<V>,
 }
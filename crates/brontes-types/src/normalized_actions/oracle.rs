@@ -0,0 +1,53 @@
+use std::fmt::{self, Debug};
+
+use clickhouse::Row;
+use colored::Colorize;
+use malachite::Rational;
+use reth_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use super::accounting::{AddressDeltas, TokenAccounting};
+use crate::{db::token_info::TokenInfoWithAddress, Protocol, ToFloatNearest};
+
+/// A new price pushed to an onchain oracle. `from` is the transmitter or
+/// keeper that pushed the update and `oracle` the feed that stores the price.
+/// The asset is only known for feeds that are registered with their token.
+#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+pub struct NormalizedOracleUpdate {
+    pub protocol:    Protocol,
+    pub trace_index: u64,
+    pub oracle:      Address,
+    pub from:        Address,
+    pub asset:       Option<TokenInfoWithAddress>,
+    pub price:       Rational,
+}
+
+impl NormalizedOracleUpdate {
+    /// Whether the update prices one of the given tokens. Updates of feeds
+    /// without a known asset could price anything.
+    pub fn may_price_any(&self, tokens: &[Address]) -> bool {
+        self.asset
+            .as_ref()
+            .map(|asset| tokens.contains(&asset.address))
+            .unwrap_or(true)
+    }
+}
+
+impl TokenAccounting for NormalizedOracleUpdate {
+    fn apply_token_deltas(&self, _delta_map: &mut AddressDeltas) {}
+}
+
+impl fmt::Display for NormalizedOracleUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.protocol.to_string().bold();
+        let price = format!("{:.4}", self.price.clone().to_float()).green();
+        let asset = self
+            .asset
+            .as_ref()
+            .map(|asset| asset.inner.symbol.clone())
+            .unwrap_or_else(|| format!("{:?}", self.oracle))
+            .bold();
+
+        write!(f, "Updated {} price to {} on {}", asset, price, protocol)
+    }
+}
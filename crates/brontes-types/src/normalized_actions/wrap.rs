@@ -0,0 +1,64 @@
+use std::fmt::{self, Debug};
+
+use alloy_primitives::U256;
+use clickhouse::Row;
+use colored::Colorize;
+use malachite::{num::basic::traits::Zero, Rational};
+use reth_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use super::accounting::{apply_delta, AddressDeltas, TokenAccounting};
+use crate::{db::token_info::TokenInfoWithAddress, Protocol, ToFloatNearest};
+
+/// A token deposited into a contract in exchange for a token that represents
+/// it, e.g. ETH for WETH, ETH for stETH or stETH for wstETH. Unwrapping is the
+/// same action with the tokens reversed. Native ETH uses the `ETH_ADDRESS`
+/// placeholder so it is kept apart from WETH in the token deltas.
+#[derive(Debug, Default, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+pub struct NormalizedWrap {
+    pub protocol:    Protocol,
+    pub trace_index: u64,
+    pub from:        Address,
+    pub recipient:   Address,
+    /// The wrapper or staking contract that holds `token_in`
+    pub contract:    Address,
+    pub token_in:    TokenInfoWithAddress,
+    pub token_out:   TokenInfoWithAddress,
+    pub amount_in:   Rational,
+    pub amount_out:  Rational,
+    pub msg_value:   U256,
+}
+
+impl NormalizedWrap {
+    /// The amount of `token_out` received per `token_in`. This is 1 for plain
+    /// wrappers and the exchange rate of the contract for liquid staking
+    /// tokens.
+    pub fn exchange_rate(&self) -> Rational {
+        if self.amount_in == Rational::ZERO {
+            return Rational::ZERO
+        }
+
+        &self.amount_out / &self.amount_in
+    }
+}
+
+impl TokenAccounting for NormalizedWrap {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(self.from, self.token_in.address, -self.amount_in.clone(), delta_map);
+        apply_delta(self.recipient, self.token_out.address, self.amount_out.clone(), delta_map);
+    }
+}
+
+impl fmt::Display for NormalizedWrap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let amount_in = format!("{:.4}", self.amount_in.clone().to_float()).red();
+        let amount_out = format!("{:.4}", self.amount_out.clone().to_float()).green();
+        let protocol = self.protocol.to_string().bold();
+
+        write!(
+            f,
+            "Wrap {} {} into {} {} via {}",
+            amount_in, self.token_in.symbol, amount_out, self.token_out.symbol, protocol
+        )
+    }
+}
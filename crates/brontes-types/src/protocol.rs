@@ -101,6 +101,21 @@ utils!(
         ClipperExchange,
         PropellerLabsSolver,
         Dodo,
+        UniswapV4,
+        ChainlinkOffchainAggregator,
+        MakerOSM,
+        CompoundV3,
+        MorphoBlue,
+        Liquity,
+        CurvecrvUSDController,
+        CurvecrvUSDLlamma,
+        WETH,
+        LidoStETH,
+        LidoWstETH,
+        RocketPoolRETH,
+        ParaswapV5,
+        KyberSwapAggregatorV2,
+        OdosV2,
         #[default]
         Unknown,
     }
@@ -146,6 +161,21 @@ impl Protocol {
             Protocol::ClipperExchange => ("ClipperExchange", ""),
             Protocol::PropellerLabsSolver => ("Propeller Labs Solver", ""),
             Protocol::Dodo => ("Dodo", "V1/V2"),
+            Protocol::UniswapV4 => ("Uniswap", "V4"),
+            Protocol::ChainlinkOffchainAggregator => ("Chainlink", "OffchainAggregator"),
+            Protocol::MakerOSM => ("Maker", "OSM"),
+            Protocol::CompoundV3 => ("Compound", "V3"),
+            Protocol::MorphoBlue => ("Morpho", "Blue"),
+            Protocol::Liquity => ("Liquity", "V1"),
+            Protocol::CurvecrvUSDController => ("Curve.fi", "crvUSD Controller"),
+            Protocol::CurvecrvUSDLlamma => ("Curve.fi", "crvUSD LLAMMA"),
+            Protocol::WETH => ("WETH", ""),
+            Protocol::LidoStETH => ("Lido", "stETH"),
+            Protocol::LidoWstETH => ("Lido", "wstETH"),
+            Protocol::RocketPoolRETH => ("Rocket Pool", "rETH"),
+            Protocol::ParaswapV5 => ("Paraswap", "V5"),
+            Protocol::KyberSwapAggregatorV2 => ("KyberSwap", "Aggregator V2"),
+            Protocol::OdosV2 => ("Odos", "V2"),
             Protocol::Unknown => ("Unknown", "Unknown"),
         }
     }
@@ -155,6 +185,7 @@ impl Protocol {
             "uniswapv2" => Protocol::UniswapV2,
             "sushiswapv2" => Protocol::SushiSwapV2,
             "uniswapv3" => Protocol::UniswapV3,
+            "uniswapv4" => Protocol::UniswapV4,
             "sushiswapv3" => Protocol::SushiSwapV3,
             "curve.fibase2" => Protocol::CurveBasePool2,
             "curve.fibase3" => Protocol::CurveBasePool3,
@@ -230,6 +261,21 @@ impl fmt::Display for Protocol {
                 Protocol::ClipperExchange => "Clipper",
                 Protocol::PropellerLabsSolver => "Propeller Labs",
                 Protocol::Dodo => "Dodo",
+                Protocol::UniswapV4 => "Uni V4",
+                Protocol::ChainlinkOffchainAggregator => "Chainlink",
+                Protocol::MakerOSM => "Maker OSM",
+                Protocol::CompoundV3 => "Compound V3",
+                Protocol::MorphoBlue => "Morpho Blue",
+                Protocol::Liquity => "Liquity",
+                Protocol::CurvecrvUSDController => "Curve crvUSD",
+                Protocol::CurvecrvUSDLlamma => "Curve LLAMMA",
+                Protocol::WETH => "WETH",
+                Protocol::LidoStETH => "Lido stETH",
+                Protocol::LidoWstETH => "Lido wstETH",
+                Protocol::RocketPoolRETH => "Rocket Pool rETH",
+                Protocol::ParaswapV5 => "Paraswap V5",
+                Protocol::KyberSwapAggregatorV2 => "KyberSwap Aggregator V2",
+                Protocol::OdosV2 => "Odos V2",
                 Protocol::Unknown => "Unknown",
             }
         )
@@ -1,4 +1,6 @@
-use alloy_primitives::TxHash;
+use std::fmt::Debug;
+
+use alloy_primitives::{Log, TxHash};
 use alloy_rpc_types::AnyReceiptEnvelope;
 use reth_primitives::{
     Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, B256,
@@ -66,3 +68,28 @@ pub trait TracingProvider: Send + Sync + 'static {
         address: Address,
     ) -> eyre::Result<Option<Bytecode>>;
 }
+
+/// Re-executes the transactions of a block on top of the state of the parent
+/// block. Used to compute what would have happened if a set of transactions
+/// wasn't included or was ordered differently.
+pub trait BlockSimulator: Debug + Send + Sync {
+    /// The hashes of the transactions of the block in the order they were
+    /// included
+    fn block_tx_hashes(&self, block_number: BlockNumber) -> eyre::Result<Vec<TxHash>>;
+
+    /// Executes the given transactions of the block in the order they are
+    /// passed in. Transactions of the block that aren't passed are skipped.
+    fn simulate_block(
+        &self,
+        block_number: BlockNumber,
+        tx_hashes: &[TxHash],
+    ) -> eyre::Result<Vec<SimulatedTx>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedTx {
+    pub tx_hash:    TxHash,
+    pub is_success: bool,
+    pub gas_used:   u64,
+    pub logs:       Vec<Log>,
+}
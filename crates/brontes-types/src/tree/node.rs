@@ -69,36 +69,27 @@ impl Node {
     ) {
         if head.trace_index == self.index {
             let mut results = Vec::new();
+            self.collect(&mut results, head.collect_args(), &node_data_index, nodes);
 
-            self.collect(
-                &mut results,
-                head.collect_args(),
-                &|data| {
-                    (
-                        NodeDataIndex {
-                            trace_index:    data.node.index,
-                            data_idx:       data.node.data as u64,
-                            multi_data_idx: data.idx,
-                        },
-                        data.data.clone(),
-                    )
-                },
-                nodes,
-            );
-
-            // should always be the first index
-            let this = nodes.get_mut(self.data).unwrap().first_mut().unwrap();
-            let clear_collapsed_nodes = head.parse(this, results);
+            return self.parse_complex_classification(head, self.data, results, nodes)
+        }
 
-            clear_collapsed_nodes
-                .into_iter()
-                // remove the outer indexes first to ensure no unreachable
-                .sorted_unstable_by(|a, b| b.multi_data_idx.cmp(&a.multi_data_idx))
-                .for_each(|index| {
-                    self.clear_node_data(index, nodes);
+        // the head is a child of this node, so the call-frames after it are its
+        // siblings
+        if head.search_siblings {
+            if let Some(pos) = self
+                .inner
+                .iter()
+                .position(|node| node.index == head.trace_index)
+            {
+                let mut results = Vec::new();
+                self.inner[pos..].iter().for_each(|node| {
+                    node.collect(&mut results, head.collect_args(), &node_data_index, nodes)
                 });
 
-            return
+                let head_data = self.inner[pos].data;
+                return self.parse_complex_classification(head, head_data, results, nodes)
+            }
         }
 
         if self.inner.len() <= 1 {
@@ -149,6 +140,26 @@ impl Node {
         warn!("was not able to find node in tree, should be unreachable");
     }
 
+    fn parse_complex_classification<V: NormalizedAction>(
+        &mut self,
+        head: &MultiCallFrameClassification<V>,
+        head_data: usize,
+        results: Vec<(NodeDataIndex, V)>,
+        nodes: &mut NodeData<V>,
+    ) {
+        // should always be the first index
+        let this = nodes.get_mut(head_data).unwrap().first_mut().unwrap();
+        let clear_collapsed_nodes = head.parse(this, results);
+
+        clear_collapsed_nodes
+            .into_iter()
+            // remove the outer indexes first to ensure no unreachable
+            .sorted_unstable_by(|a, b| b.multi_data_idx.cmp(&a.multi_data_idx))
+            .for_each(|index| {
+                self.clear_node_data(index, nodes);
+            });
+    }
+
     pub fn modify_node_if_contains_childs<F, V: NormalizedAction>(
         &mut self,
         find: &TreeSearchBuilder<V>,
@@ -576,3 +587,14 @@ impl Node {
         }
     }
 }
+
+fn node_data_index<V: NormalizedAction>(data: NodeWithDataRef<'_, V>) -> (NodeDataIndex, V) {
+    (
+        NodeDataIndex {
+            trace_index:    data.node.index,
+            data_idx:       data.node.data as u64,
+            multi_data_idx: data.idx,
+        },
+        data.data.clone(),
+    )
+}
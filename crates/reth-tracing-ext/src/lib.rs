@@ -14,7 +14,7 @@ use reth_network_api::noop::NoopNetwork;
 use reth_node_ethereum::EthEvmConfig;
 use reth_primitives::{BlockId, PruneModes, MAINNET};
 use reth_provider::{providers::BlockchainProvider, ProviderFactory};
-use reth_revm::{inspectors::GasInspector, EvmProcessorFactory};
+use reth_revm::EvmProcessorFactory;
 use reth_rpc::{
     eth::{
         cache::{EthStateCache, EthStateCacheConfig},
@@ -25,17 +25,14 @@ use reth_rpc::{
     EthApi, TraceApi,
 };
 use reth_tasks::pool::{BlockingTaskGuard, BlockingTaskPool};
-use reth_tracer::{
-    arena::CallTraceArena,
-    config::{StackSnapshotType, TracingInspectorConfig},
-    inspector::BrontesTracingInspector,
-};
+use reth_tracer::{config::TracingInspectorConfig, inspector::BrontesTracingInspector};
 use reth_transaction_pool::{
     blobstore::NoopBlobStore, validate::EthTransactionValidatorBuilder, CoinbaseTipOrdering,
     EthPooledTransaction, EthTransactionValidator, Pool, TransactionValidationTaskExecutor,
 };
 mod provider;
 pub mod reth_tracer;
+mod simulation;
 
 pub type Provider = BlockchainProvider<
     Arc<DatabaseEnv>,
@@ -149,23 +146,7 @@ impl TracingClient {
         &self,
         block_id: BlockId,
     ) -> EthResult<Option<Vec<TxTrace>>> {
-        let insp_setup = || BrontesTracingInspector {
-            config:                TracingInspectorConfig {
-                record_logs:              true,
-                record_steps:             false,
-                record_state_diff:        false,
-                record_stack_snapshots:   StackSnapshotType::None,
-                record_memory_snapshots:  false,
-                record_call_return_data:  true,
-                exclude_precompile_calls: true,
-            },
-            traces:                CallTraceArena::default(),
-            trace_stack:           Vec::new(),
-            step_stack:            Vec::new(),
-            last_call_return_data: None,
-            gas_inspector:         GasInspector::default(),
-            spec_id:               None,
-        };
+        let insp_setup = || BrontesTracingInspector::new(TracingInspectorConfig::brontes());
 
         self.api
             .trace_block_with_inspector(block_id, insp_setup, move |tx_info, inspector, res, _, _| {
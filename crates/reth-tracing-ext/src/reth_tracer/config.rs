@@ -53,6 +53,21 @@ impl TracingInspectorConfig {
         }
     }
 
+    /// Returns the config brontes traces blocks with.
+    ///
+    /// Only the calls, their return data and logs are recorded
+    pub const fn brontes() -> Self {
+        Self {
+            record_steps:             false,
+            record_memory_snapshots:  false,
+            record_stack_snapshots:   StackSnapshotType::None,
+            record_state_diff:        false,
+            exclude_precompile_calls: true,
+            record_call_return_data:  true,
+            record_logs:              true,
+        }
+    }
+
     /// Returns a config for geth style traces.
     ///
     /// This config does _not_ record opcode level traces and is suited for
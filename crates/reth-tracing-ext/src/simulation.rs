@@ -0,0 +1,120 @@
+use brontes_types::{
+    traits::{BlockSimulator, SimulatedTx},
+    FastHashMap,
+};
+use eyre::eyre;
+use reth_primitives::{
+    revm::{
+        config::revm_spec,
+        env::{fill_cfg_and_block_env, tx_env_with_recovered},
+    },
+    BlockNumber, Head, Header, TxHash, MAINNET, U256,
+};
+use reth_provider::{
+    BlockReader, HeaderProvider, StateProviderFactory, TransactionVariant, TransactionsProvider,
+};
+use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_rpc::eth::EthTransactions;
+use revm::{
+    primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId},
+    DatabaseCommit,
+};
+
+use crate::TracingClient;
+
+impl BlockSimulator for TracingClient {
+    fn block_tx_hashes(&self, block_number: BlockNumber) -> eyre::Result<Vec<TxHash>> {
+        Ok(self
+            .trace
+            .provider()
+            .transactions_by_block(block_number.into())?
+            .ok_or_else(|| eyre!("block {block_number} not found"))?
+            .iter()
+            .map(|tx| tx.hash())
+            .collect())
+    }
+
+    fn simulate_block(
+        &self,
+        block_number: BlockNumber,
+        tx_hashes: &[TxHash],
+    ) -> eyre::Result<Vec<SimulatedTx>> {
+        let provider = self.trace.provider();
+        let block = provider
+            .block_with_senders(block_number.into(), TransactionVariant::WithHash)?
+            .ok_or_else(|| eyre!("block {block_number} not found"))?;
+        let total_difficulty = provider
+            .header_td_by_number(block_number)?
+            .ok_or_else(|| eyre!("no total difficulty for block {block_number}"))?;
+
+        let spec_id = block_spec_id(&block.header, total_difficulty);
+        let mut cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), spec_id);
+        let mut block_env = BlockEnv::default();
+        fill_cfg_and_block_env(&mut cfg, &mut block_env, &MAINNET, &block.header, total_difficulty);
+
+        // execution starts from the state at the end of the parent block
+        let state = provider.history_by_block_number(block_number.saturating_sub(1))?;
+        let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+        let mut txs = block
+            .into_transactions_ecrecovered()
+            .map(|tx| (tx.hash(), tx))
+            .collect::<FastHashMap<_, _>>();
+
+        tx_hashes
+            .iter()
+            .map(|tx_hash| {
+                let tx = txs.remove(tx_hash).ok_or_else(|| {
+                    eyre!("tx {tx_hash:?} isn't part of block {block_number} or was passed twice")
+                })?;
+                let env = EnvWithHandlerCfg::new_with_cfg_env(
+                    cfg.clone(),
+                    block_env.clone(),
+                    tx_env_with_recovered(&tx),
+                );
+
+                let (res, _) = self.api.transact(&mut db, env)?;
+                // the following txs need to see the state changes of this one
+                db.commit(res.state);
+
+                Ok(SimulatedTx {
+                    tx_hash:    *tx_hash,
+                    is_success: res.result.is_success(),
+                    gas_used:   res.result.gas_used(),
+                    logs:       res.result.into_logs(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The hardfork `header` was executed under on mainnet
+fn block_spec_id(header: &Header, total_difficulty: U256) -> SpecId {
+    revm_spec(
+        &MAINNET,
+        Head {
+            number: header.number,
+            timestamp: header.timestamp,
+            difficulty: header.difficulty,
+            total_difficulty,
+            hash: header.hash_slow(),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_follows_the_block() {
+        let header = |number, timestamp| Header { number, timestamp, ..Default::default() };
+        // mainnet's terminal total difficulty
+        let merged = U256::from(58_750_000_000_000_000_000_000_u128);
+
+        assert_eq!(block_spec_id(&header(12_965_000, 1628166822), U256::ZERO), SpecId::LONDON);
+        assert_eq!(block_spec_id(&header(15_537_394, 1663224179), merged), SpecId::MERGE);
+        assert_eq!(block_spec_id(&header(17_034_870, 1681338455), merged), SpecId::SHANGHAI);
+        assert_eq!(block_spec_id(&header(19_426_587, 1710338135), merged), SpecId::CANCUN);
+    }
+}
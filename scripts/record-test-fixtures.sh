@@ -0,0 +1,11 @@
+#!/bin/bash
+# records the fixtures the inspector tests replay from when BRONTES_TEST_FIXTURES is set.
+# needs a node and a populated brontes db, the fixtures are written to crates/brontes-inspect/fixtures
+
+DIR="crates/brontes-inspect/fixtures"
+
+# aave v3 and aave v2 liquidation tests
+TX_HASHES="0xdd951e0fc5dc4c98b8daaccdb750ff3dc9ad24a7f689aad2a088757266ab1d55,0x725551f77f94f0ff01046aa4f4b93669d689f7eda6bb8cd87e2be780935eb2db"
+
+if cargo run --features sorella-server -- db record-fixture --tx-hashes $TX_HASHES --dir $DIR; then : ; else exit 1; fi
+echo "recorded fixtures to $DIR"
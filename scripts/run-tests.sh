@@ -45,6 +45,10 @@ fi
 
 if [ "$3" = "$TEST" ]; then 
   if cargo test -j 20 --features $4 --profile release -- --nocapture --show-output; then : ;else  teardown $2; exit 1; fi
+  # replay the inspector tests that have recorded fixtures without the node
+  if [ -f crates/brontes-inspect/fixtures/index.json ]; then
+    if BRONTES_TEST_FIXTURES="$(pwd)/crates/brontes-inspect/fixtures" cargo test -j 20 --profile release -p brontes-inspect liquidations -- --nocapture; then : ;else  teardown $2; exit 1; fi
+  fi
 fi
 
 if [ "$3" = "$BENCH" ]; then 